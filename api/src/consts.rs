@@ -3,23 +3,81 @@ pub const CURRENCY: &[u8]       = b"currency";
 pub const POOL: &[u8]           = b"pool";
 pub const TREASURY: &[u8]       = b"treasury";
 pub const METADATA: &[u8]       = b"metadata";
+pub const FEE_SCHEDULE: &[u8]   = b"fee_schedule";
+pub const VOLUME: &[u8]         = b"volume";
+pub const CURVE_TABLE: &[u8]    = b"curve_table";
+pub const PROTOCOL_CONFIG: &[u8] = b"protocol_config";
+pub const SYMBOL: &[u8]         = b"symbol";
+pub const REGISTRY: &[u8]       = b"registry";
+pub const PROFILE: &[u8]        = b"profile";
+pub const PURCHASES: &[u8]      = b"purchases";
+pub const VESTING: &[u8]        = b"vesting";
+pub const CASH_LINK: &[u8]      = b"cash_link";
+pub const LIMIT_ORDER: &[u8]    = b"limit_order";
+pub const DCA: &[u8]            = b"dca";
+pub const DISTRIBUTOR: &[u8]    = b"distributor";
+pub const TRADE_HOOK: &[u8]     = b"trade_hook";
 
 pub const METADATA_URI: &str    = "https://currency.flipcash.com/{}/metadata.json";
 
 pub const MAX_NAME_LEN: usize   = 32;
 pub const MAX_SYMBOL_LEN: usize = 8;
 
-pub const TOKEN_DECIMALS: u8    = 10; // Decimals for the new currency
-pub const MAX_TOKEN_SUPPLY: u64 = 21_000_000;
-pub const QUARKS_PER_TOKEN: u64 = 10_000_000_000;
-pub const SCALED_QUARKS_PER_TOKEN: u128 = 10_000_000_000_000_000_000_000_000_000;
+pub const MAX_FEE_EXEMPTIONS: usize = 16;
+pub const MAX_FEE_TIERS: usize  = 4;
+pub const NO_FEE_TIER: u8       = u8::MAX; // Sentinel for "no volume tier applied"
+
+pub const MAX_ALLOWED_BASE_MINTS: usize = 8;
+pub const REGISTRY_PAGE_LEN: usize = 64;     // Currency mints per registry page
+
+pub const MAX_DESCRIPTION_LEN: usize = 512;  // Bytes of UTF-8 in a currency profile description
+pub const MAX_PROFILE_LINKS: usize   = 8;
+pub const MAX_LINK_KEY_LEN: usize    = 16;   // e.g. "website", "x", "telegram"
+pub const MAX_LINK_VALUE_LEN: usize  = 128;  // e.g. a URL or handle
+
+// Token-2022 base mint extensions a pool only accepts when its creator opts in
+pub const ALLOW_PERMANENT_DELEGATE: u8    = 1 << 0; // Delegate can move vault funds
+pub const ALLOW_TRANSFER_HOOK: u8         = 1 << 1; // Hook program runs on every transfer
+pub const ALLOW_PAUSABLE: u8              = 1 << 2; // Authority can halt all transfers
+pub const ALLOW_DEFAULT_ACCOUNT_STATE: u8 = 1 << 3; // New accounts (vaults) may start frozen
+
+pub const MAX_PRESALE_PROOF_LEN: usize = 16; // Allowlists of up to 65,536 wallets
+pub const MAX_BATCH_RECIPIENTS: usize = 16;  // Recipients per BatchBuy (fits a transaction)
+pub const MAX_DISTRIBUTOR_PROOF_LEN: usize = 16; // Distributors of up to 65,536 recipients
+pub const MAX_DISTRIBUTOR_RECIPIENTS: u64 = 1 << MAX_DISTRIBUTOR_PROOF_LEN;
+
+// Vesting of a pool authority's initial buy
+pub const MAX_VESTING_DURATION: i64 = 10 * 365 * 24 * 60 * 60; // Ten years, in seconds
+
+// Post-trade hooks
+pub const MAX_TRADE_HOOK_COMPUTE_UNITS: u32 = 50_000; // Per trade, including the CPI itself
+pub const TRADE_HOOK_COMPUTE_RESERVE: u64 = 40_000;   // Left after the hook for payouts and VM deposits
+
+// Trades a paused pool rejects
+pub const PAUSE_BUYS: u8  = 1 << 0;
+pub const PAUSE_SELLS: u8 = 1 << 1;
+
+// Currency mint parameters, chosen per currency at creation
+pub const DEFAULT_TOKEN_DECIMALS: u8 = 10;
+pub const DEFAULT_TOKEN_SUPPLY: u64  = 21_000_000;
+pub const MAX_TOKEN_DECIMALS: u8     = 18;                              // UnsignedNumeric precision
+pub const SCALED_QUARKS_PER_TOKEN: u128 = 10_000_000_000_000_000_000_000_000_000; // At DEFAULT_TOKEN_DECIMALS
 
 // Constants for the default curve from $0.01 to $1_000_000 over 21_000_000 tokens
 pub const CURVE_A: u128         = 11400_230149967394933471;
 pub const CURVE_B: u128         = 0_000000877175273521;
 pub const CURVE_C: u128         = CURVE_B;
 
-// Maximum cumulative value to buy all 21mm tokens (from DISCRETE_CUMULATIVE_VALUE_TABLE)
-// This is the value at Supply: 21000000 with 18 decimal precision
-pub const SCALED_MAX_CUMULATIVE_VALUE: u128 = 1139973004315032342581716939500;
+// Per-pool curve parameters (prices are fixed point with CURVE_PRICE_DECIMALS)
+pub const CURVE_PRICE_DECIMALS: u8 = 9;
+pub const DEFAULT_CURVE_START_PRICE: u64 = 10_000_000;                  // $0.01
+pub const DEFAULT_CURVE_END_PRICE: u64   = 1_000_000_000_000_000;       // $1_000_000
+pub const DEFAULT_CURVE_STEP_SIZE: u64   = 100;                         // DISCRETE_PRICING_STEP_SIZE
+pub const MIN_CURVE_SUPPLY: u64          = 1_000;
+pub const MAX_CURVE_SUPPLY: u64          = 1_000_000_000;               // Keeps supply in quarks within u64
+pub const MAX_CURVE_PRICE_RATIO: u64     = 1_000_000_000;               // end_price / start_price
+pub const SIGMOID_STEEPNESS: u64         = 10;                          // k * max_supply
+pub const CURVE_SOLVER_TOLERANCE: u128   = 1_000_000;                   // 1e-12 tokens
+pub const CURVE_SOLVER_MAX_ITERATIONS: usize = 128;                    // Enough to bisect across MAX_CURVE_SUPPLY
+pub const CURVE_TABLE_CHUNK_LEN: usize   = 24;                          // Entries per WriteCurveTable (fits a transaction)
 
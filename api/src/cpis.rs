@@ -1,8 +1,14 @@
 use solana_program::{
     program_pack::Pack,
 };
+use spl_token_2022::{
+    extension::{metadata_pointer, ExtensionType},
+    state::Mint,
+};
+use spl_token_metadata_interface::state::TokenMetadata;
 use steel::*;
 
+use crate::token::token_account_len;
 use crate::utils::check_program;
 
 pub const VM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([13, 198, 40, 104, 167, 126, 85, 128, 122, 28, 213, 136, 201, 162, 154, 118, 107, 62, 226, 174, 205, 192, 125, 224, 140, 254, 145, 7, 221, 75, 49, 101]);
@@ -12,6 +18,7 @@ pub fn create_token_account<'info>(
     target: &AccountInfo<'info>,
     seeds: &[&[u8]],
     payer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
     system_program: &AccountInfo<'info>,
     rent_sysvar: &AccountInfo<'info>,
 ) -> ProgramResult {
@@ -20,21 +27,24 @@ pub fn create_token_account<'info>(
         return Err(ProgramError::AccountAlreadyInitialized);
     }
 
+    // Token-2022 mints may require account extensions
+    let space = token_account_len(mint)?;
+
     // Safely create the account with system program
     steel::allocate_account_with_bump(
         target,
         system_program,
         payer,
-        spl_token::state::Account::LEN,
-        &spl_token::id(),
+        space,
+        token_program.key,
         &seeds[0..seeds.len()-1],
         seeds[seeds.len()-1][0],
     )?;
 
     // Initialize the PDA.
     solana_program::program::invoke(
-        &spl_token::instruction::initialize_account(
-            &spl_token::id(),
+        &spl_token_2022::instruction::initialize_account(
+            token_program.key,
             target.key,
             mint.key,
             target.key,
@@ -49,6 +59,10 @@ pub fn create_token_account<'info>(
     )
 }
 
+/// Creates a mint owned by `token_program`. Token-2022 mints also get a
+/// MetadataPointer to themselves so TokenMetadata can be written to the mint
+/// later (see `initialize_token_metadata_signed_with_bump`).
+#[allow(clippy::too_many_arguments)]
 pub fn create_mint_account<'info>(
     mint: &AccountInfo<'info>,
     mint_authority: &Pubkey,
@@ -56,6 +70,7 @@ pub fn create_mint_account<'info>(
     decimals: u8,
     seeds: &[&[u8]],
     payer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
     system_program: &AccountInfo<'info>,
     rent_sysvar: &AccountInfo<'info>,
 ) -> ProgramResult {
@@ -64,21 +79,41 @@ pub fn create_mint_account<'info>(
         return Err(ProgramError::AccountAlreadyInitialized);
     }
 
+    let is_token_2022 = token_program.key.eq(&spl_token_2022::ID);
+    let space = if is_token_2022 {
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::MetadataPointer])?
+    } else {
+        spl_token::state::Mint::LEN
+    };
+
     // Safely create the account with system program
     steel::allocate_account_with_bump(
         mint,
         system_program,
         payer,
-        spl_token::state::Mint::LEN,
-        &spl_token::id(),
+        space,
+        token_program.key,
         &seeds[0..seeds.len()-1],
         seeds[seeds.len()-1][0],
     )?;
 
+    // Extensions must be initialized before the mint itself
+    if is_token_2022 {
+        solana_program::program::invoke(
+            &metadata_pointer::instruction::initialize(
+                token_program.key,
+                mint.key,
+                Some(*mint_authority),
+                Some(*mint.key),
+            )?,
+            std::slice::from_ref(mint),
+        )?;
+    }
+
     // Initialize the mint
     solana_program::program::invoke(
-        &spl_token::instruction::initialize_mint(
-            &spl_token::id(),
+        &spl_token_2022::instruction::initialize_mint(
+            token_program.key,
             mint.key,
             mint_authority,
             freeze_authority,
@@ -93,6 +128,122 @@ pub fn create_mint_account<'info>(
     Ok(())
 }
 
+/// Writes TokenMetadata into a Token-2022 mint created by
+/// `create_mint_account`. The mint authority is a PDA; the last seed is the
+/// bump. Token-2022 reallocates the mint but does not fund it, so the payer
+/// tops up rent first.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_token_metadata_signed_with_bump<'info>(
+    mint: &AccountInfo<'info>,
+    update_authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    name: String,
+    symbol: String,
+    uri: String,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let metadata = TokenMetadata {
+        update_authority: Some(*update_authority.key).try_into()?,
+        mint: *mint.key,
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: vec![],
+    };
+
+    let space = mint.data_len()
+        .checked_add(metadata.tlv_size_of()?)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let lamports = solana_program::rent::Rent::get()?.minimum_balance(space)
+        .saturating_sub(mint.lamports());
+
+    if lamports > 0 {
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(payer.key, mint.key, lamports),
+            &[
+                payer.clone(),
+                mint.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    solana_program::program::invoke_signed(
+        &spl_token_metadata_interface::instruction::initialize(
+            token_program.key,
+            mint.key,
+            update_authority.key,
+            mint.key,
+            mint.key, // mint_authority
+            name,
+            symbol,
+            uri,
+        ),
+        &[
+            mint.clone(),
+            update_authority.clone(),
+            mint.clone(),
+            mint.clone(),
+        ],
+        &[seeds],
+    )
+}
+
+/// `transfer_checked` for either token program. Token-2022 transfer hook
+/// accounts are looked up by key in `extra_accounts`; pass an empty slice
+/// when the mint has no hook.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_extra_accounts<'info>(
+    authority: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    extra_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from.clone(),
+        mint.clone(),
+        to.clone(),
+        authority.clone(),
+        extra_accounts,
+        amount,
+        decimals,
+        &[],
+    )
+}
+
+/// Same as `transfer_checked_with_extra_accounts`, with a PDA authority.
+/// The last seed is the bump.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_signed_with_extra_accounts<'info>(
+    authority: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    extra_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from.clone(),
+        mint.clone(),
+        to.clone(),
+        authority.clone(),
+        extra_accounts,
+        amount,
+        decimals,
+        &[seeds],
+    )
+}
 
 // todo: properly expose CPI in code-vm
 pub fn deposit_into_vm<'info>(
@@ -1,6 +1,30 @@
-use brine_fp::UnsignedNumeric;
+use brine_fp::{SignedNumeric, UnsignedNumeric};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use crate::consts::*;
 use crate::table::*;
+use crate::utils::*;
+use crate::state::CurveTableEntry;
+
+/// Pricing model shared by every curve a pool can use. Supplies and token
+/// amounts are in whole tokens; values and prices in whole base tokens.
+pub trait BondingCurve {
+    /// Calculate token price at a given supply
+    fn spot_price_at_supply(&self, current_supply: &UnsignedNumeric) -> Option<UnsignedNumeric>;
+
+    /// Calculate total cost to buy `tokens` starting at `current_supply`
+    fn tokens_to_value(
+        &self,
+        current_supply: &UnsignedNumeric,
+        tokens: &UnsignedNumeric,
+    ) -> Option<UnsignedNumeric>;
+
+    /// Calculate number of tokens received for `value` starting at `current_supply`
+    fn value_to_tokens(
+        &self,
+        current_supply: &UnsignedNumeric,
+        value: &UnsignedNumeric,
+    ) -> Option<UnsignedNumeric>;
+}
 
 #[derive(Debug, Clone)]
 pub struct ContinuousExponentialCurve {
@@ -18,8 +42,36 @@ impl ContinuousExponentialCurve {
         }
     }
 
+    /// Exponential curve through `start_price` at zero supply and `end_price`
+    /// at `max_supply`.
+    pub fn from_params(params: &CurveParams) -> Option<Self> {
+        if params.start_price == 0 || params.end_price <= params.start_price {
+            return None;
+        }
+
+        let start_price = to_numeric(params.start_price, CURVE_PRICE_DECIMALS).ok()?;
+        let end_price = to_numeric(params.end_price, CURVE_PRICE_DECIMALS).ok()?;
+        let max_supply = UnsignedNumeric::new(params.max_supply.into())?;
+
+        // c = ln(end / start) / max_supply, b = c, a * b = start
+        let c = end_price
+            .checked_div(&start_price)?
+            .log()?
+            .value
+            .checked_div(&max_supply)?;
+        let a = start_price.checked_div(&c)?;
+
+        Some(Self {
+            a,
+            b: c.clone(),
+            c,
+        })
+    }
+}
+
+impl BondingCurve for ContinuousExponentialCurve {
     /// Calculate token price at a given supply
-    pub fn spot_price_at_supply(&self, current_supply: &UnsignedNumeric) -> Option<UnsignedNumeric> {
+    fn spot_price_at_supply(&self, current_supply: &UnsignedNumeric) -> Option<UnsignedNumeric> {
         // R'(S) = a * b * e^(c * s)
 
         let c_times_s = self.c.checked_mul(current_supply)?;
@@ -29,7 +81,7 @@ impl ContinuousExponentialCurve {
 
     /// Calculate total cost to buy `num_tokens` starting at `current_supply`
     /// “How much does it cost to get X tokens?”
-    pub fn tokens_to_value(
+    fn tokens_to_value(
         &self,
         current_supply: &UnsignedNumeric,
         tokens: &UnsignedNumeric,
@@ -55,7 +107,7 @@ impl ContinuousExponentialCurve {
 
     /// Calculate number of tokens received for a `value` amount starting at `current_supply`
     /// “How many tokens can I get for Y value?”
-    pub fn value_to_tokens(
+    fn value_to_tokens(
         &self,
         current_supply: &UnsignedNumeric,
         value: &UnsignedNumeric,
@@ -74,34 +126,281 @@ impl ContinuousExponentialCurve {
     }
 }
 
+/// Curve shape recorded in a pool.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum CurveKind {
+    #[default]
+    DiscreteExponential = 0,      // Stepped exponential (the original Flipcash curve)
+    Exponential,                  // ContinuousExponentialCurve
+    Linear,                       // LinearCurve
+    Sigmoid,                      // SigmoidCurve
+    Table,                        // Stepped prices read from a finalized CurveTable account
+}
+
+/// Per-pool bonding curve parameters.
+///
+/// Prices are fixed point with `CURVE_PRICE_DECIMALS` decimals and denominated
+/// in whole base tokens per whole currency token. Supplies are in whole tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveParams {
+    pub kind: CurveKind,
+    pub start_price: u64,         // Price of the first step
+    pub end_price: u64,           // Price at max supply
+    pub max_supply: u64,          // Tokens sold by the curve
+    pub step_size: u64,           // Tokens per price step (discrete curves only)
+}
+
+impl Default for CurveParams {
+    fn default() -> Self {
+        Self {
+            kind: CurveKind::DiscreteExponential,
+            start_price: DEFAULT_CURVE_START_PRICE,
+            end_price: DEFAULT_CURVE_END_PRICE,
+            max_supply: DEFAULT_TOKEN_SUPPLY,
+            step_size: DEFAULT_CURVE_STEP_SIZE,
+        }
+    }
+}
+
+impl CurveParams {
+    /// Number of price steps, if `step_size` evenly divides `max_supply`
+    pub fn num_steps(&self) -> Option<u64> {
+        match self.max_supply.checked_rem(self.step_size) {
+            Some(0) => Some(self.max_supply / self.step_size),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
-pub struct DiscreteExponentialCurve;
+pub struct DiscreteExponentialCurve<'a> {
+    step_size: u128,
+    num_steps: usize,
+    pricing: DiscretePricing<'a>,
+}
+
+/// Precomputed step prices and cumulative values, as raw `UnsignedNumeric`
+/// values. Entry `i` holds the price of step `i` and the value of all steps
+/// below it.
+#[derive(Debug, Clone, Copy)]
+pub enum DiscreteTable<'a> {
+    /// DISCRETE_PRICING_TABLE and DISCRETE_CUMULATIVE_VALUE_TABLE
+    Static,
+
+    /// Entries uploaded to a CurveTable account
+    Account(&'a [CurveTableEntry]),
+}
+
+impl DiscreteTable<'_> {
+    fn price(&self, step: usize) -> Option<u128> {
+        match self {
+            Self::Static => DISCRETE_PRICING_TABLE.get(step).copied(),
+            Self::Account(entries) => entries.get(step).map(|e| e.price()),
+        }
+    }
 
+    fn cumulative_value(&self, step: usize) -> Option<u128> {
+        match self {
+            Self::Static => DISCRETE_CUMULATIVE_VALUE_TABLE.get(step).copied(),
+            Self::Account(entries) => entries.get(step).map(|e| e.cumulative_value()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum DiscretePricing<'a> {
+    Table(DiscreteTable<'a>),
+
+    /// price(i) = start_price * e^(growth * i), summed as a geometric series
+    Computed {
+        start_price: UnsignedNumeric,
+        growth: UnsignedNumeric,          // ln(end_price / start_price) / num_steps
+        step_value: UnsignedNumeric,      // start_price * step_size
+        ratio_less_one: UnsignedNumeric,  // e^growth - 1
+    },
+}
 
 /// Discrete implementation of ContinuousExponentialCurve
-impl DiscreteExponentialCurve {
+impl<'a> DiscreteExponentialCurve<'a> {
+    /// The $0.01 to $1_000_000 curve over 21_000_000 tokens, served from the
+    /// precomputed tables.
     pub fn default() -> Self {
-        Self
+        Self {
+            step_size: DISCRETE_PRICING_STEP_SIZE,
+            num_steps: DISCRETE_PRICING_TABLE.len() - 1,
+            pricing: DiscretePricing::Table(DiscreteTable::Static),
+        }
     }
 
-    pub fn spot_price_at_supply(&self, current_supply: &UnsignedNumeric) -> Option<UnsignedNumeric> {
-        let step_size = UnsignedNumeric::new(DISCRETE_PRICING_STEP_SIZE).unwrap();
-        let step_index = current_supply.
-            checked_div(&step_size).
-            unwrap().
-            floor().
-            unwrap().
-            to_imprecise()
-            .unwrap() as usize;
-        if step_index >= DISCRETE_PRICING_TABLE.len() {
+    /// Steps priced from an uploaded table. The table needs at least two
+    /// entries: the last one only marks the end of the curve.
+    pub fn from_table(step_size: u64, entries: &'a [CurveTableEntry]) -> Option<Self> {
+        if step_size == 0 || entries.len() < 2 {
             return None;
         }
 
-        let price = DISCRETE_PRICING_TABLE[step_index];
-        Some(UnsignedNumeric::from_scaled_u128(price))
+        Some(Self {
+            step_size: step_size.into(),
+            num_steps: entries.len() - 1,
+            pricing: DiscretePricing::Table(DiscreteTable::Account(entries)),
+        })
     }
 
-    pub fn tokens_to_value(
+    /// Builds the curve for a pool's parameters. The default parameters keep
+    /// using the precomputed tables so existing currencies price exactly as
+    /// before; anything else is computed from the geometric series.
+    pub fn from_params(params: &CurveParams) -> Option<Self> {
+        if params.eq(&CurveParams::default()) {
+            return Some(Self::default());
+        }
+
+        Self::computed(params)
+    }
+
+    fn computed(params: &CurveParams) -> Option<Self> {
+        let num_steps = params.num_steps()?;
+        if num_steps == 0 || params.start_price == 0 || params.end_price < params.start_price {
+            return None;
+        }
+
+        let start_price = to_numeric(params.start_price, CURVE_PRICE_DECIMALS).ok()?;
+        let end_price = to_numeric(params.end_price, CURVE_PRICE_DECIMALS).ok()?;
+        let step_size = UnsignedNumeric::new(params.step_size.into())?;
+
+        let growth = end_price
+            .checked_div(&start_price)?
+            .log()?
+            .value
+            .checked_div(&UnsignedNumeric::new(num_steps.into())?)?;
+        let ratio_less_one = growth_factor(&growth, 1)?
+            .checked_sub(&UnsignedNumeric::one())?;
+        let step_value = start_price.checked_mul(&step_size)?;
+
+        Some(Self {
+            step_size: params.step_size.into(),
+            num_steps: usize::try_from(num_steps).ok()?,
+            pricing: DiscretePricing::Computed {
+                start_price,
+                growth,
+                step_value,
+                ratio_less_one,
+            },
+        })
+    }
+
+    /// Total tokens sold by the curve
+    pub fn max_supply(&self) -> Option<UnsignedNumeric> {
+        UnsignedNumeric::new(self.num_steps as u128 * self.step_size)
+    }
+
+    /// Total value paid to buy out the curve
+    pub fn max_cumulative_value(&self) -> Option<UnsignedNumeric> {
+        self.cumulative_value_at_step(self.num_steps)
+    }
+
+    fn price_at_step(&self, step: usize) -> Option<UnsignedNumeric> {
+        if step > self.num_steps {
+            return None;
+        }
+
+        match &self.pricing {
+            DiscretePricing::Table(table) => {
+                Some(UnsignedNumeric::from_scaled_u128(table.price(step)?))
+            }
+            DiscretePricing::Computed { start_price, growth, .. } => {
+                start_price.checked_mul(&growth_factor(growth, step)?)
+            }
+        }
+    }
+
+    /// Value of all complete steps below `step`
+    fn cumulative_value_at_step(&self, step: usize) -> Option<UnsignedNumeric> {
+        if step > self.num_steps {
+            return None;
+        }
+
+        match &self.pricing {
+            DiscretePricing::Table(table) => {
+                Some(UnsignedNumeric::from_scaled_u128(table.cumulative_value(step)?))
+            }
+            DiscretePricing::Computed { growth, step_value, ratio_less_one, .. } => {
+                // Flat curve
+                if growth.eq(&UnsignedNumeric::zero()) {
+                    return step_value.checked_mul(&UnsignedNumeric::new(step as u128)?);
+                }
+
+                // step_value * (r^step - 1) / (r - 1)
+                growth_factor(growth, step)?
+                    .checked_sub(&UnsignedNumeric::one())?
+                    .checked_mul(step_value)?
+                    .checked_div(ratio_less_one)
+            }
+        }
+    }
+
+    /// Last step in `low..=num_steps` whose cumulative value does not exceed `target`
+    fn last_step_within(&self, target: &UnsignedNumeric, low: usize) -> Option<usize> {
+        match &self.pricing {
+            DiscretePricing::Table(table) => {
+                // Binary search for the step where cumulative value exceeds or equals target
+                let mut low = low;
+                let mut high = self.num_steps;
+                let target_raw = target.value.as_u128();
+                while low < high {
+                    let mid = (low + high + 1) / 2;
+                    let mid_cumulative = table.cumulative_value(mid)?;
+
+                    if mid_cumulative <= target_raw {
+                        low = mid;
+                    } else {
+                        high = mid - 1;
+                    }
+                }
+                Some(low)
+            }
+            DiscretePricing::Computed { growth, step_value, ratio_less_one, .. } => {
+                // Invert the geometric series, then correct for rounding
+                let estimate = if growth.eq(&UnsignedNumeric::zero()) {
+                    target.checked_div(step_value)?
+                } else {
+                    target
+                        .checked_mul(ratio_less_one)?
+                        .checked_div(step_value)?
+                        .checked_add(&UnsignedNumeric::one())?
+                        .log()?
+                        .value
+                        .checked_div(growth)?
+                };
+                let estimate = estimate.floor()?.to_imprecise()?;
+
+                let mut step = estimate.clamp(low as u128, self.num_steps as u128) as usize;
+                while step > low && self.cumulative_value_at_step(step)?.greater_than(target) {
+                    step -= 1;
+                }
+                while step < self.num_steps
+                    && self.cumulative_value_at_step(step + 1)?.less_than_or_equal(target)
+                {
+                    step += 1;
+                }
+                Some(step)
+            }
+        }
+    }
+}
+
+impl BondingCurve for DiscreteExponentialCurve<'_> {
+    fn spot_price_at_supply(&self, current_supply: &UnsignedNumeric) -> Option<UnsignedNumeric> {
+        let step_size = UnsignedNumeric::new(self.step_size)?;
+        let step_index = current_supply
+            .checked_div(&step_size)?
+            .floor()?
+            .to_imprecise()? as usize;
+
+        self.price_at_step(step_index)
+    }
+
+    fn tokens_to_value(
         &self,
         current_supply: &UnsignedNumeric,
         tokens: &UnsignedNumeric,
@@ -112,7 +411,7 @@ impl DiscreteExponentialCurve {
             return Some(UnsignedNumeric::zero());
         }
 
-        let step_size = UnsignedNumeric::new(DISCRETE_PRICING_STEP_SIZE).unwrap();
+        let step_size = UnsignedNumeric::new(self.step_size)?;
         let end_supply = current_supply.checked_add(tokens)?;
 
         let start_step = current_supply
@@ -124,12 +423,12 @@ impl DiscreteExponentialCurve {
             .floor()?
             .to_imprecise()? as usize;
 
-        if end_step >= DISCRETE_PRICING_TABLE.len() {
+        if end_step > self.num_steps {
             return None;
         }
 
         // Calculate partial tokens in start step (from current_supply to next step boundary)
-        let start_step_boundary = UnsignedNumeric::new((start_step as u128 + 1) * DISCRETE_PRICING_STEP_SIZE)?;
+        let start_step_boundary = UnsignedNumeric::new((start_step as u128 + 1) * self.step_size)?;
         let tokens_in_start_step = if start_step_boundary.greater_than(&end_supply) {
             // All tokens are within the same step
             tokens.clone()
@@ -138,11 +437,11 @@ impl DiscreteExponentialCurve {
         };
 
         // Calculate partial tokens in end step (from end step boundary to end_supply)
-        let end_step_boundary = UnsignedNumeric::new(end_step as u128 * DISCRETE_PRICING_STEP_SIZE)?;
+        let end_step_boundary = UnsignedNumeric::new(end_step as u128 * self.step_size)?;
         let tokens_in_end_step = end_supply.checked_sub(&end_step_boundary)?;
 
         // Cost for partial start step
-        let start_price = UnsignedNumeric::from_scaled_u128(DISCRETE_PRICING_TABLE[start_step]);
+        let start_price = self.price_at_step(start_step)?;
         let start_cost = tokens_in_start_step.checked_mul(&start_price)?;
 
         // If start and end are in the same step, we're done
@@ -151,20 +450,20 @@ impl DiscreteExponentialCurve {
         }
 
         // Cost for complete steps between start_step+1 and end_step-1 (inclusive)
-        // Use cumulative table: cumulative[end_step] - cumulative[start_step + 1]
-        let cumulative_start = UnsignedNumeric::from_scaled_u128(DISCRETE_CUMULATIVE_VALUE_TABLE[start_step + 1]);
-        let cumulative_end = UnsignedNumeric::from_scaled_u128(DISCRETE_CUMULATIVE_VALUE_TABLE[end_step]);
+        // Use cumulative values: cumulative[end_step] - cumulative[start_step + 1]
+        let cumulative_start = self.cumulative_value_at_step(start_step + 1)?;
+        let cumulative_end = self.cumulative_value_at_step(end_step)?;
         let middle_cost = cumulative_end.checked_sub(&cumulative_start)?;
 
         // Cost for partial end step
-        let end_price = UnsignedNumeric::from_scaled_u128(DISCRETE_PRICING_TABLE[end_step]);
+        let end_price = self.price_at_step(end_step)?;
         let end_cost = tokens_in_end_step.checked_mul(&end_price)?;
 
         // Total cost
         start_cost.checked_add(&middle_cost)?.checked_add(&end_cost)
     }
 
-    pub fn value_to_tokens(
+    fn value_to_tokens(
         &self,
         current_supply: &UnsignedNumeric,
         value: &UnsignedNumeric,
@@ -175,7 +474,7 @@ impl DiscreteExponentialCurve {
             return Some(UnsignedNumeric::zero());
         }
 
-        let step_size = UnsignedNumeric::new(DISCRETE_PRICING_STEP_SIZE).unwrap();
+        let step_size = UnsignedNumeric::new(self.step_size)?;
 
         // Get current step index and position within step
         let start_step = current_supply
@@ -183,14 +482,14 @@ impl DiscreteExponentialCurve {
             .floor()?
             .to_imprecise()? as usize;
 
-        if start_step >= DISCRETE_PRICING_TABLE.len() - 1 {
+        if start_step >= self.num_steps {
             return None;
         }
 
         // Calculate cost to complete the current partial step
-        let start_step_boundary = UnsignedNumeric::new((start_step as u128 + 1) * DISCRETE_PRICING_STEP_SIZE)?;
+        let start_step_boundary = UnsignedNumeric::new((start_step as u128 + 1) * self.step_size)?;
         let tokens_in_start_step = start_step_boundary.checked_sub(current_supply)?;
-        let start_price = UnsignedNumeric::from_scaled_u128(DISCRETE_PRICING_TABLE[start_step]);
+        let start_price = self.price_at_step(start_step)?;
         let cost_to_complete_start_step = tokens_in_start_step.checked_mul(&start_price)?;
 
         // If we can't even complete the start step, just divide by price
@@ -202,44 +501,25 @@ impl DiscreteExponentialCurve {
         let remaining_after_start = value.checked_sub(&cost_to_complete_start_step)?;
 
         // Calculate the cumulative value at start_step + 1 (where we'll be after completing start step)
-        let base_cumulative = UnsignedNumeric::from_scaled_u128(DISCRETE_CUMULATIVE_VALUE_TABLE[start_step + 1]);
+        let base_cumulative = self.cumulative_value_at_step(start_step + 1)?;
 
         // Target cumulative = base_cumulative + remaining_value
         let target_cumulative = base_cumulative.checked_add(&remaining_after_start)?;
 
-        // Binary search for the step where cumulative value exceeds or equals target
-        let mut low = start_step + 1;
-        let mut high = DISCRETE_CUMULATIVE_VALUE_TABLE.len() - 1;
-        let target_cumulative_raw = target_cumulative.value.as_u128();
-        while low < high {
-            let mid = (low + high + 1) / 2;
-            let mid_cumulative = DISCRETE_CUMULATIVE_VALUE_TABLE[mid];
-
-            if mid_cumulative <= target_cumulative_raw {
-                low = mid;
-            } else {
-                high = mid - 1;
-            }
-        }
-
-        // low is now the last step where cumulative <= target
-        let end_step = low;
-
-        if end_step >= DISCRETE_PRICING_TABLE.len() {
-            return None;
-        }
+        // Find the last step where cumulative <= target
+        let end_step = self.last_step_within(&target_cumulative, start_step + 1)?;
 
         // Calculate tokens from complete steps
-        let end_step_supply = UnsignedNumeric::new(end_step as u128 * DISCRETE_PRICING_STEP_SIZE)?;
+        let end_step_supply = UnsignedNumeric::new(end_step as u128 * self.step_size)?;
         let tokens_from_complete_steps = end_step_supply.checked_sub(&start_step_boundary)?;
 
         // Calculate remaining value after complete steps
-        let cumulative_at_end_step = UnsignedNumeric::from_scaled_u128(DISCRETE_CUMULATIVE_VALUE_TABLE[end_step]);
+        let cumulative_at_end_step = self.cumulative_value_at_step(end_step)?;
         let value_used_for_complete_steps = cumulative_at_end_step.checked_sub(&base_cumulative)?;
         let remaining_value = remaining_after_start.checked_sub(&value_used_for_complete_steps)?;
 
         // Buy partial tokens in end step with remaining value
-        let end_price = UnsignedNumeric::from_scaled_u128(DISCRETE_PRICING_TABLE[end_step]);
+        let end_price = self.price_at_step(end_step)?;
         let tokens_in_end_step = remaining_value.checked_div(&end_price)?;
 
         // Total tokens
@@ -249,6 +529,316 @@ impl DiscreteExponentialCurve {
     }
 }
 
+/// Price grows linearly from `start_price` at zero supply to `end_price` at max supply.
+#[derive(Debug, Clone)]
+pub struct LinearCurve {
+    pub start_price: UnsignedNumeric,
+    pub slope: UnsignedNumeric,
+}
+
+impl LinearCurve {
+    pub fn from_params(params: &CurveParams) -> Option<Self> {
+        if params.start_price == 0 || params.end_price < params.start_price {
+            return None;
+        }
+
+        let start_price = to_numeric(params.start_price, CURVE_PRICE_DECIMALS).ok()?;
+        let end_price = to_numeric(params.end_price, CURVE_PRICE_DECIMALS).ok()?;
+        let max_supply = UnsignedNumeric::new(params.max_supply.into())?;
+
+        let slope = end_price
+            .checked_sub(&start_price)?
+            .checked_div(&max_supply)?;
+
+        Some(Self {
+            start_price,
+            slope,
+        })
+    }
+}
+
+impl BondingCurve for LinearCurve {
+    fn spot_price_at_supply(&self, current_supply: &UnsignedNumeric) -> Option<UnsignedNumeric> {
+        // R'(S) = start + slope * s
+        self.slope
+            .checked_mul(current_supply)?
+            .checked_add(&self.start_price)
+    }
+
+    fn tokens_to_value(
+        &self,
+        current_supply: &UnsignedNumeric,
+        tokens: &UnsignedNumeric,
+    ) -> Option<UnsignedNumeric> {
+        // Area of the trapezoid under the price line
+        let new_supply = current_supply.checked_add(tokens)?;
+        let start_price = self.spot_price_at_supply(current_supply)?;
+        let end_price = self.spot_price_at_supply(&new_supply)?;
+
+        start_price
+            .checked_add(&end_price)?
+            .checked_mul(tokens)?
+            .checked_div(&UnsignedNumeric::new(2)?)
+    }
+
+    fn value_to_tokens(
+        &self,
+        current_supply: &UnsignedNumeric,
+        value: &UnsignedNumeric,
+    ) -> Option<UnsignedNumeric> {
+        // Solve (slope / 2) * t^2 + p * t - value = 0 for t, in the form
+        // t = 2 * value / (p + sqrt(p^2 + 2 * slope * value)) to avoid cancellation
+
+        let price = self.spot_price_at_supply(current_supply)?;
+        let two = UnsignedNumeric::new(2)?;
+
+        let discriminant = price
+            .checked_mul(&price)?
+            .checked_add(&two.checked_mul(&self.slope)?.checked_mul(value)?)?;
+        let denominator = price.checked_add(&sqrt(&discriminant)?)?;
+
+        two.checked_mul(value)?.checked_div(&denominator)
+    }
+}
+
+/// S-shaped curve that is nearly flat around `start_price` early on, rises
+/// fastest at half the max supply and levels off near `end_price`.
+///
+/// R'(S) = start + (end - start) / (1 + e^(-k * (s - m)))
+#[derive(Debug, Clone)]
+pub struct SigmoidCurve {
+    pub min_price: UnsignedNumeric,
+    pub max_supply: UnsignedNumeric,
+    pub price_range: UnsignedNumeric,
+    pub steepness: UnsignedNumeric,   // k
+    pub midpoint: UnsignedNumeric,    // m
+}
+
+impl SigmoidCurve {
+    pub fn from_params(params: &CurveParams) -> Option<Self> {
+        if params.start_price == 0 || params.end_price < params.start_price {
+            return None;
+        }
+
+        let min_price = to_numeric(params.start_price, CURVE_PRICE_DECIMALS).ok()?;
+        let end_price = to_numeric(params.end_price, CURVE_PRICE_DECIMALS).ok()?;
+        let max_supply = UnsignedNumeric::new(params.max_supply.into())?;
+
+        Some(Self {
+            price_range: end_price.checked_sub(&min_price)?,
+            min_price,
+            steepness: UnsignedNumeric::new(SIGMOID_STEEPNESS.into())?.checked_div(&max_supply)?,
+            midpoint: max_supply.checked_div(&UnsignedNumeric::new(2)?)?,
+            max_supply,
+        })
+    }
+
+    /// k * (s - m)
+    fn exponent(&self, supply: &UnsignedNumeric) -> Option<SignedNumeric> {
+        supply
+            .signed()
+            .checked_sub(&self.midpoint.signed())?
+            .checked_mul(&self.steepness.signed())
+    }
+
+    /// Antiderivative of the sigmoid term: (range / k) * ln(1 + e^(k * (s - m)))
+    fn sigmoid_integral(&self, supply: &UnsignedNumeric) -> Option<UnsignedNumeric> {
+        let softplus = self.exponent(supply)?
+            .exp()?
+            .checked_add(&UnsignedNumeric::one())?
+            .log()?
+            .value;
+
+        self.price_range
+            .checked_mul(&softplus)?
+            .checked_div(&self.steepness)
+    }
+}
+
+impl BondingCurve for SigmoidCurve {
+    fn spot_price_at_supply(&self, current_supply: &UnsignedNumeric) -> Option<UnsignedNumeric> {
+        let denominator = self.exponent(current_supply)?
+            .negate()
+            .exp()?
+            .checked_add(&UnsignedNumeric::one())?;
+
+        self.price_range
+            .checked_div(&denominator)?
+            .checked_add(&self.min_price)
+    }
+
+    fn tokens_to_value(
+        &self,
+        current_supply: &UnsignedNumeric,
+        tokens: &UnsignedNumeric,
+    ) -> Option<UnsignedNumeric> {
+        let new_supply = current_supply.checked_add(tokens)?;
+
+        let base_value = self.min_price.checked_mul(tokens)?;
+        let sigmoid_value = self.sigmoid_integral(&new_supply)?
+            .checked_sub(&self.sigmoid_integral(current_supply)?)?;
+
+        base_value.checked_add(&sigmoid_value)
+    }
+
+    fn value_to_tokens(
+        &self,
+        current_supply: &UnsignedNumeric,
+        value: &UnsignedNumeric,
+    ) -> Option<UnsignedNumeric> {
+        // No closed form. The cost of t tokens is convex in t (price only
+        // rises), so the answer is bracketed between nothing and the rest of
+        // the supply and found with Newton's method from the upper end of the
+        // bracket. On steep curves Newton can crawl, and fixed point rounding
+        // can push it outside the bracket, so it falls back to bisection
+        // whenever a step leaves the bracket or doesn't at least halve the
+        // step before last. Only a token count that costs no more than
+        // `value` is returned.
+
+        if value.eq(&UnsignedNumeric::zero()) {
+            return Some(UnsignedNumeric::zero());
+        }
+
+        let two = UnsignedNumeric::new(2)?;
+        let tolerance = UnsignedNumeric::from_scaled_u128(CURVE_SOLVER_TOLERANCE);
+
+        let mut low = UnsignedNumeric::zero();
+        let mut high = self.max_supply.checked_sub(current_supply)?;
+        let mut high_cost = self.tokens_to_value(current_supply, &high)?;
+
+        // Not enough supply left to spend it all
+        if high_cost.less_than_or_equal(value) {
+            return high_cost.eq(value).then_some(high);
+        }
+
+        let mut last_step = high.clone();
+        let mut step = high.clone();
+
+        for _ in 0..CURVE_SOLVER_MAX_ITERATIONS {
+            let (width, _) = high.unsigned_sub(&low);
+            if width.less_than_or_equal(&tolerance) {
+                return Some(low);
+            }
+
+            let excess = high_cost.checked_sub(value)?;
+            let price = self.spot_price_at_supply(&current_supply.checked_add(&high)?)?;
+            let newton_step = excess.checked_div(&price)?;
+            let newton_step = if newton_step.less_than(&tolerance) { tolerance.clone() } else { newton_step };
+
+            let newton = high.checked_sub(&newton_step)
+                .filter(|guess| guess.greater_than(&low))
+                .filter(|_| newton_step.checked_mul(&two).is_some_and(|double| double.less_than_or_equal(&last_step)));
+
+            last_step = step;
+            let guess = match newton {
+                Some(guess) => {
+                    step = newton_step;
+                    guess
+                }
+                None => {
+                    step = width.checked_div(&two)?;
+                    low.checked_add(&step)?
+                }
+            };
+
+            let cost = self.tokens_to_value(current_supply, &guess)?;
+            if cost.less_than_or_equal(value) {
+                low = guess;
+            } else {
+                high = guess;
+                high_cost = cost;
+            }
+        }
+
+        // Didn't converge
+        None
+    }
+}
+
+/// The curve selected by a pool's `CurveKind`, so callers can price any pool
+/// without knowing its shape. Table-backed curves borrow the table account's
+/// entries for `'a`.
+#[derive(Debug, Clone)]
+pub enum PoolCurve<'a> {
+    DiscreteExponential(DiscreteExponentialCurve<'a>),
+    Exponential(ContinuousExponentialCurve),
+    Linear(LinearCurve),
+    Sigmoid(SigmoidCurve),
+}
+
+impl<'a> PoolCurve<'a> {
+    /// Builds a parametric curve. `CurveKind::Table` curves need their table,
+    /// see `from_table`.
+    pub fn from_params(params: &CurveParams) -> Option<Self> {
+        Some(match params.kind {
+            CurveKind::DiscreteExponential =>
+                Self::DiscreteExponential(DiscreteExponentialCurve::from_params(params)?),
+            CurveKind::Exponential =>
+                Self::Exponential(ContinuousExponentialCurve::from_params(params)?),
+            CurveKind::Linear =>
+                Self::Linear(LinearCurve::from_params(params)?),
+            CurveKind::Sigmoid =>
+                Self::Sigmoid(SigmoidCurve::from_params(params)?),
+            CurveKind::Table => return None,
+        })
+    }
+
+    pub fn from_table(step_size: u64, entries: &'a [CurveTableEntry]) -> Option<Self> {
+        DiscreteExponentialCurve::from_table(step_size, entries)
+            .map(Self::DiscreteExponential)
+    }
+
+    fn inner(&self) -> &dyn BondingCurve {
+        match self {
+            Self::DiscreteExponential(curve) => curve,
+            Self::Exponential(curve) => curve,
+            Self::Linear(curve) => curve,
+            Self::Sigmoid(curve) => curve,
+        }
+    }
+}
+
+impl BondingCurve for PoolCurve<'_> {
+    fn spot_price_at_supply(&self, current_supply: &UnsignedNumeric) -> Option<UnsignedNumeric> {
+        self.inner().spot_price_at_supply(current_supply)
+    }
+
+    fn tokens_to_value(
+        &self,
+        current_supply: &UnsignedNumeric,
+        tokens: &UnsignedNumeric,
+    ) -> Option<UnsignedNumeric> {
+        self.inner().tokens_to_value(current_supply, tokens)
+    }
+
+    fn value_to_tokens(
+        &self,
+        current_supply: &UnsignedNumeric,
+        value: &UnsignedNumeric,
+    ) -> Option<UnsignedNumeric> {
+        self.inner().value_to_tokens(current_supply, value)
+    }
+}
+
+fn sqrt(value: &UnsignedNumeric) -> Option<UnsignedNumeric> {
+    let scale = UnsignedNumeric::one().value;
+    Some(UnsignedNumeric {
+        value: value.value.checked_mul(scale)?.integer_sqrt(),
+    })
+}
+
+/// e^(growth * step), exact at step zero
+fn growth_factor(growth: &UnsignedNumeric, step: usize) -> Option<UnsignedNumeric> {
+    if step == 0 {
+        return Some(UnsignedNumeric::one());
+    }
+
+    growth
+        .checked_mul(&UnsignedNumeric::new(step as u128)?)?
+        .signed()
+        .exp()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1206,6 +1796,224 @@ mod tests {
         assert_approx_eq(&tokens, &expected_10_5, 100);
     }
 
+    #[test]
+    fn test_computed_curve_matches_tables_for_default_params() {
+        let table = DiscreteExponentialCurve::default();
+        let computed = DiscreteExponentialCurve::computed(&CurveParams::default()).unwrap();
+
+        for step in [0, 1, 2, 1_000, 52_500, 105_000, 157_500, 209_999, 210_000] {
+            let table_price = table.price_at_step(step).unwrap();
+            let computed_price = computed.price_at_step(step).unwrap();
+            let tolerance = table_price.value.as_u128() / 10_000_000_000; // 1e-10 relative
+            assert_approx_eq(&computed_price, &table_price, tolerance.max(1_000_000));
+
+            let table_value = table.cumulative_value_at_step(step).unwrap();
+            let computed_value = computed.cumulative_value_at_step(step).unwrap();
+            let tolerance = table_value.value.as_u128() / 10_000_000_000;
+            assert_approx_eq(&computed_value, &table_value, tolerance.max(1_000_000));
+        }
+
+        assert!(computed.price_at_step(210_001).is_none());
+    }
+
+    #[test]
+    fn test_computed_curve_roundtrip() {
+        let curve = DiscreteExponentialCurve::from_params(&CurveParams {
+            kind: CurveKind::DiscreteExponential,
+            start_price: 1_000_000_000,           // $1
+            end_price: 1_000_000_000_000,         // $1_000
+            max_supply: 1_000_000,
+            step_size: 10,
+        }).unwrap();
+        let zero = UnsignedNumeric::zero();
+
+        for tokens in [1u128, 15, 1_234, 500_000, 999_999, 1_000_000] {
+            let tokens = UnsignedNumeric::new(tokens).unwrap();
+            let value = curve.tokens_to_value(&zero, &tokens).unwrap();
+            let back = curve.value_to_tokens(&zero, &value).unwrap();
+            assert_approx_eq(&back, &tokens, 1_000_000);
+        }
+
+        let max_value = curve.max_cumulative_value().unwrap();
+        let max_supply = curve.max_supply().unwrap();
+        assert_approx_eq(&curve.tokens_to_value(&zero, &max_supply).unwrap(), &max_value, 1_000_000_000_000);
+        assert!(curve.spot_price_at_supply(&max_supply).is_some());
+        assert!(curve.tokens_to_value(&zero, &max_supply.checked_add(&UnsignedNumeric::new(10).unwrap()).unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_flat_curve() {
+        let curve = DiscreteExponentialCurve::from_params(&CurveParams {
+            kind: CurveKind::DiscreteExponential,
+            start_price: 1_000_000_000,           // $1
+            end_price: 1_000_000_000,
+            max_supply: 1_000_000,
+            step_size: 1_000,
+        }).unwrap();
+        let zero = UnsignedNumeric::zero();
+        let one = UnsignedNumeric::one();
+        let tokens = UnsignedNumeric::new(12_345).unwrap();
+
+        assert_eq!(curve.spot_price_at_supply(&tokens).unwrap(), one);
+        assert_eq!(curve.tokens_to_value(&zero, &tokens).unwrap(), tokens);
+        assert_eq!(curve.value_to_tokens(&zero, &tokens).unwrap(), tokens);
+    }
+
+    #[test]
+    fn test_invalid_curve_params() {
+        let valid = CurveParams {
+            kind: CurveKind::DiscreteExponential,
+            start_price: 1_000_000_000,
+            end_price: 2_000_000_000,
+            max_supply: 1_000_000,
+            step_size: 100,
+        };
+        assert!(DiscreteExponentialCurve::from_params(&valid).is_some());
+
+        for params in [
+            CurveParams { step_size: 0, ..valid },
+            CurveParams { step_size: 300, ..valid },
+            CurveParams { start_price: 0, ..valid },
+            CurveParams { end_price: valid.start_price - 1, ..valid },
+        ] {
+            assert!(DiscreteExponentialCurve::from_params(&params).is_none(), "{:?}", params);
+        }
+    }
+
+    fn shaped_params(kind: CurveKind) -> CurveParams {
+        CurveParams {
+            kind,
+            start_price: 1_000_000_000,           // $1
+            end_price: 3_000_000_000,             // $3
+            max_supply: 1_000_000,
+            step_size: 100,
+        }
+    }
+
+    #[test]
+    fn test_linear_curve() {
+        let curve = LinearCurve::from_params(&shaped_params(CurveKind::Linear)).unwrap();
+        let zero = UnsignedNumeric::zero();
+        let max_supply = UnsignedNumeric::new(1_000_000).unwrap();
+
+        assert_eq!(curve.spot_price_at_supply(&zero).unwrap(), UnsignedNumeric::one());
+        assert_approx_eq(&curve.spot_price_at_supply(&max_supply).unwrap(), &UnsignedNumeric::new(3).unwrap(), 1_000);
+
+        // Average price of $2 across the whole curve
+        let total = curve.tokens_to_value(&zero, &max_supply).unwrap();
+        assert_approx_eq(&total, &UnsignedNumeric::new(2_000_000).unwrap(), 1_000_000);
+
+        let supply = UnsignedNumeric::new(250_000).unwrap();
+        for tokens in [1u128, 1_234, 500_000, 750_000] {
+            let tokens = UnsignedNumeric::new(tokens).unwrap();
+            let value = curve.tokens_to_value(&supply, &tokens).unwrap();
+            let back = curve.value_to_tokens(&supply, &value).unwrap();
+            assert_approx_eq(&back, &tokens, 1_000_000);
+        }
+    }
+
+    #[test]
+    fn test_sigmoid_curve() {
+        let curve = SigmoidCurve::from_params(&shaped_params(CurveKind::Sigmoid)).unwrap();
+        let zero = UnsignedNumeric::zero();
+        let midpoint = UnsignedNumeric::new(500_000).unwrap();
+
+        // Halfway between start and end price at the midpoint
+        assert_approx_eq(&curve.spot_price_at_supply(&midpoint).unwrap(), &UnsignedNumeric::new(2).unwrap(), 1_000);
+
+        let mut last = zero.clone();
+        for supply in (0..=1_000_000u128).step_by(50_000) {
+            let price = curve.spot_price_at_supply(&UnsignedNumeric::new(supply).unwrap()).unwrap();
+            assert!(price.greater_than(&last), "price must rise at {}", supply);
+            last = price;
+        }
+
+        // Symmetric around the midpoint, so the average price is also $2
+        let total = curve.tokens_to_value(&zero, &UnsignedNumeric::new(1_000_000).unwrap()).unwrap();
+        assert_approx_eq(&total, &UnsignedNumeric::new(2_000_000).unwrap(), 1_000_000_000);
+
+        let supply = UnsignedNumeric::new(100_000).unwrap();
+        for tokens in [1u128, 1_234, 400_000, 900_000] {
+            let tokens = UnsignedNumeric::new(tokens).unwrap();
+            let value = curve.tokens_to_value(&supply, &tokens).unwrap();
+            let back = curve.value_to_tokens(&supply, &value).unwrap();
+            assert_approx_eq(&back, &tokens, 1_000_000_000);
+        }
+    }
+
+    #[test]
+    fn test_sigmoid_value_to_tokens_never_overshoots() {
+        use rand::{Rng, SeedableRng};
+
+        let curve = SigmoidCurve::from_params(&shaped_params(CurveKind::Sigmoid)).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(29);
+
+        for _ in 0..500 {
+            let supply = UnsignedNumeric::new(rng.gen_range(0..900_000u128)).unwrap();
+            let value = UnsignedNumeric::from_scaled_u128(rng.gen_range(1..100_000_000_000_000_000_000_000u128)); // Up to $100,000
+
+            let tokens = curve.value_to_tokens(&supply, &value).unwrap();
+            let cost = curve.tokens_to_value(&supply, &tokens).unwrap();
+            assert!(
+                cost.less_than_or_equal(&value),
+                "{:?} tokens at supply {:?} cost {:?}, more than {:?}",
+                tokens, supply, cost, value,
+            );
+        }
+    }
+
+    #[test]
+    fn test_sigmoid_value_to_tokens_on_steep_curves() {
+        // Buys solve from zero supply for the pool's whole value, so even the
+        // steepest curve a pool accepts has to round trip up to max supply
+        let zero = UnsignedNumeric::zero();
+
+        for ratio in [100, 1_000_000, MAX_CURVE_PRICE_RATIO] {
+            let params = CurveParams { end_price: 1_000_000_000 * ratio, ..shaped_params(CurveKind::Sigmoid) };
+            let curve = PoolCurve::from_params(&params).unwrap();
+
+            for tokens in [1u128, 500_000, 800_000, 950_000, 1_000_000] {
+                let tokens = UnsignedNumeric::new(tokens).unwrap();
+                let value = curve.tokens_to_value(&zero, &tokens).unwrap();
+
+                let back = curve.value_to_tokens(&zero, &value)
+                    .unwrap_or_else(|| panic!("no solution for {:?} at ratio {}", tokens, ratio));
+                assert_approx_eq(&back, &tokens, 1_000_000_000);
+                assert!(curve.tokens_to_value(&zero, &back).unwrap().less_than_or_equal(&value));
+            }
+
+            // More than the rest of the supply costs has no solution
+            let all = curve.tokens_to_value(&zero, &UnsignedNumeric::new(1_000_000).unwrap()).unwrap();
+            let too_much = all.checked_add(&UnsignedNumeric::one()).unwrap();
+            assert!(curve.value_to_tokens(&zero, &too_much).is_none());
+        }
+    }
+
+    #[test]
+    fn test_pool_curve_dispatch() {
+        let zero = UnsignedNumeric::zero();
+        let tokens = UnsignedNumeric::new(10_000).unwrap();
+
+        for kind in [
+            CurveKind::DiscreteExponential,
+            CurveKind::Exponential,
+            CurveKind::Linear,
+            CurveKind::Sigmoid,
+        ] {
+            let params = shaped_params(kind);
+            let curve = PoolCurve::from_params(&params).unwrap();
+            let value = curve.tokens_to_value(&zero, &tokens).unwrap();
+
+            // Early tokens on every shape cost close to the start price
+            assert!(value.greater_than_or_equal(&tokens), "{:?}", kind);
+            assert!(value.less_than(&tokens.checked_mul(&UnsignedNumeric::new(2).unwrap()).unwrap()), "{:?}", kind);
+        }
+
+        let flat = CurveParams { end_price: 1_000_000_000, ..shaped_params(CurveKind::Exponential) };
+        assert!(PoolCurve::from_params(&flat).is_none());
+        assert!(PoolCurve::from_params(&CurveParams { kind: CurveKind::Linear, ..flat }).is_some());
+    }
+
     #[test]
     #[ignore]
     fn generate_discrete_curve_table() {
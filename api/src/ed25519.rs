@@ -0,0 +1,94 @@
+use steel::*;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const DATA_START: usize = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN;
+const PUBKEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Data for an Ed25519 program instruction that checks one signature, with
+/// the key, signature and message all in the instruction itself.
+pub fn ed25519_instruction_data(pubkey: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Vec<u8> {
+    let pubkey_offset = DATA_START;
+    let signature_offset = pubkey_offset + PUBKEY_LEN;
+    let message_offset = signature_offset + SIGNATURE_LEN;
+
+    let offsets = [
+        signature_offset as u16,
+        CURRENT_INSTRUCTION,
+        pubkey_offset as u16,
+        CURRENT_INSTRUCTION,
+        message_offset as u16,
+        message.len() as u16,
+        CURRENT_INSTRUCTION,
+    ];
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.extend_from_slice(&[1, 0]); // One signature, then padding
+    for offset in offsets {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    data
+}
+
+/// Key and message of the single signature an Ed25519 program instruction
+/// checked. The runtime has already failed the transaction if the signature
+/// is invalid, so only the layout is checked here. Instructions that check
+/// several signatures, or read any of them from another instruction, are
+/// rejected.
+pub fn ed25519_signed_message(data: &[u8]) -> Option<(Pubkey, &[u8])> {
+    if data.len() < DATA_START || data[0] != 1 {
+        return None;
+    }
+
+    let offsets: Vec<u16> = data[SIGNATURE_OFFSETS_START..DATA_START]
+        .chunks_exact(2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .collect();
+    let [_, signature_ix, pubkey_offset, pubkey_ix, message_offset, message_len, message_ix] = offsets[..] else {
+        return None;
+    };
+
+    if signature_ix != CURRENT_INSTRUCTION || pubkey_ix != CURRENT_INSTRUCTION || message_ix != CURRENT_INSTRUCTION {
+        return None;
+    }
+
+    let pubkey_offset = pubkey_offset as usize;
+    let pubkey = data.get(pubkey_offset..pubkey_offset + PUBKEY_LEN)?;
+    let message_offset = message_offset as usize;
+    let message = data.get(message_offset..message_offset + message_len as usize)?;
+
+    Some((Pubkey::new_from_array(pubkey.try_into().ok()?), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ed25519_signed_message() {
+        let pubkey = Pubkey::new_unique();
+        let message = b"hello";
+        let data = ed25519_instruction_data(&pubkey, &[7; 64], message);
+
+        assert_eq!(ed25519_signed_message(&data), Some((pubkey, &message[..])));
+
+        // Offsets into other instructions aren't trusted
+        let mut other_ix = data.clone();
+        other_ix[4..6].copy_from_slice(&0u16.to_le_bytes());
+        assert_eq!(ed25519_signed_message(&other_ix), None);
+
+        // Nor are several signatures
+        let mut several = data.clone();
+        several[0] = 2;
+        assert_eq!(ed25519_signed_message(&several), None);
+
+        // Nor a message running past the end
+        assert_eq!(ed25519_signed_message(&data[..data.len() - 1]), None);
+        assert_eq!(ed25519_signed_message(&data[..DATA_START - 1]), None);
+    }
+}
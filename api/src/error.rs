@@ -0,0 +1,31 @@
+use steel::*;
+
+#[repr(u32)]
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq, IntoPrimitive)]
+pub enum FlipcashError {
+    #[error("Currency name is empty")]
+    NameEmpty = 0,
+    #[error("Currency name is too long")]
+    NameTooLong = 1,
+    #[error("Currency name contains an invalid character")]
+    NameInvalidCharacter = 2,
+    #[error("Currency name has leading or trailing whitespace")]
+    NameNotTrimmed = 3,
+
+    #[error("Currency symbol is empty")]
+    SymbolEmpty = 4,
+    #[error("Currency symbol is too long")]
+    SymbolTooLong = 5,
+    #[error("Currency symbol contains an invalid character")]
+    SymbolInvalidCharacter = 6,
+    #[error("Currency symbol must be uppercase")]
+    SymbolNotUppercase = 7,
+
+    #[error("Trade would move the price outside the circuit breaker band")]
+    PriceBandExceeded = 8,
+
+    #[error("Vesting schedule is invalid")]
+    InvalidVestingSchedule = 9,
+}
+
+error!(FlipcashError);
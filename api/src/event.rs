@@ -1,5 +1,6 @@
 use bytemuck::{Pod, Zeroable};
 use num_enum::TryFromPrimitive;
+use solana_program::pubkey::Pubkey;
 use crate::event;
 
 #[repr(u8)]
@@ -9,6 +10,14 @@ pub enum EventType {
 
     BuyEvent,
     SellEvent,
+    PauseEvent,
+    ResumeEvent,
+    CircuitBreakerEvent,
+    PoolCreatedEvent,
+    BatchBuyEvent,
+    LimitOrderEvent,
+    DcaFillEvent,
+    DistributorClaimEvent,
 }
 
 #[repr(C)]
@@ -20,8 +29,289 @@ pub struct BuyEvent {
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 pub struct SellEvent {
-    // todo
+    pub pool: Pubkey,
+    pub seller: Pubkey,
+    pub in_amount: u64,           // Currency quarks sold
+    pub out_amount: u64,          // Base quarks received after fees
+    pub fee_amount: u64,          // Base quarks retained as fees
+    pub sell_fee: u16,            // Basis points actually applied
+    pub fee_tier: u8,             // Index into FeeSchedule tiers, or NO_FEE_TIER
+    pub fee_exempt: u8,           // 1 if the seller was on the exemption list
+
+    _padding: [u8; 4],
+}
+
+impl SellEvent {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        pool: Pubkey,
+        seller: Pubkey,
+        in_amount: u64,
+        out_amount: u64,
+        fee_amount: u64,
+        sell_fee: u16,
+        fee_tier: u8,
+        fee_exempt: bool,
+    ) -> Self {
+        Self {
+            pool,
+            seller,
+            in_amount,
+            out_amount,
+            fee_amount,
+            sell_fee,
+            fee_tier,
+            fee_exempt: fee_exempt as u8,
+            _padding: [0; 4],
+        }
+    }
+}
+
+/// Logged when a pool's paused trades change.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct PauseEvent {
+    pub pool: Pubkey,
+    pub authority: Pubkey,        // Pool authority or protocol admin that paused it
+    pub paused: u8,               // PAUSE_* bits now in effect
+
+    _padding: [u8; 7],
+}
+
+impl PauseEvent {
+    pub fn new(pool: Pubkey, authority: Pubkey, paused: u8) -> Self {
+        Self {
+            pool,
+            authority,
+            paused,
+            _padding: [0; 7],
+        }
+    }
+}
+
+/// Logged when a paused pool resumes trading.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ResumeEvent {
+    pub pool: Pubkey,
+    pub authority: Pubkey,        // Pool authority or protocol admin that resumed it
+}
+
+impl ResumeEvent {
+    pub fn new(pool: Pubkey, authority: Pubkey) -> Self {
+        Self {
+            pool,
+            authority,
+        }
+    }
+}
+
+/// Logged when a trade is rejected for moving the price outside the
+/// circuit breaker band. It is only visible in the failed transaction's logs.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CircuitBreakerEvent {
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    pub reference_price: [u8; 16], // Raw UnsignedNumeric spot price when the window opened
+    pub price: [u8; 16],           // Raw UnsignedNumeric spot price the trade would have left
+    pub window_slot: u64,          // Slot the window opened
+    pub band: u16,                 // Basis points
+
+    _padding: [u8; 6],
+}
+
+impl CircuitBreakerEvent {
+    pub fn new(
+        pool: Pubkey,
+        trader: Pubkey,
+        reference_price: u128,
+        price: u128,
+        window_slot: u64,
+        band: u16,
+    ) -> Self {
+        Self {
+            pool,
+            trader,
+            reference_price: reference_price.to_le_bytes(),
+            price: price.to_le_bytes(),
+            window_slot,
+            band,
+            _padding: [0; 6],
+        }
+    }
+}
+
+/// Logged when a pool is created, along with the authority's initial buy
+/// if it made one.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct PoolCreatedEvent {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub currency: Pubkey,
+    pub base_mint: Pubkey,
+    pub initial_buy_in: u64,      // Base quarks the authority spent, 0 if it didn't buy
+    pub initial_buy_out: u64,     // Currency quarks the authority received
+}
+
+impl PoolCreatedEvent {
+    pub fn new(
+        pool: Pubkey,
+        authority: Pubkey,
+        currency: Pubkey,
+        base_mint: Pubkey,
+        initial_buy_in: u64,
+        initial_buy_out: u64,
+    ) -> Self {
+        Self {
+            pool,
+            authority,
+            currency,
+            base_mint,
+            initial_buy_in,
+            initial_buy_out,
+        }
+    }
+}
+
+/// Logged for each recipient paid by a batch buy.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct BatchBuyEvent {
+    pub pool: Pubkey,
+    pub buyer: Pubkey,
+    pub recipient: Pubkey,        // Token account paid
+    pub amount: u64,              // Currency quarks paid to the recipient
+    pub share: u32,               // Recipient's share of the batch
+
+    _padding: [u8; 4],
+}
+
+impl BatchBuyEvent {
+    pub fn new(
+        pool: Pubkey,
+        buyer: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        share: u32,
+    ) -> Self {
+        Self {
+            pool,
+            buyer,
+            recipient,
+            amount,
+            share,
+            _padding: [0; 4],
+        }
+    }
+}
+
+/// Logged when a limit order fills.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct LimitOrderEvent {
+    pub pool: Pubkey,
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub executor: Pubkey,         // Paid the bounty
+    pub in_amount: u64,           // Quarks spent from the escrow
+    pub out_amount: u64,          // Quarks paid to the owner
+    pub bounty: u64,              // Lamports paid to the executor
+    pub side: u8,                 // OrderSide
+
+    _padding: [u8; 7],
+}
+
+impl LimitOrderEvent {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        pool: Pubkey,
+        order: Pubkey,
+        owner: Pubkey,
+        executor: Pubkey,
+        in_amount: u64,
+        out_amount: u64,
+        bounty: u64,
+        side: u8,
+    ) -> Self {
+        Self {
+            pool,
+            order,
+            owner,
+            executor,
+            in_amount,
+            out_amount,
+            bounty,
+            side,
+            _padding: [0; 7],
+        }
+    }
+}
+
+/// Logged for each fill of a DCA plan.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct DcaFillEvent {
+    pub pool: Pubkey,
+    pub plan: Pubkey,
+    pub owner: Pubkey,
+    pub in_amount: u64,           // Base quarks spent
+    pub out_amount: u64,          // Currency quarks paid to the owner
+    pub fill: u64,                // Fills so far, this one included
+    pub next_fill_at: i64,        // Unix timestamp the next fill is due
+}
+
+impl DcaFillEvent {
+    pub fn new(
+        pool: Pubkey,
+        plan: Pubkey,
+        owner: Pubkey,
+        in_amount: u64,
+        out_amount: u64,
+        fill: u64,
+        next_fill_at: i64,
+    ) -> Self {
+        Self {
+            pool,
+            plan,
+            owner,
+            in_amount,
+            out_amount,
+            fill,
+            next_fill_at,
+        }
+    }
+}
+
+/// Logged for each claim from a distributor.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct DistributorClaimEvent {
+    pub distributor: Pubkey,
+    pub recipient: Pubkey,
+    pub index: u64,               // Recipient's entry in the list
+    pub amount: u64,              // Currency quarks claimed
+}
+
+impl DistributorClaimEvent {
+    pub fn new(distributor: Pubkey, recipient: Pubkey, index: u64, amount: u64) -> Self {
+        Self {
+            distributor,
+            recipient,
+            index,
+            amount,
+        }
+    }
 }
 
 event!(EventType, BuyEvent);
 event!(EventType, SellEvent);
+event!(EventType, PauseEvent);
+event!(EventType, ResumeEvent);
+event!(EventType, CircuitBreakerEvent);
+event!(EventType, PoolCreatedEvent);
+event!(EventType, BatchBuyEvent);
+event!(EventType, LimitOrderEvent);
+event!(EventType, DcaFillEvent);
+event!(EventType, DistributorClaimEvent);
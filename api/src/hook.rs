@@ -0,0 +1,184 @@
+//! Interface for post-trade hooks. A pool authority may register a hook
+//! program that the Flipcash program CPIs after every buy and sell on the
+//! pool, with a `TradeSummary` as instruction data. The accounts are:
+//!
+//! 0. `[signer]` The pool's trade hook authority, `find_trade_hook_authority_pda(pool)`
+//! 1. `[]` The pool
+//! 2. Every account the trader passed after the hook program and its
+//!    authority, never as signers. The hook's own accounts come first, then
+//!    any transfer hook accounts of the base mint
+//!
+//! The hook runs once the trade is priced and the trader has paid, before
+//! the trader is paid out. Hooks should check the first two accounts with
+//! `check_trade_hook_accounts` before trusting the summary. A hook may use
+//! at most the pool's `hook_compute_units`, or the whole trade fails.
+
+use brine_fp::UnsignedNumeric;
+use steel::*;
+use crate::consts::TRADE_HOOK;
+use crate::state::{LiquidityPool, OrderSide};
+
+/// Leads a trade hook's instruction data: sha256("flipcash:on_trade")[..8].
+pub const TRADE_HOOK_DISCRIMINATOR: [u8; 8] = [45, 3, 203, 236, 90, 72, 208, 250];
+
+/// What a hook program is told about a trade, after it has been settled
+/// against the pool.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct TradeSummary {
+    pub pool: Pubkey,
+    pub trader: Pubkey,           // Wallet, or escrow PDA for cranked orders
+    pub mint_a: Pubkey,           // Currency
+    pub mint_b: Pubkey,           // Base
+
+    pub target_amount: u64,       // Currency quarks bought or sold
+    pub base_amount: u64,         // Base quarks paid, or received after fees when selling
+    pub fee_amount: u64,          // Base quarks of sell fee, 0 for buys
+    pub slot: u64,
+    pub timestamp: i64,           // Unix timestamp
+    pub price_after: [u8; 16],    // Raw UnsignedNumeric spot price after the trade
+
+    pub side: u8,                 // OrderSide
+
+    _padding: [u8; 7],
+}
+
+impl TradeSummary {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        pool: Pubkey,
+        trader: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        side: OrderSide,
+        target_amount: u64,
+        base_amount: u64,
+        fee_amount: u64,
+        price_after: u128,
+        slot: u64,
+        timestamp: i64,
+    ) -> Self {
+        Self {
+            pool,
+            trader,
+            mint_a,
+            mint_b,
+            target_amount,
+            base_amount,
+            fee_amount,
+            slot,
+            timestamp,
+            price_after: price_after.to_le_bytes(),
+            side: side.into(),
+            _padding: [0; 7],
+        }
+    }
+
+    pub fn side(&self) -> Result<OrderSide, ProgramError> {
+        OrderSide::try_from(self.side)
+            .map_err(|_| ProgramError::InvalidInstructionData)
+    }
+
+    pub fn price_after(&self) -> UnsignedNumeric {
+        UnsignedNumeric::from_scaled_u128(u128::from_le_bytes(self.price_after))
+    }
+
+    /// Instruction data for a hook CPI.
+    pub fn to_instruction_data(&self) -> Vec<u8> {
+        [TRADE_HOOK_DISCRIMINATOR.as_slice(), bytemuck::bytes_of(self)].concat()
+    }
+
+    /// Reads a summary back out of a hook's instruction data. Instruction
+    /// data isn't aligned, so the summary is copied out.
+    pub fn from_instruction_data(data: &[u8]) -> Result<Self, ProgramError> {
+        let Some(summary) = data.strip_prefix(&TRADE_HOOK_DISCRIMINATOR) else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        bytemuck::try_pod_read_unaligned(summary)
+            .map_err(|_| ProgramError::InvalidInstructionData)
+    }
+}
+
+/// The CPI the Flipcash program makes into a pool's hook. Extra accounts
+/// keep their writability but are never passed as signers.
+pub fn trade_hook_instruction(
+    hook_program: Pubkey,
+    hook_authority: Pubkey,
+    pool: Pubkey,
+    summary: &TradeSummary,
+    extra_accounts: &[AccountInfo],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(hook_authority, true),
+        AccountMeta::new_readonly(pool, false),
+    ];
+    accounts.extend(extra_accounts.iter().map(|info| AccountMeta {
+        pubkey: *info.key,
+        is_signer: false,
+        is_writable: info.is_writable,
+    }));
+
+    Instruction {
+        program_id: hook_program,
+        accounts,
+        data: summary.to_instruction_data(),
+    }
+}
+
+/// For hook programs: checks that a call really came from the Flipcash
+/// program for the summary's pool. Uses the bump stored in the pool rather
+/// than searching for it, to stay within the compute budget.
+pub fn check_trade_hook_accounts(
+    hook_authority_info: &AccountInfo,
+    pool_info: &AccountInfo,
+    summary: &TradeSummary,
+) -> ProgramResult {
+    let pool = pool_info.as_account::<LiquidityPool>(&crate::ID)?;
+    let hook_authority = Pubkey::create_program_address(
+        &[TRADE_HOOK, pool_info.key.as_ref(), &[pool.hook_authority_bump]],
+        &crate::ID,
+    ).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    if !hook_authority_info.is_signer ||
+        hook_authority_info.key.ne(&hook_authority) ||
+        pool_info.key.ne(&summary.pool) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trade_summary_round_trip() {
+        let summary = TradeSummary::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            OrderSide::Sell,
+            1_000,
+            250,
+            5,
+            42 << 64,
+            7,
+            1_700_000_000,
+        );
+
+        let data = summary.to_instruction_data();
+        assert_eq!(data.len(), 8 + 192);
+        assert_eq!(TradeSummary::from_instruction_data(&data).unwrap(), summary);
+        assert_eq!(summary.side().unwrap(), OrderSide::Sell);
+
+        // Unaligned data still parses
+        let shifted = [&[0u8][..], &data].concat();
+        assert_eq!(TradeSummary::from_instruction_data(&shifted[1..]).unwrap(), summary);
+
+        assert!(TradeSummary::from_instruction_data(&data[1..]).is_err());
+        assert!(TradeSummary::from_instruction_data(&data[..100]).is_err());
+    }
+}
@@ -13,6 +13,45 @@ pub enum InstructionType {
     BuyAndDepositIntoVmIx,
     SellAndDepositIntoVmIx,
     BurnFeesIx,
+    InitializeFeeScheduleIx,
+    SetFeeExemptionIx,
+    SetFeeTiersIx,
+    InitializeTraderVolumeIx,
+    SetBurnPolicyIx,
+    MigratePoolIx,
+    InitializeCurveTableIx,
+    WriteCurveTableIx,
+    FinalizeCurveTableIx,
+    MigrateCurrencyIx,
+    InitializeProtocolConfigIx,
+    UpdateProtocolConfigIx,
+    SetBaseMintAllowedIx,
+    InitializeCurrencyProfileIx,
+    SetProfileDescriptionIx,
+    SetProfileLinkIx,
+    PauseIx,
+    ResumeIx,
+    SetCircuitBreakerIx,
+    SetLaunchIx,
+    InitializeWalletPurchasesIx,
+    JoinPresaleIx,
+    ClaimVestedIx,
+    BatchBuyIx,
+    CreateCashLinkIx,
+    ClaimCashLinkIx,
+    ReclaimCashLinkIx,
+    CreateLimitOrderIx,
+    ExecuteLimitOrderIx,
+    CancelLimitOrderIx,
+    CreateDcaPlanIx,
+    DepositDcaIx,
+    WithdrawDcaIx,
+    CloseDcaPlanIx,
+    ExecuteDcaIx,
+    CreateDistributorIx,
+    ClaimDistributionIx,
+    ClawbackDistributorIx,
+    SetTradeHookIx,
 }
 
 instruction!(InstructionType, InitializeCurrencyIx);
@@ -23,15 +62,59 @@ instruction!(InstructionType, SellTokensIx);
 instruction!(InstructionType, BuyAndDepositIntoVmIx);
 instruction!(InstructionType, SellAndDepositIntoVmIx);
 instruction!(InstructionType, BurnFeesIx);
+instruction!(InstructionType, InitializeFeeScheduleIx);
+instruction!(InstructionType, SetFeeExemptionIx);
+instruction!(InstructionType, SetFeeTiersIx);
+instruction!(InstructionType, InitializeTraderVolumeIx);
+instruction!(InstructionType, SetBurnPolicyIx);
+instruction!(InstructionType, MigratePoolIx);
+instruction!(InstructionType, InitializeCurveTableIx);
+instruction!(InstructionType, WriteCurveTableIx);
+instruction!(InstructionType, FinalizeCurveTableIx);
+instruction!(InstructionType, MigrateCurrencyIx);
+instruction!(InstructionType, InitializeProtocolConfigIx);
+instruction!(InstructionType, UpdateProtocolConfigIx);
+instruction!(InstructionType, SetBaseMintAllowedIx);
+instruction!(InstructionType, InitializeCurrencyProfileIx);
+instruction!(InstructionType, SetProfileDescriptionIx);
+instruction!(InstructionType, SetProfileLinkIx);
+instruction!(InstructionType, PauseIx);
+instruction!(InstructionType, ResumeIx);
+instruction!(InstructionType, SetCircuitBreakerIx);
+instruction!(InstructionType, SetLaunchIx);
+instruction!(InstructionType, InitializeWalletPurchasesIx);
+instruction!(InstructionType, JoinPresaleIx);
+instruction!(InstructionType, ClaimVestedIx);
+instruction!(InstructionType, BatchBuyIx);
+instruction!(InstructionType, CreateCashLinkIx);
+instruction!(InstructionType, ClaimCashLinkIx);
+instruction!(InstructionType, ReclaimCashLinkIx);
+instruction!(InstructionType, CreateLimitOrderIx);
+instruction!(InstructionType, ExecuteLimitOrderIx);
+instruction!(InstructionType, CancelLimitOrderIx);
+instruction!(InstructionType, CreateDcaPlanIx);
+instruction!(InstructionType, DepositDcaIx);
+instruction!(InstructionType, WithdrawDcaIx);
+instruction!(InstructionType, CloseDcaPlanIx);
+instruction!(InstructionType, ExecuteDcaIx);
+instruction!(InstructionType, CreateDistributorIx);
+instruction!(InstructionType, ClaimDistributionIx);
+instruction!(InstructionType, ClawbackDistributorIx);
+instruction!(InstructionType, SetTradeHookIx);
 
 #[derive(Debug)]
 pub struct ParsedInitializeCurrencyIx {
-    pub name: String,
-    pub symbol: String,
+    pub name: CurrencyName,
+    pub symbol: CurrencySymbol,
     pub seed: [u8; 32],
+    pub decimals: u8,
+    pub max_supply: u64,
 
     pub bump: u8,
     pub mint_bump: u8,
+    pub symbol_bump: u8,
+    pub registry_bump: u8,
+    pub registry_page_bump: u8,
 }
 
 #[repr(C)]
@@ -43,37 +126,49 @@ pub struct InitializeCurrencyIx {
 
     pub bump: u8,
     pub mint_bump: u8,
-    _padding: [u8; 6],
+    pub decimals: u8,
+    pub symbol_bump: u8,
+    pub registry_bump: u8,
+    pub registry_page_bump: u8,
+    _padding: [u8; 2],
+    pub max_supply: [u8; 8],
 }
 
 impl InitializeCurrencyIx {
     pub fn from_struct(parsed: ParsedInitializeCurrencyIx) -> Self {
-        let name = to_name(&parsed.name);
-        let symbol = to_symbol(&parsed.symbol);
-
         Self {
-            name,
-            symbol,
+            name: *parsed.name.as_bytes(),
+            symbol: *parsed.symbol.as_bytes(),
             seed: parsed.seed,
 
             bump: parsed.bump,
             mint_bump: parsed.mint_bump,
-            _padding: [0; 6],
+            decimals: parsed.decimals,
+            symbol_bump: parsed.symbol_bump,
+            registry_bump: parsed.registry_bump,
+            registry_page_bump: parsed.registry_page_bump,
+            _padding: [0; 2],
+            max_supply: parsed.max_supply.to_le_bytes(),
         }
     }
 
-    pub fn to_struct(&self) -> Result<ParsedInitializeCurrencyIx, std::io::Error> {
-        let name = from_name(&self.name);
-        let symbol = from_symbol(&self.symbol);
+    pub fn to_struct(&self) -> Result<ParsedInitializeCurrencyIx, FlipcashError> {
+        let name = CurrencyName::from_bytes(&self.name)?;
+        let symbol = CurrencySymbol::from_bytes(&self.symbol)?;
 
         Ok(ParsedInitializeCurrencyIx {
             name,
             symbol,
 
             seed: self.seed,
+            decimals: self.decimals,
+            max_supply: u64::from_le_bytes(self.max_supply),
 
             bump: self.bump,
             mint_bump: self.mint_bump,
+            symbol_bump: self.symbol_bump,
+            registry_bump: self.registry_bump,
+            registry_page_bump: self.registry_page_bump,
         })
     }
 }
@@ -81,42 +176,113 @@ impl InitializeCurrencyIx {
 #[derive(Debug)]
 pub struct ParsedInitializePoolIx {
     pub sell_fee: u16,
+    pub burn_policy: FeeBurnPolicy,
+    pub burn_cooldown: i64,
+    pub curve: CurveParams,
+    pub allowed_extensions: u8,
+    pub initial_buy_amount: u64, // Base quarks the authority spends on the new pool, 0 for none
+    pub vesting_cliff: i64,      // Seconds after creation before any of the initial buy vests
+    pub vesting_duration: i64,   // Seconds over which the initial buy vests, 0 to pay it out now
+    pub launch_at: i64,          // As in SetLaunch, applied before the initial buy
+    pub presale_cap: u64,
+    pub presale_root: [u8; 32],
+    pub launch_window: u64,
+    pub launch_cap: u64,
 
     pub bump: u8,
     pub vault_a_bump: u8,
     pub vault_b_bump: u8,
+    pub vesting_bump: u8,
+    pub vesting_vault_bump: u8,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct InitializePoolIx {
     pub sell_fee: [u8; 2],
+    pub burn_cooldown: [u8; 8],
+    pub curve_start_price: [u8; 8],
+    pub curve_end_price: [u8; 8],
+    pub curve_max_supply: [u8; 8],
+    pub curve_step_size: [u8; 8],
+    pub initial_buy_amount: [u8; 8],
+    pub vesting_cliff: [u8; 8],
+    pub vesting_duration: [u8; 8],
+    pub launch_at: [u8; 8],
+    pub presale_cap: [u8; 8],
+    pub presale_root: [u8; 32],
+    pub launch_window: [u8; 8],
+    pub launch_cap: [u8; 8],
+    pub curve_kind: u8,
+    pub burn_policy: u8,
+    pub allowed_extensions: u8,
 
     pub bump: u8,
     pub vault_a_bump: u8,
     pub vault_b_bump: u8,
-    _padding: [u8; 1],
+    pub vesting_bump: u8,
+    pub vesting_vault_bump: u8,
 }
 
 impl InitializePoolIx {
     pub fn from_struct(parsed: ParsedInitializePoolIx) -> Self {
         Self {
             sell_fee: parsed.sell_fee.to_le_bytes(),
+            burn_cooldown: parsed.burn_cooldown.to_le_bytes(),
+            curve_start_price: parsed.curve.start_price.to_le_bytes(),
+            curve_end_price: parsed.curve.end_price.to_le_bytes(),
+            curve_max_supply: parsed.curve.max_supply.to_le_bytes(),
+            curve_step_size: parsed.curve.step_size.to_le_bytes(),
+            initial_buy_amount: parsed.initial_buy_amount.to_le_bytes(),
+            vesting_cliff: parsed.vesting_cliff.to_le_bytes(),
+            vesting_duration: parsed.vesting_duration.to_le_bytes(),
+            launch_at: parsed.launch_at.to_le_bytes(),
+            presale_cap: parsed.presale_cap.to_le_bytes(),
+            presale_root: parsed.presale_root,
+            launch_window: parsed.launch_window.to_le_bytes(),
+            launch_cap: parsed.launch_cap.to_le_bytes(),
+            curve_kind: parsed.curve.kind.into(),
+            burn_policy: parsed.burn_policy.into(),
+            allowed_extensions: parsed.allowed_extensions,
 
             bump: parsed.bump,
             vault_a_bump: parsed.vault_a_bump,
             vault_b_bump: parsed.vault_b_bump,
-            _padding: [0; 1],
+            vesting_bump: parsed.vesting_bump,
+            vesting_vault_bump: parsed.vesting_vault_bump,
         }
     }
 
     pub fn to_struct(&self) -> Result<ParsedInitializePoolIx, std::io::Error> {
         Ok(ParsedInitializePoolIx {
             sell_fee: u16::from_le_bytes(self.sell_fee),
+            burn_policy: to_burn_policy(self.burn_policy)?,
+            burn_cooldown: i64::from_le_bytes(self.burn_cooldown),
+            curve: CurveParams {
+                kind: CurveKind::try_from(self.curve_kind).map_err(|_| std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Invalid curve kind",
+                ))?,
+                start_price: u64::from_le_bytes(self.curve_start_price),
+                end_price: u64::from_le_bytes(self.curve_end_price),
+                max_supply: u64::from_le_bytes(self.curve_max_supply),
+                step_size: u64::from_le_bytes(self.curve_step_size),
+            },
+            allowed_extensions: self.allowed_extensions,
+            initial_buy_amount: u64::from_le_bytes(self.initial_buy_amount),
+            vesting_cliff: i64::from_le_bytes(self.vesting_cliff),
+            vesting_duration: i64::from_le_bytes(self.vesting_duration),
+            launch_at: i64::from_le_bytes(self.launch_at),
+            presale_cap: u64::from_le_bytes(self.presale_cap),
+            presale_root: self.presale_root,
+            launch_window: u64::from_le_bytes(self.launch_window),
+            launch_cap: u64::from_le_bytes(self.launch_cap),
 
             bump: self.bump,
             vault_a_bump: self.vault_a_bump,
             vault_b_bump: self.vault_b_bump,
+            vesting_bump: self.vesting_bump,
+            vesting_vault_bump: self.vesting_vault_bump,
         })
     }
 }
@@ -286,3 +452,1277 @@ impl BurnFeesIx {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct ParsedInitializeFeeScheduleIx {
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitializeFeeScheduleIx {
+    pub bump: u8,
+}
+
+impl InitializeFeeScheduleIx {
+    pub fn from_struct(parsed: ParsedInitializeFeeScheduleIx) -> Self {
+        Self {
+            bump: parsed.bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedInitializeFeeScheduleIx {
+        ParsedInitializeFeeScheduleIx {
+            bump: self.bump,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedSetFeeExemptionIx {
+    pub exempt: bool,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetFeeExemptionIx {
+    pub exempt: u8,
+}
+
+impl SetFeeExemptionIx {
+    pub fn from_struct(parsed: ParsedSetFeeExemptionIx) -> Self {
+        Self {
+            exempt: parsed.exempt as u8,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedSetFeeExemptionIx {
+        ParsedSetFeeExemptionIx {
+            exempt: self.exempt != 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedSetFeeTiersIx {
+    pub tiers: Vec<FeeTier>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetFeeTiersIx {
+    pub min_volumes: [[u8; 8]; MAX_FEE_TIERS],
+    pub sell_fees: [[u8; 2]; MAX_FEE_TIERS],
+    pub num_tiers: u8,
+}
+
+impl SetFeeTiersIx {
+    pub fn from_struct(parsed: ParsedSetFeeTiersIx) -> Result<Self, ProgramError> {
+        if parsed.tiers.len() > MAX_FEE_TIERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut min_volumes = [[0u8; 8]; MAX_FEE_TIERS];
+        let mut sell_fees = [[0u8; 2]; MAX_FEE_TIERS];
+        for (i, tier) in parsed.tiers.iter().enumerate() {
+            min_volumes[i] = tier.min_volume.to_le_bytes();
+            sell_fees[i] = tier.sell_fee.to_le_bytes();
+        }
+
+        Ok(Self {
+            min_volumes,
+            sell_fees,
+            num_tiers: parsed.tiers.len() as u8,
+        })
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedSetFeeTiersIx, std::io::Error> {
+        if self.num_tiers as usize > MAX_FEE_TIERS {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Too many fee tiers",
+            ));
+        }
+
+        let tiers = (0..self.num_tiers as usize)
+            .map(|i| FeeTier::new(
+                u64::from_le_bytes(self.min_volumes[i]),
+                u16::from_le_bytes(self.sell_fees[i]),
+            ))
+            .collect();
+
+        Ok(ParsedSetFeeTiersIx {
+            tiers,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedInitializeTraderVolumeIx {
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitializeTraderVolumeIx {
+    pub bump: u8,
+}
+
+impl InitializeTraderVolumeIx {
+    pub fn from_struct(parsed: ParsedInitializeTraderVolumeIx) -> Self {
+        Self {
+            bump: parsed.bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedInitializeTraderVolumeIx {
+        ParsedInitializeTraderVolumeIx {
+            bump: self.bump,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedSetBurnPolicyIx {
+    pub burn_policy: FeeBurnPolicy,
+    pub burn_cooldown: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetBurnPolicyIx {
+    pub burn_cooldown: [u8; 8],
+    pub burn_policy: u8,
+}
+
+impl SetBurnPolicyIx {
+    pub fn from_struct(parsed: ParsedSetBurnPolicyIx) -> Self {
+        Self {
+            burn_cooldown: parsed.burn_cooldown.to_le_bytes(),
+            burn_policy: parsed.burn_policy.into(),
+        }
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedSetBurnPolicyIx, std::io::Error> {
+        Ok(ParsedSetBurnPolicyIx {
+            burn_policy: to_burn_policy(self.burn_policy)?,
+            burn_cooldown: i64::from_le_bytes(self.burn_cooldown),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedMigratePoolIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MigratePoolIx {
+}
+
+impl MigratePoolIx {
+    pub fn from_struct(_parsed: ParsedMigratePoolIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedMigratePoolIx {
+        ParsedMigratePoolIx {
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedMigrateCurrencyIx {
+    pub symbol_bump: u8,
+    pub registry_bump: u8,
+    pub registry_page_bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MigrateCurrencyIx {
+    pub symbol_bump: u8,
+    pub registry_bump: u8,
+    pub registry_page_bump: u8,
+}
+
+impl MigrateCurrencyIx {
+    pub fn from_struct(parsed: ParsedMigrateCurrencyIx) -> Self {
+        Self {
+            symbol_bump: parsed.symbol_bump,
+            registry_bump: parsed.registry_bump,
+            registry_page_bump: parsed.registry_page_bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedMigrateCurrencyIx {
+        ParsedMigrateCurrencyIx {
+            symbol_bump: self.symbol_bump,
+            registry_bump: self.registry_bump,
+            registry_page_bump: self.registry_page_bump,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedInitializeCurveTableIx {
+    pub seed: [u8; 32],
+    pub step_size: u64,
+    pub num_steps: u64,
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitializeCurveTableIx {
+    pub seed: [u8; 32],
+    pub step_size: [u8; 8],
+    pub num_steps: [u8; 8],
+    pub bump: u8,
+}
+
+impl InitializeCurveTableIx {
+    pub fn from_struct(parsed: ParsedInitializeCurveTableIx) -> Self {
+        Self {
+            seed: parsed.seed,
+            step_size: parsed.step_size.to_le_bytes(),
+            num_steps: parsed.num_steps.to_le_bytes(),
+            bump: parsed.bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedInitializeCurveTableIx {
+        ParsedInitializeCurveTableIx {
+            seed: self.seed,
+            step_size: u64::from_le_bytes(self.step_size),
+            num_steps: u64::from_le_bytes(self.num_steps),
+            bump: self.bump,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedWriteCurveTableIx {
+    pub entries: Vec<CurveTableEntry>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct WriteCurveTableIx {
+    pub entries: [CurveTableEntry; CURVE_TABLE_CHUNK_LEN],
+    pub num_entries: u8,
+}
+
+impl WriteCurveTableIx {
+    pub fn from_struct(parsed: ParsedWriteCurveTableIx) -> Self {
+        assert!(parsed.entries.len() <= CURVE_TABLE_CHUNK_LEN, "too many curve table entries");
+
+        let mut entries = [CurveTableEntry::default(); CURVE_TABLE_CHUNK_LEN];
+        entries[..parsed.entries.len()].copy_from_slice(&parsed.entries);
+
+        Self {
+            entries,
+            num_entries: parsed.entries.len() as u8,
+        }
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedWriteCurveTableIx, std::io::Error> {
+        if self.num_entries as usize > CURVE_TABLE_CHUNK_LEN {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Too many curve table entries",
+            ));
+        }
+
+        Ok(ParsedWriteCurveTableIx {
+            entries: self.entries[..self.num_entries as usize].to_vec(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedFinalizeCurveTableIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct FinalizeCurveTableIx {
+}
+
+impl FinalizeCurveTableIx {
+    pub fn from_struct(_parsed: ParsedFinalizeCurveTableIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedFinalizeCurveTableIx {
+        ParsedFinalizeCurveTableIx {
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedInitializeProtocolConfigIx {
+    pub min_sell_fee: u16,
+    pub max_sell_fee: u16,
+    pub creation_policy: CreationPolicy,
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitializeProtocolConfigIx {
+    pub min_sell_fee: [u8; 2],
+    pub max_sell_fee: [u8; 2],
+    pub creation_policy: u8,
+    pub bump: u8,
+}
+
+impl InitializeProtocolConfigIx {
+    pub fn from_struct(parsed: ParsedInitializeProtocolConfigIx) -> Self {
+        Self {
+            min_sell_fee: parsed.min_sell_fee.to_le_bytes(),
+            max_sell_fee: parsed.max_sell_fee.to_le_bytes(),
+            creation_policy: parsed.creation_policy.into(),
+            bump: parsed.bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedInitializeProtocolConfigIx, std::io::Error> {
+        Ok(ParsedInitializeProtocolConfigIx {
+            min_sell_fee: u16::from_le_bytes(self.min_sell_fee),
+            max_sell_fee: u16::from_le_bytes(self.max_sell_fee),
+            creation_policy: to_creation_policy(self.creation_policy)?,
+            bump: self.bump,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedUpdateProtocolConfigIx {
+    pub min_sell_fee: u16,
+    pub max_sell_fee: u16,
+    pub creation_policy: CreationPolicy,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct UpdateProtocolConfigIx {
+    pub min_sell_fee: [u8; 2],
+    pub max_sell_fee: [u8; 2],
+    pub creation_policy: u8,
+}
+
+impl UpdateProtocolConfigIx {
+    pub fn from_struct(parsed: ParsedUpdateProtocolConfigIx) -> Self {
+        Self {
+            min_sell_fee: parsed.min_sell_fee.to_le_bytes(),
+            max_sell_fee: parsed.max_sell_fee.to_le_bytes(),
+            creation_policy: parsed.creation_policy.into(),
+        }
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedUpdateProtocolConfigIx, std::io::Error> {
+        Ok(ParsedUpdateProtocolConfigIx {
+            min_sell_fee: u16::from_le_bytes(self.min_sell_fee),
+            max_sell_fee: u16::from_le_bytes(self.max_sell_fee),
+            creation_policy: to_creation_policy(self.creation_policy)?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedSetBaseMintAllowedIx {
+    pub allowed: bool,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetBaseMintAllowedIx {
+    pub allowed: u8,
+}
+
+impl SetBaseMintAllowedIx {
+    pub fn from_struct(parsed: ParsedSetBaseMintAllowedIx) -> Self {
+        Self {
+            allowed: parsed.allowed as u8,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedSetBaseMintAllowedIx {
+        ParsedSetBaseMintAllowedIx {
+            allowed: self.allowed != 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedInitializeCurrencyProfileIx {
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitializeCurrencyProfileIx {
+    pub bump: u8,
+}
+
+impl InitializeCurrencyProfileIx {
+    pub fn from_struct(parsed: ParsedInitializeCurrencyProfileIx) -> Self {
+        Self {
+            bump: parsed.bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedInitializeCurrencyProfileIx {
+        ParsedInitializeCurrencyProfileIx {
+            bump: self.bump,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedSetProfileDescriptionIx {
+    pub description: String,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetProfileDescriptionIx {
+    pub description: [u8; MAX_DESCRIPTION_LEN],
+    pub description_len: [u8; 2],
+}
+
+impl SetProfileDescriptionIx {
+    pub fn from_struct(parsed: ParsedSetProfileDescriptionIx) -> Self {
+        assert!(parsed.description.len() <= MAX_DESCRIPTION_LEN, "description too long");
+
+        let mut description = [0u8; MAX_DESCRIPTION_LEN];
+        description[..parsed.description.len()].copy_from_slice(parsed.description.as_bytes());
+
+        Self {
+            description,
+            description_len: (parsed.description.len() as u16).to_le_bytes(),
+        }
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedSetProfileDescriptionIx, std::io::Error> {
+        let len = u16::from_le_bytes(self.description_len) as usize;
+        let description = self.description.get(..len)
+            .and_then(|description| String::from_utf8(description.to_vec()).ok())
+            .ok_or_else(|| std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Invalid profile description",
+            ))?;
+
+        Ok(ParsedSetProfileDescriptionIx {
+            description,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedSetProfileLinkIx {
+    pub key: String,
+    pub value: Option<String>, // None removes the link
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetProfileLinkIx {
+    pub key: [u8; MAX_LINK_KEY_LEN],
+    pub value: [u8; MAX_LINK_VALUE_LEN], // All zeros removes the link
+}
+
+impl SetProfileLinkIx {
+    pub fn from_struct(parsed: ParsedSetProfileLinkIx) -> Self {
+        let value = parsed.value.unwrap_or_default();
+        assert!(parsed.key.len() <= MAX_LINK_KEY_LEN, "link key too long");
+        assert!(value.len() <= MAX_LINK_VALUE_LEN, "link value too long");
+
+        let mut ix = Self::zeroed();
+        ix.key[..parsed.key.len()].copy_from_slice(parsed.key.as_bytes());
+        ix.value[..value.len()].copy_from_slice(value.as_bytes());
+        ix
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedSetProfileLinkIx, std::io::Error> {
+        let to_string = |bytes: &[u8]| unpad(bytes)
+            .and_then(|bytes| String::from_utf8(bytes.to_vec()).ok())
+            .ok_or_else(|| std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Invalid profile link",
+            ));
+
+        let value = to_string(&self.value)?;
+
+        Ok(ParsedSetProfileLinkIx {
+            key: to_string(&self.key)?,
+            value: (!value.is_empty()).then_some(value),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedPauseIx {
+    pub pause_sells: bool, // Buys are always paused
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct PauseIx {
+    pub pause_sells: u8,
+}
+
+impl PauseIx {
+    pub fn from_struct(parsed: ParsedPauseIx) -> Self {
+        Self {
+            pause_sells: parsed.pause_sells as u8,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedPauseIx {
+        ParsedPauseIx {
+            pause_sells: self.pause_sells != 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedResumeIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ResumeIx {
+}
+
+impl ResumeIx {
+    pub fn from_struct(_parsed: ParsedResumeIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedResumeIx {
+        ParsedResumeIx {
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedSetCircuitBreakerIx {
+    pub band: u16,   // Basis points, 0 disables the breaker
+    pub window: u64, // Slots
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetCircuitBreakerIx {
+    pub window: [u8; 8],
+    pub band: [u8; 2],
+}
+
+impl SetCircuitBreakerIx {
+    pub fn from_struct(parsed: ParsedSetCircuitBreakerIx) -> Self {
+        Self {
+            window: parsed.window.to_le_bytes(),
+            band: parsed.band.to_le_bytes(),
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedSetCircuitBreakerIx {
+        ParsedSetCircuitBreakerIx {
+            band: u16::from_le_bytes(self.band),
+            window: u64::from_le_bytes(self.window),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedSetLaunchIx {
+    pub launch_at: i64,          // Unix timestamp, 0 to open trading now
+    pub presale_cap: u64,        // Base quarks per allowlisted wallet
+    pub presale_root: [u8; 32],  // Allowlist merkle root, zero for no presale
+    pub launch_window: u64,      // Seconds after launch with capped buys, 0 for none
+    pub launch_cap: u64,         // Base quarks per wallet within the window
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetLaunchIx {
+    pub launch_at: [u8; 8],
+    pub presale_cap: [u8; 8],
+    pub presale_root: [u8; 32],
+    pub launch_window: [u8; 8],
+    pub launch_cap: [u8; 8],
+}
+
+impl SetLaunchIx {
+    pub fn from_struct(parsed: ParsedSetLaunchIx) -> Self {
+        Self {
+            launch_at: parsed.launch_at.to_le_bytes(),
+            presale_cap: parsed.presale_cap.to_le_bytes(),
+            presale_root: parsed.presale_root,
+            launch_window: parsed.launch_window.to_le_bytes(),
+            launch_cap: parsed.launch_cap.to_le_bytes(),
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedSetLaunchIx {
+        ParsedSetLaunchIx {
+            launch_at: i64::from_le_bytes(self.launch_at),
+            presale_cap: u64::from_le_bytes(self.presale_cap),
+            presale_root: self.presale_root,
+            launch_window: u64::from_le_bytes(self.launch_window),
+            launch_cap: u64::from_le_bytes(self.launch_cap),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedInitializeWalletPurchasesIx {
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitializeWalletPurchasesIx {
+    pub bump: u8,
+}
+
+impl InitializeWalletPurchasesIx {
+    pub fn from_struct(parsed: ParsedInitializeWalletPurchasesIx) -> Self {
+        Self {
+            bump: parsed.bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedInitializeWalletPurchasesIx {
+        ParsedInitializeWalletPurchasesIx {
+            bump: self.bump,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedJoinPresaleIx {
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct JoinPresaleIx {
+    pub proof: [[u8; 32]; MAX_PRESALE_PROOF_LEN],
+    pub proof_len: u8,
+}
+
+impl JoinPresaleIx {
+    pub fn from_struct(parsed: ParsedJoinPresaleIx) -> Self {
+        let mut proof = [[0u8; 32]; MAX_PRESALE_PROOF_LEN];
+        let proof_len = parsed.proof.len().min(MAX_PRESALE_PROOF_LEN);
+        proof[..proof_len].copy_from_slice(&parsed.proof[..proof_len]);
+
+        Self {
+            proof,
+            proof_len: proof_len as u8,
+        }
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedJoinPresaleIx, std::io::Error> {
+        let proof_len = self.proof_len as usize;
+        if proof_len > MAX_PRESALE_PROOF_LEN {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Presale proof too long",
+            ));
+        }
+
+        Ok(ParsedJoinPresaleIx {
+            proof: self.proof[..proof_len].to_vec(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedClaimVestedIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClaimVestedIx {
+}
+
+impl ClaimVestedIx {
+    pub fn from_struct(_parsed: ParsedClaimVestedIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedClaimVestedIx {
+        ParsedClaimVestedIx {
+        }
+    }
+}
+
+/// A share of a batch buy, paid to the recipient token account at `index`
+/// among the instruction's trailing accounts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatchRecipient {
+    pub index: u8,
+    pub share: u32,
+}
+
+#[derive(Debug)]
+pub struct ParsedBatchBuyIx {
+    pub in_amount: u64,
+    pub min_amount_out: u64,
+    pub recipients: Vec<BatchRecipient>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct BatchBuyIx {
+    pub in_amount: [u8; 8],
+    pub min_amount_out: [u8; 8],
+    pub shares: [[u8; 4]; MAX_BATCH_RECIPIENTS],
+    pub indexes: [u8; MAX_BATCH_RECIPIENTS],
+    pub num_recipients: u8,
+}
+
+impl BatchBuyIx {
+    pub fn from_struct(parsed: ParsedBatchBuyIx) -> Self {
+        assert!(parsed.recipients.len() <= MAX_BATCH_RECIPIENTS, "too many batch recipients");
+
+        let mut shares = [[0u8; 4]; MAX_BATCH_RECIPIENTS];
+        let mut indexes = [0u8; MAX_BATCH_RECIPIENTS];
+        for (i, recipient) in parsed.recipients.iter().enumerate() {
+            shares[i] = recipient.share.to_le_bytes();
+            indexes[i] = recipient.index;
+        }
+
+        Self {
+            in_amount: parsed.in_amount.to_le_bytes(),
+            min_amount_out: parsed.min_amount_out.to_le_bytes(),
+            shares,
+            indexes,
+            num_recipients: parsed.recipients.len() as u8,
+        }
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedBatchBuyIx, std::io::Error> {
+        if self.num_recipients as usize > MAX_BATCH_RECIPIENTS {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Too many batch recipients",
+            ));
+        }
+
+        let recipients = (0..self.num_recipients as usize)
+            .map(|i| BatchRecipient {
+                index: self.indexes[i],
+                share: u32::from_le_bytes(self.shares[i]),
+            })
+            .collect();
+
+        Ok(ParsedBatchBuyIx {
+            in_amount: u64::from_le_bytes(self.in_amount),
+            min_amount_out: u64::from_le_bytes(self.min_amount_out),
+            recipients,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedCreateCashLinkIx {
+    pub amount: u64,             // Currency quarks to deposit, 0 when buying
+    pub in_amount: u64,          // Base quarks to buy the currency with, 0 when depositing
+    pub min_amount_out: u64,
+    pub expires_at: i64,
+
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CreateCashLinkIx {
+    pub amount: [u8; 8],
+    pub in_amount: [u8; 8],
+    pub min_amount_out: [u8; 8],
+    pub expires_at: [u8; 8],
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl CreateCashLinkIx {
+    pub fn from_struct(parsed: ParsedCreateCashLinkIx) -> Self {
+        Self {
+            amount: parsed.amount.to_le_bytes(),
+            in_amount: parsed.in_amount.to_le_bytes(),
+            min_amount_out: parsed.min_amount_out.to_le_bytes(),
+            expires_at: parsed.expires_at.to_le_bytes(),
+            bump: parsed.bump,
+            vault_bump: parsed.vault_bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedCreateCashLinkIx {
+        ParsedCreateCashLinkIx {
+            amount: u64::from_le_bytes(self.amount),
+            in_amount: u64::from_le_bytes(self.in_amount),
+            min_amount_out: u64::from_le_bytes(self.min_amount_out),
+            expires_at: i64::from_le_bytes(self.expires_at),
+            bump: self.bump,
+            vault_bump: self.vault_bump,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedClaimCashLinkIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClaimCashLinkIx {
+}
+
+impl ClaimCashLinkIx {
+    pub fn from_struct(_parsed: ParsedClaimCashLinkIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedClaimCashLinkIx {
+        ParsedClaimCashLinkIx {
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedReclaimCashLinkIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ReclaimCashLinkIx {
+}
+
+impl ReclaimCashLinkIx {
+    pub fn from_struct(_parsed: ParsedReclaimCashLinkIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedReclaimCashLinkIx {
+        ParsedReclaimCashLinkIx {
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedCreateLimitOrderIx {
+    pub id: u64,
+    pub side: OrderSide,
+    pub amount: u64,             // Base quarks to buy with, or currency quarks to sell
+    pub min_amount_out: u64,
+    pub target_price: u64,       // Fixed point with CURVE_PRICE_DECIMALS
+    pub bounty: u64,             // Lamports for whoever executes the order
+    pub expires_at: i64,
+
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CreateLimitOrderIx {
+    pub id: [u8; 8],
+    pub amount: [u8; 8],
+    pub min_amount_out: [u8; 8],
+    pub target_price: [u8; 8],
+    pub bounty: [u8; 8],
+    pub expires_at: [u8; 8],
+    pub side: u8,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl CreateLimitOrderIx {
+    pub fn from_struct(parsed: ParsedCreateLimitOrderIx) -> Self {
+        Self {
+            id: parsed.id.to_le_bytes(),
+            amount: parsed.amount.to_le_bytes(),
+            min_amount_out: parsed.min_amount_out.to_le_bytes(),
+            target_price: parsed.target_price.to_le_bytes(),
+            bounty: parsed.bounty.to_le_bytes(),
+            expires_at: parsed.expires_at.to_le_bytes(),
+            side: parsed.side.into(),
+            bump: parsed.bump,
+            vault_bump: parsed.vault_bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedCreateLimitOrderIx, std::io::Error> {
+        Ok(ParsedCreateLimitOrderIx {
+            id: u64::from_le_bytes(self.id),
+            side: OrderSide::try_from(self.side).map_err(|_| std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Invalid order side",
+            ))?,
+            amount: u64::from_le_bytes(self.amount),
+            min_amount_out: u64::from_le_bytes(self.min_amount_out),
+            target_price: u64::from_le_bytes(self.target_price),
+            bounty: u64::from_le_bytes(self.bounty),
+            expires_at: i64::from_le_bytes(self.expires_at),
+            bump: self.bump,
+            vault_bump: self.vault_bump,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedExecuteLimitOrderIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ExecuteLimitOrderIx {
+}
+
+impl ExecuteLimitOrderIx {
+    pub fn from_struct(_parsed: ParsedExecuteLimitOrderIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedExecuteLimitOrderIx {
+        ParsedExecuteLimitOrderIx {
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedCancelLimitOrderIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CancelLimitOrderIx {
+}
+
+impl CancelLimitOrderIx {
+    pub fn from_struct(_parsed: ParsedCancelLimitOrderIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedCancelLimitOrderIx {
+        ParsedCancelLimitOrderIx {
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedCreateDcaPlanIx {
+    pub id: u64,
+    pub amount_per_fill: u64,    // Most base quarks spent per fill
+    pub min_amount_out: u64,     // Least currency quarks a full fill may buy
+    pub interval: i64,           // Seconds between fills
+    pub first_fill_at: i64,      // Unix timestamp of the first fill, 0 for now
+
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CreateDcaPlanIx {
+    pub id: [u8; 8],
+    pub amount_per_fill: [u8; 8],
+    pub min_amount_out: [u8; 8],
+    pub interval: [u8; 8],
+    pub first_fill_at: [u8; 8],
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl CreateDcaPlanIx {
+    pub fn from_struct(parsed: ParsedCreateDcaPlanIx) -> Self {
+        Self {
+            id: parsed.id.to_le_bytes(),
+            amount_per_fill: parsed.amount_per_fill.to_le_bytes(),
+            min_amount_out: parsed.min_amount_out.to_le_bytes(),
+            interval: parsed.interval.to_le_bytes(),
+            first_fill_at: parsed.first_fill_at.to_le_bytes(),
+            bump: parsed.bump,
+            vault_bump: parsed.vault_bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedCreateDcaPlanIx {
+        ParsedCreateDcaPlanIx {
+            id: u64::from_le_bytes(self.id),
+            amount_per_fill: u64::from_le_bytes(self.amount_per_fill),
+            min_amount_out: u64::from_le_bytes(self.min_amount_out),
+            interval: i64::from_le_bytes(self.interval),
+            first_fill_at: i64::from_le_bytes(self.first_fill_at),
+            bump: self.bump,
+            vault_bump: self.vault_bump,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedDepositDcaIx {
+    pub amount: u64,             // Base quarks to add
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct DepositDcaIx {
+    pub amount: [u8; 8],
+}
+
+impl DepositDcaIx {
+    pub fn from_struct(parsed: ParsedDepositDcaIx) -> Self {
+        Self {
+            amount: parsed.amount.to_le_bytes(),
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedDepositDcaIx {
+        ParsedDepositDcaIx {
+            amount: u64::from_le_bytes(self.amount),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedWithdrawDcaIx {
+    pub amount: u64,             // Base quarks to take out, 0 for everything
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct WithdrawDcaIx {
+    pub amount: [u8; 8],
+}
+
+impl WithdrawDcaIx {
+    pub fn from_struct(parsed: ParsedWithdrawDcaIx) -> Self {
+        Self {
+            amount: parsed.amount.to_le_bytes(),
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedWithdrawDcaIx {
+        ParsedWithdrawDcaIx {
+            amount: u64::from_le_bytes(self.amount),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedCloseDcaPlanIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CloseDcaPlanIx {
+}
+
+impl CloseDcaPlanIx {
+    pub fn from_struct(_parsed: ParsedCloseDcaPlanIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedCloseDcaPlanIx {
+        ParsedCloseDcaPlanIx {
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedExecuteDcaIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ExecuteDcaIx {
+}
+
+impl ExecuteDcaIx {
+    pub fn from_struct(_parsed: ParsedExecuteDcaIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedExecuteDcaIx {
+        ParsedExecuteDcaIx {
+        }
+    }
+}
+
+fn to_burn_policy(value: u8) -> Result<FeeBurnPolicy, std::io::Error> {
+    FeeBurnPolicy::try_from(value).map_err(|_| std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "Invalid fee burn policy",
+    ))
+}
+
+fn to_creation_policy(value: u8) -> Result<CreationPolicy, std::io::Error> {
+    CreationPolicy::try_from(value).map_err(|_| std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "Invalid creation policy",
+    ))
+}
+
+#[derive(Debug)]
+pub struct ParsedCreateDistributorIx {
+    pub id: u64,
+    pub root: [u8; 32],          // Merkle root over distributor_leaf entries
+    pub total_amount: u64,       // Currency quarks to fund, the sum of all entries
+    pub num_recipients: u64,     // Entries in the recipient list
+    pub expires_at: i64,         // Unix timestamp after which leftovers can be clawed back
+
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CreateDistributorIx {
+    pub id: [u8; 8],
+    pub root: [u8; 32],
+    pub total_amount: [u8; 8],
+    pub num_recipients: [u8; 8],
+    pub expires_at: [u8; 8],
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl CreateDistributorIx {
+    pub fn from_struct(parsed: ParsedCreateDistributorIx) -> Self {
+        Self {
+            id: parsed.id.to_le_bytes(),
+            root: parsed.root,
+            total_amount: parsed.total_amount.to_le_bytes(),
+            num_recipients: parsed.num_recipients.to_le_bytes(),
+            expires_at: parsed.expires_at.to_le_bytes(),
+            bump: parsed.bump,
+            vault_bump: parsed.vault_bump,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedCreateDistributorIx {
+        ParsedCreateDistributorIx {
+            id: u64::from_le_bytes(self.id),
+            root: self.root,
+            total_amount: u64::from_le_bytes(self.total_amount),
+            num_recipients: u64::from_le_bytes(self.num_recipients),
+            expires_at: i64::from_le_bytes(self.expires_at),
+            bump: self.bump,
+            vault_bump: self.vault_bump,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedClaimDistributionIx {
+    pub index: u64,              // Claimant's entry in the recipient list
+    pub amount: u64,             // Currency quarks the entry is for
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClaimDistributionIx {
+    pub index: [u8; 8],
+    pub amount: [u8; 8],
+    pub proof: [[u8; 32]; MAX_DISTRIBUTOR_PROOF_LEN],
+    pub proof_len: u8,
+}
+
+impl ClaimDistributionIx {
+    pub fn from_struct(parsed: ParsedClaimDistributionIx) -> Self {
+        let mut proof = [[0u8; 32]; MAX_DISTRIBUTOR_PROOF_LEN];
+        let proof_len = parsed.proof.len().min(MAX_DISTRIBUTOR_PROOF_LEN);
+        proof[..proof_len].copy_from_slice(&parsed.proof[..proof_len]);
+
+        Self {
+            index: parsed.index.to_le_bytes(),
+            amount: parsed.amount.to_le_bytes(),
+            proof,
+            proof_len: proof_len as u8,
+        }
+    }
+
+    pub fn to_struct(&self) -> Result<ParsedClaimDistributionIx, std::io::Error> {
+        let proof_len = self.proof_len as usize;
+        if proof_len > MAX_DISTRIBUTOR_PROOF_LEN {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Distributor proof too long",
+            ));
+        }
+
+        Ok(ParsedClaimDistributionIx {
+            index: u64::from_le_bytes(self.index),
+            amount: u64::from_le_bytes(self.amount),
+            proof: self.proof[..proof_len].to_vec(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedClawbackDistributorIx {
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClawbackDistributorIx {
+}
+
+impl ClawbackDistributorIx {
+    pub fn from_struct(_parsed: ParsedClawbackDistributorIx) -> Self {
+        Self {
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedClawbackDistributorIx {
+        ParsedClawbackDistributorIx {
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParsedSetTradeHookIx {
+    pub compute_units: u32, // Most compute the hook may use per trade
+    pub required: bool,     // Whether trades must run the hook, even when short on compute
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetTradeHookIx {
+    pub compute_units: [u8; 4],
+    pub required: u8,
+}
+
+impl SetTradeHookIx {
+    pub fn from_struct(parsed: ParsedSetTradeHookIx) -> Self {
+        Self {
+            compute_units: parsed.compute_units.to_le_bytes(),
+            required: parsed.required as u8,
+        }
+    }
+
+    pub fn to_struct(&self) -> ParsedSetTradeHookIx {
+        ParsedSetTradeHookIx {
+            compute_units: u32::from_le_bytes(self.compute_units),
+            required: self.required != 0,
+        }
+    }
+}
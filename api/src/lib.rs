@@ -7,8 +7,14 @@ pub mod instruction;
 pub mod state;
 pub mod pda;
 pub mod cpis;
+pub mod token;
 pub mod utils;
+pub mod error;
+pub mod name;
 pub mod event;
+pub mod merkle;
+pub mod ed25519;
+pub mod hook;
 mod macros;
 
 #[cfg(not(target_os = "solana"))]
@@ -22,7 +28,13 @@ pub mod prelude {
     pub use crate::state::*;
     pub use crate::pda::*;
     pub use crate::cpis::*;
+    pub use crate::token::*;
     pub use crate::utils::*;
+    pub use crate::error::*;
+    pub use crate::name::*;
+    pub use crate::merkle::*;
+    pub use crate::ed25519::*;
+    pub use crate::hook::*;
     pub use brine_fp::UnsignedNumeric;
 
     #[cfg(not(target_os = "solana"))]
@@ -0,0 +1,92 @@
+use solana_program::hash::hashv;
+
+// Domain separation so a pair of nodes can never pass for a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf_hash(data: &[&[u8]]) -> [u8; 32] {
+    hashv(&[&[LEAF_PREFIX], data].concat()).to_bytes()
+}
+
+/// Hashes a pair of nodes in sorted order, so proofs don't need to say which
+/// side each sibling is on.
+fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Checks that `leaf` is in the tree with this `root`.
+pub fn verify_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| node_hash(&node, sibling));
+    computed.eq(root)
+}
+
+/// Builds roots and proofs off-chain. A node without a sibling is carried up
+/// to the next layer unchanged.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<[u8; 32]>) -> Self {
+        let mut layers = vec![leaves];
+        while layers.last().is_some_and(|layer| layer.len() > 1) {
+            let next = layers.last().unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node_hash(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    /// Zero for an empty tree.
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last()
+            .and_then(|layer| layer.first().copied())
+            .unwrap_or_default()
+    }
+
+    pub fn proof(&self, mut index: usize) -> Option<Vec<[u8; 32]>> {
+        if index >= self.layers[0].len() {
+            return None;
+        }
+
+        let mut proof = vec![];
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merkle_proofs() {
+        for num_leaves in 1..=9u8 {
+            let leaves: Vec<_> = (0..num_leaves).map(|i| leaf_hash(&[&[i]])).collect();
+            let tree = MerkleTree::new(leaves.clone());
+            let root = tree.root();
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(index).unwrap();
+                assert!(verify_proof(&root, *leaf, &proof));
+                assert!(!verify_proof(&root, leaf_hash(&[&[num_leaves]]), &proof));
+            }
+            assert!(tree.proof(leaves.len()).is_none());
+        }
+
+        assert_eq!(MerkleTree::new(vec![]).root(), [0; 32]);
+    }
+}
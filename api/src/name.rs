@@ -0,0 +1,181 @@
+use std::fmt;
+use crate::consts::*;
+use crate::error::FlipcashError;
+
+/// A currency name as stored on-chain: 1 to MAX_NAME_LEN bytes of printable
+/// UTF-8, zero padded, with no leading or trailing whitespace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurrencyName([u8; MAX_NAME_LEN]);
+
+impl CurrencyName {
+    pub fn new(name: &str) -> Result<Self, FlipcashError> {
+        if name.is_empty() {
+            return Err(FlipcashError::NameEmpty);
+        }
+        if name.len() > MAX_NAME_LEN {
+            return Err(FlipcashError::NameTooLong);
+        }
+        if name.chars().any(|c| c.is_control()) {
+            return Err(FlipcashError::NameInvalidCharacter);
+        }
+        if name.trim() != name {
+            return Err(FlipcashError::NameNotTrimmed);
+        }
+
+        let mut bytes = [0u8; MAX_NAME_LEN];
+        bytes[..name.len()].copy_from_slice(name.as_bytes());
+        Ok(Self(bytes))
+    }
+
+    pub fn from_bytes(bytes: &[u8; MAX_NAME_LEN]) -> Result<Self, FlipcashError> {
+        let name = unpad(bytes).ok_or(FlipcashError::NameInvalidCharacter)?;
+        let name = std::str::from_utf8(name)
+            .map_err(|_| FlipcashError::NameInvalidCharacter)?;
+        Self::new(name)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; MAX_NAME_LEN] {
+        &self.0
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Validated on construction
+        std::str::from_utf8(unpad(&self.0).unwrap_or_default()).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for CurrencyName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// A currency symbol as stored on-chain: 1 to MAX_SYMBOL_LEN printable ASCII
+/// characters, zero padded, with no whitespace or lowercase letters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurrencySymbol([u8; MAX_SYMBOL_LEN]);
+
+impl CurrencySymbol {
+    pub fn new(symbol: &str) -> Result<Self, FlipcashError> {
+        if symbol.is_empty() {
+            return Err(FlipcashError::SymbolEmpty);
+        }
+        if symbol.len() > MAX_SYMBOL_LEN {
+            return Err(FlipcashError::SymbolTooLong);
+        }
+        if !symbol.bytes().all(|c| c.is_ascii_graphic()) {
+            return Err(FlipcashError::SymbolInvalidCharacter);
+        }
+        if symbol.bytes().any(|c| c.is_ascii_lowercase()) {
+            return Err(FlipcashError::SymbolNotUppercase);
+        }
+
+        let mut bytes = [0u8; MAX_SYMBOL_LEN];
+        bytes[..symbol.len()].copy_from_slice(symbol.as_bytes());
+        Ok(Self(bytes))
+    }
+
+    pub fn from_bytes(bytes: &[u8; MAX_SYMBOL_LEN]) -> Result<Self, FlipcashError> {
+        let symbol = unpad(bytes).ok_or(FlipcashError::SymbolInvalidCharacter)?;
+        let symbol = std::str::from_utf8(symbol)
+            .map_err(|_| FlipcashError::SymbolInvalidCharacter)?;
+        Self::new(symbol)
+    }
+
+    /// Reads a symbol stored before symbols had to be uppercase. Lowercase
+    /// letters are uppercased rather than rejected.
+    pub fn from_legacy_bytes(bytes: &[u8; MAX_SYMBOL_LEN]) -> Result<Self, FlipcashError> {
+        Self::from_bytes(&bytes.map(|c| c.to_ascii_uppercase()))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; MAX_SYMBOL_LEN] {
+        &self.0
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Validated on construction
+        std::str::from_utf8(unpad(&self.0).unwrap_or_default()).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for CurrencySymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Strips the zero padding, rejecting zeros in the middle of the value.
+pub(crate) fn unpad(bytes: &[u8]) -> Option<&[u8]> {
+    let len = bytes.iter().position(|&c| c == 0).unwrap_or(bytes.len());
+    bytes[len..].iter().all(|&c| c == 0).then_some(&bytes[..len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_currency_name() {
+        let name = CurrencyName::new("dark-sky").unwrap();
+        assert_eq!(name.as_str(), "dark-sky");
+        assert_eq!(name.as_bytes()[..8], *b"dark-sky");
+        assert_eq!(name.as_bytes()[8..], [0u8; MAX_NAME_LEN - 8]);
+        assert_eq!(CurrencyName::from_bytes(name.as_bytes()).unwrap(), name);
+
+        assert!(CurrencyName::new("Dark Sky ☀").is_ok());
+        assert!(CurrencyName::new(&"a".repeat(MAX_NAME_LEN)).is_ok());
+
+        assert_eq!(CurrencyName::new(""), Err(FlipcashError::NameEmpty));
+        assert_eq!(CurrencyName::new(&"a".repeat(MAX_NAME_LEN + 1)), Err(FlipcashError::NameTooLong));
+        assert_eq!(CurrencyName::new("dark\nsky"), Err(FlipcashError::NameInvalidCharacter));
+        assert_eq!(CurrencyName::new("dark\0sky"), Err(FlipcashError::NameInvalidCharacter));
+        assert_eq!(CurrencyName::new(" dark-sky"), Err(FlipcashError::NameNotTrimmed));
+        assert_eq!(CurrencyName::new("dark-sky\u{a0}"), Err(FlipcashError::NameNotTrimmed));
+    }
+
+    #[test]
+    fn test_currency_name_from_bytes() {
+        let mut bytes = [0u8; MAX_NAME_LEN];
+        assert_eq!(CurrencyName::from_bytes(&bytes), Err(FlipcashError::NameEmpty));
+
+        bytes[..4].copy_from_slice(b"dark");
+        bytes[5] = b's';
+        assert_eq!(CurrencyName::from_bytes(&bytes), Err(FlipcashError::NameInvalidCharacter));
+
+        bytes[5] = 0;
+        bytes[1] = 0xff;
+        assert_eq!(CurrencyName::from_bytes(&bytes), Err(FlipcashError::NameInvalidCharacter));
+    }
+
+    #[test]
+    fn test_currency_symbol() {
+        let symbol = CurrencySymbol::new("DSKY").unwrap();
+        assert_eq!(symbol.as_str(), "DSKY");
+        assert_eq!(symbol.as_bytes(), b"DSKY\0\0\0\0");
+        assert_eq!(CurrencySymbol::from_bytes(symbol.as_bytes()).unwrap(), symbol);
+        assert_eq!(symbol.to_string(), "DSKY");
+
+        assert!(CurrencySymbol::new("C0").is_ok());
+        assert!(CurrencySymbol::new("$FLIP").is_ok());
+        assert!(CurrencySymbol::new("ABCDEFGH").is_ok());
+
+        assert_eq!(CurrencySymbol::new(""), Err(FlipcashError::SymbolEmpty));
+        assert_eq!(CurrencySymbol::new("ABCDEFGHI"), Err(FlipcashError::SymbolTooLong));
+        assert_eq!(CurrencySymbol::new("D SKY"), Err(FlipcashError::SymbolInvalidCharacter));
+        assert_eq!(CurrencySymbol::new(" DSKY"), Err(FlipcashError::SymbolInvalidCharacter));
+        assert_eq!(CurrencySymbol::new("DSKÝ"), Err(FlipcashError::SymbolInvalidCharacter));
+        assert_eq!(CurrencySymbol::new("dsky"), Err(FlipcashError::SymbolNotUppercase));
+        assert_eq!(CurrencySymbol::from_bytes(b"DS\0KY\0\0\0"), Err(FlipcashError::SymbolInvalidCharacter));
+    }
+
+    #[test]
+    fn test_currency_symbol_from_legacy_bytes() {
+        let symbol = CurrencySymbol::new("DSKY").unwrap();
+        assert_eq!(CurrencySymbol::from_legacy_bytes(b"dsky\0\0\0\0").unwrap(), symbol);
+        assert_eq!(CurrencySymbol::from_legacy_bytes(b"DsKy\0\0\0\0").unwrap(), symbol);
+        assert_eq!(CurrencySymbol::from_legacy_bytes(symbol.as_bytes()).unwrap(), symbol);
+
+        assert_eq!(CurrencySymbol::from_legacy_bytes(&[0; MAX_SYMBOL_LEN]), Err(FlipcashError::SymbolEmpty));
+        assert_eq!(CurrencySymbol::from_legacy_bytes(b"d sky\0\0\0"), Err(FlipcashError::SymbolInvalidCharacter));
+    }
+}
@@ -1,11 +1,10 @@
 use steel::*;
 use crate::consts::*;
-use crate::utils::to_name;
+use crate::name::CurrencyName;
 
-pub fn find_mint_pda(authority: &Pubkey, name: &str, seed: &[u8; 32]) -> (Pubkey, u8) {
-    let name = to_name(name);
+pub fn find_mint_pda(authority: &Pubkey, name: &CurrencyName, seed: &[u8; 32]) -> (Pubkey, u8) {
     Pubkey::find_program_address(
-        &[MINT, authority.as_ref(), name.as_ref(), seed],
+        &[MINT, authority.as_ref(), name.as_bytes(), seed],
         &crate::id(),
     )
 }
@@ -24,6 +23,14 @@ pub fn find_pool_pda(currency: &Pubkey) -> (Pubkey, u8) {
     )
 }
 
+/// Pools after a currency's first are keyed by their base mint as well.
+pub fn find_additional_pool_pda(currency: &Pubkey, base_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POOL, currency.as_ref(), base_mint.as_ref()],
+        &crate::id(),
+    )
+}
+
 pub fn find_vault_pda(pool: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[TREASURY, pool.as_ref(), mint.as_ref()],
@@ -31,9 +38,125 @@ pub fn find_vault_pda(pool: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
     )
 }
 
+pub fn find_fee_schedule_pda(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[FEE_SCHEDULE, pool.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn find_trader_volume_pda(pool: &Pubkey, trader: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOLUME, pool.as_ref(), trader.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn find_wallet_purchases_pda(pool: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PURCHASES, pool.as_ref(), wallet.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn find_vesting_pda(pool: &Pubkey, beneficiary: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VESTING, pool.as_ref(), beneficiary.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn find_cash_link_pda(link_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CASH_LINK, link_key.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn find_limit_order_pda(pool: &Pubkey, owner: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LIMIT_ORDER, pool.as_ref(), owner.as_ref(), &id.to_le_bytes()],
+        &crate::id(),
+    )
+}
+
+pub fn find_dca_plan_pda(pool: &Pubkey, owner: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DCA, pool.as_ref(), owner.as_ref(), &id.to_le_bytes()],
+        &crate::id(),
+    )
+}
+
+pub fn find_distributor_pda(mint: &Pubkey, authority: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DISTRIBUTOR, mint.as_ref(), authority.as_ref(), &id.to_le_bytes()],
+        &crate::id(),
+    )
+}
+
+/// Signs a pool's post-trade hook CPIs, so hooks can tell real trades from
+/// spoofed calls.
+pub fn find_trade_hook_authority_pda(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TRADE_HOOK, pool.as_ref()],
+        &crate::id(),
+    )
+}
+
 pub fn metadata_pda(mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[ METADATA, mpl_token_metadata::ID.as_ref(), mint.as_ref() ],
         &mpl_token_metadata::ID,
     )
 }
+
+pub fn find_curve_table_pda(authority: &Pubkey, seed: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CURVE_TABLE, authority.as_ref(), seed],
+        &crate::id(),
+    )
+}
+
+pub fn find_protocol_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROTOCOL_CONFIG],
+        &crate::id(),
+    )
+}
+
+/// The program's ProgramData account, which records its upgrade authority.
+pub fn find_program_data_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[crate::ID.as_ref()],
+        &solana_program::bpf_loader_upgradeable::id(),
+    )
+}
+
+/// `symbol` is the padded bytes of a validated `CurrencySymbol`.
+pub fn find_symbol_pda(symbol: &[u8; MAX_SYMBOL_LEN]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SYMBOL, symbol],
+        &crate::id(),
+    )
+}
+
+pub fn find_registry_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REGISTRY],
+        &crate::id(),
+    )
+}
+
+pub fn find_registry_page_pda(page: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REGISTRY, &page.to_le_bytes()],
+        &crate::id(),
+    )
+}
+
+pub fn find_profile_pda(currency: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROFILE, currency.as_ref()],
+        &crate::id(),
+    )
+}
@@ -1,14 +1,22 @@
 use steel::*;
 use crate::prelude::*;
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_initialize_currency_ix(
     authority: Pubkey,
-    name: String,
-    symbol: String,
+    name: CurrencyName,
+    symbol: CurrencySymbol,
     seed: [u8; 32],
+    decimals: u8,
+    max_supply: u64,       // Whole tokens
+    token_program: Pubkey, // SPL Token (Metaplex metadata) or Token-2022 (metadata on the mint)
+    registry_page: u64,    // CurrencyRegistry::next_page, 0 for the first currency
 ) -> Instruction {
     let (mint_pda, mint_bump) = find_mint_pda(&authority, &name, &seed);
     let (currency_pda, currency_bump) = find_currency_pda(&mint_pda);
+    let (symbol_pda, symbol_bump) = find_symbol_pda(symbol.as_bytes());
+    let (registry_pda, registry_bump) = find_registry_pda();
+    let (registry_page_pda, registry_page_bump) = find_registry_page_pda(registry_page);
 
     println!("mint_pda: {}, bump: {} (target)", mint_pda, mint_bump);
 
@@ -18,7 +26,10 @@ pub fn build_initialize_currency_ix(
             AccountMeta::new(authority, true),
             AccountMeta::new(mint_pda, false),
             AccountMeta::new(currency_pda, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(symbol_pda, false),
+            AccountMeta::new(registry_pda, false),
+            AccountMeta::new(registry_page_pda, false),
+            AccountMeta::new_readonly(token_program, false),
             AccountMeta::new_readonly(system_program::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
         ],
@@ -27,25 +38,130 @@ pub fn build_initialize_currency_ix(
                 name,
                 symbol,
                 seed,
+                decimals,
+                max_supply,
                 bump: currency_bump,
                 mint_bump,
+                symbol_bump,
+                registry_bump,
+                registry_page_bump,
             }
         ).to_bytes(),
     }
 }
 
+/// How a new pool trades. The default is a fee-free pool on the default
+/// curve whose fees only its authority can burn, with no initial buy.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PoolOptions {
+    pub sell_fee: u16,                   // Basis points taken on sells
+    pub burn_policy: FeeBurnPolicy,
+    pub burn_cooldown: i64,              // Seconds, for PermissionlessAfterCooldown
+    pub curve: CurveParams,
+    pub allowed_extensions: u8,          // ALLOW_* bits for risky Token-2022 base mint extensions
+    pub curve_table: Option<Pubkey>,     // Required for CurveKind::Table
+    pub initial_buy: Option<InitialBuy>,
+    pub launch: LaunchSchedule,          // Applied before the initial buy
+}
+
+/// A buy the pool authority makes as part of creating a pool, so nobody can
+/// buy ahead of it.
+#[derive(Clone, Copy, Debug)]
+pub struct InitialBuy {
+    pub amount: u64,              // Base quarks to spend
+    pub authority_target: Pubkey, // Receives the bought currency, unless it vests
+    pub authority_base: Pubkey,   // Pays for it
+    pub vesting: Option<VestingSchedule>,
+}
+
+/// Holds an initial buy in a vesting account that releases it to the
+/// authority linearly over `duration` seconds from pool creation.
+#[derive(Clone, Copy, Debug)]
+pub struct VestingSchedule {
+    pub cliff: i64,               // Seconds before anything vests
+    pub duration: i64,            // Seconds until everything has vested
+}
+
 pub fn build_initialize_pool_ix(
     authority: Pubkey,
     currency: Pubkey,
     target_mint: Pubkey,
+    target_token_program: Pubkey, // SPL Token or Token-2022, whichever owns the target mint
     base_mint: Pubkey,    // Probably USDC
+    base_token_program: Pubkey, // SPL Token or Token-2022, whichever owns the base mint
+    options: PoolOptions,
+    ) -> Instruction {
+    let (pool_pda, pool_bump) = find_pool_pda(&currency);
 
-    sell_fee: u16,
+    initialize_pool_ix(
+        pool_pda,
+        pool_bump,
+        authority,
+        currency,
+        target_mint,
+        target_token_program,
+        base_mint,
+        base_token_program,
+        options,
+    )
+}
+
+/// Adds a pool to a currency that already has one, backed by another base
+/// mint. It takes its curve supply from the currency's unallocated supply.
+pub fn build_initialize_additional_pool_ix(
+    authority: Pubkey,
+    currency: Pubkey,
+    target_mint: Pubkey,
+    target_token_program: Pubkey, // SPL Token or Token-2022, whichever owns the target mint
+    base_mint: Pubkey,    // Probably USDC
+    base_token_program: Pubkey, // SPL Token or Token-2022, whichever owns the base mint
+    options: PoolOptions,
     ) -> Instruction {
+    let (pool_pda, pool_bump) = find_additional_pool_pda(&currency, &base_mint);
 
-    let (pool_pda, pool_bump) = find_pool_pda(&currency);
+    initialize_pool_ix(
+        pool_pda,
+        pool_bump,
+        authority,
+        currency,
+        target_mint,
+        target_token_program,
+        base_mint,
+        base_token_program,
+        options,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn initialize_pool_ix(
+    pool_pda: Pubkey,
+    pool_bump: u8,
+    authority: Pubkey,
+    currency: Pubkey,
+    target_mint: Pubkey,
+    target_token_program: Pubkey, // SPL Token or Token-2022, whichever owns the target mint
+    base_mint: Pubkey,    // Probably USDC
+    base_token_program: Pubkey, // SPL Token or Token-2022, whichever owns the base mint
+    options: PoolOptions,
+    ) -> Instruction {
+
+    let PoolOptions {
+        sell_fee,
+        burn_policy,
+        burn_cooldown,
+        curve,
+        allowed_extensions,
+        curve_table,
+        initial_buy,
+        launch,
+    } = options;
+    let (primary_pool_pda, _) = find_pool_pda(&currency);
+    let (protocol_config_pda, _) = find_protocol_config_pda();
     let (vault_a_pda, vault_a_bump) = find_vault_pda(&pool_pda, &target_mint);
     let (vault_b_pda, vault_b_bump) = find_vault_pda(&pool_pda, &base_mint);
+    let (vesting_pda, vesting_bump) = find_vesting_pda(&pool_pda, &authority);
+    let (vesting_vault_pda, vesting_vault_bump) = find_vault_pda(&vesting_pda, &target_mint);
+    let vesting = initial_buy.and_then(|buy| buy.vesting);
 
     println!("pool_pda: {}, bump: {}", pool_pda, pool_bump);
     println!("vault_a_pda: {}, bump: {} (target)", vault_a_pda, vault_a_bump);
@@ -53,24 +169,45 @@ pub fn build_initialize_pool_ix(
 
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(authority, true),
-            AccountMeta::new_readonly(currency, false),
-            AccountMeta::new(target_mint, false),
-            AccountMeta::new_readonly(base_mint, false),
-            AccountMeta::new(pool_pda, false),
-            AccountMeta::new(vault_a_pda, false),
-            AccountMeta::new(vault_b_pda, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(system_program::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-        ],
+        accounts: [
+            vec![
+                AccountMeta::new(authority, true),
+                AccountMeta::new(currency, false),
+                AccountMeta::new(target_mint, false),
+                AccountMeta::new_readonly(base_mint, false),
+                AccountMeta::new_readonly(protocol_config_pda, false),
+                AccountMeta::new(pool_pda, false),
+                AccountMeta::new(vault_a_pda, false),
+                AccountMeta::new(vault_b_pda, false),
+                AccountMeta::new_readonly(target_token_program, false),
+                AccountMeta::new_readonly(base_token_program, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            primary_pool_metas(pool_pda, primary_pool_pda),
+            curve_table_metas(curve_table),
+            initial_buy_metas(initial_buy, vesting_pda, vesting_vault_pda),
+        ].concat(),
         data: InitializePoolIx::from_struct(
             ParsedInitializePoolIx {
                 sell_fee,
+                burn_policy,
+                burn_cooldown,
+                curve,
+                allowed_extensions,
+                initial_buy_amount: initial_buy.map_or(0, |buy| buy.amount),
+                vesting_cliff: vesting.map_or(0, |vesting| vesting.cliff),
+                vesting_duration: vesting.map_or(0, |vesting| vesting.duration),
+                launch_at: launch.launch_at,
+                presale_cap: launch.presale_cap,
+                presale_root: launch.presale_root,
+                launch_window: launch.launch_window,
+                launch_cap: launch.launch_cap,
                 bump: pool_bump,
                 vault_a_bump,
                 vault_b_bump,
+                vesting_bump,
+                vesting_vault_bump,
             }
         ).to_bytes(),
     }
@@ -80,21 +217,27 @@ pub fn build_initialize_metadata_ix(
     authority: Pubkey,
     currency: Pubkey,
     mint: Pubkey,
+    token_program: Pubkey,
     ) -> Instruction {
 
-    let (metadata_pda, metadata_bump) = metadata_pda(&mint);
-
-    println!("metadata_pda: {}, bump: {}", metadata_pda, metadata_bump);
+    // Token-2022 mints hold their own metadata
+    let (metadata, metadata_program) = if token_program == spl_token_2022::id() {
+        (mint, spl_token_2022::id())
+    } else {
+        let (metadata_pda, metadata_bump) = metadata_pda(&mint);
+        println!("metadata_pda: {}, bump: {}", metadata_pda, metadata_bump);
+        (metadata_pda, mpl_token_metadata::ID)
+    };
 
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(authority, true),
-            AccountMeta::new_readonly(currency, false),
+            AccountMeta::new(currency, false),
             AccountMeta::new(mint, false),
-            AccountMeta::new(metadata_pda, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(metadata_program, false),
+            AccountMeta::new_readonly(token_program, false),
             AccountMeta::new_readonly(system_program::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
         ],
@@ -104,35 +247,82 @@ pub fn build_initialize_metadata_ix(
     }
 }
 
+/// Optional trailing accounts of a buy or sell. The default suits a pool
+/// with a parametric curve, no trade hook and a plain base mint.
+#[derive(Clone, Debug, Default)]
+pub struct TradeOptions {
+    pub curve_table: Option<Pubkey>,      // Table-backed pools only
+    pub hook_accounts: Vec<AccountMeta>,  // `trade_hook_metas`, then the base mint's transfer hook accounts
+}
+
+impl TradeOptions {
+    /// Options for trading against `pool`: its curve table, and its trade
+    /// hook if it has one. Accounts the trade hook needs of its own, and then
+    /// transfer hook accounts, still have to be added to `hook_accounts`.
+    pub fn for_pool(pool: &LiquidityPool, pool_address: &Pubkey) -> Self {
+        Self {
+            curve_table: pool.is_table_backed().then_some(pool.curve_table),
+            hook_accounts: if pool.has_trade_hook() {
+                trade_hook_metas(pool_address, pool.hook_program, vec![])
+            } else {
+                vec![]
+            },
+        }
+    }
+}
+
+/// Accounts of the VM that a VM trade deposits into.
+#[derive(Clone, Copy, Debug)]
+pub struct VmAccounts {
+    pub authority: Pubkey,
+    pub vm: Pubkey,
+    pub memory: Pubkey,
+    pub omnibus: Pubkey,
+    pub vta_owner: Pubkey,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn build_buy_tokens_ix(
     buyer: Pubkey,
     pool: Pubkey,
     target_mint: Pubkey,
+    target_token_program: Pubkey,
     base_mint: Pubkey,
+    base_token_program: Pubkey,
     in_amount: u64,
     min_amount_out: u64,
     buyer_target: Pubkey,
     buyer_base: Pubkey,
+    options: TradeOptions,
 ) -> Instruction {
     let (vault_a_pda, vault_a_bump) = find_vault_pda(&pool, &target_mint);
     let (vault_b_pda, vault_b_bump) = find_vault_pda(&pool, &base_mint);
+    let (wallet_purchases_pda, _) = find_wallet_purchases_pda(&pool, &buyer);
 
     println!("vault_a_pda: {}, bump: {} (target)", vault_a_pda, vault_a_bump);
     println!("vault_b_pda: {}, bump: {} (base)", vault_b_pda, vault_b_bump);
 
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(buyer, true),
-            AccountMeta::new_readonly(pool, false),
-            AccountMeta::new_readonly(target_mint, false),
-            AccountMeta::new_readonly(base_mint, false),
-            AccountMeta::new(vault_a_pda, false),
-            AccountMeta::new(vault_b_pda, false),
-            AccountMeta::new(buyer_target, false),
-            AccountMeta::new(buyer_base, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts: [
+            vec![
+                AccountMeta::new(buyer, true),
+                AccountMeta::new(pool, false),
+                AccountMeta::new_readonly(target_mint, false),
+                AccountMeta::new_readonly(base_mint, false),
+                AccountMeta::new(vault_a_pda, false),
+                AccountMeta::new(vault_b_pda, false),
+                AccountMeta::new(buyer_target, false),
+                AccountMeta::new(buyer_base, false),
+                AccountMeta::new_readonly(target_token_program, false),
+                AccountMeta::new_readonly(base_token_program, false),
+            ],
+            curve_table_metas(options.curve_table),
+            vec![
+                AccountMeta::new(wallet_purchases_pda, false),
+            ],
+            options.hook_accounts,
+        ].concat(),
         data: BuyTokensIx::from_struct(ParsedBuyTokensIx {
             in_amount,
             min_amount_out,
@@ -140,35 +330,114 @@ pub fn build_buy_tokens_ix(
     }
 }
 
+/// Buys once and splits the currency across `recipients` by share. Each
+/// recipient is a currency token account, listed once however many shares
+/// it is given.
+#[allow(clippy::too_many_arguments)]
+pub fn build_batch_buy_ix(
+    buyer: Pubkey,
+    pool: Pubkey,
+    target_mint: Pubkey,
+    target_token_program: Pubkey,
+    base_mint: Pubkey,
+    base_token_program: Pubkey,
+    in_amount: u64,
+    min_amount_out: u64,
+    buyer_base: Pubkey,
+    recipients: &[(Pubkey, u32)], // (recipient token account, share)
+    curve_table: Option<Pubkey>,  // Table-backed pools only
+) -> Instruction {
+    let (vault_a_pda, _) = find_vault_pda(&pool, &target_mint);
+    let (vault_b_pda, _) = find_vault_pda(&pool, &base_mint);
+    let (wallet_purchases_pda, _) = find_wallet_purchases_pda(&pool, &buyer);
+
+    let mut recipient_accounts: Vec<Pubkey> = vec![];
+    let recipients = recipients.iter()
+        .map(|(account, share)| {
+            let index = recipient_accounts.iter()
+                .position(|key| key.eq(account))
+                .unwrap_or_else(|| {
+                    recipient_accounts.push(*account);
+                    recipient_accounts.len() - 1
+                });
+            BatchRecipient { index: index as u8, share: *share }
+        })
+        .collect();
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: [
+            vec![
+                AccountMeta::new(buyer, true),
+                AccountMeta::new(pool, false),
+                AccountMeta::new_readonly(target_mint, false),
+                AccountMeta::new_readonly(base_mint, false),
+                AccountMeta::new(vault_a_pda, false),
+                AccountMeta::new(vault_b_pda, false),
+                AccountMeta::new(buyer_base, false),
+                AccountMeta::new_readonly(target_token_program, false),
+                AccountMeta::new_readonly(base_token_program, false),
+            ],
+            curve_table_metas(curve_table),
+            vec![
+                AccountMeta::new(wallet_purchases_pda, false),
+            ],
+            recipient_accounts.iter()
+                .map(|account| AccountMeta::new(*account, false))
+                .collect(),
+        ].concat(),
+        data: BatchBuyIx::from_struct(ParsedBatchBuyIx {
+            in_amount,
+            min_amount_out,
+            recipients,
+        }).to_bytes(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn build_sell_tokens_ix(
     seller: Pubkey,
     pool: Pubkey,
     target_mint: Pubkey,
+    target_token_program: Pubkey,
     base_mint: Pubkey,
+    base_token_program: Pubkey,
     in_amount: u64,
     min_amount_out: u64,
     seller_target: Pubkey,
     seller_base: Pubkey,
+    options: TradeOptions,
 ) -> Instruction {
     let (vault_a_pda, vault_a_bump) = find_vault_pda(&pool, &target_mint);
     let (vault_b_pda, vault_b_bump) = find_vault_pda(&pool, &base_mint);
+    let (fee_schedule_pda, _) = find_fee_schedule_pda(&pool);
+    let (trader_volume_pda, _) = find_trader_volume_pda(&pool, &seller);
 
     println!("vault_a_pda: {}, bump: {} (target)", vault_a_pda, vault_a_bump);
     println!("vault_b_pda: {}, bump: {} (base)", vault_b_pda, vault_b_bump);
 
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(seller, true),
-            AccountMeta::new(pool, false),
-            AccountMeta::new_readonly(target_mint, false),
-            AccountMeta::new_readonly(base_mint, false),
-            AccountMeta::new(vault_a_pda, false),
-            AccountMeta::new(vault_b_pda, false),
-            AccountMeta::new(seller_target, false),
-            AccountMeta::new(seller_base, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts: [
+            vec![
+                AccountMeta::new(seller, true),
+                AccountMeta::new(pool, false),
+                AccountMeta::new_readonly(target_mint, false),
+                AccountMeta::new_readonly(base_mint, false),
+                AccountMeta::new(vault_a_pda, false),
+                AccountMeta::new(vault_b_pda, false),
+                AccountMeta::new(seller_target, false),
+                AccountMeta::new(seller_base, false),
+                AccountMeta::new_readonly(target_token_program, false),
+                AccountMeta::new_readonly(base_token_program, false),
+            ],
+            curve_table_metas(options.curve_table),
+            vec![
+                AccountMeta::new_readonly(fee_schedule_pda, false),
+                AccountMeta::new(trader_volume_pda, false),
+            ],
+            options.hook_accounts,
+        ].concat(),
         data: SellTokensIx::from_struct(ParsedSellTokensIx {
             in_amount,
             min_amount_out,
@@ -176,44 +445,53 @@ pub fn build_sell_tokens_ix(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_buy_and_deposit_into_vm_ix(
     buyer: Pubkey,
     pool: Pubkey,
     target_mint: Pubkey,
+    target_token_program: Pubkey,
     base_mint: Pubkey,
+    base_token_program: Pubkey,
     buyer_base: Pubkey,
-    vm_authority: Pubkey,
-	vm: Pubkey,
-	vm_memory: Pubkey,
-	vm_omnibus: Pubkey,
-	vta_owner: Pubkey,
+    vm: VmAccounts,
     in_amount: u64,
     min_amount_out: u64,
+    options: TradeOptions,
 ) -> Instruction {
     let (vault_a_pda, vault_a_bump) = find_vault_pda(&pool, &target_mint);
     let (vault_b_pda, vault_b_bump) = find_vault_pda(&pool, &base_mint);
+    let (wallet_purchases_pda, _) = find_wallet_purchases_pda(&pool, &buyer);
 
     println!("vault_a_pda: {}, bump: {} (target)", vault_a_pda, vault_a_bump);
     println!("vault_b_pda: {}, bump: {} (base)", vault_b_pda, vault_b_bump);
 
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(buyer, true),
-            AccountMeta::new_readonly(pool, false),
-            AccountMeta::new_readonly(target_mint, false),
-            AccountMeta::new_readonly(base_mint, false),
-            AccountMeta::new(vault_a_pda, false),
-            AccountMeta::new(vault_b_pda, false),
-            AccountMeta::new(buyer_base, false),
-            AccountMeta::new(vm_authority, true),
-            AccountMeta::new(vm, false),
-            AccountMeta::new(vm_memory, false),
-            AccountMeta::new(vm_omnibus, false),
-            AccountMeta::new_readonly(vta_owner, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(VM_PROGRAM_ID, false),
-        ],
+        accounts: [
+            vec![
+                AccountMeta::new(buyer, true),
+                AccountMeta::new(pool, false),
+                AccountMeta::new_readonly(target_mint, false),
+                AccountMeta::new_readonly(base_mint, false),
+                AccountMeta::new(vault_a_pda, false),
+                AccountMeta::new(vault_b_pda, false),
+                AccountMeta::new(buyer_base, false),
+                AccountMeta::new(vm.authority, true),
+                AccountMeta::new(vm.vm, false),
+                AccountMeta::new(vm.memory, false),
+                AccountMeta::new(vm.omnibus, false),
+                AccountMeta::new_readonly(vm.vta_owner, false),
+                AccountMeta::new_readonly(target_token_program, false),
+                AccountMeta::new_readonly(base_token_program, false),
+                AccountMeta::new_readonly(VM_PROGRAM_ID, false),
+            ],
+            curve_table_metas(options.curve_table),
+            vec![
+                AccountMeta::new(wallet_purchases_pda, false),
+            ],
+            options.hook_accounts,
+        ].concat(),
         data: BuyTokensIx::from_struct(ParsedBuyTokensIx {
             in_amount,
             min_amount_out,
@@ -221,44 +499,55 @@ pub fn build_buy_and_deposit_into_vm_ix(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn build_sell_and_deposit_into_vm_ix(
     seller: Pubkey,
     pool: Pubkey,
     target_mint: Pubkey,
+    target_token_program: Pubkey,
     base_mint: Pubkey,
+    base_token_program: Pubkey,
     seller_target: Pubkey,
-    vm_authority: Pubkey,
-	vm: Pubkey,
-	vm_memory: Pubkey,
-	vm_omnibus: Pubkey,
-	vta_owner: Pubkey,
+    vm: VmAccounts,
     in_amount: u64,
     min_amount_out: u64,
+    options: TradeOptions,
 ) -> Instruction {
     let (vault_a_pda, vault_a_bump) = find_vault_pda(&pool, &target_mint);
     let (vault_b_pda, vault_b_bump) = find_vault_pda(&pool, &base_mint);
+    let (fee_schedule_pda, _) = find_fee_schedule_pda(&pool);
+    let (trader_volume_pda, _) = find_trader_volume_pda(&pool, &seller);
 
     println!("vault_a_pda: {}, bump: {} (target)", vault_a_pda, vault_a_bump);
     println!("vault_b_pda: {}, bump: {} (base)", vault_b_pda, vault_b_bump);
 
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(seller, true),
-            AccountMeta::new(pool, false),
-            AccountMeta::new_readonly(target_mint, false),
-            AccountMeta::new_readonly(base_mint, false),
-            AccountMeta::new(vault_a_pda, false),
-            AccountMeta::new(vault_b_pda, false),
-            AccountMeta::new(seller_target, false),
-            AccountMeta::new(vm_authority, true),
-            AccountMeta::new(vm, false),
-            AccountMeta::new(vm_memory, false),
-            AccountMeta::new(vm_omnibus, false),
-            AccountMeta::new_readonly(vta_owner, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(VM_PROGRAM_ID, false),
-        ],
+        accounts: [
+            vec![
+                AccountMeta::new(seller, true),
+                AccountMeta::new(pool, false),
+                AccountMeta::new_readonly(target_mint, false),
+                AccountMeta::new_readonly(base_mint, false),
+                AccountMeta::new(vault_a_pda, false),
+                AccountMeta::new(vault_b_pda, false),
+                AccountMeta::new(seller_target, false),
+                AccountMeta::new(vm.authority, true),
+                AccountMeta::new(vm.vm, false),
+                AccountMeta::new(vm.memory, false),
+                AccountMeta::new(vm.omnibus, false),
+                AccountMeta::new_readonly(vm.vta_owner, false),
+                AccountMeta::new_readonly(target_token_program, false),
+                AccountMeta::new_readonly(base_token_program, false),
+                AccountMeta::new_readonly(VM_PROGRAM_ID, false),
+            ],
+            curve_table_metas(options.curve_table),
+            vec![
+                AccountMeta::new_readonly(fee_schedule_pda, false),
+                AccountMeta::new(trader_volume_pda, false),
+            ],
+            options.hook_accounts,
+        ].concat(),
         data: SellTokensIx::from_struct(ParsedSellTokensIx {
             in_amount,
             min_amount_out,
@@ -270,6 +559,7 @@ pub fn build_burn_fees_ix(
     payer: Pubkey,
     pool: Pubkey,
     base_mint: Pubkey,
+    base_token_program: Pubkey,
 ) -> Instruction {
     let (vault_b_pda, _) = find_vault_pda(&pool, &base_mint);
 
@@ -280,8 +570,1189 @@ pub fn build_burn_fees_ix(
             AccountMeta::new(pool, false),
             AccountMeta::new(base_mint, false),
             AccountMeta::new(vault_b_pda, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(base_token_program, false),
         ],
         data: BurnFeesIx::from_struct(ParsedBurnFeesIx {}).to_bytes(),
     }
 }
+
+pub fn build_set_burn_policy_ix(
+    authority: Pubkey,
+    pool: Pubkey,
+    burn_policy: FeeBurnPolicy,
+    burn_cooldown: i64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(pool, false),
+        ],
+        data: SetBurnPolicyIx::from_struct(ParsedSetBurnPolicyIx {
+            burn_policy,
+            burn_cooldown,
+        }).to_bytes(),
+    }
+}
+
+/// Grows a pool created with the v0 layout to the current one. Anyone may
+/// migrate a pool, paying for the extra rent.
+pub fn build_migrate_pool_ix(
+    payer: Pubkey,
+    pool: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: MigratePoolIx::from_struct(ParsedMigratePoolIx {
+        }).to_bytes(),
+    }
+}
+
+/// Grows a currency created with the v0 layout to the current one. Anyone
+/// may migrate a currency, paying for the extra rent.
+pub fn build_migrate_currency_ix(
+    payer: Pubkey,
+    mint: Pubkey,
+    symbol: CurrencySymbol, // CurrencySymbol::from_legacy_bytes of the stored symbol
+    registry_page: u64,     // CurrencyRegistry::next_page, 0 if there is no registry yet
+) -> Instruction {
+    let (currency_pda, _) = find_currency_pda(&mint);
+    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (symbol_pda, symbol_bump) = find_symbol_pda(symbol.as_bytes());
+    let (registry_pda, registry_bump) = find_registry_pda();
+    let (registry_page_pda, registry_page_bump) = find_registry_page_pda(registry_page);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(currency_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(pool_pda, false),
+            AccountMeta::new(symbol_pda, false),
+            AccountMeta::new(registry_pda, false),
+            AccountMeta::new(registry_page_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: MigrateCurrencyIx::from_struct(ParsedMigrateCurrencyIx {
+            symbol_bump,
+            registry_bump,
+            registry_page_bump,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_initialize_fee_schedule_ix(
+    authority: Pubkey,
+    pool: Pubkey,
+) -> Instruction {
+    let (fee_schedule_pda, fee_schedule_bump) = find_fee_schedule_pda(&pool);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new(fee_schedule_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: InitializeFeeScheduleIx::from_struct(ParsedInitializeFeeScheduleIx {
+            bump: fee_schedule_bump,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_set_fee_exemption_ix(
+    authority: Pubkey,
+    pool: Pubkey,
+    wallet: Pubkey,
+    exempt: bool,
+) -> Instruction {
+    let (fee_schedule_pda, _) = find_fee_schedule_pda(&pool);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new(fee_schedule_pda, false),
+            AccountMeta::new_readonly(wallet, false),
+        ],
+        data: SetFeeExemptionIx::from_struct(ParsedSetFeeExemptionIx {
+            exempt,
+        }).to_bytes(),
+    }
+}
+
+/// Fails if there are more than `MAX_FEE_TIERS` tiers.
+pub fn build_set_fee_tiers_ix(
+    authority: Pubkey,
+    pool: Pubkey,
+    tiers: Vec<FeeTier>,
+) -> Result<Instruction, ProgramError> {
+    let (fee_schedule_pda, _) = find_fee_schedule_pda(&pool);
+
+    Ok(Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new(fee_schedule_pda, false),
+        ],
+        data: SetFeeTiersIx::from_struct(ParsedSetFeeTiersIx {
+            tiers,
+        })?.to_bytes(),
+    })
+}
+
+pub fn build_initialize_trader_volume_ix(
+    payer: Pubkey,
+    pool: Pubkey,
+    trader: Pubkey,
+) -> Instruction {
+    let (trader_volume_pda, trader_volume_bump) = find_trader_volume_pda(&pool, &trader);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new_readonly(trader, false),
+            AccountMeta::new(trader_volume_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: InitializeTraderVolumeIx::from_struct(ParsedInitializeTraderVolumeIx {
+            bump: trader_volume_bump,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_initialize_curve_table_ix(
+    authority: Pubkey,
+    seed: [u8; 32],
+    step_size: u64,
+    num_steps: u64,
+) -> Instruction {
+    let (curve_table_pda, curve_table_bump) = find_curve_table_pda(&authority, &seed);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(curve_table_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: InitializeCurveTableIx::from_struct(ParsedInitializeCurveTableIx {
+            seed,
+            step_size,
+            num_steps,
+            bump: curve_table_bump,
+        }).to_bytes(),
+    }
+}
+
+/// Appends up to CURVE_TABLE_CHUNK_LEN entries to an unfinalized table.
+pub fn build_write_curve_table_ix(
+    authority: Pubkey,
+    curve_table: Pubkey,
+    entries: Vec<CurveTableEntry>,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(curve_table, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: WriteCurveTableIx::from_struct(ParsedWriteCurveTableIx {
+            entries,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_finalize_curve_table_ix(
+    authority: Pubkey,
+    curve_table: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(curve_table, false),
+        ],
+        data: FinalizeCurveTableIx::from_struct(ParsedFinalizeCurveTableIx {
+        }).to_bytes(),
+    }
+}
+
+/// Additional pools take the currency's primary pool as the first optional
+/// trailing account on creation, so the program can check its base mint.
+fn primary_pool_metas(pool: Pubkey, primary_pool: Pubkey) -> Vec<AccountMeta> {
+    if pool == primary_pool {
+        return vec![];
+    }
+    vec![AccountMeta::new_readonly(primary_pool, false)]
+}
+
+pub fn build_initialize_protocol_config_ix(
+    admin: Pubkey, // Must be the program's upgrade authority
+    min_sell_fee: u16,
+    max_sell_fee: u16,
+    creation_policy: CreationPolicy,
+) -> Instruction {
+    let (protocol_config_pda, protocol_config_bump) = find_protocol_config_pda();
+    let (program_data_pda, _) = find_program_data_pda();
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new(protocol_config_pda, false),
+            AccountMeta::new_readonly(program_data_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: InitializeProtocolConfigIx::from_struct(ParsedInitializeProtocolConfigIx {
+            min_sell_fee,
+            max_sell_fee,
+            creation_policy,
+            bump: protocol_config_bump,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_update_protocol_config_ix(
+    admin: Pubkey,
+    new_admin: Pubkey, // Pass `admin` to keep it
+    min_sell_fee: u16,
+    max_sell_fee: u16,
+    creation_policy: CreationPolicy,
+) -> Instruction {
+    let (protocol_config_pda, _) = find_protocol_config_pda();
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new(protocol_config_pda, false),
+            AccountMeta::new_readonly(new_admin, false),
+        ],
+        data: UpdateProtocolConfigIx::from_struct(ParsedUpdateProtocolConfigIx {
+            min_sell_fee,
+            max_sell_fee,
+            creation_policy,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_set_base_mint_allowed_ix(
+    admin: Pubkey,
+    base_mint: Pubkey,
+    allowed: bool,
+) -> Instruction {
+    let (protocol_config_pda, _) = find_protocol_config_pda();
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new(protocol_config_pda, false),
+            AccountMeta::new_readonly(base_mint, false),
+        ],
+        data: SetBaseMintAllowedIx::from_struct(ParsedSetBaseMintAllowedIx {
+            allowed,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_initialize_currency_profile_ix(
+    authority: Pubkey,
+    currency: Pubkey,
+) -> Instruction {
+    let (profile_pda, profile_bump) = find_profile_pda(&currency);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(currency, false),
+            AccountMeta::new(profile_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: InitializeCurrencyProfileIx::from_struct(ParsedInitializeCurrencyProfileIx {
+            bump: profile_bump,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_set_profile_description_ix(
+    authority: Pubkey,
+    currency: Pubkey,
+    description: String,
+) -> Instruction {
+    let (profile_pda, _) = find_profile_pda(&currency);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(currency, false),
+            AccountMeta::new(profile_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: SetProfileDescriptionIx::from_struct(ParsedSetProfileDescriptionIx {
+            description,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_set_profile_link_ix(
+    authority: Pubkey,
+    currency: Pubkey,
+    key: String,
+    value: Option<String>, // None removes the link
+) -> Instruction {
+    let (profile_pda, _) = find_profile_pda(&currency);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(currency, false),
+            AccountMeta::new(profile_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: SetProfileLinkIx::from_struct(ParsedSetProfileLinkIx {
+            key,
+            value,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_set_circuit_breaker_ix(
+    authority: Pubkey,
+    pool: Pubkey,
+    band: u16,   // Basis points, 0 disables the breaker
+    window: u64, // Slots
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(pool, false),
+        ],
+        data: SetCircuitBreakerIx::from_struct(ParsedSetCircuitBreakerIx {
+            band,
+            window,
+        }).to_bytes(),
+    }
+}
+
+/// When a pool opens to everyone, and who may buy before and right after.
+/// The default opens the pool now with no presale or launch window.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LaunchSchedule {
+    pub launch_at: i64,          // Unix timestamp, 0 to open trading now
+    pub presale_cap: u64,        // Base quarks per allowlisted wallet
+    pub presale_root: [u8; 32],  // Allowlist merkle root, zero for no presale
+    pub launch_window: u64,      // Seconds after launch with capped buys, 0 for none
+    pub launch_cap: u64,         // Base quarks per wallet within the window
+}
+
+pub fn build_set_launch_ix(
+    authority: Pubkey,
+    pool: Pubkey,
+    target_mint: Pubkey,
+    schedule: LaunchSchedule,
+) -> Instruction {
+    let (vault_a_pda, _) = find_vault_pda(&pool, &target_mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(target_mint, false),
+            AccountMeta::new_readonly(vault_a_pda, false),
+        ],
+        data: SetLaunchIx::from_struct(ParsedSetLaunchIx {
+            launch_at: schedule.launch_at,
+            presale_cap: schedule.presale_cap,
+            presale_root: schedule.presale_root,
+            launch_window: schedule.launch_window,
+            launch_cap: schedule.launch_cap,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_initialize_wallet_purchases_ix(
+    payer: Pubkey,
+    pool: Pubkey,
+    wallet: Pubkey,
+) -> Instruction {
+    let (wallet_purchases_pda, wallet_purchases_bump) = find_wallet_purchases_pda(&pool, &wallet);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new_readonly(wallet, false),
+            AccountMeta::new(wallet_purchases_pda, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: InitializeWalletPurchasesIx::from_struct(ParsedInitializeWalletPurchasesIx {
+            bump: wallet_purchases_bump,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_join_presale_ix(
+    wallet: Pubkey,
+    pool: Pubkey,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    let (wallet_purchases_pda, _) = find_wallet_purchases_pda(&pool, &wallet);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(wallet, true),
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new(wallet_purchases_pda, false),
+        ],
+        data: JoinPresaleIx::from_struct(ParsedJoinPresaleIx {
+            proof,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_claim_vested_ix(
+    beneficiary: Pubkey,
+    pool: Pubkey,
+    target_mint: Pubkey,
+    target_token_program: Pubkey,
+    beneficiary_target: Pubkey,
+) -> Instruction {
+    let (vesting_pda, _) = find_vesting_pda(&pool, &beneficiary);
+    let (vesting_vault_pda, _) = find_vault_pda(&vesting_pda, &target_mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(beneficiary, true),
+            AccountMeta::new(vesting_pda, false),
+            AccountMeta::new(vesting_vault_pda, false),
+            AccountMeta::new_readonly(target_mint, false),
+            AccountMeta::new(beneficiary_target, false),
+            AccountMeta::new_readonly(target_token_program, false),
+        ],
+        data: ClaimVestedIx::from_struct(ParsedClaimVestedIx {
+        }).to_bytes(),
+    }
+}
+
+/// Locks currency from the creator's token account in a new cash link for
+/// `link_key`.
+pub fn build_create_cash_link_ix(
+    creator: Pubkey,
+    link_key: Pubkey,
+    target_mint: Pubkey,
+    target_token_program: Pubkey,
+    amount: u64,
+    expires_at: i64,
+    creator_target: Pubkey,
+) -> Instruction {
+    cash_link_ix(
+        creator,
+        link_key,
+        target_mint,
+        target_token_program,
+        amount,
+        0,
+        0,
+        expires_at,
+        vec![
+            AccountMeta::new(creator_target, false),
+        ],
+    )
+}
+
+/// Buys currency from a pool straight into a new cash link for `link_key`.
+#[allow(clippy::too_many_arguments)]
+pub fn build_buy_cash_link_ix(
+    creator: Pubkey,
+    link_key: Pubkey,
+    pool: Pubkey,
+    target_mint: Pubkey,
+    target_token_program: Pubkey,
+    base_mint: Pubkey,
+    base_token_program: Pubkey,
+    in_amount: u64,
+    min_amount_out: u64,
+    expires_at: i64,
+    creator_base: Pubkey,
+    curve_table: Option<Pubkey>, // Table-backed pools only
+) -> Instruction {
+    let (vault_a_pda, _) = find_vault_pda(&pool, &target_mint);
+    let (vault_b_pda, _) = find_vault_pda(&pool, &base_mint);
+    let (wallet_purchases_pda, _) = find_wallet_purchases_pda(&pool, &creator);
+
+    cash_link_ix(
+        creator,
+        link_key,
+        target_mint,
+        target_token_program,
+        0,
+        in_amount,
+        min_amount_out,
+        expires_at,
+        [
+            vec![
+                AccountMeta::new(pool, false),
+                AccountMeta::new_readonly(base_mint, false),
+                AccountMeta::new(vault_a_pda, false),
+                AccountMeta::new(vault_b_pda, false),
+                AccountMeta::new(creator_base, false),
+                AccountMeta::new_readonly(base_token_program, false),
+            ],
+            curve_table_metas(curve_table),
+            vec![
+                AccountMeta::new(wallet_purchases_pda, false),
+            ],
+        ].concat(),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cash_link_ix(
+    creator: Pubkey,
+    link_key: Pubkey,
+    target_mint: Pubkey,
+    target_token_program: Pubkey,
+    amount: u64,
+    in_amount: u64,
+    min_amount_out: u64,
+    expires_at: i64,
+    source_metas: Vec<AccountMeta>,
+) -> Instruction {
+    let (cash_link_pda, cash_link_bump) = find_cash_link_pda(&link_key);
+    let (cash_link_vault_pda, cash_link_vault_bump) = find_vault_pda(&cash_link_pda, &target_mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: [
+            vec![
+                AccountMeta::new(creator, true),
+                AccountMeta::new_readonly(link_key, false),
+                AccountMeta::new(cash_link_pda, false),
+                AccountMeta::new(cash_link_vault_pda, false),
+                AccountMeta::new_readonly(target_mint, false),
+                AccountMeta::new_readonly(target_token_program, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            source_metas,
+        ].concat(),
+        data: CreateCashLinkIx::from_struct(ParsedCreateCashLinkIx {
+            amount,
+            in_amount,
+            min_amount_out,
+            expires_at,
+            bump: cash_link_bump,
+            vault_bump: cash_link_vault_bump,
+        }).to_bytes(),
+    }
+}
+
+/// Proves the holder of a cash link's secret wants it paid to
+/// `destination`. `signature` is the link key's signature over
+/// `cash_link_claim_message`, and this must come right before the claim.
+pub fn build_cash_link_signature_ix(
+    link_key: Pubkey,
+    created_slot: u64, // CashLink::created_slot
+    signature: [u8; 64],
+    destination: Pubkey,
+) -> Instruction {
+    let (cash_link_pda, _) = find_cash_link_pda(&link_key);
+    let message = cash_link_claim_message(&cash_link_pda, created_slot, &destination);
+
+    Instruction {
+        program_id: solana_program::ed25519_program::id(),
+        accounts: vec![],
+        data: ed25519_instruction_data(&link_key, &signature, &message),
+    }
+}
+
+pub fn build_claim_cash_link_ix(
+    link_key: Pubkey,
+    creator: Pubkey, // Receives the rent of the closed link
+    target_mint: Pubkey,
+    target_token_program: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
+    let (cash_link_pda, _) = find_cash_link_pda(&link_key);
+    let (cash_link_vault_pda, _) = find_vault_pda(&cash_link_pda, &target_mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(cash_link_pda, false),
+            AccountMeta::new(cash_link_vault_pda, false),
+            AccountMeta::new_readonly(target_mint, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new(creator, false),
+            AccountMeta::new_readonly(target_token_program, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+        data: ClaimCashLinkIx::from_struct(ParsedClaimCashLinkIx {
+        }).to_bytes(),
+    }
+}
+
+pub fn build_reclaim_cash_link_ix(
+    creator: Pubkey,
+    link_key: Pubkey,
+    target_mint: Pubkey,
+    target_token_program: Pubkey,
+    creator_target: Pubkey,
+) -> Instruction {
+    let (cash_link_pda, _) = find_cash_link_pda(&link_key);
+    let (cash_link_vault_pda, _) = find_vault_pda(&cash_link_pda, &target_mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(creator, true),
+            AccountMeta::new(cash_link_pda, false),
+            AccountMeta::new(cash_link_vault_pda, false),
+            AccountMeta::new_readonly(target_mint, false),
+            AccountMeta::new(creator_target, false),
+            AccountMeta::new_readonly(target_token_program, false),
+        ],
+        data: ReclaimCashLinkIx::from_struct(ParsedReclaimCashLinkIx {
+        }).to_bytes(),
+    }
+}
+
+/// Escrows `amount` of `escrow_mint` (the pool's base mint when buying, its
+/// currency when selling) in a new limit order.
+#[allow(clippy::too_many_arguments)]
+pub fn build_create_limit_order_ix(
+    owner: Pubkey,
+    pool: Pubkey,
+    id: u64,
+    side: OrderSide,
+    escrow_mint: Pubkey,
+    escrow_token_program: Pubkey,
+    owner_source: Pubkey,
+    amount: u64,
+    min_amount_out: u64,
+    target_price: u64,
+    bounty: u64,
+    expires_at: i64,
+) -> Instruction {
+    let (order_pda, order_bump) = find_limit_order_pda(&pool, &owner, id);
+    let (order_vault_pda, order_vault_bump) = find_vault_pda(&order_pda, &escrow_mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new(order_pda, false),
+            AccountMeta::new(order_vault_pda, false),
+            AccountMeta::new_readonly(escrow_mint, false),
+            AccountMeta::new(owner_source, false),
+            AccountMeta::new_readonly(escrow_token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: CreateLimitOrderIx::from_struct(ParsedCreateLimitOrderIx {
+            id,
+            side,
+            amount,
+            min_amount_out,
+            target_price,
+            bounty,
+            expires_at,
+            bump: order_bump,
+            vault_bump: order_vault_bump,
+        }).to_bytes(),
+    }
+}
+
+/// Fills a crossed limit order, paying its bounty to `executor`.
+/// `owner_source` gets back any escrow left over and `owner_destination`
+/// receives what the order bought or sold for.
+#[allow(clippy::too_many_arguments)]
+pub fn build_execute_limit_order_ix(
+    executor: Pubkey,
+    owner: Pubkey,
+    pool: Pubkey,
+    id: u64,
+    side: OrderSide,
+    target_mint: Pubkey,
+    target_token_program: Pubkey,
+    base_mint: Pubkey,
+    base_token_program: Pubkey,
+    owner_source: Pubkey,
+    owner_destination: Pubkey,
+    curve_table: Option<Pubkey>, // Table-backed pools only
+) -> Instruction {
+    let (order_pda, _) = find_limit_order_pda(&pool, &owner, id);
+    let (vault_a_pda, _) = find_vault_pda(&pool, &target_mint);
+    let (vault_b_pda, _) = find_vault_pda(&pool, &base_mint);
+
+    // The escrow vault trades as its own wallet
+    let (order_vault_pda, side_metas) = match side {
+        OrderSide::Buy => {
+            let (order_vault_pda, _) = find_vault_pda(&order_pda, &base_mint);
+            let (wallet_purchases_pda, _) = find_wallet_purchases_pda(&pool, &order_vault_pda);
+            (order_vault_pda, vec![
+                AccountMeta::new(wallet_purchases_pda, false),
+            ])
+        }
+        OrderSide::Sell => {
+            let (order_vault_pda, _) = find_vault_pda(&order_pda, &target_mint);
+            let (fee_schedule_pda, _) = find_fee_schedule_pda(&pool);
+            let (trader_volume_pda, _) = find_trader_volume_pda(&pool, &order_vault_pda);
+            (order_vault_pda, vec![
+                AccountMeta::new_readonly(fee_schedule_pda, false),
+                AccountMeta::new(trader_volume_pda, false),
+            ])
+        }
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: [
+            vec![
+                AccountMeta::new(executor, true),
+                AccountMeta::new(order_pda, false),
+                AccountMeta::new(owner, false),
+                AccountMeta::new(order_vault_pda, false),
+                AccountMeta::new(owner_source, false),
+                AccountMeta::new(owner_destination, false),
+                AccountMeta::new(pool, false),
+                AccountMeta::new_readonly(target_mint, false),
+                AccountMeta::new_readonly(base_mint, false),
+                AccountMeta::new(vault_a_pda, false),
+                AccountMeta::new(vault_b_pda, false),
+                AccountMeta::new_readonly(target_token_program, false),
+                AccountMeta::new_readonly(base_token_program, false),
+            ],
+            curve_table_metas(curve_table),
+            side_metas,
+        ].concat(),
+        data: ExecuteLimitOrderIx::from_struct(ParsedExecuteLimitOrderIx {
+        }).to_bytes(),
+    }
+}
+
+/// Cancels a limit order, refunding its escrow and bounty to the owner.
+/// Anyone may cancel an expired order.
+#[allow(clippy::too_many_arguments)]
+pub fn build_cancel_limit_order_ix(
+    authority: Pubkey,
+    owner: Pubkey,
+    pool: Pubkey,
+    id: u64,
+    escrow_mint: Pubkey,
+    escrow_token_program: Pubkey,
+    owner_source: Pubkey,
+) -> Instruction {
+    let (order_pda, _) = find_limit_order_pda(&pool, &owner, id);
+    let (order_vault_pda, _) = find_vault_pda(&order_pda, &escrow_mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(order_pda, false),
+            AccountMeta::new(owner, false),
+            AccountMeta::new(order_vault_pda, false),
+            AccountMeta::new_readonly(escrow_mint, false),
+            AccountMeta::new(owner_source, false),
+            AccountMeta::new_readonly(escrow_token_program, false),
+        ],
+        data: CancelLimitOrderIx::from_struct(ParsedCancelLimitOrderIx {
+        }).to_bytes(),
+    }
+}
+
+/// Opens a DCA plan that buys up to `amount_per_fill` base quarks of the
+/// pool's currency every `interval` seconds, failing any fill that buys less
+/// than `min_amount_out` for a full amount. Fund it with a deposit.
+#[allow(clippy::too_many_arguments)]
+pub fn build_create_dca_plan_ix(
+    owner: Pubkey,
+    pool: Pubkey,
+    id: u64,
+    base_mint: Pubkey,
+    base_token_program: Pubkey,
+    amount_per_fill: u64,
+    min_amount_out: u64,
+    interval: i64,
+    first_fill_at: i64, // 0 to start right away
+) -> Instruction {
+    let (plan_pda, plan_bump) = find_dca_plan_pda(&pool, &owner, id);
+    let (plan_vault_pda, plan_vault_bump) = find_vault_pda(&plan_pda, &base_mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new(plan_pda, false),
+            AccountMeta::new(plan_vault_pda, false),
+            AccountMeta::new_readonly(base_mint, false),
+            AccountMeta::new_readonly(base_token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: CreateDcaPlanIx::from_struct(ParsedCreateDcaPlanIx {
+            id,
+            amount_per_fill,
+            min_amount_out,
+            interval,
+            first_fill_at,
+            bump: plan_bump,
+            vault_bump: plan_vault_bump,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_deposit_dca_ix(
+    owner: Pubkey,
+    pool: Pubkey,
+    id: u64,
+    base_mint: Pubkey,
+    base_token_program: Pubkey,
+    owner_base: Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: dca_escrow_metas(owner, pool, id, base_mint, base_token_program, owner_base),
+        data: DepositDcaIx::from_struct(ParsedDepositDcaIx {
+            amount,
+        }).to_bytes(),
+    }
+}
+
+/// Takes `amount` base quarks back out of a DCA plan, or all of them when
+/// `amount` is 0.
+pub fn build_withdraw_dca_ix(
+    owner: Pubkey,
+    pool: Pubkey,
+    id: u64,
+    base_mint: Pubkey,
+    base_token_program: Pubkey,
+    owner_base: Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: dca_escrow_metas(owner, pool, id, base_mint, base_token_program, owner_base),
+        data: WithdrawDcaIx::from_struct(ParsedWithdrawDcaIx {
+            amount,
+        }).to_bytes(),
+    }
+}
+
+/// Closes a DCA plan, refunding what is left of its escrow to `owner_base`.
+pub fn build_close_dca_plan_ix(
+    owner: Pubkey,
+    pool: Pubkey,
+    id: u64,
+    base_mint: Pubkey,
+    base_token_program: Pubkey,
+    owner_base: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: dca_escrow_metas(owner, pool, id, base_mint, base_token_program, owner_base),
+        data: CloseDcaPlanIx::from_struct(ParsedCloseDcaPlanIx {
+        }).to_bytes(),
+    }
+}
+
+/// Runs one due fill of a DCA plan, delivering the bought tokens to
+/// `owner_target`. Anyone may execute.
+#[allow(clippy::too_many_arguments)]
+pub fn build_execute_dca_ix(
+    executor: Pubkey,
+    owner: Pubkey,
+    pool: Pubkey,
+    id: u64,
+    target_mint: Pubkey,
+    target_token_program: Pubkey,
+    base_mint: Pubkey,
+    base_token_program: Pubkey,
+    owner_target: Pubkey,
+    curve_table: Option<Pubkey>, // Table-backed pools only
+) -> Instruction {
+    let (plan_pda, _) = find_dca_plan_pda(&pool, &owner, id);
+    let (plan_vault_pda, _) = find_vault_pda(&plan_pda, &base_mint);
+    let (vault_a_pda, _) = find_vault_pda(&pool, &target_mint);
+    let (vault_b_pda, _) = find_vault_pda(&pool, &base_mint);
+    let (wallet_purchases_pda, _) = find_wallet_purchases_pda(&pool, &plan_vault_pda);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: [
+            vec![
+                AccountMeta::new(executor, true),
+                AccountMeta::new(plan_pda, false),
+                AccountMeta::new(plan_vault_pda, false),
+                AccountMeta::new(owner_target, false),
+                AccountMeta::new(pool, false),
+                AccountMeta::new_readonly(target_mint, false),
+                AccountMeta::new_readonly(base_mint, false),
+                AccountMeta::new(vault_a_pda, false),
+                AccountMeta::new(vault_b_pda, false),
+                AccountMeta::new_readonly(target_token_program, false),
+                AccountMeta::new_readonly(base_token_program, false),
+            ],
+            curve_table_metas(curve_table),
+            vec![
+                AccountMeta::new(wallet_purchases_pda, false),
+            ],
+        ].concat(),
+        data: ExecuteDcaIx::from_struct(ParsedExecuteDcaIx {
+        }).to_bytes(),
+    }
+}
+
+/// Creates a distributor for `mint` and funds it with `total_amount` from
+/// `authority_source`. `root` commits to `num_recipients` entries built with
+/// `distributor_leaf`.
+#[allow(clippy::too_many_arguments)]
+pub fn build_create_distributor_ix(
+    authority: Pubkey,
+    id: u64,
+    mint: Pubkey,
+    token_program: Pubkey,
+    authority_source: Pubkey,
+    root: [u8; 32],
+    total_amount: u64,
+    num_recipients: u64,
+    expires_at: i64,
+) -> Instruction {
+    let (distributor_pda, distributor_bump) = find_distributor_pda(&mint, &authority, id);
+    let (distributor_vault_pda, distributor_vault_bump) = find_vault_pda(&distributor_pda, &mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(distributor_pda, false),
+            AccountMeta::new(distributor_vault_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(authority_source, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: CreateDistributorIx::from_struct(ParsedCreateDistributorIx {
+            id,
+            root,
+            total_amount,
+            num_recipients,
+            expires_at,
+            bump: distributor_bump,
+            vault_bump: distributor_vault_bump,
+        }).to_bytes(),
+    }
+}
+
+/// Claims entry `index` of a distributor for `claimant`, paying `amount` to
+/// `claimant_destination`.
+#[allow(clippy::too_many_arguments)]
+pub fn build_claim_distribution_ix(
+    claimant: Pubkey,
+    distributor: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    claimant_destination: Pubkey,
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    let (distributor_vault_pda, _) = find_vault_pda(&distributor, &mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(claimant, true),
+            AccountMeta::new(distributor, false),
+            AccountMeta::new(distributor_vault_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(claimant_destination, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+        data: ClaimDistributionIx::from_struct(ParsedClaimDistributionIx {
+            index,
+            amount,
+            proof,
+        }).to_bytes(),
+    }
+}
+
+/// Returns an expired distributor's unclaimed tokens to `authority_destination`
+/// and closes it.
+pub fn build_clawback_distributor_ix(
+    authority: Pubkey,
+    distributor: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    authority_destination: Pubkey,
+) -> Instruction {
+    let (distributor_vault_pda, _) = find_vault_pda(&distributor, &mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(distributor, false),
+            AccountMeta::new(distributor_vault_pda, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(authority_destination, false),
+            AccountMeta::new_readonly(token_program, false),
+        ],
+        data: ClawbackDistributorIx::from_struct(ParsedClawbackDistributorIx {
+        }).to_bytes(),
+    }
+}
+
+/// Registers the program a pool CPIs after every trade, or clears it when
+/// `hook_program` is None.
+pub fn build_set_trade_hook_ix(
+    authority: Pubkey,
+    pool: Pubkey,
+    hook_program: Option<Pubkey>,
+    compute_units: u32, // Most compute the hook may use per trade
+    required: bool,     // Whether trades must run the hook, even when short on compute
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(hook_program.unwrap_or(system_program::id()), false),
+        ],
+        data: SetTradeHookIx::from_struct(ParsedSetTradeHookIx {
+            compute_units,
+            required,
+        }).to_bytes(),
+    }
+}
+
+/// Accounts a trade passes right after its other optional accounts to run
+/// the pool's trade hook: the hook program, its authority PDA, then whatever
+/// accounts the hook itself needs. Trades on a pool with a hook fail without
+/// them. Transfer hook accounts and a batch buy's recipients come after.
+pub fn trade_hook_metas(pool: &Pubkey, hook_program: Pubkey, hook_accounts: Vec<AccountMeta>) -> Vec<AccountMeta> {
+    let (hook_authority_pda, _) = find_trade_hook_authority_pda(pool);
+
+    [
+        vec![
+            AccountMeta::new_readonly(hook_program, false),
+            AccountMeta::new_readonly(hook_authority_pda, false),
+        ],
+        hook_accounts,
+    ].concat()
+}
+
+pub fn build_pause_ix(
+    authority: Pubkey, // Pool authority or protocol admin
+    pool: Pubkey,
+    pause_sells: bool,
+) -> Instruction {
+    let (protocol_config_pda, _) = find_protocol_config_pda();
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(protocol_config_pda, false),
+        ],
+        data: PauseIx::from_struct(ParsedPauseIx {
+            pause_sells,
+        }).to_bytes(),
+    }
+}
+
+pub fn build_resume_ix(
+    authority: Pubkey, // Pool authority or protocol admin
+    pool: Pubkey,
+) -> Instruction {
+    let (protocol_config_pda, _) = find_protocol_config_pda();
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new_readonly(protocol_config_pda, false),
+        ],
+        data: ResumeIx::from_struct(ParsedResumeIx {
+        }).to_bytes(),
+    }
+}
+
+/// Deposits, withdrawals and closing all move base between the owner and a
+/// DCA plan's escrow.
+fn dca_escrow_metas(
+    owner: Pubkey,
+    pool: Pubkey,
+    id: u64,
+    base_mint: Pubkey,
+    base_token_program: Pubkey,
+    owner_base: Pubkey,
+) -> Vec<AccountMeta> {
+    let (plan_pda, _) = find_dca_plan_pda(&pool, &owner, id);
+    let (plan_vault_pda, _) = find_vault_pda(&plan_pda, &base_mint);
+
+    vec![
+        AccountMeta::new(owner, true),
+        AccountMeta::new(plan_pda, false),
+        AccountMeta::new(plan_vault_pda, false),
+        AccountMeta::new_readonly(base_mint, false),
+        AccountMeta::new(owner_base, false),
+        AccountMeta::new_readonly(base_token_program, false),
+    ]
+}
+
+/// Table-backed pools take their CurveTable as the first optional trailing
+/// account on pool creation and trades.
+fn curve_table_metas(curve_table: Option<Pubkey>) -> Vec<AccountMeta> {
+    curve_table
+        .map(|curve_table| AccountMeta::new_readonly(curve_table, false))
+        .into_iter()
+        .collect()
+}
+
+/// The accounts for an initial buy follow the curve table on pool creation:
+/// where the currency goes, the authority's base account, then the vesting
+/// account if it vests.
+fn initial_buy_metas(initial_buy: Option<InitialBuy>, vesting: Pubkey, vesting_vault: Pubkey) -> Vec<AccountMeta> {
+    match initial_buy {
+        Some(buy) if buy.vesting.is_some() => vec![
+            AccountMeta::new(vesting_vault, false),
+            AccountMeta::new(buy.authority_base, false),
+            AccountMeta::new(vesting, false),
+        ],
+        Some(buy) => vec![
+            AccountMeta::new(buy.authority_target, false),
+            AccountMeta::new(buy.authority_base, false),
+        ],
+        None => vec![],
+    }
+}
@@ -0,0 +1,49 @@
+use steel::*;
+use super::AccountType;
+use crate::state;
+
+/// Currency held for whoever has the secret key of `link_key`, which is
+/// shared off-chain as a link. Claiming takes a signature from that key over
+/// `cash_link_claim_message`. Once `expires_at` passes, only the creator can
+/// take the currency back. The tokens sit in a vault at
+/// `find_vault_pda(cash_link, mint)`.
+/// PDA seeds: ["cash_link", link_key]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CashLink {
+    pub creator: Pubkey,
+    pub link_key: Pubkey,         // One-time key whose secret is the link
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+
+    pub amount: u64,              // Currency quarks deposited
+    pub created_at: i64,          // Unix timestamp
+    pub created_slot: u64,        // Signed by claims, so they only work on this link
+    pub expires_at: i64,          // Unix timestamp
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+    pub vault_bump: u8,
+
+    _padding: [u8; 6],
+}
+
+impl CashLink {
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// What the link key signs to release a cash link to `destination`. Binding
+/// the destination keeps a claim seen in flight from being redirected, and
+/// binding the creation slot keeps it from being replayed on a link later
+/// recreated with the same key.
+pub fn cash_link_claim_message(cash_link: &Pubkey, created_slot: u64, destination: &Pubkey) -> [u8; 72] {
+    let mut message = [0u8; 72];
+    message[..32].copy_from_slice(cash_link.as_ref());
+    message[32..40].copy_from_slice(&created_slot.to_le_bytes());
+    message[40..].copy_from_slice(destination.as_ref());
+    message
+}
+
+state!(AccountType, CashLink);
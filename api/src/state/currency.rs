@@ -1,5 +1,6 @@
 use steel::*;
 use super::AccountType;
+use crate::consts::*;
 use crate::state;
 
 /// Currency configuration account that stores metadata for a custom currency.
@@ -17,7 +18,87 @@ pub struct CurrencyConfig {
     pub bump: u8,
     pub mint_bump: u8,                   // Mint bump seed
 
-    _padding: [u8; 6],
+    pub decimals: u8,                    // Mint decimals (target)
+    pub has_metadata: u8,                // Set once InitializeMetadata has run
+    _padding: [u8; 4],
+    pub max_supply: u64,                 // Total supply in whole tokens (target)
+    pub supply_allocated: u64,           // Whole tokens minted to pool vaults so far
+}
+
+/// Size of a currency account, discriminator included, from before
+/// currencies recorded their decimals, supply and metadata status. Such
+/// currencies have to be migrated with MigrateCurrency before they can be used.
+pub const CURRENCY_CONFIG_V0_LEN: usize = 8 + 144;
+
+impl CurrencyConfig {
+    /// Reads a currency account in the v0 layout. Every v0 currency had the
+    /// default decimals and supply, minted in full to its pool on creation.
+    /// Whether it has a pool and metadata is read off its other accounts.
+    pub fn unpack_v0(data: &[u8], has_pool: bool, has_metadata: bool) -> Result<Self, ProgramError> {
+        if data.len() != CURRENCY_CONFIG_V0_LEN || data[0] != Self::discriminator() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut currency = Self::zeroed();
+        bytemuck::bytes_of_mut(&mut currency)[..CURRENCY_CONFIG_V0_LEN - 8]
+            .copy_from_slice(&data[8..]);
+
+        // These overlap the v0 padding
+        currency.decimals = DEFAULT_TOKEN_DECIMALS;
+        currency.has_metadata = has_metadata as u8;
+        currency._padding = [0; 4];
+
+        currency.max_supply = DEFAULT_TOKEN_SUPPLY;
+        currency.supply_allocated = if has_pool { DEFAULT_TOKEN_SUPPLY } else { 0 };
+
+        Ok(currency)
+    }
+
+    /// Whole tokens not yet allocated to a pool.
+    pub fn supply_unallocated(&self) -> u64 {
+        self.max_supply.saturating_sub(self.supply_allocated)
+    }
 }
 
 state!(AccountType, CurrencyConfig);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpack_v0() {
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        let mut data = vec![0u8; CURRENCY_CONFIG_V0_LEN];
+        data[0] = CurrencyConfig::discriminator();
+        data[8..40].copy_from_slice(authority.as_ref());
+        data[40..72].copy_from_slice(mint.as_ref());
+        data[72..76].copy_from_slice(b"test");         // name
+        data[104..108].copy_from_slice(b"TEST");       // symbol
+        data[144..146].copy_from_slice(&[255, 254]);   // bumps
+        data[146..152].fill(7);                        // padding
+
+        let currency = CurrencyConfig::unpack_v0(&data, true, true).unwrap();
+        assert_eq!(currency.authority, authority);
+        assert_eq!(currency.mint, mint);
+        assert_eq!(&currency.name[..5], b"test\0");
+        assert_eq!(&currency.symbol[..5], b"TEST\0");
+        assert_eq!((currency.bump, currency.mint_bump), (255, 254));
+        assert_eq!(currency.decimals, DEFAULT_TOKEN_DECIMALS);
+        assert_eq!(currency.has_metadata, 1);
+        assert_eq!(currency._padding, [0; 4]);
+        assert_eq!(currency.max_supply, DEFAULT_TOKEN_SUPPLY);
+        assert_eq!(currency.supply_unallocated(), 0);
+
+        let currency = CurrencyConfig::unpack_v0(&data, false, false).unwrap();
+        assert_eq!(currency.has_metadata, 0);
+        assert_eq!(currency.supply_unallocated(), DEFAULT_TOKEN_SUPPLY);
+
+        // Current-sized and foreign accounts are left alone
+        assert!(CurrencyConfig::unpack_v0(&[0u8; 8 + std::mem::size_of::<CurrencyConfig>()], true, true).is_err());
+        data[0] = AccountType::LiquidityPool.into();
+        assert!(CurrencyConfig::unpack_v0(&data, true, true).is_err());
+    }
+}
@@ -0,0 +1,207 @@
+use steel::*;
+use super::AccountType;
+use crate::state;
+
+/// One step of an uploaded price table. Values are raw `UnsignedNumeric`
+/// (18 decimal) u128s stored little endian, matching the entries of
+/// DISCRETE_PRICING_TABLE and DISCRETE_CUMULATIVE_VALUE_TABLE.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct CurveTableEntry {
+    pub price: [u8; 16],            // Price per whole token within this step
+    pub cumulative_value: [u8; 16], // Value of all steps below this one
+}
+
+impl CurveTableEntry {
+    pub fn new(price: u128, cumulative_value: u128) -> Self {
+        Self {
+            price: price.to_le_bytes(),
+            cumulative_value: cumulative_value.to_le_bytes(),
+        }
+    }
+
+    pub fn price(&self) -> u128 {
+        u128::from_le_bytes(self.price)
+    }
+
+    pub fn cumulative_value(&self) -> u128 {
+        u128::from_le_bytes(self.cumulative_value)
+    }
+}
+
+/// Hand-tuned discrete price schedule, uploaded by its authority in chunks.
+/// The header is followed by `num_entries` CurveTableEntry values; a complete
+/// table holds `num_steps + 1` of them. Only finalized tables can back a pool.
+/// PDA seeds: ["curve_table", authority_pubkey, seed]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CurveTable {
+    pub authority: Pubkey,        // Uploads entries and finalizes
+    pub seed: [u8; 32],           // Lets one authority own several tables
+
+    pub step_size: u64,           // Whole tokens per step
+    pub num_steps: u64,           // Steps in the curve
+    pub num_entries: u64,         // Entries uploaded so far
+
+    pub is_finalized: u8,
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+
+    _padding: [u8; 6],
+}
+
+impl CurveTable {
+    /// Account size once `num_entries` entries are uploaded
+    pub fn size_for(num_entries: usize) -> Option<usize> {
+        num_entries
+            .checked_mul(std::mem::size_of::<CurveTableEntry>())?
+            .checked_add(Self::get_size())
+    }
+
+    /// Splits account data into the header and the entries uploaded so far.
+    pub fn unpack_entries(data: &[u8]) -> Result<(&Self, &[CurveTableEntry]), ProgramError> {
+        if data.len() < Self::get_size() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (header, entries) = data.split_at(Self::get_size());
+        let table = Self::try_from_bytes(header)?;
+        let entries = bytemuck::try_cast_slice::<u8, CurveTableEntry>(entries)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if entries.len() as u64 != table.num_entries {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((table, entries))
+    }
+
+    pub fn unpack_entries_mut(data: &mut [u8]) -> Result<(&mut Self, &mut [CurveTableEntry]), ProgramError> {
+        if data.len() < Self::get_size() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (header, entries) = data.split_at_mut(Self::get_size());
+        let table = Self::try_from_bytes_mut(header)?;
+        let entries = bytemuck::try_cast_slice_mut::<u8, CurveTableEntry>(entries)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if entries.len() as u64 != table.num_entries {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((table, entries))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.num_steps.checked_add(1) == Some(self.num_entries)
+    }
+
+    /// Checks that `entries` can follow `prev` in a table with `step_size`.
+    ///
+    /// The first entry must have a zero cumulative value and a non-zero price.
+    /// After that prices never decrease, and each cumulative value is exactly
+    /// the previous one plus a full step at the previous price, the same
+    /// relation DISCRETE_CUMULATIVE_VALUE_TABLE holds.
+    pub fn check_entries(
+        step_size: u64,
+        prev: Option<&CurveTableEntry>,
+        entries: &[CurveTableEntry],
+    ) -> bool {
+        let mut prev = prev.copied();
+
+        for entry in entries {
+            let valid = match prev {
+                None => entry.cumulative_value() == 0 && entry.price() > 0,
+                Some(prev) => {
+                    let expected = prev.price()
+                        .checked_mul(step_size.into())
+                        .and_then(|v| v.checked_add(prev.cumulative_value()));
+
+                    entry.price() >= prev.price() &&
+                    expected == Some(entry.cumulative_value())
+                }
+            };
+
+            if !valid {
+                return false;
+            }
+
+            prev = Some(*entry);
+        }
+
+        true
+    }
+}
+
+state!(AccountType, CurveTable);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::*;
+
+    fn static_entries(len: usize) -> Vec<CurveTableEntry> {
+        (0..len)
+            .map(|i| CurveTableEntry::new(
+                DISCRETE_PRICING_TABLE[i],
+                DISCRETE_CUMULATIVE_VALUE_TABLE[i],
+            ))
+            .collect()
+    }
+
+    #[test]
+    fn test_static_tables_pass_checks() {
+        let entries = static_entries(1_000);
+        let step_size = DISCRETE_PRICING_STEP_SIZE as u64;
+
+        assert!(CurveTable::check_entries(step_size, None, &entries));
+
+        // Same result when uploaded in chunks
+        let (head, tail) = entries.split_at(400);
+        assert!(CurveTable::check_entries(step_size, None, head));
+        assert!(CurveTable::check_entries(step_size, head.last(), tail));
+    }
+
+    #[test]
+    fn test_check_entries_rejects_bad_tables() {
+        let step_size = DISCRETE_PRICING_STEP_SIZE as u64;
+        let entries = static_entries(3);
+
+        // Must start at zero value
+        assert!(!CurveTable::check_entries(step_size, None, &entries[1..]));
+
+        // Prices can't fall
+        let mut falling = entries.clone();
+        falling[2] = CurveTableEntry::new(entries[1].price() - 1, entries[2].cumulative_value());
+        assert!(!CurveTable::check_entries(step_size, None, &falling));
+
+        // Cumulative value must match the step prices
+        let mut skewed = entries.clone();
+        skewed[2] = CurveTableEntry::new(entries[2].price(), entries[2].cumulative_value() + 1);
+        assert!(!CurveTable::check_entries(step_size, None, &skewed));
+
+        // Wrong step size
+        assert!(!CurveTable::check_entries(step_size + 1, None, &entries));
+    }
+
+    #[test]
+    fn test_unpack_entries() {
+        let entries = static_entries(4);
+        let mut table = CurveTable::zeroed();
+        table.num_entries = entries.len() as u64;
+
+        let mut data = vec![0u8; CurveTable::size_for(entries.len()).unwrap()];
+        data[0] = AccountType::CurveTable.into();
+        data[8..CurveTable::get_size()].copy_from_slice(bytemuck::bytes_of(&table));
+        data[CurveTable::get_size()..].copy_from_slice(bytemuck::cast_slice(&entries));
+
+        let (_, unpacked) = CurveTable::unpack_entries(&data).unwrap();
+        assert_eq!(unpacked, entries.as_slice());
+
+        // Header count must match the data length
+        data.truncate(data.len() - std::mem::size_of::<CurveTableEntry>());
+        assert!(CurveTable::unpack_entries(&data).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use steel::*;
+use super::AccountType;
+use crate::merkle::{leaf_hash, verify_proof};
+use crate::state;
+
+/// Airdrop of a currency to a list of wallets, committed to as a merkle root
+/// over `distributor_leaf` entries. Recipients claim with a proof until
+/// `expires_at`, after which the authority can claw back whatever is left.
+/// The header is followed by a bitmap of `num_recipients` bits recording
+/// which entries have been claimed. The tokens sit in a vault at
+/// `find_vault_pda(distributor, mint)`.
+/// PDA seeds: ["distributor", mint, authority, id]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Distributor {
+    pub authority: Pubkey,        // Funds the airdrop and claws back leftovers
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub root: [u8; 32],           // Merkle root of the recipient list
+
+    pub id: u64,                  // Picked by the authority, one distributor per id
+    pub total_amount: u64,        // Currency quarks funded
+    pub claimed_amount: u64,      // Currency quarks claimed so far
+    pub num_recipients: u64,      // Entries in the recipient list
+    pub num_claimed: u64,         // Entries claimed so far
+    pub created_at: i64,          // Unix timestamp
+    pub expires_at: i64,          // Unix timestamp
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+    pub vault_bump: u8,
+
+    _padding: [u8; 6],
+}
+
+impl Distributor {
+    /// Account size with a claimed bitmap for `num_recipients` entries
+    pub fn size_for(num_recipients: u64) -> Option<usize> {
+        usize::try_from(num_recipients.div_ceil(8))
+            .ok()?
+            .checked_add(Self::get_size())
+    }
+
+    /// Splits account data into the header and the claimed bitmap.
+    pub fn unpack_claims(data: &[u8]) -> Result<(&Self, &[u8]), ProgramError> {
+        if data.len() < Self::get_size() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (header, claims) = data.split_at(Self::get_size());
+        let distributor = Self::try_from_bytes(header)?;
+
+        if Self::size_for(distributor.num_recipients) != Some(data.len()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((distributor, claims))
+    }
+
+    pub fn unpack_claims_mut(data: &mut [u8]) -> Result<(&mut Self, &mut [u8]), ProgramError> {
+        if data.len() < Self::get_size() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let len = data.len();
+        let (header, claims) = data.split_at_mut(Self::get_size());
+        let distributor = Self::try_from_bytes_mut(header)?;
+
+        if Self::size_for(distributor.num_recipients) != Some(len) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((distributor, claims))
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Returns whether `wallet` is owed `amount` as entry `index`, given its
+    /// proof.
+    pub fn is_recipient(&self, index: u64, wallet: &Pubkey, amount: u64, proof: &[[u8; 32]]) -> bool {
+        index < self.num_recipients &&
+        verify_proof(&self.root, distributor_leaf(index, wallet, amount), proof)
+    }
+
+    pub fn is_claimed(claims: &[u8], index: u64) -> bool {
+        claims[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(claims: &mut [u8], index: u64) {
+        claims[(index / 8) as usize] |= 1 << (index % 8);
+    }
+}
+
+/// Distributor leaf for entry `index` of a recipient list. The index keeps
+/// a wallet listed twice from claiming either entry twice.
+pub fn distributor_leaf(index: u64, wallet: &Pubkey, amount: u64) -> [u8; 32] {
+    leaf_hash(&[&index.to_le_bytes(), wallet.as_ref(), &amount.to_le_bytes()])
+}
+
+state!(AccountType, Distributor);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::MerkleTree;
+
+    #[test]
+    fn test_claims() {
+        let recipients: Vec<_> = (0..10u64).map(|i| (Pubkey::new_unique(), 100 * (i + 1))).collect();
+        let leaves = recipients.iter()
+            .enumerate()
+            .map(|(i, (wallet, amount))| distributor_leaf(i as u64, wallet, *amount))
+            .collect();
+        let tree = MerkleTree::new(leaves);
+
+        let mut data = vec![0u8; Distributor::size_for(10).unwrap()];
+        assert_eq!(data.len(), Distributor::get_size() + 2);
+        data[0] = AccountType::Distributor as u8;
+        assert!(Distributor::unpack_claims(&data).is_err());
+
+        {
+            let distributor = Distributor::try_from_bytes_mut(&mut data[..Distributor::get_size()]).unwrap();
+            distributor.root = tree.root();
+            distributor.num_recipients = 10;
+        }
+        let (distributor, claims) = Distributor::unpack_claims_mut(&mut data).unwrap();
+
+        let (wallet, amount) = recipients[9];
+        let proof = tree.proof(9).unwrap();
+        assert!(distributor.is_recipient(9, &wallet, amount, &proof));
+        assert!(!distributor.is_recipient(9, &wallet, amount + 1, &proof));
+        assert!(!distributor.is_recipient(8, &wallet, amount, &proof));
+        assert!(!distributor.is_recipient(10, &wallet, amount, &proof));
+
+        assert!(!Distributor::is_claimed(claims, 9));
+        Distributor::set_claimed(claims, 9);
+        assert!(Distributor::is_claimed(claims, 9));
+        assert!(!Distributor::is_claimed(claims, 8));
+        assert_eq!(claims, &[0, 0b10]);
+    }
+}
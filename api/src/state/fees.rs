@@ -0,0 +1,119 @@
+use steel::*;
+use super::AccountType;
+use crate::consts::*;
+use crate::state;
+
+/// A single volume-based sell fee tier.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct FeeTier {
+    pub min_volume: u64,          // Cumulative sell volume (base quarks) to qualify
+    pub sell_fee: u16,            // Basis points applied once qualified
+
+    _padding: [u8; 6],
+}
+
+impl FeeTier {
+    pub fn new(min_volume: u64, sell_fee: u16) -> Self {
+        Self {
+            min_volume,
+            sell_fee,
+            _padding: [0; 6],
+        }
+    }
+}
+
+/// Authority-managed sell fee overrides for a liquidity pool.
+/// PDA seeds: ["fee_schedule", pool_pubkey]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct FeeSchedule {
+    pub pool: Pubkey,                                   // Pool these overrides apply to
+    pub exemptions: [Pubkey; MAX_FEE_EXEMPTIONS],       // Sellers that pay no sell fee
+    pub tiers: [FeeTier; MAX_FEE_TIERS],                // Sorted by ascending min_volume
+
+    pub num_exemptions: u8,
+    pub num_tiers: u8,
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+
+    _padding: [u8; 5],
+}
+
+impl FeeSchedule {
+    pub fn is_exempt(&self, wallet: &Pubkey) -> bool {
+        self.exemptions[..self.num_exemptions as usize]
+            .iter()
+            .any(|e| e.eq(wallet))
+    }
+
+    /// Returns the highest tier (and its index) unlocked by `volume`, if any.
+    pub fn tier_for_volume(&self, volume: u64) -> Option<(u8, &FeeTier)> {
+        self.tiers[..self.num_tiers as usize]
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, t)| volume >= t.min_volume)
+            .map(|(i, t)| (i as u8, t))
+    }
+}
+
+/// Cumulative sell volume for a trader against a single pool, used to unlock
+/// fee tiers. Buys don't count towards it.
+/// PDA seeds: ["volume", pool_pubkey, trader_pubkey]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct TraderVolume {
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    pub sell_volume: u64,         // Cumulative sell value in base quarks (before fees)
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+
+    _padding: [u8; 7],
+}
+
+state!(AccountType, FeeSchedule);
+state!(AccountType, TraderVolume);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(tiers: &[FeeTier]) -> FeeSchedule {
+        let mut schedule = FeeSchedule::zeroed();
+        schedule.tiers[..tiers.len()].copy_from_slice(tiers);
+        schedule.num_tiers = tiers.len() as u8;
+        schedule
+    }
+
+    #[test]
+    fn test_tier_for_volume() {
+        let schedule = schedule(&[
+            FeeTier::new(1_000, 80),
+            FeeTier::new(10_000, 50),
+            FeeTier::new(100_000, 10),
+        ]);
+
+        assert!(schedule.tier_for_volume(0).is_none());
+        assert!(schedule.tier_for_volume(999).is_none());
+        assert_eq!(schedule.tier_for_volume(1_000).unwrap().0, 0);
+        assert_eq!(schedule.tier_for_volume(99_999).unwrap().1.sell_fee, 50);
+        assert_eq!(schedule.tier_for_volume(u64::MAX).unwrap().0, 2);
+    }
+
+    #[test]
+    fn test_is_exempt_ignores_unused_slots() {
+        let mut schedule = schedule(&[]);
+        let wallet = Pubkey::new_unique();
+
+        assert!(!schedule.is_exempt(&Pubkey::default()));
+        assert!(!schedule.is_exempt(&wallet));
+
+        schedule.exemptions[0] = wallet;
+        schedule.num_exemptions = 1;
+        assert!(schedule.is_exempt(&wallet));
+    }
+}
@@ -0,0 +1,142 @@
+use brine_fp::UnsignedNumeric;
+use steel::*;
+use super::AccountType;
+use crate::consts::*;
+use crate::state;
+use crate::utils::to_numeric;
+
+/// Which way a limit order trades against its pool.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum OrderSide {
+    Buy = 0,                        // Spend base once the price drops to the target
+    Sell,                           // Sell currency once the price rises to the target
+}
+
+/// A resting order to trade against a pool once its spot price crosses
+/// `target_price`. What the order spends (base when buying, currency when
+/// selling) is escrowed in a vault at `find_vault_pda(limit_order, mint)`.
+/// Anyone may execute a crossed order and collect its `bounty`.
+/// PDA seeds: ["limit_order", pool_pubkey, owner_pubkey, id]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct LimitOrder {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub vault: Pubkey,
+
+    pub id: u64,                  // Picked by the owner, one order per id
+    pub amount: u64,              // Quarks escrowed: base when buying, currency when selling
+    pub min_amount_out: u64,      // Least the fill may return, in quarks
+    pub target_price: u64,        // Fixed point with CURVE_PRICE_DECIMALS
+    pub bounty: u64,              // Lamports paid to whoever executes the order
+
+    pub created_at: i64,          // Unix timestamp
+    pub expires_at: i64,          // Unix timestamp
+
+    pub side: u8,                 // OrderSide
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+    pub vault_bump: u8,
+
+    _padding: [u8; 5],
+}
+
+impl LimitOrder {
+    pub fn side(&self) -> Result<OrderSide, ProgramError> {
+        OrderSide::try_from(self.side)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Whether the order can fill at `spot_price`: buys at or below the
+    /// target, sells at or above it.
+    pub fn is_crossed(&self, spot_price: &UnsignedNumeric) -> Result<bool, ProgramError> {
+        let target_price = to_numeric(self.target_price, CURVE_PRICE_DECIMALS)?;
+
+        Ok(match self.side()? {
+            OrderSide::Buy => !spot_price.greater_than(&target_price),
+            OrderSide::Sell => !spot_price.less_than(&target_price),
+        })
+    }
+
+    /// Least a fill of the whole `amount` may return so that it trades no
+    /// worse than `target_price` on average: currency quarks when buying,
+    /// base quarks when selling. Never below the owner's `min_amount_out`.
+    pub fn min_fill_amount(&self, target_decimals: u8, base_decimals: u8) -> Result<u64, ProgramError> {
+        let target_price = to_numeric(self.target_price, CURVE_PRICE_DECIMALS)?;
+
+        let (worst_out, out_decimals) = match self.side()? {
+            OrderSide::Buy => (to_numeric(self.amount, base_decimals)?.checked_div(&target_price), target_decimals),
+            OrderSide::Sell => (to_numeric(self.amount, target_decimals)?.checked_mul(&target_price), base_decimals),
+        };
+
+        // Round down to whole quarks, so a fill right at the target passes
+        let worst_out_raw = 10u128.checked_pow(out_decimals as u32)
+            .and_then(UnsignedNumeric::new)
+            .zip(worst_out)
+            .and_then(|(scale, out)| out.checked_mul(&scale))
+            .and_then(|out| out.floor())
+            .and_then(|out| out.to_imprecise())
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        Ok(self.min_amount_out.max(u64::try_from(worst_out_raw).unwrap_or(u64::MAX)))
+    }
+}
+
+state!(AccountType, LimitOrder);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_crossed() {
+        let price = |cents: u64| to_numeric(cents, 2).unwrap();
+
+        let mut order = LimitOrder::zeroed();
+        order.target_price = 3 * 10u64.pow(CURVE_PRICE_DECIMALS as u32 - 2); // $0.03
+
+        order.side = OrderSide::Buy.into();
+        assert!(order.is_crossed(&price(2)).unwrap());
+        assert!(order.is_crossed(&price(3)).unwrap());
+        assert!(!order.is_crossed(&price(4)).unwrap());
+
+        order.side = OrderSide::Sell.into();
+        assert!(!order.is_crossed(&price(2)).unwrap());
+        assert!(order.is_crossed(&price(3)).unwrap());
+        assert!(order.is_crossed(&price(4)).unwrap());
+
+        order.side = 42;
+        assert!(order.is_crossed(&price(3)).is_err());
+    }
+
+    #[test]
+    fn test_min_fill_amount() {
+        let mut order = LimitOrder::zeroed();
+        order.target_price = 25 * 10u64.pow(CURVE_PRICE_DECIMALS as u32 - 2); // $0.25
+
+        // 10 USDC buys at least 40 tokens
+        order.side = OrderSide::Buy.into();
+        order.amount = 10_000_000;
+        assert_eq!(order.min_fill_amount(10, 6).unwrap(), 40 * 10u64.pow(10));
+
+        // 40 tokens sell for at least 10 USDC
+        order.side = OrderSide::Sell.into();
+        order.amount = 40 * 10u64.pow(10);
+        assert_eq!(order.min_fill_amount(10, 6).unwrap(), 10_000_000);
+
+        // Rounds down to whole quarks
+        order.amount = 3;
+        assert_eq!(order.min_fill_amount(0, 0).unwrap(), 0);
+
+        // The owner's own minimum still applies when it is stricter
+        order.min_amount_out = 20_000_000;
+        order.amount = 40 * 10u64.pow(10);
+        assert_eq!(order.min_fill_amount(10, 6).unwrap(), 20_000_000);
+    }
+}
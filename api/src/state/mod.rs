@@ -1,8 +1,30 @@
 mod currency;
 mod pool;
+mod fees;
+mod curve_table;
+mod protocol;
+mod registry;
+mod profile;
+mod purchases;
+mod vesting;
+mod cash_link;
+mod limit_order;
+mod dca;
+mod distributor;
 
 pub use currency::*;
 pub use pool::*;
+pub use fees::*;
+pub use curve_table::*;
+pub use protocol::*;
+pub use registry::*;
+pub use profile::*;
+pub use purchases::*;
+pub use vesting::*;
+pub use cash_link::*;
+pub use limit_order::*;
+pub use dca::*;
+pub use distributor::*;
 
 use steel::*;
 
@@ -13,4 +35,18 @@ pub enum AccountType {
     Unknown = 0,
     CurrencyConfig,
     LiquidityPool,
+    FeeSchedule,
+    TraderVolume,
+    CurveTable,
+    ProtocolConfig,
+    SymbolReservation,
+    CurrencyRegistry,
+    CurrencyRegistryPage,
+    CurrencyProfile,
+    WalletPurchases,
+    Vesting,
+    CashLink,
+    LimitOrder,
+    DcaPlan,
+    Distributor,
 }
@@ -1,9 +1,28 @@
+use brine_fp::UnsignedNumeric;
 use steel::*;
 use super::AccountType;
+use crate::consts::*;
 use crate::state;
+use crate::utils::from_basis_points;
+use crate::merkle::{leaf_hash, verify_proof};
+use crate::curve::{CurveKind, CurveParams, PoolCurve};
+use super::{CurveTable, ProtocolConfig};
+
+/// Who may call BurnFees on a pool.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum FeeBurnPolicy {
+    #[default]
+    AuthorityOnly = 0,              // Only the pool authority
+    PermissionlessAfterCooldown,    // Authority anytime, anyone once the cooldown has elapsed
+    Permissionless,                 // Anyone, anytime
+}
 
 /// Liquidity pool account that manages the discrete exponential bonding curve for a currency.
-/// PDA seeds: ["pool", currency_pubkey]
+/// PDA seeds: ["pool", currency_pubkey] for a currency's first pool, then
+/// ["pool", currency_pubkey, base_mint_pubkey] for each additional base mint.
+/// Every pool sells its own slice of the currency supply and only buys back
+/// what it has sold.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 pub struct LiquidityPool {
@@ -12,7 +31,7 @@ pub struct LiquidityPool {
 
     // SPL token accounts
     pub mint_a: Pubkey,           // SPL Mint A (target)
-    pub mint_b: Pubkey,           // SPL Mint B (base, probably USDC)
+    pub mint_b: Pubkey,           // SPL or Token-2022 Mint B (base, probably USDC)
     pub vault_a: Pubkey,          // Vault A (target)
     pub vault_b: Pubkey,          // Vault B (base)                                  
 
@@ -25,7 +44,390 @@ pub struct LiquidityPool {
     pub vault_a_bump: u8,
     pub vault_b_bump: u8,
 
-    _padding: [u8; 3],
+    // Fee disposal
+    pub burn_policy: u8,          // FeeBurnPolicy
+
+    pub curve_kind: u8,           // CurveKind
+
+    pub allowed_extensions: u8,   // ALLOW_* bits for risky Token-2022 base mint extensions
+
+    pub burn_cooldown: i64,       // Seconds since last burn before anyone may burn
+    pub last_burn_at: i64,        // Unix timestamp of the last burn (or pool creation)
+
+    // Bonding curve (see CurveParams)
+    pub curve_start_price: u64,
+    pub curve_end_price: u64,
+    pub curve_max_supply: u64,    // Whole tokens
+    pub curve_step_size: u64,     // Whole tokens
+    pub curve_table: Pubkey,      // CurveTable account for CurveKind::Table, default otherwise
+
+    // Emergency halt, set by the pool authority or protocol admin
+    pub paused: u8,               // PAUSE_* bits
+    _padding: [u8; 1],
+
+    // Circuit breaker: within a window, trades may not move the spot price
+    // more than breaker_band away from its price when the window opened
+    pub breaker_band: u16,        // Basis points, 0 disables the breaker
+    _breaker_padding: [u8; 4],
+    pub breaker_window: u64,      // Slots before the reference price resets
+    pub breaker_slot: u64,        // Slot the current window opened
+    pub breaker_price: [u8; 16],  // Raw UnsignedNumeric spot price when the window opened, zero if none
+
+    // Launch: before launch_at only allowlisted wallets may buy, up to presale_cap each
+    pub launch_at: i64,           // Unix timestamp, no earlier than when the launch was set
+    pub presale_cap: u64,         // Base quarks each allowlisted wallet may spend before launch
+    pub presale_root: [u8; 32],   // Merkle root of allowlisted wallets, zero if there is no presale
+
+    // Anti-sniping: for launch_window seconds from launch_at, each wallet
+    // may spend at most launch_cap
+    pub launch_window: u64,       // Seconds, 0 disables the window
+    pub launch_cap: u64,          // Base quarks per wallet within the window
+
+    // Post-trade hook: a program CPI'd with a TradeSummary after every trade
+    pub hook_program: Pubkey,     // Default if the pool has no hook
+    pub hook_compute_units: u32,  // Most compute the hook may use per trade
+    pub hook_required: u8,        // 1 if trades must run the hook, 0 if it's skipped when short on compute
+    pub hook_authority_bump: u8,  // Bump of the trade hook authority PDA
+    _hook_padding: [u8; 2],
+}
+
+/// Size of a pool account, discriminator included, from before pools kept
+/// their own fee burn, curve, pause, breaker, launch and hook settings.
+/// Such pools have to be migrated with MigratePool before they can be used.
+pub const LIQUIDITY_POOL_V0_LEN: usize = 8 + 208;
+
+impl LiquidityPool {
+    /// Reads a pool account in the v0 layout. That layout is a prefix of the
+    /// current one, so newer settings take the values v0 pools behaved by:
+    /// the default curve, permissionless fee burns and everything else off.
+    pub fn unpack_v0(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != LIQUIDITY_POOL_V0_LEN || data[0] != Self::discriminator() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut pool = Self::zeroed();
+        bytemuck::bytes_of_mut(&mut pool)[..LIQUIDITY_POOL_V0_LEN - 8]
+            .copy_from_slice(&data[8..]);
+
+        // These overlap the v0 padding
+        let curve = CurveParams::default();
+        pool.burn_policy = FeeBurnPolicy::Permissionless.into();
+        pool.curve_kind = curve.kind.into();
+        pool.allowed_extensions = 0;
+
+        pool.curve_start_price = curve.start_price;
+        pool.curve_end_price = curve.end_price;
+        pool.curve_max_supply = curve.max_supply;
+        pool.curve_step_size = curve.step_size;
+
+        Ok(pool)
+    }
+
+    pub fn curve_params(&self) -> Result<CurveParams, ProgramError> {
+        let kind = CurveKind::try_from(self.curve_kind)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        Ok(CurveParams {
+            kind,
+            start_price: self.curve_start_price,
+            end_price: self.curve_end_price,
+            max_supply: self.curve_max_supply,
+            step_size: self.curve_step_size,
+        })
+    }
+
+    /// The pool's pricing curve, for quoting with `BondingCurve`. Table-backed
+    /// pools need their table account, see `table_curve`.
+    pub fn curve(&self) -> Result<PoolCurve<'static>, ProgramError> {
+        PoolCurve::from_params(&self.curve_params()?)
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    pub fn is_table_backed(&self) -> bool {
+        self.curve_kind == u8::from(CurveKind::Table)
+    }
+
+    /// The pool's pricing curve, read from the data of its CurveTable account.
+    pub fn table_curve<'a>(&self, table_key: &Pubkey, table_data: &'a [u8]) -> Result<PoolCurve<'a>, ProgramError> {
+        if !self.is_table_backed() || self.curve_table.ne(table_key) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (table, entries) = CurveTable::unpack_entries(table_data)?;
+        if table.is_finalized == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        PoolCurve::from_table(table.step_size, entries)
+            .ok_or(ProgramError::InvalidAccountData)
+    }
+
+    pub fn is_buy_paused(&self) -> bool {
+        self.paused & PAUSE_BUYS != 0
+    }
+
+    pub fn is_sell_paused(&self) -> bool {
+        self.paused & PAUSE_SELLS != 0
+    }
+
+    pub fn is_launched(&self, now: i64) -> bool {
+        now >= self.launch_at
+    }
+
+    /// Whether buys at `now` fall within the capped window after launch.
+    /// The window opens at the scheduled launch, whether or not anyone buys.
+    pub fn is_launch_window(&self, now: i64) -> bool {
+        self.launch_window > 0 &&
+        self.is_launched(now) &&
+        now < self.launch_at.saturating_add_unsigned(self.launch_window)
+    }
+
+    pub fn has_presale(&self) -> bool {
+        self.presale_root != [0; 32]
+    }
+
+    /// Returns whether `wallet` is on the presale allowlist, given its proof.
+    pub fn is_presale_wallet(&self, wallet: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        self.has_presale() &&
+        verify_proof(&self.presale_root, presale_leaf(wallet), proof)
+    }
+
+    pub fn is_breaker_enabled(&self) -> bool {
+        self.breaker_band > 0
+    }
+
+    /// Reference spot price of the current circuit breaker window.
+    pub fn breaker_price(&self) -> UnsignedNumeric {
+        UnsignedNumeric::from_scaled_u128(u128::from_le_bytes(self.breaker_price))
+    }
+
+    /// Checks a trade that moves the spot price from `price_before` to
+    /// `price_after` at `slot` against the circuit breaker. The first trade
+    /// of each window records `price_before` as the window's reference.
+    /// Returns false if `price_after` lands outside the band, in which case
+    /// the caller fails the trade and the recorded window is rolled back.
+    pub fn check_price_band(
+        &mut self,
+        slot: u64,
+        price_before: &UnsignedNumeric,
+        price_after: &UnsignedNumeric,
+    ) -> Result<bool, ProgramError> {
+        if !self.is_breaker_enabled() {
+            return Ok(true);
+        }
+
+        if self.breaker_price == [0; 16] || slot >= self.breaker_slot.saturating_add(self.breaker_window) {
+            self.breaker_slot = slot;
+            self.breaker_price = price_before.value.as_u128().to_le_bytes();
+        }
+
+        let reference = self.breaker_price();
+        let band = reference
+            .checked_mul(&from_basis_points(self.breaker_band)?)
+            .ok_or(ProgramError::InvalidArgument)?;
+        let lower = reference.checked_sub(&band).unwrap_or_else(UnsignedNumeric::zero);
+        let upper = reference.checked_add(&band).ok_or(ProgramError::InvalidArgument)?;
+
+        Ok(!price_after.less_than(&lower) && !price_after.greater_than(&upper))
+    }
+
+    pub fn has_trade_hook(&self) -> bool {
+        self.hook_program != Pubkey::default()
+    }
+
+    pub fn is_trade_hook_required(&self) -> bool {
+        self.hook_required != 0
+    }
+
+    /// Returns whether `caller` may pause or resume the pool.
+    pub fn can_pause(&self, caller: &Pubkey, protocol_config: &ProtocolConfig) -> bool {
+        self.authority.eq(caller) || protocol_config.admin.eq(caller)
+    }
+
+    pub fn burn_policy(&self) -> Result<FeeBurnPolicy, ProgramError> {
+        FeeBurnPolicy::try_from(self.burn_policy)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Returns whether `caller` may burn accumulated fees at time `now`.
+    pub fn can_burn_fees(&self, caller: &Pubkey, now: i64) -> Result<bool, ProgramError> {
+        if self.authority.eq(caller) {
+            return Ok(true);
+        }
+
+        Ok(match self.burn_policy()? {
+            FeeBurnPolicy::AuthorityOnly => false,
+            FeeBurnPolicy::PermissionlessAfterCooldown =>
+                now >= self.last_burn_at.saturating_add(self.burn_cooldown),
+            FeeBurnPolicy::Permissionless => true,
+        })
+    }
+}
+
+/// Presale allowlist leaf for a wallet.
+pub fn presale_leaf(wallet: &Pubkey) -> [u8; 32] {
+    leaf_hash(&[wallet.as_ref()])
 }
 
 state!(AccountType, LiquidityPool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::MerkleTree;
+
+    fn pool(policy: FeeBurnPolicy, cooldown: i64, last_burn_at: i64) -> LiquidityPool {
+        let mut pool = LiquidityPool::zeroed();
+        pool.authority = Pubkey::new_unique();
+        pool.burn_policy = policy.into();
+        pool.burn_cooldown = cooldown;
+        pool.last_burn_at = last_burn_at;
+        pool
+    }
+
+    #[test]
+    fn test_unpack_v0() {
+        let authority = Pubkey::new_unique();
+        let vault_b = Pubkey::new_unique();
+
+        let mut data = vec![0u8; LIQUIDITY_POOL_V0_LEN];
+        data[0] = LiquidityPool::discriminator();
+        data[8..40].copy_from_slice(authority.as_ref());
+        data[168..200].copy_from_slice(vault_b.as_ref());
+        data[200..208].copy_from_slice(&1_234u64.to_le_bytes()); // fees_accumulated
+        data[208..210].copy_from_slice(&50u16.to_le_bytes());    // sell_fee
+        data[210..213].copy_from_slice(&[255, 254, 253]);        // bumps
+        data[213..216].copy_from_slice(&[7, 7, 7]);              // padding
+
+        let pool = LiquidityPool::unpack_v0(&data).unwrap();
+        assert_eq!(pool.authority, authority);
+        assert_eq!(pool.vault_b, vault_b);
+        assert_eq!(pool.fees_accumulated, 1_234);
+        assert_eq!(pool.sell_fee, 50);
+        assert_eq!((pool.bump, pool.vault_a_bump, pool.vault_b_bump), (255, 254, 253));
+
+        assert_eq!(pool.curve_params().unwrap(), CurveParams::default());
+        assert_eq!(pool.burn_policy().unwrap(), FeeBurnPolicy::Permissionless);
+        assert_eq!((pool.burn_cooldown, pool.last_burn_at), (0, 0));
+        assert_eq!(pool.allowed_extensions, 0);
+        assert_eq!(pool.paused, 0);
+        assert!(!pool.is_breaker_enabled());
+        assert!(pool.is_launched(0));
+        assert!(!pool.has_presale());
+        assert!(!pool.is_launch_window(0));
+        assert!(!pool.has_trade_hook());
+
+        // Current-sized and foreign accounts are left alone
+        assert!(LiquidityPool::unpack_v0(&[0u8; 8 + std::mem::size_of::<LiquidityPool>()]).is_err());
+        data[0] = AccountType::CurrencyConfig.into();
+        assert!(LiquidityPool::unpack_v0(&data).is_err());
+    }
+
+    #[test]
+    fn test_can_burn_fees() {
+        let anyone = Pubkey::new_unique();
+
+        let p = pool(FeeBurnPolicy::AuthorityOnly, 0, 0);
+        assert!(p.can_burn_fees(&p.authority, 0).unwrap());
+        assert!(!p.can_burn_fees(&anyone, i64::MAX).unwrap());
+
+        let p = pool(FeeBurnPolicy::PermissionlessAfterCooldown, 3_600, 1_000);
+        assert!(p.can_burn_fees(&p.authority, 1_000).unwrap());
+        assert!(!p.can_burn_fees(&anyone, 4_599).unwrap());
+        assert!(p.can_burn_fees(&anyone, 4_600).unwrap());
+
+        let p = pool(FeeBurnPolicy::Permissionless, 0, 0);
+        assert!(p.can_burn_fees(&anyone, 0).unwrap());
+
+        let mut p = pool(FeeBurnPolicy::Permissionless, 0, 0);
+        p.burn_policy = 42;
+        assert!(p.can_burn_fees(&anyone, 0).is_err());
+    }
+
+    #[test]
+    fn test_check_price_band() {
+        let price = |value: u64| UnsignedNumeric::new(value.into()).unwrap();
+
+        let mut p = pool(FeeBurnPolicy::AuthorityOnly, 0, 0);
+        assert!(p.check_price_band(100, &price(1), &price(1_000)).unwrap());
+        assert_eq!(p.breaker_price, [0; 16]);
+
+        p.breaker_band = 1_000; // 10%
+        p.breaker_window = 10;
+
+        // The first trade opens a window at its starting price
+        assert!(p.check_price_band(100, &price(100), &price(105)).unwrap());
+        assert_eq!(p.breaker_slot, 100);
+        assert_eq!(p.breaker_price().to_imprecise(), Some(100));
+
+        // Later trades in the window are measured against that price
+        assert!(p.check_price_band(105, &price(105), &price(110)).unwrap());
+        assert!(!p.check_price_band(105, &price(110), &price(111)).unwrap());
+        assert!(p.check_price_band(109, &price(105), &price(90)).unwrap());
+        assert!(!p.check_price_band(109, &price(90), &price(89)).unwrap());
+        assert_eq!(p.breaker_slot, 100);
+
+        // Once the window rolls over the reference resets
+        assert!(p.check_price_band(110, &price(110), &price(120)).unwrap());
+        assert_eq!(p.breaker_slot, 110);
+        assert_eq!(p.breaker_price().to_imprecise(), Some(110));
+    }
+
+    #[test]
+    fn test_presale() {
+        let allowed = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let tree = MerkleTree::new(vec![presale_leaf(&Pubkey::new_unique()), presale_leaf(&allowed)]);
+        let proof = tree.proof(1).unwrap();
+
+        let mut p = pool(FeeBurnPolicy::AuthorityOnly, 0, 0);
+        assert!(p.is_launched(0));
+        assert!(!p.is_presale_wallet(&allowed, &proof));
+
+        p.launch_at = 1_000;
+        p.presale_root = tree.root();
+        assert!(!p.is_launched(999));
+        assert!(p.is_launched(1_000));
+        assert!(p.is_presale_wallet(&allowed, &proof));
+        assert!(!p.is_presale_wallet(&stranger, &proof));
+    }
+
+    #[test]
+    fn test_launch_window() {
+        let mut p = pool(FeeBurnPolicy::AuthorityOnly, 0, 0);
+        assert!(!p.is_launch_window(0));
+
+        p.launch_window = 100;
+        assert!(p.is_launch_window(0));
+        assert!(!p.is_launch_window(100));
+
+        // A scheduled window runs from launch, however late the first buy
+        p.launch_at = 1_000;
+        assert!(!p.is_launch_window(999));
+        assert!(p.is_launch_window(1_000));
+        assert!(p.is_launch_window(1_099));
+        assert!(!p.is_launch_window(1_100));
+    }
+
+    #[test]
+    fn test_pause() {
+        let anyone = Pubkey::new_unique();
+        let mut protocol_config = ProtocolConfig::zeroed();
+        protocol_config.admin = Pubkey::new_unique();
+
+        let mut p = pool(FeeBurnPolicy::AuthorityOnly, 0, 0);
+        assert!(!p.is_buy_paused());
+        assert!(!p.is_sell_paused());
+
+        p.paused = PAUSE_BUYS;
+        assert!(p.is_buy_paused());
+        assert!(!p.is_sell_paused());
+
+        p.paused = PAUSE_BUYS | PAUSE_SELLS;
+        assert!(p.is_sell_paused());
+
+        assert!(p.can_pause(&p.authority, &protocol_config));
+        assert!(p.can_pause(&protocol_config.admin, &protocol_config));
+        assert!(!p.can_pause(&anyone, &protocol_config));
+    }
+}
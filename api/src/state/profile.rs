@@ -0,0 +1,192 @@
+use steel::*;
+use super::AccountType;
+use crate::consts::*;
+use crate::name::unpad;
+use crate::state;
+
+/// A named link on a currency profile, e.g. "website" or "x". Both halves
+/// are zero padded.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ProfileLink {
+    pub key: [u8; MAX_LINK_KEY_LEN],     // Lowercase ASCII letters, digits, '-' and '_'
+    pub value: [u8; MAX_LINK_VALUE_LEN], // Printable UTF-8 with no surrounding whitespace
+}
+
+impl ProfileLink {
+    pub fn new(key: &str, value: &str) -> Option<Self> {
+        if key.len() > MAX_LINK_KEY_LEN || value.len() > MAX_LINK_VALUE_LEN {
+            return None;
+        }
+
+        let mut link = Self::zeroed();
+        link.key[..key.len()].copy_from_slice(key.as_bytes());
+        link.value[..value.len()].copy_from_slice(value.as_bytes());
+        link.is_valid().then_some(link)
+    }
+
+    pub fn key(&self) -> &str {
+        unpad(&self.key)
+            .and_then(|key| std::str::from_utf8(key).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn value(&self) -> &str {
+        unpad(&self.value)
+            .and_then(|value| std::str::from_utf8(value).ok())
+            .unwrap_or_default()
+    }
+
+    /// Keys are short lowercase identifiers like "website" or "x".
+    pub fn is_valid_key(key: &[u8]) -> bool {
+        !key.is_empty() &&
+        key.len() <= MAX_LINK_KEY_LEN &&
+        key.iter().all(|&c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-' || c == b'_')
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let value = unpad(&self.value)
+            .and_then(|value| std::str::from_utf8(value).ok())
+            .unwrap_or_default();
+
+        unpad(&self.key).is_some_and(Self::is_valid_key) &&
+        !value.is_empty() &&
+        !value.chars().any(|c| c.is_control()) &&
+        value.trim() == value
+    }
+}
+
+/// Optional, authority-managed details for a currency page: a description
+/// and a few named links. The header is followed by `description_len` bytes
+/// of UTF-8 and then `num_links` ProfileLink values, and the account is
+/// resized on every update to fit exactly that.
+/// PDA seeds: ["profile", currency_pubkey]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CurrencyProfile {
+    pub currency: Pubkey,
+
+    pub description_len: u16,
+    pub num_links: u8,
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+
+    _padding: [u8; 4],
+}
+
+impl CurrencyProfile {
+    /// Account size for a profile with this description and number of links
+    pub fn size_for(description_len: usize, num_links: usize) -> usize {
+        Self::get_size() + description_len + num_links * std::mem::size_of::<ProfileLink>()
+    }
+
+    /// Descriptions may span several lines but carry no other control characters.
+    pub fn is_valid_description(description: &[u8]) -> bool {
+        description.len() <= MAX_DESCRIPTION_LEN &&
+        std::str::from_utf8(description)
+            .is_ok_and(|description| !description.chars().any(|c| c.is_control() && c != '\n'))
+    }
+
+    /// Splits account data into the header, description and links.
+    pub fn unpack_profile(data: &[u8]) -> Result<(&Self, &str, &[ProfileLink]), ProgramError> {
+        if data.len() < Self::get_size() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (header, body) = data.split_at(Self::get_size());
+        let profile = Self::try_from_bytes(header)?;
+
+        if body.len() != Self::size_for(profile.description_len as usize, profile.num_links as usize) - Self::get_size() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (description, links) = body.split_at(profile.description_len as usize);
+        let description = std::str::from_utf8(description)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let links = bytemuck::try_cast_slice::<u8, ProfileLink>(links)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        Ok((profile, description, links))
+    }
+
+    /// Writes the description and links after the header. `data` must be
+    /// exactly `size_for` the new contents.
+    pub fn pack_body(data: &mut [u8], description: &[u8], links: &[ProfileLink]) -> Result<(), ProgramError> {
+        if data.len() != Self::size_for(description.len(), links.len()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (header, body) = data.split_at_mut(Self::get_size());
+        let (description_data, links_data) = body.split_at_mut(description.len());
+        description_data.copy_from_slice(description);
+        links_data.copy_from_slice(bytemuck::cast_slice(links));
+
+        let profile = Self::try_from_bytes_mut(header)?;
+        profile.description_len = description.len() as u16;
+        profile.num_links = links.len() as u8;
+
+        Ok(())
+    }
+}
+
+state!(AccountType, CurrencyProfile);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_link() {
+        let link = ProfileLink::new("website", "https://flipcash.com").unwrap();
+        assert_eq!(link.key(), "website");
+        assert_eq!(link.value(), "https://flipcash.com");
+
+        assert!(ProfileLink::new("x", "@flipcash").is_some());
+        assert!(ProfileLink::new("", "@flipcash").is_none());
+        assert!(ProfileLink::new("Website", "https://flipcash.com").is_none());
+        assert!(ProfileLink::new("web site", "https://flipcash.com").is_none());
+        assert!(ProfileLink::new(&"k".repeat(MAX_LINK_KEY_LEN + 1), "value").is_none());
+        assert!(ProfileLink::new("website", "").is_none());
+        assert!(ProfileLink::new("website", " https://flipcash.com").is_none());
+        assert!(ProfileLink::new("website", "https://flip\ncash.com").is_none());
+        assert!(ProfileLink::new("website", &"v".repeat(MAX_LINK_VALUE_LEN + 1)).is_none());
+    }
+
+    #[test]
+    fn test_description() {
+        assert!(CurrencyProfile::is_valid_description(b""));
+        assert!(CurrencyProfile::is_valid_description("Dark skies ahead.\nBuy early ☀".as_bytes()));
+        assert!(CurrencyProfile::is_valid_description(&[b'a'; MAX_DESCRIPTION_LEN]));
+
+        assert!(!CurrencyProfile::is_valid_description(&[b'a'; MAX_DESCRIPTION_LEN + 1]));
+        assert!(!CurrencyProfile::is_valid_description(b"tab\tseparated"));
+        assert!(!CurrencyProfile::is_valid_description(&[0xff, 0xfe]));
+    }
+
+    #[test]
+    fn test_pack_and_unpack_profile() {
+        let description = "Dark skies ahead";
+        let links = [
+            ProfileLink::new("website", "https://flipcash.com").unwrap(),
+            ProfileLink::new("x", "@flipcash").unwrap(),
+        ];
+
+        let mut data = vec![0u8; CurrencyProfile::size_for(description.len(), links.len())];
+        data[0] = AccountType::CurrencyProfile.into();
+        CurrencyProfile::pack_body(&mut data, description.as_bytes(), &links).unwrap();
+
+        let (profile, unpacked_description, unpacked_links) = CurrencyProfile::unpack_profile(&data).unwrap();
+        assert_eq!(profile.description_len as usize, description.len());
+        assert_eq!(profile.num_links, 2);
+        assert_eq!(unpacked_description, description);
+        assert_eq!(unpacked_links, &links);
+
+        // Trailing bytes the header doesn't account for are rejected
+        data.push(0);
+        assert!(CurrencyProfile::unpack_profile(&data).is_err());
+
+        // So is a body that doesn't match its new contents
+        assert!(CurrencyProfile::pack_body(&mut data, b"", &links).is_err());
+    }
+}
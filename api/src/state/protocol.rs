@@ -0,0 +1,89 @@
+use steel::*;
+use super::AccountType;
+use crate::consts::*;
+use crate::state;
+
+/// Who may create pools.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, IntoPrimitive, TryFromPrimitive)]
+pub enum CreationPolicy {
+    Permissionless = 0,             // Anyone, against an allowed base mint
+    AdminOnly,                      // Only the protocol admin
+}
+
+/// Program-wide settings every pool is created against.
+/// PDA seeds: ["protocol_config"]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,                                  // Manages this account
+    pub base_mints: [Pubkey; MAX_ALLOWED_BASE_MINTS],   // Mints pools may use as their base
+
+    pub min_sell_fee: u16,        // Basis points
+    pub max_sell_fee: u16,        // Basis points
+
+    pub num_base_mints: u8,
+    pub creation_policy: u8,      // CreationPolicy
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+
+    _padding: [u8; 1],
+}
+
+impl ProtocolConfig {
+    pub fn is_base_mint_allowed(&self, mint: &Pubkey) -> bool {
+        self.base_mints[..self.num_base_mints as usize]
+            .iter()
+            .any(|m| m.eq(mint))
+    }
+
+    pub fn creation_policy(&self) -> Result<CreationPolicy, ProgramError> {
+        CreationPolicy::try_from(self.creation_policy)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Returns whether `creator` may create a pool.
+    pub fn can_create_pool(&self, creator: &Pubkey) -> Result<bool, ProgramError> {
+        Ok(match self.creation_policy()? {
+            CreationPolicy::Permissionless => true,
+            CreationPolicy::AdminOnly => self.admin.eq(creator),
+        })
+    }
+}
+
+state!(AccountType, ProtocolConfig);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_base_mint_allowed_ignores_unused_slots() {
+        let mut config = ProtocolConfig::zeroed();
+        let mint = Pubkey::new_unique();
+
+        assert!(!config.is_base_mint_allowed(&Pubkey::default()));
+        assert!(!config.is_base_mint_allowed(&mint));
+
+        config.base_mints[0] = mint;
+        config.num_base_mints = 1;
+        assert!(config.is_base_mint_allowed(&mint));
+    }
+
+    #[test]
+    fn test_can_create_pool() {
+        let mut config = ProtocolConfig::zeroed();
+        config.admin = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+
+        assert!(config.can_create_pool(&creator).unwrap());
+
+        config.creation_policy = CreationPolicy::AdminOnly.into();
+        assert!(!config.can_create_pool(&creator).unwrap());
+        assert!(config.can_create_pool(&config.admin).unwrap());
+
+        config.creation_policy = u8::MAX;
+        assert!(config.can_create_pool(&creator).is_err());
+    }
+}
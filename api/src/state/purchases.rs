@@ -0,0 +1,24 @@
+use steel::*;
+use super::AccountType;
+use crate::state;
+
+/// A wallet's buys from a single pool, used to enforce launch limits.
+/// Buys pass it as an optional trailing account.
+/// PDA seeds: ["purchases", pool_pubkey, wallet_pubkey]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct WalletPurchases {
+    pub pool: Pubkey,
+    pub wallet: Pubkey,
+    pub presale_spent: u64,       // Base quarks spent before launch
+    pub launch_spent: u64,        // Base quarks spent during the launch window
+
+    pub presale_allowed: u8,      // 1 once the wallet has proven it is on the presale allowlist
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+
+    _padding: [u8; 6],
+}
+
+state!(AccountType, WalletPurchases);
@@ -0,0 +1,93 @@
+use steel::*;
+use super::AccountType;
+use crate::consts::*;
+use crate::state;
+
+/// Claims a symbol for a single currency.
+/// PDA seeds: ["symbol", symbol]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct SymbolReservation {
+    pub symbol: [u8; MAX_SYMBOL_LEN],   // Uppercase, zero padded
+    pub mint: Pubkey,                   // Currency mint holding the symbol
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+
+    _padding: [u8; 7],
+}
+
+/// Running count of registered currencies, which locates the page the next
+/// one is appended to.
+/// PDA seeds: ["registry"]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CurrencyRegistry {
+    pub num_currencies: u64,
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+
+    _padding: [u8; 7],
+}
+
+impl CurrencyRegistry {
+    /// Page the next currency is appended to.
+    pub fn next_page(&self) -> u64 {
+        self.num_currencies / REGISTRY_PAGE_LEN as u64
+    }
+
+    /// Number of pages holding at least one currency.
+    pub fn num_pages(&self) -> u64 {
+        self.num_currencies.div_ceil(REGISTRY_PAGE_LEN as u64)
+    }
+}
+
+/// Currency mints in creation order, REGISTRY_PAGE_LEN per page.
+/// PDA seeds: ["registry", page_index (u64 LE)]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CurrencyRegistryPage {
+    pub page: u64,
+    pub num_mints: u64,
+    pub mints: [Pubkey; REGISTRY_PAGE_LEN],
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+
+    _padding: [u8; 7],
+}
+
+impl CurrencyRegistryPage {
+    pub fn mints(&self) -> &[Pubkey] {
+        &self.mints[..self.num_mints as usize]
+    }
+}
+
+state!(AccountType, SymbolReservation);
+state!(AccountType, CurrencyRegistry);
+state!(AccountType, CurrencyRegistryPage);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_pages() {
+        let mut registry = CurrencyRegistry::zeroed();
+        assert_eq!(registry.next_page(), 0);
+        assert_eq!(registry.num_pages(), 0);
+
+        registry.num_currencies = 1;
+        assert_eq!(registry.next_page(), 0);
+        assert_eq!(registry.num_pages(), 1);
+
+        registry.num_currencies = REGISTRY_PAGE_LEN as u64;
+        assert_eq!(registry.next_page(), 1);
+        assert_eq!(registry.num_pages(), 1);
+
+        registry.num_currencies += 1;
+        assert_eq!(registry.next_page(), 1);
+        assert_eq!(registry.num_pages(), 2);
+    }
+}
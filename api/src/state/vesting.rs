@@ -0,0 +1,77 @@
+use steel::*;
+use super::AccountType;
+use crate::state;
+
+/// Currency bought by a pool authority at launch, released to it linearly
+/// between `start_at` and `end_at`, with nothing claimable before `cliff_at`.
+/// The tokens sit in a vault at `find_vault_pda(vesting, mint)`.
+/// PDA seeds: ["vesting", pool_pubkey, beneficiary_pubkey]
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Vesting {
+    pub pool: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+
+    pub total_amount: u64,        // Currency quarks vesting
+    pub claimed_amount: u64,      // Currency quarks already claimed
+
+    pub start_at: i64,            // Unix timestamp
+    pub cliff_at: i64,            // Unix timestamp, nothing is claimable before it
+    pub end_at: i64,              // Unix timestamp, everything is claimable from it
+
+    // Bump seeds for PDAs
+    pub bump: u8,
+    pub vault_bump: u8,
+
+    _padding: [u8; 6],
+}
+
+impl Vesting {
+    /// Currency quarks released by `now`, claimed or not.
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now < self.cliff_at {
+            return 0;
+        }
+        if now >= self.end_at {
+            return self.total_amount;
+        }
+
+        let elapsed = now.saturating_sub(self.start_at) as u128;
+        let duration = self.end_at.saturating_sub(self.start_at) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
+    }
+
+    /// Currency quarks the beneficiary may claim at `now`.
+    pub fn claimable_amount(&self, now: i64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.claimed_amount)
+    }
+}
+
+state!(AccountType, Vesting);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vested_amount() {
+        let mut vesting = Vesting::zeroed();
+        vesting.total_amount = 1_000;
+        vesting.start_at = 100;
+        vesting.cliff_at = 200;
+        vesting.end_at = 1_100;
+
+        assert_eq!(vesting.vested_amount(0), 0);
+        assert_eq!(vesting.vested_amount(199), 0);
+        assert_eq!(vesting.vested_amount(200), 100);
+        assert_eq!(vesting.vested_amount(600), 500);
+        assert_eq!(vesting.vested_amount(1_100), 1_000);
+        assert_eq!(vesting.vested_amount(i64::MAX), 1_000);
+
+        vesting.claimed_amount = 500;
+        assert_eq!(vesting.claimable_amount(600), 0);
+        assert_eq!(vesting.claimable_amount(700), 100);
+    }
+}
@@ -0,0 +1,172 @@
+use spl_token_2022::{
+    extension::{
+        transfer_fee::TransferFeeConfig,
+        BaseStateWithExtensions,
+        ExtensionType,
+        StateWithExtensions,
+    },
+    state::{Account, Mint},
+};
+use steel::*;
+
+use crate::consts::*;
+use crate::utils::{check_condition, check_program};
+
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    program_id.eq(&spl_token::ID) || program_id.eq(&spl_token_2022::ID)
+}
+
+/// Checks that `token_program_info` is the SPL Token or Token-2022 program
+/// that owns `mint_info`.
+pub fn check_token_program(token_program_info: &AccountInfo, mint_info: &AccountInfo) -> ProgramResult {
+    check_condition(
+        is_token_program(mint_info.owner),
+        "Mint is not owned by a token program"
+    )?;
+
+    check_program(token_program_info, mint_info.owner)
+}
+
+/// Unpacks a mint owned by either token program. Unlike steel's `as_mint`,
+/// Token-2022 mints with extensions are accepted.
+pub fn unpack_mint(mint_info: &AccountInfo) -> Result<Mint, ProgramError> {
+    if !is_token_program(mint_info.owner) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+
+    Ok(mint.base)
+}
+
+/// Unpacks a token account owned by either token program. Unlike steel's
+/// `as_token_account`, Token-2022 accounts with extensions are accepted.
+pub fn unpack_token_account(token_account_info: &AccountInfo) -> Result<Account, ProgramError> {
+    if !is_token_program(token_account_info.owner) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = token_account_info.try_borrow_data()?;
+    let account = StateWithExtensions::<Account>::unpack(&data)?;
+
+    Ok(account.base)
+}
+
+/// Extensions on `mint_info`, empty for SPL Token mints.
+pub fn mint_extensions(mint_info: &AccountInfo) -> Result<Vec<ExtensionType>, ProgramError> {
+    if mint_info.owner.eq(&spl_token::ID) {
+        return Ok(Vec::new());
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+
+    mint.get_extension_types()
+}
+
+/// Whether a pool can hold a base mint carrying `extensions`, given the
+/// ALLOW_* bits its creator opted into. Non-transferable mints can never
+/// back a pool.
+pub fn extensions_allowed(extensions: &[ExtensionType], allowed: u8) -> bool {
+    extensions.iter().all(|extension| {
+        let required = match extension {
+            ExtensionType::NonTransferable => return false,
+            ExtensionType::PermanentDelegate => ALLOW_PERMANENT_DELEGATE,
+            ExtensionType::TransferHook => ALLOW_TRANSFER_HOOK,
+            ExtensionType::Pausable => ALLOW_PAUSABLE,
+            ExtensionType::DefaultAccountState => ALLOW_DEFAULT_ACCOUNT_STATE,
+            _ => return true,
+        };
+
+        allowed & required != 0
+    })
+}
+
+pub fn check_base_mint_extensions(mint_info: &AccountInfo, allowed: u8) -> ProgramResult {
+    let extensions = mint_extensions(mint_info)?;
+
+    check_condition(
+        extensions_allowed(&extensions, allowed),
+        "Base mint has extensions the pool does not allow"
+    )
+}
+
+/// Fee Token-2022 withholds when `amount` is transferred this epoch. Zero
+/// for mints without a transfer fee.
+pub fn transfer_fee(mint_info: &AccountInfo, amount: u64, epoch: u64) -> Result<u64, ProgramError> {
+    if mint_info.owner.eq(&spl_token::ID) {
+        return Ok(0);
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+
+    let Ok(config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(0);
+    };
+
+    config.calculate_epoch_fee(epoch, amount)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Amount to transfer this epoch so that `received` arrives after the
+/// transfer fee is withheld.
+pub fn amount_before_transfer_fee(mint_info: &AccountInfo, received: u64, epoch: u64) -> Result<u64, ProgramError> {
+    if mint_info.owner.eq(&spl_token::ID) {
+        return Ok(received);
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&data)?;
+
+    let Ok(config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(received);
+    };
+
+    let fee = config.calculate_inverse_epoch_fee(epoch, received)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    received.checked_add(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Size of a token account for `mint_info`, including the account
+/// extensions the mint requires (e.g. withheld transfer fees).
+pub fn token_account_len(mint_info: &AccountInfo) -> Result<usize, ProgramError> {
+    let extensions = mint_extensions(mint_info)?;
+    let required = ExtensionType::get_required_init_account_extensions(&extensions);
+
+    ExtensionType::try_calculate_account_len::<Account>(&required)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extensions_allowed() {
+        assert!(extensions_allowed(&[], 0));
+        assert!(extensions_allowed(&[
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::MetadataPointer,
+            ExtensionType::MintCloseAuthority,
+        ], 0));
+
+        // Risky extensions need their opt-in bit
+        assert!(!extensions_allowed(&[ExtensionType::PermanentDelegate], 0));
+        assert!(!extensions_allowed(&[ExtensionType::PermanentDelegate], ALLOW_TRANSFER_HOOK));
+        assert!(extensions_allowed(&[ExtensionType::PermanentDelegate], ALLOW_PERMANENT_DELEGATE));
+        assert!(!extensions_allowed(&[
+            ExtensionType::TransferHook,
+            ExtensionType::Pausable,
+        ], ALLOW_TRANSFER_HOOK));
+        assert!(extensions_allowed(&[
+            ExtensionType::TransferHook,
+            ExtensionType::Pausable,
+        ], ALLOW_TRANSFER_HOOK | ALLOW_PAUSABLE));
+
+        // Never tradable
+        assert!(!extensions_allowed(&[ExtensionType::NonTransferable], u8::MAX));
+    }
+}
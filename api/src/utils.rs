@@ -45,34 +45,6 @@ pub fn check_sysvar(account: &AccountInfo, sysvar_id: &Pubkey) -> ProgramResult
     Ok(())
 }
 
-pub fn to_name(val: &str) -> [u8; MAX_NAME_LEN] {
-    assert!(val.len() <= MAX_NAME_LEN, "name too long");
-
-    let mut name_bytes = [0u8; MAX_NAME_LEN];
-    name_bytes[..val.as_bytes().len()].copy_from_slice(val.as_bytes());
-    name_bytes
-}
-
-pub fn from_name(val: &[u8]) -> String {
-    let mut name_bytes = val.to_vec();
-    name_bytes.retain(|&x| x != 0);
-    String::from_utf8(name_bytes).unwrap()
-}
-
-pub fn to_symbol(val: &str) -> [u8; MAX_SYMBOL_LEN] {
-    assert!(val.len() <= MAX_SYMBOL_LEN, "symbol too long");
-
-    let mut symbol_bytes = [0u8; MAX_SYMBOL_LEN];
-    symbol_bytes[..val.as_bytes().len()].copy_from_slice(val.as_bytes());
-    symbol_bytes
-}
-
-pub fn from_symbol(val: &[u8]) -> String {
-    let mut symbol_bytes = val.to_vec();
-    symbol_bytes.retain(|&x| x != 0);
-    String::from_utf8(symbol_bytes).unwrap()
-}
-
 /// Convert token amount to a UnsignedNumeric value (e.g., 10_000_000 with 6 decimals -> 10.0 UnsignedNumeric)
 #[inline(always)]
 pub fn to_numeric(amount: u64, decimal_places: u8) -> Result<UnsignedNumeric, ProgramError> {
@@ -86,7 +58,7 @@ pub fn to_numeric(amount: u64, decimal_places: u8) -> Result<UnsignedNumeric, Pr
     let mut divisor= UnsignedNumeric::from_scaled_u128(SCALED_QUARKS_PER_TOKEN);
     if decimal_places == 6 {
         divisor = UnsignedNumeric::from_scaled_u128(1_000_000_000_000_000_000_000_000);
-    } else if decimal_places != TOKEN_DECIMALS {
+    } else if decimal_places != DEFAULT_TOKEN_DECIMALS {
         let scale = 10u64.checked_pow(decimal_places as u32)
             .ok_or(ProgramError::InvalidArgument)?;
         divisor = UnsignedNumeric::new(scale.into())
@@ -107,7 +79,7 @@ pub fn from_numeric(value: UnsignedNumeric, decimal_places: u8) -> Result<u64, P
     let mut multiplier = UnsignedNumeric::from_scaled_u128(SCALED_QUARKS_PER_TOKEN);
     if decimal_places == 6 {
         multiplier = UnsignedNumeric::from_scaled_u128(1_000_000_000_000_000_000_000_000);
-    } else if decimal_places != TOKEN_DECIMALS {
+    } else if decimal_places != DEFAULT_TOKEN_DECIMALS {
         let scale = 10u64.checked_pow(decimal_places as u32)
             .ok_or(ProgramError::InvalidArgument)?;
         multiplier = UnsignedNumeric::new(scale.into())
@@ -121,6 +93,14 @@ pub fn from_numeric(value: UnsignedNumeric, decimal_places: u8) -> Result<u64, P
     u64::try_from(result).map_err(|_| ProgramError::InvalidArgument)
 }
 
+/// Converts whole tokens into quarks for a mint with `decimal_places`
+#[inline(always)]
+pub fn to_quarks(tokens: u64, decimal_places: u8) -> Result<u64, ProgramError> {
+    10u64.checked_pow(decimal_places as u32)
+        .and_then(|scale| tokens.checked_mul(scale))
+        .ok_or(ProgramError::InvalidArgument)
+}
+
 /// Converts basis points (e.g. 123) into an UnsignedNumeric (e.g. 0.0123)
 #[inline(always)]
 pub fn from_basis_points(bps: u16) -> Result<UnsignedNumeric, ProgramError> {
@@ -140,6 +120,25 @@ pub fn to_basis_points(numeric: &UnsignedNumeric) -> Result<u16, ProgramError> {
     u16::try_from(bps).map_err(|_| ProgramError::InvalidArgument)
 }
 
+/// Splits `amount` in proportion to `shares`, rounding down. The last share
+/// takes whatever rounding leaves over, so the parts always add up to `amount`.
+pub fn split_by_shares(amount: u64, shares: &[u32]) -> Result<Vec<u64>, ProgramError> {
+    let total: u64 = shares.iter().map(|share| *share as u64).sum();
+    if total == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut parts: Vec<u64> = shares.iter()
+        .map(|share| (amount as u128 * *share as u128 / total as u128) as u64)
+        .collect();
+    let split: u64 = parts.iter().sum();
+    if let Some(last) = parts.last_mut() {
+        *last += amount - split;
+    }
+
+    Ok(parts)
+}
+
 pub fn modulo(dividend: &UnsignedNumeric, divisor: &UnsignedNumeric) -> Option<UnsignedNumeric> {
     let quotient = dividend.checked_div(divisor).unwrap().floor().unwrap();
     let remainder = dividend.checked_sub(&divisor.checked_mul(&quotient).unwrap()).unwrap();
@@ -152,31 +151,10 @@ mod tests {
     use brine_fp::InnerUint;
 
     #[test]
-    fn test_to_name() {
-        let name = "TestName";
-        let name_bytes = to_name(name);
-        assert_eq!(name_bytes[..name.len()], *name.as_bytes());
-    }
-
-    #[test]
-    fn test_from_name() {
-        let name_bytes = [84, 101, 115, 116, 78, 97, 109, 101, 0, 0];
-        let name = from_name(&name_bytes);
-        assert_eq!(name, "TestName");
-    }
-
-    #[test]
-    fn test_to_symbol() {
-        let symbol = "TST";
-        let symbol_bytes = to_symbol(symbol);
-        assert_eq!(symbol_bytes[..symbol.len()], *symbol.as_bytes());
-    }
-
-    #[test]
-    fn test_from_symbol() {
-        let symbol_bytes = [84, 83, 84, 0, 0, 0, 0, 0];
-        let symbol = from_symbol(&symbol_bytes);
-        assert_eq!(symbol, "TST");
+    fn test_to_quarks() {
+        assert_eq!(to_quarks(DEFAULT_TOKEN_SUPPLY, DEFAULT_TOKEN_DECIMALS).unwrap(), 210_000_000_000_000_000);
+        assert_eq!(to_quarks(1_000, 0).unwrap(), 1_000);
+        assert!(to_quarks(MAX_CURVE_SUPPLY, MAX_TOKEN_DECIMALS).is_err());
     }
 
     #[test]
@@ -252,6 +230,16 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_split_by_shares() {
+        assert_eq!(split_by_shares(100, &[1, 1]).unwrap(), vec![50, 50]);
+        assert_eq!(split_by_shares(100, &[1, 1, 1]).unwrap(), vec![33, 33, 34]);
+        assert_eq!(split_by_shares(100, &[3, 0, 1]).unwrap(), vec![75, 0, 25]);
+        assert_eq!(split_by_shares(u64::MAX, &[u32::MAX, u32::MAX]).unwrap(), vec![u64::MAX / 2, u64::MAX / 2 + 1]);
+        assert!(split_by_shares(100, &[0, 0]).is_err());
+        assert!(split_by_shares(100, &[]).is_err());
+    }
+
     #[test]
     fn test_basis_points_roundtrip() {
         let percent = UnsignedNumeric::new(123).unwrap() // 123% = 1.23
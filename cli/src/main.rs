@@ -7,7 +7,8 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use anyhow::Result;
 use flipcash_api::prelude::*;
-use flipcash_client::{create_mint, create_ata, mint_to, get_currency_account, get_pool_account, program};
+use flipcash_client::{create_mint, create_ata, mint_to, get_currency_account, get_currency_pools, get_currency_pool, get_currency_profile_account, get_protocol_config_account, get_registered_currencies, get_vesting_account, get_cash_link_account, decode_cash_link, get_limit_orders, get_dca_plans, get_distributor_account, read_distribution_csv, program};
+use flipcash_client::consts::LIMIT_ORDER_BOUNTY;
 use keypair::{get_keypair_path, get_payer};
 
 #[derive(Debug, Clone)]
@@ -49,6 +50,125 @@ impl FromStr for Cluster {
     }
 }
 
+fn parse_burn_policy(s: &str) -> Result<FeeBurnPolicy, String> {
+    match s {
+        "authority" => Ok(FeeBurnPolicy::AuthorityOnly),
+        "cooldown" => Ok(FeeBurnPolicy::PermissionlessAfterCooldown),
+        "anyone" => Ok(FeeBurnPolicy::Permissionless),
+        _ => Err(format!(
+            "Invalid burn policy: '{}'. Use authority, cooldown, or anyone",
+            s
+        )),
+    }
+}
+
+fn parse_creation_policy(s: &str) -> Result<CreationPolicy, String> {
+    match s {
+        "anyone" => Ok(CreationPolicy::Permissionless),
+        "admin" => Ok(CreationPolicy::AdminOnly),
+        _ => Err(format!(
+            "Invalid creation policy: '{}'. Use anyone or admin",
+            s
+        )),
+    }
+}
+
+fn parse_order_side(s: &str) -> Result<OrderSide, String> {
+    match s {
+        "buy" => Ok(OrderSide::Buy),
+        "sell" => Ok(OrderSide::Sell),
+        _ => Err(format!(
+            "Invalid order side: '{}'. Use buy or sell",
+            s
+        )),
+    }
+}
+
+fn parse_curve_kind(s: &str) -> Result<CurveKind, String> {
+    match s {
+        "discrete" => Ok(CurveKind::DiscreteExponential),
+        "exponential" => Ok(CurveKind::Exponential),
+        "linear" => Ok(CurveKind::Linear),
+        "sigmoid" => Ok(CurveKind::Sigmoid),
+        "table" => Ok(CurveKind::Table),
+        _ => Err(format!(
+            "Invalid curve: '{}'. Use discrete, exponential, linear, sigmoid, or table",
+            s
+        )),
+    }
+}
+
+fn parse_allowed_extension(s: &str) -> Result<u8, String> {
+    match s {
+        "permanent-delegate" => Ok(ALLOW_PERMANENT_DELEGATE),
+        "transfer-hook" => Ok(ALLOW_TRANSFER_HOOK),
+        "pausable" => Ok(ALLOW_PAUSABLE),
+        "default-account-state" => Ok(ALLOW_DEFAULT_ACCOUNT_STATE),
+        _ => Err(format!(
+            "Invalid extension: '{}'. Use permanent-delegate, transfer-hook, pausable, or default-account-state",
+            s
+        )),
+    }
+}
+
+/// Parses a profile link as "key=value", or "key=" to remove it.
+fn parse_profile_link(s: &str) -> Result<(String, Option<String>), String> {
+    let (key, value) = s.split_once('=')
+        .ok_or_else(|| format!("Invalid link: '{}'. Use key=value, or key= to remove it", s))?;
+    Ok((key.to_string(), (!value.is_empty()).then(|| value.to_string())))
+}
+
+/// Parses a decimal price in base tokens (e.g. "0.0125") into a raw
+/// `UnsignedNumeric` value, without going through floating point.
+fn parse_table_price(s: &str) -> Result<u128, String> {
+    const SCALE_DECIMALS: usize = 18;
+
+    let s = s.trim();
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || fraction.len() > SCALE_DECIMALS
+        || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("Invalid price: '{}'", s));
+    }
+
+    let digits = format!("{}{:0<width$}", whole, fraction, width = SCALE_DECIMALS);
+    digits.parse::<u128>().map_err(|_| format!("Invalid price: '{}'", s))
+}
+
+/// Reads one wallet,share pair per line, skipping blank lines.
+fn read_recipients(path: &std::path::Path) -> Result<Vec<(Pubkey, u32)>> {
+    std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (wallet, share) = line.split_once(',')
+                .ok_or_else(|| anyhow::anyhow!("Expected wallet,share: '{}'", line))?;
+            let wallet = wallet.trim().parse::<Pubkey>()
+                .map_err(|_| anyhow::anyhow!("Invalid wallet: '{}'", wallet))?;
+            let share = share.trim().parse::<u32>()
+                .map_err(|_| anyhow::anyhow!("Invalid share: '{}'", share))?;
+            Ok((wallet, share))
+        })
+        .collect()
+}
+
+/// Vesting schedule for an initial buy, or `None` to pay it out now.
+fn vesting_schedule(cliff: i64, duration: i64) -> Option<VestingSchedule> {
+    (duration > 0).then_some(VestingSchedule { cliff, duration })
+}
+
+/// Reads one wallet address per line, skipping blank lines.
+fn read_allowlist(path: &std::path::Path) -> Result<Vec<Pubkey>> {
+    std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse::<Pubkey>().map_err(|_| anyhow::anyhow!("Invalid wallet: '{}'", line)))
+        .collect()
+}
+
 #[derive(Parser)]
 #[command(name = "flipcash-cli")]
 #[command(about = "CLI for interacting with the Flipcash Solana program")]
@@ -84,19 +204,121 @@ enum Commands {
         #[arg(long, help = "Name of the currency (max 32 characters)")]
         name: String,
 
-        #[arg(long, help = "Symbol of the currency (max 8 characters)")]
+        #[arg(long, help = "Symbol of the currency (max 8 uppercase characters)")]
         symbol: String,
 
         #[arg(long, help = "Base mint address (e.g., USDC mint)")]
         base_mint: Pubkey,
+
+        #[arg(long, default_value = "discrete", value_parser = parse_curve_kind, help = "Curve shape (discrete, exponential, linear, sigmoid, or table)")]
+        curve: CurveKind,
+
+        #[arg(long, required_if_eq("curve", "table"), help = "Finalized curve table address (table curve only)")]
+        curve_table: Option<Pubkey>,
+
+        #[arg(long, default_value_t = 0.01, help = "Curve price of the first token (in base tokens)")]
+        start_price: f64,
+
+        #[arg(long, default_value_t = 1_000_000.0, help = "Curve price at max supply (in base tokens)")]
+        end_price: f64,
+
+        #[arg(long, default_value_t = DEFAULT_TOKEN_DECIMALS, help = "Currency mint decimals (max 18)")]
+        decimals: u8,
+
+        #[arg(long, default_value_t = DEFAULT_TOKEN_SUPPLY, help = "Total currency supply")]
+        max_supply: u64,
+
+        #[arg(long, help = "Tokens sold by the first pool, leaving the rest for add-pool (default: the whole supply)")]
+        pool_supply: Option<u64>,
+
+        #[arg(long, default_value_t = DEFAULT_CURVE_STEP_SIZE, help = "Tokens per curve price step")]
+        step_size: u64,
+
+        #[arg(long = "allow-extension", value_parser = parse_allowed_extension, help = "Risky Token-2022 base mint extension to accept (repeatable)")]
+        allowed_extensions: Vec<u8>,
+
+        #[arg(long, help = "Create the currency mint under Token-2022 with metadata on the mint instead of Metaplex")]
+        token_2022: bool,
+
+        #[arg(long, default_value_t = 0.0, help = "Base tokens to spend buying from the pool as it is created, before anyone else can")]
+        initial_buy: f64,
+
+        #[arg(long, default_value_t = 0, help = "Seconds over which the initial buy vests instead of being paid out (default: paid out now)")]
+        vesting_duration: i64,
+
+        #[arg(long, default_value_t = 0, help = "Seconds before any of the initial buy vests")]
+        vesting_cliff: i64,
     },
 
-    /// Retrieves metadata for a currency and its pool
+    /// Adds a pool against another base mint to an existing currency
+    AddPool {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint address for the new pool")]
+        base_mint: Pubkey,
+
+        #[arg(long, default_value = "discrete", value_parser = parse_curve_kind, help = "Curve shape (discrete, exponential, linear, sigmoid, or table)")]
+        curve: CurveKind,
+
+        #[arg(long, required_if_eq("curve", "table"), help = "Finalized curve table address (table curve only)")]
+        curve_table: Option<Pubkey>,
+
+        #[arg(long, default_value_t = 0.01, help = "Curve price of the first token (in base tokens)")]
+        start_price: f64,
+
+        #[arg(long, default_value_t = 1_000_000.0, help = "Curve price at max supply (in base tokens)")]
+        end_price: f64,
+
+        #[arg(long, help = "Tokens sold by this pool, taken from the currency's unallocated supply")]
+        max_supply: u64,
+
+        #[arg(long, default_value_t = DEFAULT_CURVE_STEP_SIZE, help = "Tokens per curve price step")]
+        step_size: u64,
+
+        #[arg(long = "allow-extension", value_parser = parse_allowed_extension, help = "Risky Token-2022 base mint extension to accept (repeatable)")]
+        allowed_extensions: Vec<u8>,
+
+        #[arg(long, default_value_t = 0.0, help = "Base tokens to spend buying from the pool as it is created, before anyone else can")]
+        initial_buy: f64,
+
+        #[arg(long, default_value_t = 0, help = "Seconds over which the initial buy vests instead of being paid out (default: paid out now)")]
+        vesting_duration: i64,
+
+        #[arg(long, default_value_t = 0, help = "Seconds before any of the initial buy vests")]
+        vesting_cliff: i64,
+    },
+
+    /// Uploads a custom price table that a pool can be created with
+    UploadCurveTable {
+        #[arg(long, help = "File with one step price per line (in base tokens); the last price marks the end of the curve")]
+        prices: PathBuf,
+
+        #[arg(long, default_value_t = DEFAULT_CURVE_STEP_SIZE, help = "Tokens per curve price step")]
+        step_size: u64,
+    },
+
+    /// Retrieves metadata for a currency and its pools
     GetCurrency {
         #[arg(long, help = "Currency mint address")]
         mint: Pubkey,
     },
 
+    /// Lists every registered currency in creation order
+    ListCurrencies,
+
+    /// Creates or updates a currency's on-chain profile
+    SetProfile {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Description of the currency (max 512 bytes)")]
+        description: Option<String>,
+
+        #[arg(long = "link", value_parser = parse_profile_link, help = "Link as key=value, e.g. website=https://example.com, or key= to remove it (repeatable)")]
+        links: Vec<(String, Option<String>)>,
+    },
+
     /// Buys tokens from the pool
     Buy {
         #[arg(long, help = "Currency mint address")]
@@ -109,6 +331,21 @@ enum Commands {
         amount: f64,
     },
 
+    /// Buys tokens from the pool and splits them across many wallets
+    BatchBuy {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint address (e.g., USDC mint)")]
+        base_mint: Pubkey,
+
+        #[arg(long, help = "Amount to spend (in base tokens, e.g., 100.50 USDC)")]
+        amount: f64,
+
+        #[arg(long, help = "File with one wallet,share pair per line")]
+        recipients: PathBuf,
+    },
+
     /// Sells tokens to the pool
     Sell {
         #[arg(long, help = "Currency mint address")]
@@ -129,6 +366,369 @@ enum Commands {
         #[arg(long, help = "Base mint address (e.g., USDC mint)")]
         base_mint: Pubkey,
     },
+
+    /// Sets who may burn accumulated fees from the pool
+    SetBurnPolicy {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool to update (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, value_parser = parse_burn_policy, help = "Burn policy (authority, cooldown, or anyone)")]
+        policy: FeeBurnPolicy,
+
+        #[arg(long, default_value_t = 0, help = "Seconds after the last burn before anyone may burn (cooldown policy only)")]
+        cooldown: i64,
+    },
+
+    /// Halts buys on the pool, and optionally sells. Must be signed by the pool authority or protocol admin
+    Pause {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool to pause (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, help = "Also halt sells")]
+        sells: bool,
+    },
+
+    /// Resumes trading on a paused pool. Must be signed by the pool authority or protocol admin
+    Resume {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool to resume (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+    },
+
+    /// Limits how far trades may move the pool's price within a window of slots
+    SetCircuitBreaker {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool to update (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, help = "Largest price move allowed per window (in bps), or 0 to disable")]
+        band: u16,
+
+        #[arg(long, default_value_t = 150, help = "Window length in slots")]
+        window: u64,
+    },
+
+    /// Registers a program the pool CPIs after every trade, or clears it
+    SetTradeHook {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool to update (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, help = "Hook program address (default: clear the pool's hook)")]
+        hook_program: Option<Pubkey>,
+
+        #[arg(long, default_value_t = 20_000, help = "Most compute the hook may use per trade")]
+        compute_units: u32,
+
+        #[arg(long, help = "Fail trades that are short on compute for the hook, instead of skipping it")]
+        required: bool,
+    },
+
+    /// Schedules the pool's launch, with an optional allowlist presale before it
+    SetLaunch {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool to update (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, help = "Unix timestamp trading opens to everyone, or 0 to open now")]
+        launch_at: i64,
+
+        #[arg(long, help = "File with one allowlisted wallet per line (default: no presale)")]
+        allowlist: Option<PathBuf>,
+
+        #[arg(long, default_value_t = 0.0, help = "Most each allowlisted wallet may spend before launch (in base tokens)")]
+        presale_cap: f64,
+
+        #[arg(long, default_value_t = 0, help = "Seconds after launch during which each wallet's buys are capped (default: none)")]
+        launch_window: u64,
+
+        #[arg(long, default_value_t = 0.0, help = "Most each wallet may spend during the launch window (in base tokens)")]
+        launch_cap: f64,
+    },
+
+    /// Proves the signer is on the pool's presale allowlist
+    JoinPresale {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, help = "File with one allowlisted wallet per line, as given to set-launch")]
+        allowlist: PathBuf,
+    },
+
+    /// Claims whatever has vested of the signer's initial buy
+    ClaimVested {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+    },
+
+    /// Retrieves a pool authority's vesting schedule
+    GetVesting {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, help = "Beneficiary wallet (default: the signer)")]
+        beneficiary: Option<Pubkey>,
+    },
+
+    /// Locks currency in a cash link anyone holding the link can claim
+    CreateCashLink {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Buy the currency from this base mint's pool instead of depositing it")]
+        buy_with: Option<Pubkey>,
+
+        #[arg(long, help = "Amount of currency to deposit, or of base tokens to spend with --buy-with")]
+        amount: f64,
+
+        #[arg(long, help = "Unix timestamp after which the link can no longer be claimed")]
+        expires_at: i64,
+    },
+
+    /// Claims a cash link into the signer's wallet
+    ClaimCashLink {
+        #[arg(long, help = "Cash link URL or encoded secret")]
+        link: String,
+    },
+
+    /// Takes back the currency in an expired, unclaimed cash link
+    ReclaimCashLink {
+        #[arg(long, help = "Public key of the cash link")]
+        link_key: Pubkey,
+    },
+
+    /// Retrieves a cash link
+    GetCashLink {
+        #[arg(long, help = "Cash link URL or encoded secret")]
+        link: String,
+    },
+
+    /// Places a limit order that fills once the pool's spot price crosses a target
+    CreateLimitOrder {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, value_parser = parse_order_side, help = "Order side (buy or sell)")]
+        side: OrderSide,
+
+        #[arg(long, help = "Base tokens to spend when buying, or currency to sell")]
+        amount: f64,
+
+        #[arg(long, help = "Spot price to buy at or below, or sell at or above (in base tokens)")]
+        price: f64,
+
+        #[arg(long, help = "Unix timestamp after which the order can no longer fill")]
+        expires_at: i64,
+
+        #[arg(long, default_value_t = LIMIT_ORDER_BOUNTY, help = "Lamports paid to whoever executes the order")]
+        bounty: u64,
+    },
+
+    /// Fills a limit order whose target price has been crossed, collecting its bounty
+    ExecuteLimitOrder {
+        #[arg(long, help = "Limit order address")]
+        order: Pubkey,
+    },
+
+    /// Cancels a limit order, refunding its escrow. Anyone may cancel an expired order
+    CancelLimitOrder {
+        #[arg(long, help = "Limit order address")]
+        order: Pubkey,
+    },
+
+    /// Fills every crossed limit order against a pool and cleans up expired ones
+    CrankLimitOrders {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, default_value_t = 0, help = "Seconds between passes, or 0 to run once")]
+        interval: u64,
+    },
+
+    /// Lists the open limit orders against a pool
+    GetLimitOrders {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+    },
+
+    /// Opens a DCA plan that buys a currency with base tokens on a fixed interval
+    CreateDca {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, help = "Base tokens to spend per fill")]
+        amount_per_fill: f64,
+
+        #[arg(long, help = "Most base tokens a fill may pay per whole token on average")]
+        max_price: f64,
+
+        #[arg(long, help = "Seconds between fills")]
+        interval: i64,
+
+        #[arg(long, default_value_t = 0, help = "Unix timestamp of the first fill, or 0 to start right away")]
+        first_fill_at: i64,
+
+        #[arg(long, default_value_t = 0.0, help = "Base tokens to fund the plan with")]
+        deposit: f64,
+    },
+
+    /// Adds base tokens to a DCA plan
+    DepositDca {
+        #[arg(long, help = "DCA plan address")]
+        plan: Pubkey,
+
+        #[arg(long, help = "Base tokens to deposit")]
+        amount: f64,
+    },
+
+    /// Takes base tokens back out of a DCA plan
+    WithdrawDca {
+        #[arg(long, help = "DCA plan address")]
+        plan: Pubkey,
+
+        #[arg(long, help = "Base tokens to withdraw (default: everything)")]
+        amount: Option<f64>,
+    },
+
+    /// Closes a DCA plan, refunding what is left of its escrow
+    CloseDca {
+        #[arg(long, help = "DCA plan address")]
+        plan: Pubkey,
+    },
+
+    /// Runs the next fill of a due DCA plan
+    ExecuteDca {
+        #[arg(long, help = "DCA plan address")]
+        plan: Pubkey,
+    },
+
+    /// Keeps running due DCA fills against a pool until stopped
+    RunDcaScheduler {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+
+        #[arg(long, default_value_t = 5, help = "Seconds between passes")]
+        interval: u64,
+    },
+
+    /// Lists the open DCA plans against a pool
+    GetDcaPlans {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "Base mint of the pool (default: primary pool)")]
+        base_mint: Option<Pubkey>,
+    },
+
+    /// Creates a distributor airdropping a currency to the wallets in a CSV, funded by the signer
+    CreateDistributor {
+        #[arg(long, help = "Currency mint address")]
+        mint: Pubkey,
+
+        #[arg(long, help = "CSV file with one wallet,amount line per recipient (amounts in whole tokens)")]
+        csv: PathBuf,
+
+        #[arg(long, help = "Unix timestamp after which unclaimed tokens can be clawed back")]
+        expires_at: i64,
+    },
+
+    /// Claims the signer's airdrop from a distributor
+    ClaimDistribution {
+        #[arg(long, help = "Distributor address")]
+        distributor: Pubkey,
+
+        #[arg(long, help = "CSV file the distributor was created from")]
+        csv: PathBuf,
+    },
+
+    /// Returns an expired distributor's unclaimed tokens to its authority
+    ClawbackDistributor {
+        #[arg(long, help = "Distributor address")]
+        distributor: Pubkey,
+    },
+
+    /// Retrieves a distributor
+    GetDistributor {
+        #[arg(long, help = "Distributor address")]
+        distributor: Pubkey,
+    },
+
+    /// Creates the protocol config, making the signer its admin. Must be signed by the program upgrade authority
+    InitProtocol {
+        #[arg(long, default_value_t = 0, help = "Lowest sell fee a pool may charge (in bps)")]
+        min_sell_fee: u16,
+
+        #[arg(long, default_value_t = 500, help = "Highest sell fee a pool may charge (in bps)")]
+        max_sell_fee: u16,
+
+        #[arg(long, default_value = "anyone", value_parser = parse_creation_policy, help = "Who may create pools (anyone or admin)")]
+        creation_policy: CreationPolicy,
+    },
+
+    /// Updates the protocol config. Must be signed by the protocol admin
+    UpdateProtocol {
+        #[arg(long, help = "Hand the protocol over to a new admin (default: keep the current one)")]
+        new_admin: Option<Pubkey>,
+
+        #[arg(long, help = "Lowest sell fee a pool may charge (in bps)")]
+        min_sell_fee: u16,
+
+        #[arg(long, help = "Highest sell fee a pool may charge (in bps)")]
+        max_sell_fee: u16,
+
+        #[arg(long, value_parser = parse_creation_policy, help = "Who may create pools (anyone or admin)")]
+        creation_policy: CreationPolicy,
+    },
+
+    /// Adds a base mint to the protocol allowlist, or removes it
+    AllowBaseMint {
+        #[arg(long, help = "Base mint address (e.g., USDC mint)")]
+        base_mint: Pubkey,
+
+        #[arg(long, help = "Remove the base mint instead. Existing pools keep trading")]
+        remove: bool,
+    },
+
+    /// Retrieves the protocol config
+    GetProtocol,
 }
 
 #[tokio::main]
@@ -153,13 +753,28 @@ async fn main() -> Result<()> {
             println!("Minted {} tokens to ATA {}. Signature: {}", initial_amount, ata, mint_to_signature);
         }
 
-        Commands::CreateCurrency { name, symbol, base_mint } => {
+        Commands::CreateCurrency { name, symbol, base_mint, curve, curve_table, start_price, end_price, decimals, max_supply, pool_supply, step_size, allowed_extensions, token_2022, initial_buy, vesting_duration, vesting_cliff } => {
+            let curve = CurveParams {
+                kind: curve,
+                start_price: (start_price * 10f64.powi(CURVE_PRICE_DECIMALS as i32)).round() as u64,
+                end_price: (end_price * 10f64.powi(CURVE_PRICE_DECIMALS as i32)).round() as u64,
+                max_supply: pool_supply.unwrap_or(max_supply),
+                step_size,
+            };
             let (currency_sig, pool_sig, mint_pda, currency_pda, pool_pda) = program::initialize(
                 &client,
                 &payer,
                 name.clone(),
                 symbol.clone(),
+                decimals,
+                max_supply,
+                token_2022,
                 base_mint,
+                curve,
+                allowed_extensions.iter().fold(0, |bits, bit| bits | bit),
+                curve_table,
+                initial_buy,
+                vesting_schedule(vesting_cliff, vesting_duration),
             ).await?;
             println!("Currency created. Signature: {}", currency_sig);
             println!("Pool created. Signature: {}", pool_sig);
@@ -168,29 +783,162 @@ async fn main() -> Result<()> {
             println!("Pool PDA: {}", pool_pda);
         }
 
+        Commands::AddPool { mint, base_mint, curve, curve_table, start_price, end_price, max_supply, step_size, allowed_extensions, initial_buy, vesting_duration, vesting_cliff } => {
+            let curve = CurveParams {
+                kind: curve,
+                start_price: (start_price * 10f64.powi(CURVE_PRICE_DECIMALS as i32)).round() as u64,
+                end_price: (end_price * 10f64.powi(CURVE_PRICE_DECIMALS as i32)).round() as u64,
+                max_supply,
+                step_size,
+            };
+            let (signature, pool_pda) = program::add_pool(
+                &client,
+                &payer,
+                mint,
+                base_mint,
+                curve,
+                allowed_extensions.iter().fold(0, |bits, bit| bits | bit),
+                curve_table,
+                initial_buy,
+                vesting_schedule(vesting_cliff, vesting_duration),
+            ).await?;
+            println!("Pool created. Signature: {}", signature);
+            println!("Pool PDA: {}", pool_pda);
+        }
+
+        Commands::UploadCurveTable { prices, step_size } => {
+            let prices = std::fs::read_to_string(&prices)?
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(parse_table_price)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| anyhow::anyhow!(e))?;
+
+            let (signature, curve_table) = program::upload_curve_table(
+                &client,
+                &payer,
+                step_size,
+                &prices,
+            ).await?;
+            println!("Curve table finalized. Signature: {}", signature);
+            println!("Curve Table: {}", curve_table);
+        }
+
         Commands::GetCurrency { mint } => {
             let (currency_pda, _) = find_currency_pda(&mint);
-            let (pool_pda, _) = find_pool_pda(&currency_pda);
 
             let (currency, _) = get_currency_account(&client, &currency_pda).await?;
-            let name = from_name(&currency.name);
-            let symbol = from_symbol(&currency.symbol);
+            let name = CurrencyName::from_bytes(&currency.name)?;
+            let symbol = CurrencySymbol::from_legacy_bytes(&currency.symbol)?;
             println!("Currency Metadata:");
             println!("  Authority: {}", currency.authority);
             println!("  Mint: {}", currency.mint);
             println!("  Name: {}", name);
             println!("  Symbol: {}", symbol);
+            println!("  Decimals: {}", currency.decimals);
+            println!("  Max Supply: {}", currency.max_supply);
+            println!("  Supply Allocated: {}", currency.supply_allocated);
 
-            let (pool, _) = get_pool_account(&client, &pool_pda).await?;
-            println!("\nPool Metadata:");
-            println!("  Authority: {}", pool.authority);
-            println!("  Currency: {}", pool.currency);
-            println!("  Mint A (Target): {}", pool.mint_a);
-            println!("  Mint B (Base): {}", pool.mint_b);
-            println!("  Vault A: {}", pool.vault_a);
-            println!("  Vault B: {}", pool.vault_b);
-            println!("  Fees Accumulated: {}", pool.fees_accumulated);
-            println!("  Sell Fee: {} bps ({}%)", pool.sell_fee, pool.sell_fee as f64 / 100.0);
+            if let Some((_, description, links)) = get_currency_profile_account(&client, &currency_pda).await? {
+                println!("\nProfile:");
+                if !description.is_empty() {
+                    println!("  Description: {}", description.replace('\n', "\n    "));
+                }
+                for link in links {
+                    println!("  {}: {}", link.key(), link.value());
+                }
+            }
+
+            for (pool, pool_pda) in get_currency_pools(&client, &currency_pda).await? {
+                println!("\nPool Metadata ({}):", pool_pda);
+                println!("  Authority: {}", pool.authority);
+                println!("  Currency: {}", pool.currency);
+                println!("  Mint A (Target): {}", pool.mint_a);
+                println!("  Mint B (Base): {}", pool.mint_b);
+                println!("  Vault A: {}", pool.vault_a);
+                println!("  Vault B: {}", pool.vault_b);
+                println!("  Fees Accumulated: {}", pool.fees_accumulated);
+                println!("  Sell Fee: {} bps ({}%)", pool.sell_fee, pool.sell_fee as f64 / 100.0);
+                match pool.burn_policy() {
+                    Ok(FeeBurnPolicy::AuthorityOnly) => println!("  Burn Policy: Authority only"),
+                    Ok(FeeBurnPolicy::PermissionlessAfterCooldown) => println!(
+                        "  Burn Policy: Permissionless after {}s cooldown", pool.burn_cooldown
+                    ),
+                    Ok(FeeBurnPolicy::Permissionless) => println!("  Burn Policy: Permissionless"),
+                    Err(_) => println!("  Burn Policy: Unknown ({})", pool.burn_policy),
+                }
+                match (pool.is_buy_paused(), pool.is_sell_paused()) {
+                    (false, false) => println!("  Paused: No"),
+                    (true, false) => println!("  Paused: Buys"),
+                    (_, true) => println!("  Paused: Buys and sells"),
+                }
+                if pool.is_breaker_enabled() {
+                    println!(
+                        "  Circuit Breaker: {} bps ({}%) per {} slots",
+                        pool.breaker_band, pool.breaker_band as f64 / 100.0, pool.breaker_window
+                    );
+                } else {
+                    println!("  Circuit Breaker: Disabled");
+                }
+                if pool.has_trade_hook() {
+                    println!(
+                        "  Trade Hook: {} ({}, up to {} CUs)",
+                        pool.hook_program,
+                        if pool.is_trade_hook_required() { "required" } else { "optional" },
+                        pool.hook_compute_units
+                    );
+                }
+                if pool.launch_at != 0 {
+                    match chrono::DateTime::from_timestamp(pool.launch_at, 0) {
+                        Some(launch_at) => println!("  Launch: {}", launch_at),
+                        None => println!("  Launch: {}", pool.launch_at),
+                    }
+                }
+                if pool.has_presale() {
+                    println!("  Presale Cap: {}", pool.presale_cap);
+                    println!("  Presale Root: {}", Pubkey::new_from_array(pool.presale_root));
+                }
+                if pool.launch_window > 0 {
+                    println!("  Launch Window: {} seconds, {} per wallet", pool.launch_window, pool.launch_cap);
+                }
+                let price_scale = 10f64.powi(CURVE_PRICE_DECIMALS as i32);
+                match pool.curve_params() {
+                    Ok(params) => println!("  Curve: {:?}", params.kind),
+                    Err(_) => println!("  Curve: Unknown ({})", pool.curve_kind),
+                }
+                println!("  Curve Start Price: {}", pool.curve_start_price as f64 / price_scale);
+                println!("  Curve End Price: {}", pool.curve_end_price as f64 / price_scale);
+                println!("  Curve Max Supply: {}", pool.curve_max_supply);
+                println!("  Curve Step Size: {}", pool.curve_step_size);
+                if pool.is_table_backed() {
+                    println!("  Curve Table: {}", pool.curve_table);
+                }
+                match chrono::DateTime::from_timestamp(pool.last_burn_at, 0) {
+                    Some(last_burn) => println!("  Last Burn: {}", last_burn),
+                    None => println!("  Last Burn: {}", pool.last_burn_at),
+                }
+            }
+        }
+
+        Commands::SetProfile { mint, description, links } => {
+            let signatures = program::set_currency_profile(&client, &payer, mint, description, links).await?;
+            for signature in signatures {
+                println!("Set profile transaction successful. Signature: {}", signature);
+            }
+        }
+
+        Commands::ListCurrencies => {
+            for (index, mint) in get_registered_currencies(&client).await?.into_iter().enumerate() {
+                let (currency_pda, _) = find_currency_pda(&mint);
+                let (currency, _) = get_currency_account(&client, &currency_pda).await?;
+                println!(
+                    "{:>5}  {:<8}  {}  {}",
+                    index,
+                    CurrencySymbol::from_legacy_bytes(&currency.symbol)?.as_str(),
+                    mint,
+                    CurrencyName::from_bytes(&currency.name)?,
+                );
+            }
         }
 
         Commands::Buy { mint, base_mint, amount } => {
@@ -198,6 +946,12 @@ async fn main() -> Result<()> {
             println!("Buy transaction successful. Signature: {}", signature);
         }
 
+        Commands::BatchBuy { mint, base_mint, amount, recipients } => {
+            let recipients = read_recipients(&recipients)?;
+            let signature = program::batch_buy(&client, &payer, mint, base_mint, amount, &recipients).await?;
+            println!("Batch buy transaction successful. Signature: {}", signature);
+        }
+
         Commands::Sell { mint, base_mint, amount } => {
             let signature = program::sell(&client, &payer, mint, base_mint, amount).await?;
             println!("Sell transaction successful. Signature: {}", signature);
@@ -207,6 +961,315 @@ async fn main() -> Result<()> {
             let signature = program::burn_fees(&client, &payer, mint, base_mint).await?;
             println!("Burn fees transaction successful. Signature: {}", signature);
         }
+
+        Commands::SetBurnPolicy { mint, base_mint, policy, cooldown } => {
+            let signature = program::set_burn_policy(&client, &payer, mint, base_mint, policy, cooldown).await?;
+            println!("Set burn policy transaction successful. Signature: {}", signature);
+        }
+
+        Commands::Pause { mint, base_mint, sells } => {
+            let signature = program::pause(&client, &payer, mint, base_mint, sells).await?;
+            println!("Pause transaction successful. Signature: {}", signature);
+        }
+
+        Commands::Resume { mint, base_mint } => {
+            let signature = program::resume(&client, &payer, mint, base_mint).await?;
+            println!("Resume transaction successful. Signature: {}", signature);
+        }
+
+        Commands::SetCircuitBreaker { mint, base_mint, band, window } => {
+            let signature = program::set_circuit_breaker(&client, &payer, mint, base_mint, band, window).await?;
+            println!("Set circuit breaker transaction successful. Signature: {}", signature);
+        }
+
+        Commands::SetTradeHook { mint, base_mint, hook_program, compute_units, required } => {
+            let signature = program::set_trade_hook(&client, &payer, mint, base_mint, hook_program, compute_units, required).await?;
+            println!("Set trade hook transaction successful. Signature: {}", signature);
+        }
+
+        Commands::SetLaunch { mint, base_mint, launch_at, allowlist, presale_cap, launch_window, launch_cap } => {
+            let allowlist = match allowlist {
+                Some(allowlist) => read_allowlist(&allowlist)?,
+                None => vec![],
+            };
+            let signature = program::set_launch(
+                &client,
+                &payer,
+                mint,
+                base_mint,
+                launch_at,
+                presale_cap,
+                &allowlist,
+                launch_window,
+                launch_cap,
+            ).await?;
+            println!("Set launch transaction successful. Signature: {}", signature);
+        }
+
+        Commands::JoinPresale { mint, base_mint, allowlist } => {
+            let allowlist = read_allowlist(&allowlist)?;
+            let signature = program::join_presale(&client, &payer, mint, base_mint, &allowlist).await?;
+            println!("Join presale transaction successful. Signature: {}", signature);
+        }
+
+        Commands::ClaimVested { mint, base_mint } => {
+            let signature = program::claim_vested(&client, &payer, mint, base_mint).await?;
+            println!("Claim vested transaction successful. Signature: {}", signature);
+        }
+
+        Commands::GetVesting { mint, base_mint, beneficiary } => {
+            let (_, pool_pda) = get_currency_pool(&client, &mint, base_mint.as_ref()).await?;
+            let beneficiary = beneficiary.unwrap_or(payer.pubkey());
+            match get_vesting_account(&client, &pool_pda, &beneficiary).await? {
+                Some(vesting) => {
+                    println!("Vesting:");
+                    println!("  Beneficiary: {}", vesting.beneficiary);
+                    println!("  Vault: {}", vesting.vault);
+                    println!("  Total: {}", vesting.total_amount);
+                    println!("  Claimed: {}", vesting.claimed_amount);
+                    println!("  Start: {}", vesting.start_at);
+                    println!("  Cliff: {}", vesting.cliff_at);
+                    println!("  End: {}", vesting.end_at);
+                }
+                None => println!("Nothing is vesting for {}", beneficiary),
+            }
+        }
+
+        Commands::CreateCashLink { mint, buy_with, amount, expires_at } => {
+            let (signature, link) = program::create_cash_link(&client, &payer, mint, buy_with, amount, expires_at).await?;
+            println!("Create cash link transaction successful. Signature: {}", signature);
+            println!("Link: {}", link);
+        }
+
+        Commands::ClaimCashLink { link } => {
+            let signature = program::claim_cash_link(&client, &payer, &link).await?;
+            println!("Claim cash link transaction successful. Signature: {}", signature);
+        }
+
+        Commands::ReclaimCashLink { link_key } => {
+            let signature = program::reclaim_cash_link(&client, &payer, link_key).await?;
+            println!("Reclaim cash link transaction successful. Signature: {}", signature);
+        }
+
+        Commands::GetCashLink { link } => {
+            let link_key = decode_cash_link(&link)?.pubkey();
+            match get_cash_link_account(&client, &link_key).await? {
+                Some(cash_link) => {
+                    println!("Cash Link ({}):", link_key);
+                    println!("  Creator: {}", cash_link.creator);
+                    println!("  Mint: {}", cash_link.mint);
+                    println!("  Vault: {}", cash_link.vault);
+                    println!("  Amount: {}", cash_link.amount);
+                    println!("  Created: {}", cash_link.created_at);
+                    println!("  Expires: {}", cash_link.expires_at);
+                }
+                None => println!("Cash link {} has already been claimed or reclaimed", link_key),
+            }
+        }
+
+        Commands::CreateLimitOrder { mint, base_mint, side, amount, price, expires_at, bounty } => {
+            let (signature, order) = program::create_limit_order(
+                &client,
+                &payer,
+                mint,
+                base_mint,
+                side,
+                amount,
+                price,
+                expires_at,
+                bounty,
+            ).await?;
+            println!("Create limit order transaction successful. Signature: {}", signature);
+            println!("Limit Order: {}", order);
+        }
+
+        Commands::ExecuteLimitOrder { order } => {
+            let signature = program::execute_limit_order(&client, &payer, order).await?;
+            println!("Execute limit order transaction successful. Signature: {}", signature);
+        }
+
+        Commands::CancelLimitOrder { order } => {
+            let signature = program::cancel_limit_order(&client, &payer, order).await?;
+            println!("Cancel limit order transaction successful. Signature: {}", signature);
+        }
+
+        Commands::CrankLimitOrders { mint, base_mint, interval } => {
+            loop {
+                let signatures = program::crank_limit_orders(&client, &payer, mint, base_mint).await?;
+                for signature in &signatures {
+                    println!("Limit order transaction successful. Signature: {}", signature);
+                }
+                if interval == 0 {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+            }
+        }
+
+        Commands::GetLimitOrders { mint, base_mint } => {
+            let (_, pool_pda) = get_currency_pool(&client, &mint, base_mint.as_ref()).await?;
+            let orders = get_limit_orders(&client, &pool_pda).await?;
+            println!("Limit Orders ({}):", orders.len());
+            for (order, address) in orders {
+                let price = order.target_price as f64 / 10f64.powi(CURVE_PRICE_DECIMALS as i32);
+                println!("  {}:", address);
+                println!("    Owner: {}", order.owner);
+                println!("    Side: {:?}", order.side()?);
+                println!("    Amount: {}", order.amount);
+                println!("    Target Price: {}", price);
+                println!("    Bounty: {} lamports", order.bounty);
+                println!("    Expires: {}", order.expires_at);
+            }
+        }
+
+        Commands::CreateDca { mint, base_mint, amount_per_fill, max_price, interval, first_fill_at, deposit } => {
+            let (signature, plan) = program::create_dca_plan(
+                &client,
+                &payer,
+                mint,
+                base_mint,
+                amount_per_fill,
+                max_price,
+                interval,
+                first_fill_at,
+                deposit,
+            ).await?;
+            println!("Create DCA plan transaction successful. Signature: {}", signature);
+            println!("DCA Plan: {}", plan);
+        }
+
+        Commands::DepositDca { plan, amount } => {
+            let signature = program::deposit_dca(&client, &payer, plan, amount).await?;
+            println!("Deposit DCA transaction successful. Signature: {}", signature);
+        }
+
+        Commands::WithdrawDca { plan, amount } => {
+            let signature = program::withdraw_dca(&client, &payer, plan, amount).await?;
+            println!("Withdraw DCA transaction successful. Signature: {}", signature);
+        }
+
+        Commands::CloseDca { plan } => {
+            let signature = program::close_dca_plan(&client, &payer, plan).await?;
+            println!("Close DCA plan transaction successful. Signature: {}", signature);
+        }
+
+        Commands::ExecuteDca { plan } => {
+            let signature = program::execute_dca(&client, &payer, plan).await?;
+            println!("Execute DCA transaction successful. Signature: {}", signature);
+        }
+
+        Commands::RunDcaScheduler { mint, base_mint, interval } => {
+            program::run_dca_scheduler(&client, &payer, mint, base_mint, interval).await?;
+        }
+
+        Commands::GetDcaPlans { mint, base_mint } => {
+            let (_, pool_pda) = get_currency_pool(&client, &mint, base_mint.as_ref()).await?;
+            let plans = get_dca_plans(&client, &pool_pda).await?;
+            println!("DCA Plans ({}):", plans.len());
+            for (plan, address) in plans {
+                println!("  {}:", address);
+                println!("    Owner: {}", plan.owner);
+                println!("    Amount Per Fill: {}", plan.amount_per_fill);
+                println!("    Min Amount Out: {}", plan.min_amount_out);
+                println!("    Interval: {}s", plan.interval);
+                println!("    Next Fill: {}", plan.next_fill_at);
+                println!("    Fills: {}", plan.fills);
+                println!("    Total Spent: {}", plan.total_spent);
+                println!("    Total Bought: {}", plan.total_bought);
+            }
+        }
+
+        Commands::CreateDistributor { mint, csv, expires_at } => {
+            let (currency_pda, _) = find_currency_pda(&mint);
+            let (currency, _) = get_currency_account(&client, &currency_pda).await?;
+            let recipients = read_distribution_csv(&csv, currency.decimals)?;
+            let (signature, distributor) = program::create_distributor(
+                &client,
+                &payer,
+                mint,
+                &recipients,
+                expires_at,
+            ).await?;
+            println!("Create distributor transaction successful. Signature: {}", signature);
+            println!("Distributor: {}", distributor);
+        }
+
+        Commands::ClaimDistribution { distributor, csv } => {
+            let (account, _) = get_distributor_account(&client, &distributor).await?
+                .ok_or_else(|| anyhow::anyhow!("Distributor {} is closed", distributor))?;
+            let (currency_pda, _) = find_currency_pda(&account.mint);
+            let (currency, _) = get_currency_account(&client, &currency_pda).await?;
+            let recipients = read_distribution_csv(&csv, currency.decimals)?;
+            let signature = program::claim_distribution(&client, &payer, distributor, &recipients).await?;
+            println!("Claim distribution transaction successful. Signature: {}", signature);
+        }
+
+        Commands::ClawbackDistributor { distributor } => {
+            let signature = program::clawback_distributor(&client, &payer, distributor).await?;
+            println!("Clawback distributor transaction successful. Signature: {}", signature);
+        }
+
+        Commands::GetDistributor { distributor } => {
+            match get_distributor_account(&client, &distributor).await? {
+                Some((account, _)) => {
+                    println!("Distributor ({}):", distributor);
+                    println!("  Authority: {}", account.authority);
+                    println!("  Mint: {}", account.mint);
+                    println!("  Vault: {}", account.vault);
+                    println!("  Total Amount: {}", account.total_amount);
+                    println!("  Claimed Amount: {}", account.claimed_amount);
+                    println!("  Claimed: {}/{}", account.num_claimed, account.num_recipients);
+                    println!("  Created: {}", account.created_at);
+                    println!("  Expires: {}", account.expires_at);
+                }
+                None => println!("Distributor {} has been clawed back", distributor),
+            }
+        }
+
+        Commands::InitProtocol { min_sell_fee, max_sell_fee, creation_policy } => {
+            let (signature, protocol_config_pda) = program::initialize_protocol_config(
+                &client,
+                &payer,
+                min_sell_fee,
+                max_sell_fee,
+                creation_policy,
+            ).await?;
+            println!("Protocol config created. Signature: {}", signature);
+            println!("Protocol Config PDA: {}", protocol_config_pda);
+        }
+
+        Commands::UpdateProtocol { new_admin, min_sell_fee, max_sell_fee, creation_policy } => {
+            let signature = program::update_protocol_config(
+                &client,
+                &payer,
+                new_admin,
+                min_sell_fee,
+                max_sell_fee,
+                creation_policy,
+            ).await?;
+            println!("Update protocol transaction successful. Signature: {}", signature);
+        }
+
+        Commands::AllowBaseMint { base_mint, remove } => {
+            let signature = program::set_base_mint_allowed(&client, &payer, base_mint, !remove).await?;
+            println!("Allow base mint transaction successful. Signature: {}", signature);
+        }
+
+        Commands::GetProtocol => {
+            let (config, protocol_config_pda) = get_protocol_config_account(&client).await?;
+            println!("Protocol Config ({}):", protocol_config_pda);
+            println!("  Admin: {}", config.admin);
+            println!("  Sell Fee Bounds: {} to {} bps", config.min_sell_fee, config.max_sell_fee);
+            match config.creation_policy() {
+                Ok(CreationPolicy::Permissionless) => println!("  Pool Creation: Anyone"),
+                Ok(CreationPolicy::AdminOnly) => println!("  Pool Creation: Admin only"),
+                Err(_) => println!("  Pool Creation: Unknown ({})", config.creation_policy),
+            }
+            println!("  Allowed Base Mints:");
+            for base_mint in &config.base_mints[..config.num_base_mints as usize] {
+                println!("    {}", base_mint);
+            }
+        }
     }
 
     Ok(())
@@ -3,3 +3,7 @@ pub const LAMPORTS_PER_TX: u64    = 5000;
 
 pub const DECIMAL_PLACES: u8      = 6;
 pub const SELL_FEE_BPS: u16       = 100; // 1% fee
+pub const BURN_COOLDOWN_SECS: i64 = 86_400; // Anyone may burn fees a day after the last burn
+pub const LIMIT_ORDER_BOUNTY: u64 = 10 * LAMPORTS_PER_TX; // Pays for the executor's transaction several times over
+
+pub const CASH_LINK_URL: &str     = "https://cash.flipcash.com/c#"; // The secret stays in the fragment, out of server logs
@@ -0,0 +1,42 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::utils::*;
+
+/// Sets how far trades may move a pool's spot price within a window of
+/// slots. A band of zero disables the breaker.
+pub async fn set_circuit_breaker(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+    band: u16,                 // Basis points
+    window: u64,               // Slots
+) -> Result<Signature> {
+    let authority = signer.pubkey();
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+
+    let set_circuit_breaker_ix = build_set_circuit_breaker_ix(authority, pool_pda, band, window);
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_circuit_breaker_ix],
+        Some(&authority),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to set circuit breaker: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
@@ -16,10 +16,11 @@ pub async fn burn_fees(
     base_mint: Pubkey,
 ) -> Result<Signature> {
     let payer = signer.pubkey();
-    let (currency_pda, _) = find_currency_pda(&mint);
-    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (_, pool_pda) = get_currency_pool(client, &mint, Some(&base_mint)).await?;
 
-    let burn_fees_ix = build_burn_fees_ix(payer, pool_pda, base_mint);
+    let base_token_program = get_token_program(client, &base_mint).await?;
+
+    let burn_fees_ix = build_burn_fees_ix(payer, pool_pda, base_mint, base_token_program);
 
     let blockhash_bytes = get_latest_blockhash(client).await?;
     let recent_blockhash = deserialize(&blockhash_bytes)?;
@@ -37,3 +38,33 @@ pub async fn burn_fees(
 
     Ok(signature)
 }
+
+pub async fn set_burn_policy(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+    burn_policy: FeeBurnPolicy,
+    burn_cooldown: i64,
+) -> Result<Signature> {
+    let authority = signer.pubkey();
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+
+    let set_burn_policy_ix = build_set_burn_policy_ix(authority, pool_pda, burn_policy, burn_cooldown);
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_burn_policy_ix],
+        Some(&authority),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to set burn policy: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
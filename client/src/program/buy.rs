@@ -20,19 +20,20 @@ pub async fn buy(
 ) -> Result<Signature> {
 
     let buyer = signer.pubkey();
-    let (currency_pda, _) = find_currency_pda(&mint);
-    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (pool, pool_pda) = get_currency_pool(client, &mint, Some(&base_mint)).await?;
+    let target_token_program = get_token_program(client, &mint).await?;
+    let base_token_program = get_token_program(client, &base_mint).await?;
 
-    let buyer_target_ata = spl_associated_token_account::get_associated_token_address(&buyer, &mint);
-    let buyer_base_ata = spl_associated_token_account::get_associated_token_address(&buyer, &base_mint);
+    let buyer_target_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&buyer, &mint, &target_token_program);
+    let buyer_base_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&buyer, &base_mint, &base_token_program);
 
     // Create buyer ATAs
-    let (_target_ata, target_ata_sig) = create_ata(client, signer, &mint, &buyer, None).await?;
+    let (_target_ata, target_ata_sig) = create_ata(client, signer, &mint, &buyer, Some(&target_token_program)).await?;
     if target_ata_sig != Signature::default() {
         println!("Created buyer target ATA: {}. Signature: {}", buyer_target_ata, target_ata_sig);
     }
 
-    let (_base_ata, base_ata_sig) = create_ata(client, signer, &base_mint, &buyer, None).await?;
+    let (_base_ata, base_ata_sig) = create_ata(client, signer, &base_mint, &buyer, Some(&base_token_program)).await?;
     if base_ata_sig != Signature::default() {
         println!("Created buyer base ATA: {}. Signature: {}", buyer_base_ata, base_ata_sig);
     }
@@ -41,22 +42,32 @@ pub async fn buy(
     let in_amount = (amount * 10f64.powi(DECIMAL_PLACES as i32)) as u64;
     let min_amount_out = 0; // Allow any output amount for simplicity
 
-    let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(100_000);
+    // Buys during the launch window are tracked per wallet
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(100_000 + pool.hook_compute_units)];
+    if pool.is_launch_window(client.get_block_time(client.get_slot().await?).await?) &&
+        get_wallet_purchases_account(client, &pool_pda, &buyer).await?.is_none() {
+        instructions.push(build_initialize_wallet_purchases_ix(buyer, pool_pda, buyer));
+    }
+
     let buy_ix = build_buy_tokens_ix(
         buyer,
         pool_pda,
         mint,
+        target_token_program,
         base_mint,
+        base_token_program,
         in_amount,
         min_amount_out,
         buyer_target_ata,
         buyer_base_ata,
+        TradeOptions::for_pool(&pool, &pool_pda),
     );
+    instructions.push(buy_ix);
 
     let blockhash_bytes = get_latest_blockhash(client).await?;
     let recent_blockhash = deserialize(&blockhash_bytes)?;
     let tx = Transaction::new_signed_with_payer(
-        &[compute_budget_ix, buy_ix],
+        &instructions,
         Some(&buyer),
         &[signer],
         recent_blockhash,
@@ -69,3 +80,78 @@ pub async fn buy(
 
     Ok(signature)
 }
+
+/// Spends one amount of base tokens on a single buy and splits the currency
+/// across `recipients` by share, paying each wallet's currency ATA.
+pub async fn batch_buy(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Pubkey,
+    amount: f64,                  // Amount in USDC
+    recipients: &[(Pubkey, u32)], // (wallet, share)
+) -> Result<Signature> {
+    if recipients.is_empty() || recipients.len() > MAX_BATCH_RECIPIENTS {
+        return Err(anyhow!("A batch buy pays 1 to {} recipients", MAX_BATCH_RECIPIENTS));
+    }
+
+    let buyer = signer.pubkey();
+    let (pool, pool_pda) = get_currency_pool(client, &mint, Some(&base_mint)).await?;
+    let curve_table = pool.is_table_backed().then_some(pool.curve_table);
+    let target_token_program = get_token_program(client, &mint).await?;
+    let base_token_program = get_token_program(client, &base_mint).await?;
+
+    let buyer_base_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&buyer, &base_mint, &base_token_program);
+
+    // Create recipient ATAs
+    let mut recipient_atas = vec![];
+    for (wallet, share) in recipients {
+        let (ata, ata_sig) = create_ata(client, signer, &mint, wallet, Some(&target_token_program)).await?;
+        if ata_sig != Signature::default() {
+            println!("Created recipient ATA: {}. Signature: {}", ata, ata_sig);
+        }
+        recipient_atas.push((ata, *share));
+    }
+
+    let in_amount = (amount * 10f64.powi(DECIMAL_PLACES as i32)) as u64;
+    let min_amount_out = 0; // Allow any output amount for simplicity
+
+    // Each transfer to a recipient costs extra compute on top of the buy
+    let units = 100_000 + 10_000 * recipients.len() as u32;
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(units)];
+    if pool.is_launch_window(client.get_block_time(client.get_slot().await?).await?) &&
+        get_wallet_purchases_account(client, &pool_pda, &buyer).await?.is_none() {
+        instructions.push(build_initialize_wallet_purchases_ix(buyer, pool_pda, buyer));
+    }
+
+    let batch_buy_ix = build_batch_buy_ix(
+        buyer,
+        pool_pda,
+        mint,
+        target_token_program,
+        base_mint,
+        base_token_program,
+        in_amount,
+        min_amount_out,
+        buyer_base_ata,
+        &recipient_atas,
+        curve_table,
+    );
+    instructions.push(batch_buy_ix);
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&buyer),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to batch buy tokens: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
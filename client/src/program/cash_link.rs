@@ -0,0 +1,210 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    signature::{Keypair, Signature, Signer},
+    signer::keypair::keypair_from_seed,
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::consts::*;
+use crate::utils::*;
+
+/// A fresh one-time key for a cash link. Its secret is the link itself.
+pub fn generate_cash_link() -> Keypair {
+    Keypair::new()
+}
+
+/// Encodes a cash link key's secret as a shareable URL.
+pub fn encode_cash_link(link: &Keypair) -> String {
+    let secret = &link.to_bytes()[..32];
+    format!("{}{}", CASH_LINK_URL, base64::encode_config(secret, base64::URL_SAFE_NO_PAD))
+}
+
+/// Recovers a cash link key from a URL made by `encode_cash_link`, or from
+/// just the encoded secret.
+pub fn decode_cash_link(link: &str) -> Result<Keypair> {
+    let encoded = link.trim().rsplit('#').next().unwrap_or_default();
+    let secret = base64::decode_config(encoded, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| anyhow!("Invalid cash link"))?;
+    if secret.len() != 32 {
+        return Err(anyhow!("Invalid cash link"));
+    }
+    keypair_from_seed(&secret).map_err(|e| anyhow!("Invalid cash link: {}", e))
+}
+
+/// Locks currency in a new cash link and returns the link to share. With a
+/// `base_mint`, `amount` is spent in that base token buying the currency from
+/// its pool; otherwise `amount` is currency the signer already holds.
+pub async fn create_cash_link(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Buy from this pool instead of depositing
+    amount: f64,
+    expires_at: i64,           // Unix timestamp
+) -> Result<(Signature, String)> {
+    let creator = signer.pubkey();
+    let link = generate_cash_link();
+    let token_program = get_token_program(client, &mint).await?;
+
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(150_000)];
+    match base_mint {
+        Some(base_mint) => {
+            let (pool, pool_pda) = get_currency_pool(client, &mint, Some(&base_mint)).await?;
+            let curve_table = pool.is_table_backed().then_some(pool.curve_table);
+            let base_token_program = get_token_program(client, &base_mint).await?;
+            let creator_base_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&creator, &base_mint, &base_token_program);
+
+            if pool.is_launch_window(client.get_block_time(client.get_slot().await?).await?) &&
+                get_wallet_purchases_account(client, &pool_pda, &creator).await?.is_none() {
+                instructions.push(build_initialize_wallet_purchases_ix(creator, pool_pda, creator));
+            }
+
+            let in_amount = (amount * 10f64.powi(DECIMAL_PLACES as i32)) as u64;
+            instructions.push(build_buy_cash_link_ix(
+                creator,
+                link.pubkey(),
+                pool_pda,
+                mint,
+                token_program,
+                base_mint,
+                base_token_program,
+                in_amount,
+                0,
+                expires_at,
+                creator_base_ata,
+                curve_table,
+            ));
+        }
+        None => {
+            let (currency_pda, _) = find_currency_pda(&mint);
+            let (currency, _) = get_currency_account(client, &currency_pda).await?;
+            let creator_target_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&creator, &mint, &token_program);
+
+            let amount = (amount * 10f64.powi(currency.decimals as i32)) as u64;
+            instructions.push(build_create_cash_link_ix(
+                creator,
+                link.pubkey(),
+                mint,
+                token_program,
+                amount,
+                expires_at,
+                creator_target_ata,
+            ));
+        }
+    }
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&creator),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to create cash link: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok((signature, encode_cash_link(&link)))
+}
+
+/// Claims a cash link into the signer's currency ATA. The signer only pays
+/// for the transaction; the link's own key authorizes the claim.
+pub async fn claim_cash_link(
+    client: &RpcClient,
+    signer: &Keypair,
+    link: &str,
+) -> Result<Signature> {
+    let claimer = signer.pubkey();
+    let link = decode_cash_link(link)?;
+    let cash_link = get_cash_link_account(client, &link.pubkey()).await?
+        .ok_or_else(|| anyhow!("Cash link has already been claimed or reclaimed"))?;
+
+    let token_program = get_token_program(client, &cash_link.mint).await?;
+    let (destination, ata_sig) = create_ata(client, signer, &cash_link.mint, &claimer, Some(&token_program)).await?;
+    if ata_sig != Signature::default() {
+        println!("Created mint ATA: {}. Signature: {}", destination, ata_sig);
+    }
+
+    let (cash_link_pda, _) = find_cash_link_pda(&link.pubkey());
+    let message = cash_link_claim_message(&cash_link_pda, cash_link.created_slot, &destination);
+    let link_signature = link.sign_message(&message);
+
+    let signature_ix = build_cash_link_signature_ix(
+        link.pubkey(),
+        cash_link.created_slot,
+        link_signature.into(),
+        destination,
+    );
+    let claim_ix = build_claim_cash_link_ix(
+        link.pubkey(),
+        cash_link.creator,
+        cash_link.mint,
+        token_program,
+        destination,
+    );
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[signature_ix, claim_ix],
+        Some(&claimer),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to claim cash link: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
+
+/// Takes back the currency in an expired, unclaimed cash link the signer
+/// created.
+pub async fn reclaim_cash_link(
+    client: &RpcClient,
+    signer: &Keypair,
+    link_key: Pubkey,
+) -> Result<Signature> {
+    let creator = signer.pubkey();
+    let cash_link = get_cash_link_account(client, &link_key).await?
+        .ok_or_else(|| anyhow!("Cash link has already been claimed or reclaimed"))?;
+
+    let token_program = get_token_program(client, &cash_link.mint).await?;
+    let (creator_target, ata_sig) = create_ata(client, signer, &cash_link.mint, &creator, Some(&token_program)).await?;
+    if ata_sig != Signature::default() {
+        println!("Created mint ATA: {}. Signature: {}", creator_target, ata_sig);
+    }
+
+    let reclaim_ix = build_reclaim_cash_link_ix(
+        creator,
+        link_key,
+        cash_link.mint,
+        token_program,
+        creator_target,
+    );
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[reclaim_ix],
+        Some(&creator),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to reclaim cash link: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
@@ -0,0 +1,98 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::utils::*;
+use rand::Rng;
+
+/// Builds table entries from step prices (raw `UnsignedNumeric` values),
+/// filling in cumulative values the way the program checks them. The last
+/// price marks the end of the curve, so `prices.len() - 1` steps are sold.
+pub fn curve_table_entries(step_size: u64, prices: &[u128]) -> Result<Vec<CurveTableEntry>> {
+    let mut entries = Vec::with_capacity(prices.len());
+    let mut cumulative_value: u128 = 0;
+
+    for (i, &price) in prices.iter().enumerate() {
+        if i > 0 {
+            cumulative_value = prices[i - 1]
+                .checked_mul(step_size.into())
+                .and_then(|v| v.checked_add(cumulative_value))
+                .ok_or_else(|| anyhow!("Curve table value overflows at step {}", i))?;
+        }
+        entries.push(CurveTableEntry::new(price, cumulative_value));
+    }
+
+    if !CurveTable::check_entries(step_size, None, &entries) {
+        return Err(anyhow!("Curve table prices must be positive and non-decreasing"));
+    }
+
+    Ok(entries)
+}
+
+/// Creates a curve table from step prices, uploads it in chunks and
+/// finalizes it so it can back a pool.
+pub async fn upload_curve_table(
+    client: &RpcClient,
+    signer: &Keypair,
+    step_size: u64,
+    prices: &[u128],
+) -> Result<(Signature, Pubkey)> {
+    let entries = curve_table_entries(step_size, prices)?;
+    if entries.len() < 2 {
+        return Err(anyhow!("Curve table needs at least two prices"));
+    }
+
+    let authority = signer.pubkey();
+    let seed: [u8; 32] = rand::thread_rng().gen(); // Random seed
+    let (curve_table_pda, _) = find_curve_table_pda(&authority, &seed);
+
+    let init_ix = build_initialize_curve_table_ix(
+        authority,
+        seed,
+        step_size,
+        entries.len() as u64 - 1,
+    );
+
+    println!("Initializing curve table with PDA: {}", curve_table_pda);
+
+    send_curve_table_ix(client, signer, init_ix).await?;
+
+    for (i, chunk) in entries.chunks(CURVE_TABLE_CHUNK_LEN).enumerate() {
+        let write_ix = build_write_curve_table_ix(authority, curve_table_pda, chunk.to_vec());
+        send_curve_table_ix(client, signer, write_ix).await
+            .map_err(|e| anyhow!("Failed to write curve table chunk {}: {}", i, e))?;
+    }
+
+    let finalize_ix = build_finalize_curve_table_ix(authority, curve_table_pda);
+    let signature = send_curve_table_ix(client, signer, finalize_ix).await?;
+
+    Ok((signature, curve_table_pda))
+}
+
+async fn send_curve_table_ix(
+    client: &RpcClient,
+    signer: &Keypair,
+    ix: Instruction,
+) -> Result<Signature> {
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&signer.pubkey()),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to upload curve table: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
@@ -0,0 +1,314 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::consts::*;
+use crate::program::append_trade_hook;
+use crate::utils::*;
+
+/// Opens a DCA plan that buys `amount_per_fill` base tokens worth of a
+/// currency every `interval` seconds, funding it with `deposit` base tokens.
+/// Fills that would pay more than `max_price` on average fail. Returns the
+/// plan's address.
+#[allow(clippy::too_many_arguments)]
+pub async fn create_dca_plan(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+    amount_per_fill: f64,
+    max_price: f64,            // Base tokens per whole token
+    interval: i64,             // Seconds between fills
+    first_fill_at: i64,        // Unix timestamp, 0 to start right away
+    deposit: f64,
+) -> Result<(Signature, Pubkey)> {
+    let owner = signer.pubkey();
+    let (currency_pda, _) = find_currency_pda(&mint);
+    let (currency, _) = get_currency_account(client, &currency_pda).await?;
+    let (pool, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+    let target_token_program = get_token_program(client, &mint).await?;
+    let base_token_program = get_token_program(client, &pool.mint_b).await?;
+
+    // Make sure the owner can receive fills
+    let (_target_ata, target_ata_sig) = create_ata(client, signer, &mint, &owner, Some(&target_token_program)).await?;
+    if target_ata_sig != Signature::default() {
+        println!("Created owner target ATA. Signature: {}", target_ata_sig);
+    }
+    let owner_base = spl_associated_token_account::get_associated_token_address_with_program_id(&owner, &pool.mint_b, &base_token_program);
+
+    let id: u64 = rand::random();
+    let min_amount_out = (amount_per_fill / max_price * 10f64.powi(currency.decimals as i32)) as u64;
+    let amount_per_fill = (amount_per_fill * 10f64.powi(DECIMAL_PLACES as i32)) as u64;
+    let deposit = (deposit * 10f64.powi(DECIMAL_PLACES as i32)) as u64;
+
+    let mut instructions = vec![build_create_dca_plan_ix(
+        owner,
+        pool_pda,
+        id,
+        pool.mint_b,
+        base_token_program,
+        amount_per_fill,
+        min_amount_out,
+        interval,
+        first_fill_at,
+    )];
+    if deposit > 0 {
+        instructions.push(build_deposit_dca_ix(
+            owner,
+            pool_pda,
+            id,
+            pool.mint_b,
+            base_token_program,
+            owner_base,
+            deposit,
+        ));
+    }
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&owner),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to create DCA plan: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    let (plan_pda, _) = find_dca_plan_pda(&pool_pda, &owner, id);
+    Ok((signature, plan_pda))
+}
+
+/// Adds `amount` base tokens to a DCA plan.
+pub async fn deposit_dca(
+    client: &RpcClient,
+    signer: &Keypair,
+    plan_address: Pubkey,
+    amount: f64,
+) -> Result<Signature> {
+    let amount = (amount * 10f64.powi(DECIMAL_PLACES as i32)) as u64;
+    let (plan, base_mint, base_token_program, owner_base) = load_dca_escrow(client, signer, plan_address).await?;
+
+    let deposit_ix = build_deposit_dca_ix(
+        plan.owner,
+        plan.pool,
+        plan.id,
+        base_mint,
+        base_token_program,
+        owner_base,
+        amount,
+    );
+
+    send_owner_ix(client, signer, deposit_ix, "deposit into DCA plan").await
+}
+
+/// Takes `amount` base tokens back out of a DCA plan, or everything when
+/// `amount` is None.
+pub async fn withdraw_dca(
+    client: &RpcClient,
+    signer: &Keypair,
+    plan_address: Pubkey,
+    amount: Option<f64>,
+) -> Result<Signature> {
+    let amount = amount
+        .map(|amount| (amount * 10f64.powi(DECIMAL_PLACES as i32)) as u64)
+        .unwrap_or(0);
+    let (plan, base_mint, base_token_program, owner_base) = load_dca_escrow(client, signer, plan_address).await?;
+
+    let withdraw_ix = build_withdraw_dca_ix(
+        plan.owner,
+        plan.pool,
+        plan.id,
+        base_mint,
+        base_token_program,
+        owner_base,
+        amount,
+    );
+
+    send_owner_ix(client, signer, withdraw_ix, "withdraw from DCA plan").await
+}
+
+/// Closes a DCA plan, refunding its remaining escrow and rent to the owner.
+pub async fn close_dca_plan(
+    client: &RpcClient,
+    signer: &Keypair,
+    plan_address: Pubkey,
+) -> Result<Signature> {
+    let (plan, base_mint, base_token_program, owner_base) = load_dca_escrow(client, signer, plan_address).await?;
+
+    let close_ix = build_close_dca_plan_ix(
+        plan.owner,
+        plan.pool,
+        plan.id,
+        base_mint,
+        base_token_program,
+        owner_base,
+    );
+
+    send_owner_ix(client, signer, close_ix, "close DCA plan").await
+}
+
+/// Runs the next fill of a due DCA plan. Anyone may execute.
+pub async fn execute_dca(
+    client: &RpcClient,
+    signer: &Keypair,
+    plan_address: Pubkey,
+) -> Result<Signature> {
+    let executor = signer.pubkey();
+    let plan = get_dca_plan_account(client, &plan_address).await?
+        .ok_or_else(|| anyhow!("DCA plan {} is closed", plan_address))?;
+    let (pool, _) = get_pool_account(client, &plan.pool).await?;
+    let curve_table = pool.is_table_backed().then_some(pool.curve_table);
+    let target_token_program = get_token_program(client, &pool.mint_a).await?;
+    let base_token_program = get_token_program(client, &pool.mint_b).await?;
+
+    let owner_target = spl_associated_token_account::get_associated_token_address_with_program_id(&plan.owner, &pool.mint_a, &target_token_program);
+
+    // The plan's escrow buys as its own wallet, so launch window buys need
+    // a purchases account for it
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(150_000 + pool.hook_compute_units)];
+    if pool.is_launch_window(client.get_block_time(client.get_slot().await?).await?) &&
+        get_wallet_purchases_account(client, &plan.pool, &plan.vault).await?.is_none() {
+        instructions.push(build_initialize_wallet_purchases_ix(executor, plan.pool, plan.vault));
+    }
+
+    let mut execute_ix = build_execute_dca_ix(
+        executor,
+        plan.owner,
+        plan.pool,
+        plan.id,
+        pool.mint_a,
+        target_token_program,
+        pool.mint_b,
+        base_token_program,
+        owner_target,
+        curve_table,
+    );
+    append_trade_hook(&mut execute_ix, &pool, &plan.pool);
+    instructions.push(execute_ix);
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&executor),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to execute DCA plan: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
+
+/// One pass of the DCA scheduler over a pool: runs a fill for every funded
+/// plan that is due by the cluster's clock. Plans that fail are reported and
+/// left for the next pass.
+pub async fn crank_dca_plans(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+) -> Result<Vec<Signature>> {
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+    let plans = get_dca_plans(client, &pool_pda).await?;
+
+    // Fills are gated on the on-chain clock, which can drift from ours on a
+    // local validator
+    let now = client.get_block_time(client.get_slot().await?).await?;
+
+    let mut signatures = Vec::new();
+    for (plan, address) in plans {
+        if !plan.is_due(now) {
+            continue;
+        }
+        let balance: u64 = client.get_token_account_balance(&plan.vault).await?.amount.parse()?;
+        if balance == 0 {
+            continue;
+        }
+
+        match execute_dca(client, signer, address).await {
+            Ok(signature) => signatures.push(signature),
+            Err(e) => println!("Skipped DCA plan {}: {}", address, e),
+        }
+    }
+
+    Ok(signatures)
+}
+
+/// Keeps cranking a pool's DCA plans every `poll_interval` seconds until the
+/// process is stopped.
+pub async fn run_dca_scheduler(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+    poll_interval: u64,
+) -> Result<()> {
+    loop {
+        match crank_dca_plans(client, signer, mint, base_mint).await {
+            Ok(signatures) => {
+                for signature in &signatures {
+                    println!("DCA fill transaction successful. Signature: {}", signature);
+                }
+            }
+            Err(e) => println!("DCA scheduler pass failed: {}", e),
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(poll_interval)).await;
+    }
+}
+
+// Looks up what an owner needs to move base in or out of a plan's escrow.
+async fn load_dca_escrow(
+    client: &RpcClient,
+    signer: &Keypair,
+    plan_address: Pubkey,
+) -> Result<(DcaPlan, Pubkey, Pubkey, Pubkey)> {
+    let plan = get_dca_plan_account(client, &plan_address).await?
+        .ok_or_else(|| anyhow!("DCA plan {} is closed", plan_address))?;
+    if plan.owner != signer.pubkey() {
+        return Err(anyhow!("DCA plan {} belongs to {}", plan_address, plan.owner));
+    }
+
+    let (pool, _) = get_pool_account(client, &plan.pool).await?;
+    let base_token_program = get_token_program(client, &pool.mint_b).await?;
+    let owner_base = spl_associated_token_account::get_associated_token_address_with_program_id(&plan.owner, &pool.mint_b, &base_token_program);
+
+    Ok((plan, pool.mint_b, base_token_program, owner_base))
+}
+
+async fn send_owner_ix(
+    client: &RpcClient,
+    signer: &Keypair,
+    ix: Instruction,
+    action: &str,
+) -> Result<Signature> {
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&signer.pubkey()),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to {}: {}", action, e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
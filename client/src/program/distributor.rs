@@ -0,0 +1,197 @@
+use std::path::Path;
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::utils::*;
+
+/// Reads a recipient list from a CSV of wallet,amount lines, with amounts in
+/// whole tokens. Blank lines and a `wallet,amount` header are skipped. The
+/// order of the file is the order of the tree, so recipients must claim with
+/// the same file the distributor was created from.
+pub fn read_distribution_csv(path: &Path, decimals: u8) -> Result<Vec<(Pubkey, u64)>> {
+    std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter(|line| !line.to_ascii_lowercase().starts_with("wallet,"))
+        .map(|line| {
+            let (wallet, amount) = line.split_once(',')
+                .ok_or_else(|| anyhow!("Expected wallet,amount: '{}'", line))?;
+            let wallet = wallet.trim().parse::<Pubkey>()
+                .map_err(|_| anyhow!("Invalid wallet: '{}'", wallet))?;
+            let amount = amount.trim().parse::<f64>()
+                .map_err(|_| anyhow!("Invalid amount: '{}'", amount))?;
+            let amount = (amount * 10f64.powi(decimals as i32)).round() as u64;
+            Ok((wallet, amount))
+        })
+        .collect()
+}
+
+/// Merkle tree over a recipient list, in the order given.
+pub fn distribution_tree(recipients: &[(Pubkey, u64)]) -> MerkleTree {
+    MerkleTree::new(recipients.iter()
+        .enumerate()
+        .map(|(index, (wallet, amount))| distributor_leaf(index as u64, wallet, *amount))
+        .collect())
+}
+
+/// Creates a distributor airdropping a currency to `recipients` and funds it
+/// from the signer's ATA. Whatever is unclaimed by `expires_at` can be
+/// clawed back. Returns the distributor's address.
+pub async fn create_distributor(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    recipients: &[(Pubkey, u64)], // Amounts in quarks
+    expires_at: i64,              // Unix timestamp
+) -> Result<(Signature, Pubkey)> {
+    if recipients.is_empty() || recipients.len() as u64 > MAX_DISTRIBUTOR_RECIPIENTS {
+        return Err(anyhow!("A distributor needs between 1 and {} recipients", MAX_DISTRIBUTOR_RECIPIENTS));
+    }
+
+    let authority = signer.pubkey();
+    let token_program = get_token_program(client, &mint).await?;
+    let authority_source = spl_associated_token_account::get_associated_token_address_with_program_id(&authority, &mint, &token_program);
+
+    let total_amount = recipients.iter()
+        .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+        .ok_or_else(|| anyhow!("Distribution total overflows"))?;
+
+    let id: u64 = rand::random();
+    let create_ix = build_create_distributor_ix(
+        authority,
+        id,
+        mint,
+        token_program,
+        authority_source,
+        distribution_tree(recipients).root(),
+        total_amount,
+        recipients.len() as u64,
+        expires_at,
+    );
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix],
+        Some(&authority),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to create distributor: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    let (distributor_pda, _) = find_distributor_pda(&mint, &authority, id);
+    Ok((signature, distributor_pda))
+}
+
+/// Claims the signer's entry in a distributor, given the recipient list it
+/// was created from. A wallet listed more than once claims its first
+/// unclaimed entry.
+pub async fn claim_distribution(
+    client: &RpcClient,
+    signer: &Keypair,
+    distributor_address: Pubkey,
+    recipients: &[(Pubkey, u64)],
+) -> Result<Signature> {
+    let claimant = signer.pubkey();
+    let (distributor, claims) = get_distributor_account(client, &distributor_address).await?
+        .ok_or_else(|| anyhow!("Distributor {} is closed", distributor_address))?;
+
+    let tree = distribution_tree(recipients);
+    if tree.root() != distributor.root {
+        return Err(anyhow!("Recipient list does not match distributor {}", distributor_address));
+    }
+
+    let (index, amount) = recipients.iter()
+        .enumerate()
+        .find(|(index, (wallet, _))| wallet.eq(&claimant) && !Distributor::is_claimed(&claims, *index as u64))
+        .map(|(index, (_, amount))| (index, *amount))
+        .ok_or_else(|| anyhow!("Nothing left to claim for {}", claimant))?;
+    let proof = tree.proof(index)
+        .ok_or_else(|| anyhow!("Failed to build distributor proof"))?;
+
+    let token_program = get_token_program(client, &distributor.mint).await?;
+    let (claimant_ata, ata_sig) = create_ata(client, signer, &distributor.mint, &claimant, Some(&token_program)).await?;
+    if ata_sig != Signature::default() {
+        println!("Created claimant ATA. Signature: {}", ata_sig);
+    }
+
+    let claim_ix = build_claim_distribution_ix(
+        claimant,
+        distributor_address,
+        distributor.mint,
+        token_program,
+        claimant_ata,
+        index as u64,
+        amount,
+        proof,
+    );
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[claim_ix],
+        Some(&claimant),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to claim distribution: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
+
+/// Returns an expired distributor's unclaimed tokens to the signer's ATA and
+/// closes it. Must be signed by the distributor's authority.
+pub async fn clawback_distributor(
+    client: &RpcClient,
+    signer: &Keypair,
+    distributor_address: Pubkey,
+) -> Result<Signature> {
+    let authority = signer.pubkey();
+    let (distributor, _) = get_distributor_account(client, &distributor_address).await?
+        .ok_or_else(|| anyhow!("Distributor {} is closed", distributor_address))?;
+
+    let token_program = get_token_program(client, &distributor.mint).await?;
+    let (authority_ata, ata_sig) = create_ata(client, signer, &distributor.mint, &authority, Some(&token_program)).await?;
+    if ata_sig != Signature::default() {
+        println!("Created authority ATA. Signature: {}", ata_sig);
+    }
+
+    let clawback_ix = build_clawback_distributor_ix(
+        authority,
+        distributor_address,
+        distributor.mint,
+        token_program,
+        authority_ata,
+    );
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[clawback_ix],
+        Some(&authority),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to claw back distributor: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
@@ -0,0 +1,102 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::utils::*;
+
+pub async fn initialize_fee_schedule(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+) -> Result<Signature> {
+    let authority = signer.pubkey();
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+
+    let ix = build_initialize_fee_schedule_ix(authority, pool_pda);
+
+    send_fee_ix(client, signer, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to initialize fee schedule: {}", e))
+}
+
+pub async fn set_fee_exemption(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+    wallet: Pubkey,
+    exempt: bool,
+) -> Result<Signature> {
+    let authority = signer.pubkey();
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+
+    let ix = build_set_fee_exemption_ix(authority, pool_pda, wallet, exempt);
+
+    send_fee_ix(client, signer, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to set fee exemption: {}", e))
+}
+
+pub async fn set_fee_tiers(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+    tiers: Vec<FeeTier>,
+) -> Result<Signature> {
+    let authority = signer.pubkey();
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+
+    let ix = build_set_fee_tiers_ix(authority, pool_pda, tiers)
+        .map_err(|_| anyhow!("At most {} fee tiers are supported", MAX_FEE_TIERS))?;
+
+    send_fee_ix(client, signer, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to set fee tiers: {}", e))
+}
+
+pub async fn initialize_trader_volume(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+    trader: Pubkey,
+) -> Result<Signature> {
+    let payer = signer.pubkey();
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+
+    let ix = build_initialize_trader_volume_ix(payer, pool_pda, trader);
+
+    send_fee_ix(client, signer, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to initialize trader volume: {}", e))
+}
+
+async fn send_fee_ix(
+    client: &RpcClient,
+    signer: &Keypair,
+    ix: Instruction,
+) -> Result<Signature> {
+    let payer = signer.pubkey();
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx).await?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
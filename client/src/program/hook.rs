@@ -0,0 +1,54 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::utils::*;
+
+/// Registers a program the pool CPIs after every buy and sell, or clears the
+/// pool's hook when `hook_program` is None. A required hook must be included
+/// in every trade; an optional one may be left out by traders.
+pub async fn set_trade_hook(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>,    // Primary pool when None
+    hook_program: Option<Pubkey>,
+    compute_units: u32,           // Most compute the hook may use per trade
+    required: bool,
+) -> Result<Signature> {
+    let authority = signer.pubkey();
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+
+    let set_trade_hook_ix = build_set_trade_hook_ix(authority, pool_pda, hook_program, compute_units, required);
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_trade_hook_ix],
+        Some(&authority),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to set trade hook: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
+
+/// Runs the pool's trade hook, if it has one, as part of a trade. Must be
+/// appended right after the trade's other optional accounts. Hooks that need
+/// accounts of their own have to be added with `trade_hook_metas` instead.
+pub fn append_trade_hook(ix: &mut Instruction, pool: &LiquidityPool, pool_address: &Pubkey) {
+    if pool.has_trade_hook() {
+        ix.accounts.extend(trade_hook_metas(pool_address, pool.hook_program, vec![]));
+    }
+}
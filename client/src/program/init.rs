@@ -1,6 +1,7 @@
 use anyhow::{Result, anyhow};
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
     signature::{Keypair, Signature, Signer},
     transaction::Transaction,
     pubkey::Pubkey,
@@ -12,33 +13,45 @@ use crate::consts::*;
 use crate::utils::*;
 use rand::Rng;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn initialize(
     client: &RpcClient,
     signer: &Keypair,
     name: String,
     symbol: String,
+    decimals: u8,
+    max_supply: u64,   // Total currency supply in whole tokens
+    token_2022: bool,  // Mint under Token-2022 with metadata on the mint instead of Metaplex
     base_mint: Pubkey, // USDC mint
+    curve: CurveParams,
+    allowed_extensions: u8,      // ALLOW_* bits for risky Token-2022 base mint extensions
+    curve_table: Option<Pubkey>, // Required for CurveKind::Table
+    initial_buy: f64,            // Amount in USDC to buy when the pool is created, 0 for none
+    vesting: Option<VestingSchedule>, // Vests the initial buy instead of paying it out
 ) -> Result<(Signature, Signature, Pubkey, Pubkey, Pubkey)> {
-    if name.len() > MAX_NAME_LEN {
-        return Err(anyhow!("Name exceeds {} characters", MAX_NAME_LEN));
-    }
-    if symbol.len() > MAX_SYMBOL_LEN {
-        return Err(anyhow!("Symbol exceeds {} characters", MAX_SYMBOL_LEN));
-    }
+    let name = CurrencyName::new(&name)?;
+    let symbol = CurrencySymbol::new(&symbol)?;
 
     let authority = signer.pubkey();
+    let token_program = if token_2022 { spl_token_2022::id() } else { spl_token::id() };
     let seed: [u8; 32] = rand::thread_rng().gen(); // Random seed
 
     // Initialize currency
     let (mint_pda, _) = find_mint_pda(&authority, &name, &seed);
     let (currency_pda, _) = find_currency_pda(&mint_pda);
 
+    let registry_page = get_next_registry_page(client).await?;
+
     let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(200_000);
     let create_currency_ix = build_initialize_currency_ix(
         authority,
         name,
         symbol,
         seed,
+        decimals,
+        max_supply,
+        token_program,
+        registry_page,
     );
 
     let blockhash_bytes = get_latest_blockhash(client).await?;
@@ -60,33 +73,46 @@ pub async fn initialize(
     println!("Currency initialized with signature: {}", currency_signature);
 
     // Create fee ATAs
-    let fee_mint_ata = spl_associated_token_account::get_associated_token_address(&authority, &mint_pda);
-    let fee_base_ata = spl_associated_token_account::get_associated_token_address(&authority, &base_mint);
+    let fee_mint_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&authority, &mint_pda, &token_program);
+    let base_token_program = get_token_program(client, &base_mint).await?;
+    let fee_base_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&authority, &base_mint, &base_token_program);
 
-    let (_mint_ata, mint_ata_sig) = create_ata(client, signer, &mint_pda, &authority, None).await?;
+    let (_mint_ata, mint_ata_sig) = create_ata(client, signer, &mint_pda, &authority, Some(&token_program)).await?;
     if mint_ata_sig != Signature::default() {
         println!("Created fee mint ATA: {}. Signature: {}", fee_mint_ata, mint_ata_sig);
     }
 
-    let (_base_ata, base_ata_sig) = create_ata(client, signer, &base_mint, &authority, None).await?;
+    let (_base_ata, base_ata_sig) = create_ata(client, signer, &base_mint, &authority, Some(&base_token_program)).await?;
     if base_ata_sig != Signature::default() {
         println!("Created fee base ATA: {}. Signature: {}", fee_base_ata, base_ata_sig);
     }
 
-    // Initialize pool
+    // Initialize pool, buying first if asked to
     let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let initial_buy = initial_buy_for(initial_buy, fee_mint_ata, fee_base_ata, vesting);
     let pool_ix = build_initialize_pool_ix(
         authority,
         currency_pda,
         mint_pda,
+        token_program,
         base_mint,
-        SELL_FEE_BPS,
+        base_token_program,
+        PoolOptions {
+            sell_fee: SELL_FEE_BPS,
+            burn_policy: FeeBurnPolicy::PermissionlessAfterCooldown,
+            burn_cooldown: BURN_COOLDOWN_SECS,
+            curve,
+            allowed_extensions,
+            curve_table,
+            initial_buy,
+            ..Default::default()
+        },
     );
 
     let blockhash_bytes = get_latest_blockhash(client).await?;
     let recent_blockhash = deserialize(&blockhash_bytes)?;
     let pool_tx = Transaction::new_signed_with_payer(
-        &[compute_budget_ix.clone(), pool_ix],
+        &[pool_compute_budget_ix(initial_buy), pool_ix],
         Some(&authority),
         &[signer],
         recent_blockhash,
@@ -106,6 +132,7 @@ pub async fn initialize(
         authority,
         currency_pda,
         mint_pda,
+        token_program,
     );
 
     let blockhash_bytes = get_latest_blockhash(client).await?;
@@ -128,3 +155,113 @@ pub async fn initialize(
 
     Ok((currency_signature, pool_signature, mint_pda, currency_pda, pool_pda))
 }
+
+/// Adds a pool against another base mint to an existing currency. The pool's
+/// curve must fit in the supply the currency has not yet allocated.
+#[allow(clippy::too_many_arguments)]
+pub async fn add_pool(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Pubkey,
+    curve: CurveParams,
+    allowed_extensions: u8,      // ALLOW_* bits for risky Token-2022 base mint extensions
+    curve_table: Option<Pubkey>, // Required for CurveKind::Table
+    initial_buy: f64,            // Amount in USDC to buy when the pool is created, 0 for none
+    vesting: Option<VestingSchedule>, // Vests the initial buy instead of paying it out
+) -> Result<(Signature, Pubkey)> {
+    let authority = signer.pubkey();
+    let (currency_pda, _) = find_currency_pda(&mint);
+    let (currency, _) = get_currency_account(client, &currency_pda).await?;
+    if curve.max_supply > currency.supply_unallocated() {
+        return Err(anyhow!(
+            "Curve max supply exceeds the {} tokens left unallocated",
+            currency.supply_unallocated()
+        ));
+    }
+
+    let (primary_pool, _) = get_currency_pool(client, &mint, None).await?;
+    if primary_pool.mint_b == base_mint {
+        return Err(anyhow!("The currency's first pool already uses base mint {}", base_mint));
+    }
+
+    let token_program = get_token_program(client, &mint).await?;
+    let base_token_program = get_token_program(client, &base_mint).await?;
+
+    // Create fee ATA
+    let fee_base_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&authority, &base_mint, &base_token_program);
+    let (_base_ata, base_ata_sig) = create_ata(client, signer, &base_mint, &authority, Some(&base_token_program)).await?;
+    if base_ata_sig != Signature::default() {
+        println!("Created fee base ATA: {}. Signature: {}", fee_base_ata, base_ata_sig);
+    }
+
+    // The initial buy is paid out to the authority's currency ATA unless it vests
+    let mint_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&authority, &mint, &token_program);
+    if initial_buy > 0.0 && vesting.is_none() {
+        let (_mint_ata, mint_ata_sig) = create_ata(client, signer, &mint, &authority, Some(&token_program)).await?;
+        if mint_ata_sig != Signature::default() {
+            println!("Created mint ATA: {}. Signature: {}", mint_ata, mint_ata_sig);
+        }
+    }
+
+    let (pool_pda, _) = find_additional_pool_pda(&currency_pda, &base_mint);
+    let initial_buy = initial_buy_for(initial_buy, mint_ata, fee_base_ata, vesting);
+    let compute_budget_ix = pool_compute_budget_ix(initial_buy);
+    let pool_ix = build_initialize_additional_pool_ix(
+        authority,
+        currency_pda,
+        mint,
+        token_program,
+        base_mint,
+        base_token_program,
+        PoolOptions {
+            sell_fee: SELL_FEE_BPS,
+            burn_policy: FeeBurnPolicy::PermissionlessAfterCooldown,
+            burn_cooldown: BURN_COOLDOWN_SECS,
+            curve,
+            allowed_extensions,
+            curve_table,
+            initial_buy,
+            ..Default::default()
+        },
+    );
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let pool_tx = Transaction::new_signed_with_payer(
+        &[compute_budget_ix, pool_ix],
+        Some(&authority),
+        &[signer],
+        recent_blockhash,
+    );
+
+    println!("Initializing pool with PDA: {}", pool_pda);
+
+    let pool_signature_bytes = send_and_confirm_transaction(client, &pool_tx)
+        .await
+        .map_err(|e| anyhow!("Failed to initialize pool: {}", e))?;
+    let pool_signature: Signature = deserialize(&pool_signature_bytes)?;
+
+    Ok((pool_signature, pool_pda))
+}
+
+fn initial_buy_for(
+    amount: f64,
+    authority_target: Pubkey,
+    authority_base: Pubkey,
+    vesting: Option<VestingSchedule>,
+) -> Option<InitialBuy> {
+    let amount = (amount * 10f64.powi(DECIMAL_PLACES as i32)) as u64;
+    (amount > 0).then_some(InitialBuy {
+        amount,
+        authority_target,
+        authority_base,
+        vesting,
+    })
+}
+
+// Creating a pool and buying from it in one instruction needs more compute
+fn pool_compute_budget_ix(initial_buy: Option<InitialBuy>) -> Instruction {
+    let units = if initial_buy.is_some() { 400_000 } else { 200_000 };
+    ComputeBudgetInstruction::set_compute_unit_limit(units)
+}
@@ -0,0 +1,110 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::consts::*;
+use crate::utils::*;
+
+/// Merkle tree over a presale allowlist, in the order given.
+pub fn presale_tree(allowlist: &[Pubkey]) -> MerkleTree {
+    MerkleTree::new(allowlist.iter().map(presale_leaf).collect())
+}
+
+/// Schedules a pool's launch. Until `launch_at`, only wallets on the
+/// allowlist may buy, each spending at most `presale_cap` base tokens. An
+/// empty allowlist means nobody can buy before launch. For `launch_window`
+/// seconds after launch, each wallet may spend at most `launch_cap`.
+#[allow(clippy::too_many_arguments)]
+pub async fn set_launch(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+    launch_at: i64,            // Unix timestamp
+    presale_cap: f64,          // Amount in USDC
+    allowlist: &[Pubkey],
+    launch_window: u64,        // Seconds
+    launch_cap: f64,           // Amount in USDC
+) -> Result<Signature> {
+    let authority = signer.pubkey();
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+
+    let presale_cap = (presale_cap * 10f64.powi(DECIMAL_PLACES as i32)) as u64;
+    let launch_cap = (launch_cap * 10f64.powi(DECIMAL_PLACES as i32)) as u64;
+    let presale_root = presale_tree(allowlist).root();
+
+    let set_launch_ix = build_set_launch_ix(
+        authority,
+        pool_pda,
+        mint,
+        LaunchSchedule {
+            launch_at,
+            presale_cap,
+            presale_root,
+            launch_window,
+            launch_cap,
+        },
+    );
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_launch_ix],
+        Some(&authority),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to set launch: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
+
+/// Proves the signer is on a pool's presale allowlist so it may buy before
+/// launch. The allowlist must match the one the launch was set with.
+pub async fn join_presale(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+    allowlist: &[Pubkey],
+) -> Result<Signature> {
+    let wallet = signer.pubkey();
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+
+    let index = allowlist.iter()
+        .position(|key| key.eq(&wallet))
+        .ok_or_else(|| anyhow!("Wallet {} is not on the allowlist", wallet))?;
+    let proof = presale_tree(allowlist).proof(index)
+        .ok_or_else(|| anyhow!("Failed to build presale proof"))?;
+
+    let mut instructions = vec![];
+    if get_wallet_purchases_account(client, &pool_pda, &wallet).await?.is_none() {
+        instructions.push(build_initialize_wallet_purchases_ix(wallet, pool_pda, wallet));
+    }
+    instructions.push(build_join_presale_ix(wallet, pool_pda, proof));
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&wallet),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to join presale: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
@@ -0,0 +1,226 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::consts::*;
+use crate::program::{PoolQuoter, append_trade_hook};
+use crate::utils::*;
+
+/// Places a limit order against a pool. Buys spend `amount` base tokens once
+/// the spot price drops to `target_price`; sells sell `amount` currency once
+/// it rises to `target_price`. Returns the order's address.
+#[allow(clippy::too_many_arguments)]
+pub async fn create_limit_order(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+    side: OrderSide,
+    amount: f64,
+    target_price: f64,         // Base tokens per whole token
+    expires_at: i64,           // Unix timestamp
+    bounty: u64,               // Lamports for whoever executes the order
+) -> Result<(Signature, Pubkey)> {
+    let owner = signer.pubkey();
+    let (currency_pda, _) = find_currency_pda(&mint);
+    let (currency, _) = get_currency_account(client, &currency_pda).await?;
+    let (pool, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+    let target_token_program = get_token_program(client, &mint).await?;
+    let base_token_program = get_token_program(client, &pool.mint_b).await?;
+
+    // Make sure the owner can receive the fill, and any escrow refunded
+    let (_target_ata, target_ata_sig) = create_ata(client, signer, &mint, &owner, Some(&target_token_program)).await?;
+    if target_ata_sig != Signature::default() {
+        println!("Created owner target ATA. Signature: {}", target_ata_sig);
+    }
+    let (_base_ata, base_ata_sig) = create_ata(client, signer, &pool.mint_b, &owner, Some(&base_token_program)).await?;
+    if base_ata_sig != Signature::default() {
+        println!("Created owner base ATA. Signature: {}", base_ata_sig);
+    }
+
+    let (escrow_mint, escrow_token_program, decimals) = match side {
+        OrderSide::Buy => (pool.mint_b, base_token_program, DECIMAL_PLACES),
+        OrderSide::Sell => (mint, target_token_program, currency.decimals),
+    };
+    let owner_source = spl_associated_token_account::get_associated_token_address_with_program_id(&owner, &escrow_mint, &escrow_token_program);
+
+    let id: u64 = rand::random();
+    let amount = (amount * 10f64.powi(decimals as i32)) as u64;
+    let target_price = (target_price * 10f64.powi(CURVE_PRICE_DECIMALS as i32)).round() as u64;
+
+    let create_ix = build_create_limit_order_ix(
+        owner,
+        pool_pda,
+        id,
+        side,
+        escrow_mint,
+        escrow_token_program,
+        owner_source,
+        amount,
+        0,
+        target_price,
+        bounty,
+        expires_at,
+    );
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix],
+        Some(&owner),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to create limit order: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    let (order_pda, _) = find_limit_order_pda(&pool_pda, &owner, id);
+    Ok((signature, order_pda))
+}
+
+/// Fills a crossed limit order, collecting its bounty.
+pub async fn execute_limit_order(
+    client: &RpcClient,
+    signer: &Keypair,
+    order_address: Pubkey,
+) -> Result<Signature> {
+    let executor = signer.pubkey();
+    let order = get_limit_order_account(client, &order_address).await?
+        .ok_or_else(|| anyhow!("Limit order {} is no longer open", order_address))?;
+    let (pool, _) = get_pool_account(client, &order.pool).await?;
+    let curve_table = pool.is_table_backed().then_some(pool.curve_table);
+    let target_token_program = get_token_program(client, &pool.mint_a).await?;
+    let base_token_program = get_token_program(client, &pool.mint_b).await?;
+
+    let owner_target = spl_associated_token_account::get_associated_token_address_with_program_id(&order.owner, &pool.mint_a, &target_token_program);
+    let owner_base = spl_associated_token_account::get_associated_token_address_with_program_id(&order.owner, &pool.mint_b, &base_token_program);
+
+    let side = order.side()?;
+    let (owner_source, owner_destination) = match side {
+        OrderSide::Buy => (owner_base, owner_target),
+        OrderSide::Sell => (owner_target, owner_base),
+    };
+
+    let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(150_000 + pool.hook_compute_units);
+    let mut execute_ix = build_execute_limit_order_ix(
+        executor,
+        order.owner,
+        order.pool,
+        order.id,
+        side,
+        pool.mint_a,
+        target_token_program,
+        pool.mint_b,
+        base_token_program,
+        owner_source,
+        owner_destination,
+        curve_table,
+    );
+    append_trade_hook(&mut execute_ix, &pool, &order.pool);
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[compute_budget_ix, execute_ix],
+        Some(&executor),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to execute limit order: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
+
+/// Cancels a limit order, refunding its escrow and bounty to the owner. Only
+/// the owner may cancel an order before it expires.
+pub async fn cancel_limit_order(
+    client: &RpcClient,
+    signer: &Keypair,
+    order_address: Pubkey,
+) -> Result<Signature> {
+    let authority = signer.pubkey();
+    let order = get_limit_order_account(client, &order_address).await?
+        .ok_or_else(|| anyhow!("Limit order {} is no longer open", order_address))?;
+    let (pool, _) = get_pool_account(client, &order.pool).await?;
+
+    let escrow_mint = match order.side()? {
+        OrderSide::Buy => pool.mint_b,
+        OrderSide::Sell => pool.mint_a,
+    };
+    let escrow_token_program = get_token_program(client, &escrow_mint).await?;
+    let owner_source = spl_associated_token_account::get_associated_token_address_with_program_id(&order.owner, &escrow_mint, &escrow_token_program);
+
+    let cancel_ix = build_cancel_limit_order_ix(
+        authority,
+        order.owner,
+        order.pool,
+        order.id,
+        escrow_mint,
+        escrow_token_program,
+        owner_source,
+    );
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[cancel_ix],
+        Some(&authority),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to cancel limit order: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
+
+/// One pass of the limit order crank over a pool: fills every order the
+/// current spot price crosses and cleans up expired ones. Orders that fail
+/// (e.g. because an earlier fill moved the price back) are reported and
+/// left for the next pass.
+pub async fn crank_limit_orders(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+) -> Result<Vec<Signature>> {
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+    let orders = get_limit_orders(client, &pool_pda).await?;
+    let now = chrono::Utc::now().timestamp();
+
+    let mut signatures = Vec::new();
+    for (order, address) in orders {
+        let result = if order.is_expired(now) {
+            cancel_limit_order(client, signer, address).await
+        } else {
+            let spot_price = PoolQuoter::load(client, mint, base_mint).await?.spot_price()?;
+            if !order.is_crossed(&spot_price)? {
+                continue;
+            }
+            execute_limit_order(client, signer, address).await
+        };
+
+        match result {
+            Ok(signature) => signatures.push(signature),
+            Err(e) => println!("Skipped limit order {}: {}", address, e),
+        }
+    }
+
+    Ok(signatures)
+}
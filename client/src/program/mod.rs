@@ -2,8 +2,36 @@ pub mod init;
 pub mod buy;
 pub mod sell;
 pub mod burn;
+pub mod fees;
+pub mod quote;
+pub mod curve_table;
+pub mod protocol;
+pub mod profile;
+pub mod pause;
+pub mod breaker;
+pub mod launch;
+pub mod vesting;
+pub mod cash_link;
+pub mod limit_order;
+pub mod dca;
+pub mod distributor;
+pub mod hook;
 
 pub use init::*;
 pub use buy::*;
 pub use sell::*;
 pub use burn::*;
+pub use fees::*;
+pub use quote::*;
+pub use curve_table::*;
+pub use protocol::*;
+pub use profile::*;
+pub use pause::*;
+pub use breaker::*;
+pub use launch::*;
+pub use vesting::*;
+pub use cash_link::*;
+pub use limit_order::*;
+pub use dca::*;
+pub use distributor::*;
+pub use hook::*;
@@ -0,0 +1,89 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::utils::*;
+
+/// Creates the currency's profile if needed, then applies the description
+/// and link changes. A link with no value is removed. Link changes go out
+/// one per transaction since a description alone fills most of one.
+pub async fn set_currency_profile(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    description: Option<String>,
+    links: Vec<(String, Option<String>)>,
+) -> Result<Vec<Signature>> {
+    let authority = signer.pubkey();
+    let (currency_pda, _) = find_currency_pda(&mint);
+
+    if let Some(description) = &description {
+        if !CurrencyProfile::is_valid_description(description.as_bytes()) {
+            return Err(anyhow!(
+                "Description must be at most {} bytes with no control characters other than newlines",
+                MAX_DESCRIPTION_LEN
+            ));
+        }
+    }
+    for (key, value) in &links {
+        let valid = match value {
+            Some(value) => ProfileLink::new(key, value).is_some(),
+            None => ProfileLink::is_valid_key(key.as_bytes()),
+        };
+        if !valid {
+            return Err(anyhow!(
+                "Invalid link {}: keys are up to {} lowercase letters, digits, '-' or '_', values up to {} bytes",
+                key, MAX_LINK_KEY_LEN, MAX_LINK_VALUE_LEN
+            ));
+        }
+    }
+
+    let mut first_ixs = vec![];
+    if get_currency_profile_account(client, &currency_pda).await?.is_none() {
+        first_ixs.push(build_initialize_currency_profile_ix(authority, currency_pda));
+    }
+    if let Some(description) = description {
+        first_ixs.push(build_set_profile_description_ix(authority, currency_pda, description));
+    }
+
+    let mut signatures = vec![];
+    if !first_ixs.is_empty() {
+        signatures.push(send_profile_ixs(client, signer, &first_ixs).await?);
+    }
+    for (key, value) in links {
+        let ix = build_set_profile_link_ix(authority, currency_pda, key, value);
+        signatures.push(send_profile_ixs(client, signer, &[ix]).await?);
+    }
+
+    Ok(signatures)
+}
+
+async fn send_profile_ixs(
+    client: &RpcClient,
+    signer: &Keypair,
+    ixs: &[Instruction],
+) -> Result<Signature> {
+    let payer = signer.pubkey();
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        ixs,
+        Some(&payer),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to update currency profile: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
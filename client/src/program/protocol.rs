@@ -0,0 +1,90 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::utils::*;
+
+pub async fn initialize_protocol_config(
+    client: &RpcClient,
+    signer: &Keypair,
+    min_sell_fee: u16,
+    max_sell_fee: u16,
+    creation_policy: CreationPolicy,
+) -> Result<(Signature, Pubkey)> {
+    let admin = signer.pubkey();
+    let (protocol_config_pda, _) = find_protocol_config_pda();
+
+    let ix = build_initialize_protocol_config_ix(admin, min_sell_fee, max_sell_fee, creation_policy);
+
+    let signature = send_protocol_ix(client, signer, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to initialize protocol config: {}", e))?;
+
+    Ok((signature, protocol_config_pda))
+}
+
+pub async fn update_protocol_config(
+    client: &RpcClient,
+    signer: &Keypair,
+    new_admin: Option<Pubkey>, // Keeps the current admin when None
+    min_sell_fee: u16,
+    max_sell_fee: u16,
+    creation_policy: CreationPolicy,
+) -> Result<Signature> {
+    let admin = signer.pubkey();
+
+    let ix = build_update_protocol_config_ix(
+        admin,
+        new_admin.unwrap_or(admin),
+        min_sell_fee,
+        max_sell_fee,
+        creation_policy,
+    );
+
+    send_protocol_ix(client, signer, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to update protocol config: {}", e))
+}
+
+pub async fn set_base_mint_allowed(
+    client: &RpcClient,
+    signer: &Keypair,
+    base_mint: Pubkey,
+    allowed: bool,
+) -> Result<Signature> {
+    let admin = signer.pubkey();
+
+    let ix = build_set_base_mint_allowed_ix(admin, base_mint, allowed);
+
+    send_protocol_ix(client, signer, ix)
+        .await
+        .map_err(|e| anyhow!("Failed to set base mint allowed: {}", e))
+}
+
+async fn send_protocol_ix(
+    client: &RpcClient,
+    signer: &Keypair,
+    ix: Instruction,
+) -> Result<Signature> {
+    let payer = signer.pubkey();
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx).await?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
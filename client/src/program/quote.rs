@@ -0,0 +1,168 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey};
+use spl_token_2022::{
+    extension::{
+        transfer_fee::{TransferFee, TransferFeeConfig},
+        BaseState,
+        BaseStateWithExtensions,
+        StateWithExtensionsOwned,
+    },
+    state::{Account as TokenAccount, Mint},
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::utils::*;
+
+/// Pool state needed to price trades off-chain, for any curve kind.
+pub struct PoolQuoter {
+    pub pool: LiquidityPool,
+    pub curve_table: Option<(CurveTable, Vec<CurveTableEntry>)>, // Table-backed pools only
+    pub target_decimals: u8,
+    pub base_decimals: u8,
+    pub base_transfer_fee: Option<TransferFee>, // Token-2022 transfer fee on the base mint, this epoch
+    pub tokens_left: u64,         // Target vault balance
+    pub value_locked: u64,        // Base vault balance net of accumulated fees
+}
+
+impl PoolQuoter {
+    pub async fn load(client: &RpcClient, mint: Pubkey, base_mint: Option<Pubkey>) -> Result<Self> {
+        let (currency_pda, _) = find_currency_pda(&mint);
+
+        let (currency, _) = get_currency_account(client, &currency_pda).await?;
+        let (pool, _) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+        let curve_table = if pool.is_table_backed() {
+            let (table, entries, _) = get_curve_table_account(client, &pool.curve_table).await?;
+            Some((table, entries))
+        } else {
+            None
+        };
+
+        let base_mint = get_token_state::<Mint>(client, &pool.mint_b).await?;
+        let vault_a = get_token_state::<TokenAccount>(client, &pool.vault_a).await?;
+        let vault_b = get_token_state::<TokenAccount>(client, &pool.vault_b).await?;
+
+        let base_transfer_fee = match base_mint.get_extension::<TransferFeeConfig>() {
+            Ok(config) => {
+                let epoch = client.get_epoch_info().await?.epoch;
+                Some(*config.get_epoch_fee(epoch))
+            }
+            Err(_) => None,
+        };
+
+        Ok(Self {
+            target_decimals: currency.decimals,
+            base_decimals: base_mint.base.decimals,
+            base_transfer_fee,
+            tokens_left: vault_a.base.amount,
+            value_locked: vault_b.base.amount.saturating_sub(pool.fees_accumulated),
+            pool,
+            curve_table,
+        })
+    }
+
+    pub fn curve(&self) -> Result<PoolCurve<'_>> {
+        match &self.curve_table {
+            Some((table, entries)) => PoolCurve::from_table(table.step_size, entries)
+                .ok_or_else(|| anyhow!("Invalid curve table")),
+            None => self.pool.curve()
+                .map_err(|e| anyhow!("Unsupported pool curve: {}", e)),
+        }
+    }
+
+    /// Current spot price in base tokens per whole token.
+    pub fn spot_price(&self) -> Result<UnsignedNumeric> {
+        self.curve()?.spot_price_at_supply(&self.supply()?)
+            .ok_or_else(|| anyhow!("Supply is outside the curve"))
+    }
+
+    /// Currency quarks received for `in_amount` base quarks.
+    pub fn quote_buy(&self, in_amount: u64) -> Result<u64> {
+        let in_amount = in_amount
+            .checked_sub(self.transfer_fee(in_amount)?)
+            .ok_or_else(|| anyhow!("Overflow"))?;
+
+        let curve = self.curve()?;
+        let zero = UnsignedNumeric::zero();
+        let max_supply = to_numeric(self.max_supply_quarks()?, self.target_decimals)?;
+        let max_value = curve.tokens_to_value(&zero, &max_supply)
+            .ok_or_else(|| anyhow!("Failed to price curve"))?;
+
+        let current_value = to_numeric(self.value_locked, self.base_decimals)?;
+        let mut new_value = current_value
+            .checked_add(&to_numeric(in_amount, self.base_decimals)?)
+            .ok_or_else(|| anyhow!("Overflow"))?;
+        if new_value.greater_than(&max_value) {
+            new_value = max_value;
+        }
+
+        let new_supply = curve.value_to_tokens(&zero, &new_value)
+            .ok_or_else(|| anyhow!("Failed to price curve"))?;
+        let mut tokens = new_supply
+            .checked_sub(&self.supply()?)
+            .ok_or_else(|| anyhow!("Curve is out of sync with the vaults"))?;
+        let tokens_left = to_numeric(self.tokens_left, self.target_decimals)?;
+        if tokens.greater_than(&tokens_left) {
+            tokens = tokens_left;
+        }
+
+        Ok(from_numeric(tokens, self.target_decimals)?)
+    }
+
+    /// Base quarks received for `in_amount` currency quarks, after any base
+    /// transfer fee, and the fee withheld at the pool's default sell fee.
+    pub fn quote_sell(&self, in_amount: u64) -> Result<(u64, u64)> {
+        let curve = self.curve()?;
+        let zero = UnsignedNumeric::zero();
+        let new_supply = self.supply()?
+            .checked_sub(&to_numeric(in_amount, self.target_decimals)?)
+            .ok_or_else(|| anyhow!("Cannot sell more than the circulating supply"))?;
+        let new_value = curve.tokens_to_value(&zero, &new_supply)
+            .ok_or_else(|| anyhow!("Failed to price curve"))?;
+
+        let value_locked = to_numeric(self.value_locked, self.base_decimals)?;
+        let total = value_locked
+            .checked_sub(&new_value)
+            .ok_or_else(|| anyhow!("Curve is out of sync with the vaults"))?;
+        let fee = total
+            .checked_mul(&from_basis_points(self.pool.sell_fee)?)
+            .ok_or_else(|| anyhow!("Overflow"))?;
+        let out = total
+            .checked_sub(&fee)
+            .ok_or_else(|| anyhow!("Overflow"))?;
+
+        let out = from_numeric(out, self.base_decimals)?;
+        let out = out
+            .checked_sub(self.transfer_fee(out)?)
+            .ok_or_else(|| anyhow!("Overflow"))?;
+
+        Ok((out, from_numeric(fee, self.base_decimals)?))
+    }
+
+    fn transfer_fee(&self, amount: u64) -> Result<u64> {
+        match &self.base_transfer_fee {
+            Some(fee) => fee.calculate_fee(amount)
+                .ok_or_else(|| anyhow!("Overflow")),
+            None => Ok(0),
+        }
+    }
+
+    fn max_supply_quarks(&self) -> Result<u64> {
+        Ok(to_quarks(self.pool.curve_max_supply, self.target_decimals)?)
+    }
+
+    fn supply(&self) -> Result<UnsignedNumeric> {
+        let supply = self.max_supply_quarks()?
+            .checked_sub(self.tokens_left)
+            .ok_or_else(|| anyhow!("Vault holds more than the max supply"))?;
+        Ok(to_numeric(supply, self.target_decimals)?)
+    }
+}
+
+// Mints and token accounts of either token program, with any extensions
+async fn get_token_state<T: BaseState + Pack>(client: &RpcClient, address: &Pubkey) -> Result<StateWithExtensionsOwned<T>> {
+    let account_bytes = get_account(client, address).await?;
+    let account: Account = deserialize(&account_bytes)?;
+    StateWithExtensionsOwned::<T>::unpack(account.data)
+        .map_err(|e| anyhow!("Failed to unpack account {}: {}", address, e))
+}
@@ -20,36 +20,42 @@ pub async fn sell(
 
     let seller = signer.pubkey();
     let (currency_pda, _) = find_currency_pda(&mint);
-    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (currency, _) = get_currency_account(client, &currency_pda).await?;
+    let (pool, pool_pda) = get_currency_pool(client, &mint, Some(&base_mint)).await?;
+    let target_token_program = get_token_program(client, &mint).await?;
+    let base_token_program = get_token_program(client, &base_mint).await?;
 
-    let seller_target_ata = spl_associated_token_account::get_associated_token_address(&seller, &mint);
-    let seller_base_ata = spl_associated_token_account::get_associated_token_address(&seller, &base_mint);
+    let seller_target_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&seller, &mint, &target_token_program);
+    let seller_base_ata = spl_associated_token_account::get_associated_token_address_with_program_id(&seller, &base_mint, &base_token_program);
 
     // Create seller ATAs
-    let (_target_ata, target_ata_sig) = create_ata(client, signer, &mint, &seller, None).await?;
+    let (_target_ata, target_ata_sig) = create_ata(client, signer, &mint, &seller, Some(&target_token_program)).await?;
     if target_ata_sig != Signature::default() {
         println!("Created seller target ATA: {}. Signature: {}", seller_target_ata, target_ata_sig);
     }
 
-    let (_base_ata, base_ata_sig) = create_ata(client, signer, &base_mint, &seller, None).await?;
+    let (_base_ata, base_ata_sig) = create_ata(client, signer, &base_mint, &seller, Some(&base_token_program)).await?;
     if base_ata_sig != Signature::default() {
         println!("Created seller base ATA: {}. Signature: {}", seller_base_ata, base_ata_sig);
     }
 
     // Convert amount (in tokens) to token amount
-    let in_amount = (amount * 10f64.powi(TOKEN_DECIMALS as i32)) as u64;
+    let in_amount = (amount * 10f64.powi(currency.decimals as i32)) as u64;
     let min_amount_out = 0; // Allow any output amount for simplicity
 
-    let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(100_000);
+    let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(100_000 + pool.hook_compute_units);
     let sell_ix = build_sell_tokens_ix(
         seller,
         pool_pda,
         mint,
+        target_token_program,
         base_mint,
+        base_token_program,
         in_amount,
         min_amount_out,
         seller_target_ata,
         seller_base_ata,
+        TradeOptions::for_pool(&pool, &pool_pda),
     );
 
     let blockhash_bytes = get_latest_blockhash(client).await?;
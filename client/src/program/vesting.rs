@@ -0,0 +1,56 @@
+use anyhow::{Result, anyhow};
+use solana_sdk::{
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+    pubkey::Pubkey,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use flipcash_api::prelude::*;
+
+use crate::utils::*;
+
+/// Claims whatever has vested of the signer's initial buy from a pool into
+/// its currency ATA.
+pub async fn claim_vested(
+    client: &RpcClient,
+    signer: &Keypair,
+    mint: Pubkey,
+    base_mint: Option<Pubkey>, // Primary pool when None
+) -> Result<Signature> {
+    let beneficiary = signer.pubkey();
+    let (_, pool_pda) = get_currency_pool(client, &mint, base_mint.as_ref()).await?;
+
+    if get_vesting_account(client, &pool_pda, &beneficiary).await?.is_none() {
+        return Err(anyhow!("Nothing is vesting for {}", beneficiary));
+    }
+
+    let token_program = get_token_program(client, &mint).await?;
+    let (mint_ata, mint_ata_sig) = create_ata(client, signer, &mint, &beneficiary, Some(&token_program)).await?;
+    if mint_ata_sig != Signature::default() {
+        println!("Created mint ATA: {}. Signature: {}", mint_ata, mint_ata_sig);
+    }
+
+    let claim_ix = build_claim_vested_ix(
+        beneficiary,
+        pool_pda,
+        mint,
+        token_program,
+        mint_ata,
+    );
+
+    let blockhash_bytes = get_latest_blockhash(client).await?;
+    let recent_blockhash = deserialize(&blockhash_bytes)?;
+    let tx = Transaction::new_signed_with_payer(
+        &[claim_ix],
+        Some(&beneficiary),
+        &[signer],
+        recent_blockhash,
+    );
+
+    let signature_bytes = send_and_confirm_transaction(client, &tx)
+        .await
+        .map_err(|e| anyhow!("Failed to claim vested currency: {}", e))?;
+    let signature: Signature = deserialize(&signature_bytes)?;
+
+    Ok(signature)
+}
@@ -1,8 +1,14 @@
 use anyhow::{Result, anyhow};
-use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_account_decoder::UiAccountEncoding;
 use solana_sdk::{pubkey::Pubkey, account::Account};
-use flipcash_api::state::{LiquidityPool, CurrencyConfig, };
-use crate::utils::{deserialize, get_account};
+use flipcash_api::prelude::*;
+use flipcash_api::state::{LiquidityPool, CurrencyConfig, FeeSchedule, TraderVolume, CurveTable, CurveTableEntry, ProtocolConfig, CurrencyRegistry, CurrencyRegistryPage, CurrencyProfile, ProfileLink, WalletPurchases};
+use crate::utils::{deserialize, get_account, get_program_account};
 
 pub async fn get_currency_account(client: &RpcClient, address: &Pubkey) -> Result<(CurrencyConfig, Pubkey)> {
     let account_bytes = get_account(client, address).await?;
@@ -24,3 +30,333 @@ pub async fn get_pool_account(
         .copied()?;
     Ok((account, *address))
 }
+
+/// Resolves the pool of a currency that trades against `base_mint`. The
+/// primary pool is used when no base mint is given.
+pub async fn get_currency_pool(
+    client: &RpcClient,
+    mint: &Pubkey,
+    base_mint: Option<&Pubkey>,
+) -> Result<(LiquidityPool, Pubkey)> {
+    let (currency_pda, _) = find_currency_pda(mint);
+    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (pool, _) = get_pool_account(client, &pool_pda).await?;
+
+    match base_mint {
+        Some(base_mint) if pool.mint_b != *base_mint => {
+            let (pool_pda, _) = find_additional_pool_pda(&currency_pda, base_mint);
+            get_pool_account(client, &pool_pda).await
+        }
+        _ => Ok((pool, pool_pda)),
+    }
+}
+
+/// Lists every pool of a currency, primary pool included.
+pub async fn get_currency_pools(
+    client: &RpcClient,
+    currency: &Pubkey,
+) -> Result<Vec<(LiquidityPool, Pubkey)>> {
+    let discriminator_len = 8;
+    let currency_offset = discriminator_len + std::mem::size_of::<Pubkey>(); // After authority
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize((discriminator_len + std::mem::size_of::<LiquidityPool>()) as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(currency_offset, currency.as_ref())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    get_program_account(client, config)
+        .await?
+        .into_iter()
+        .map(|(address, account)| {
+            let pool = LiquidityPool::unpack(&account.data)
+                .map_err(|e| anyhow!("Failed to unpack liquidity pool account: {}", e))
+                .copied()?;
+            Ok((pool, address))
+        })
+        .collect()
+}
+
+pub async fn get_fee_schedule_account(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> Result<(FeeSchedule, Pubkey)> {
+    let account_bytes = get_account(client, address).await?;
+    let account: Account = deserialize(&account_bytes)?;
+    let account = FeeSchedule::unpack(&account.data)
+        .map_err(|e| anyhow!("Failed to unpack fee schedule account: {}", e))
+        .copied()?;
+    Ok((account, *address))
+}
+
+pub async fn get_trader_volume_account(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> Result<(TraderVolume, Pubkey)> {
+    let account_bytes = get_account(client, address).await?;
+    let account: Account = deserialize(&account_bytes)?;
+    let account = TraderVolume::unpack(&account.data)
+        .map_err(|e| anyhow!("Failed to unpack trader volume account: {}", e))
+        .copied()?;
+    Ok((account, *address))
+}
+
+pub async fn get_curve_table_account(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> Result<(CurveTable, Vec<CurveTableEntry>, Pubkey)> {
+    let account_bytes = get_account(client, address).await?;
+    let account: Account = deserialize(&account_bytes)?;
+    let (table, entries) = CurveTable::unpack_entries(&account.data)
+        .map_err(|e| anyhow!("Failed to unpack curve table account: {}", e))?;
+    Ok((*table, entries.to_vec(), *address))
+}
+
+pub async fn get_protocol_config_account(client: &RpcClient) -> Result<(ProtocolConfig, Pubkey)> {
+    let (address, _) = find_protocol_config_pda();
+    let account_bytes = get_account(client, &address).await?;
+    let account: Account = deserialize(&account_bytes)?;
+    let account = ProtocolConfig::unpack(&account.data)
+        .map_err(|e| anyhow!("Failed to unpack protocol config account: {}", e))
+        .copied()?;
+    Ok((account, address))
+}
+
+pub async fn get_currency_registry_account(client: &RpcClient) -> Result<(CurrencyRegistry, Pubkey)> {
+    let (address, _) = find_registry_pda();
+    let account_bytes = get_account(client, &address).await?;
+    let account: Account = deserialize(&account_bytes)?;
+    let account = CurrencyRegistry::unpack(&account.data)
+        .map_err(|e| anyhow!("Failed to unpack currency registry account: {}", e))
+        .copied()?;
+    Ok((account, address))
+}
+
+pub async fn get_registry_page_account(
+    client: &RpcClient,
+    page: u64,
+) -> Result<(CurrencyRegistryPage, Pubkey)> {
+    let (address, _) = find_registry_page_pda(page);
+    let account_bytes = get_account(client, &address).await?;
+    let account: Account = deserialize(&account_bytes)?;
+    let account = CurrencyRegistryPage::unpack(&account.data)
+        .map_err(|e| anyhow!("Failed to unpack currency registry page account: {}", e))
+        .copied()?;
+    Ok((account, address))
+}
+
+/// Page the next currency will be registered in; 0 before the registry exists.
+pub async fn get_next_registry_page(client: &RpcClient) -> Result<u64> {
+    let (address, _) = find_registry_pda();
+    match client.get_account_with_commitment(&address, client.commitment()).await?.value {
+        Some(account) => Ok(CurrencyRegistry::unpack(&account.data)
+            .map_err(|e| anyhow!("Failed to unpack currency registry account: {}", e))?
+            .next_page()),
+        None => Ok(0),
+    }
+}
+
+/// Every registered currency mint, in creation order.
+pub async fn get_registered_currencies(client: &RpcClient) -> Result<Vec<Pubkey>> {
+    let (registry, _) = get_currency_registry_account(client).await?;
+
+    let mut mints = Vec::with_capacity(registry.num_currencies as usize);
+    for page in 0..registry.num_pages() {
+        let (registry_page, _) = get_registry_page_account(client, page).await?;
+        mints.extend_from_slice(registry_page.mints());
+    }
+    Ok(mints)
+}
+
+/// A currency's profile header, description and links; None if it has no profile.
+pub async fn get_currency_profile_account(
+    client: &RpcClient,
+    currency: &Pubkey,
+) -> Result<Option<(CurrencyProfile, String, Vec<ProfileLink>)>> {
+    let (address, _) = find_profile_pda(currency);
+    match client.get_account_with_commitment(&address, client.commitment()).await?.value {
+        Some(account) => {
+            let (profile, description, links) = CurrencyProfile::unpack_profile(&account.data)
+                .map_err(|e| anyhow!("Failed to unpack currency profile account: {}", e))?;
+            Ok(Some((*profile, description.to_string(), links.to_vec())))
+        }
+        None => Ok(None),
+    }
+}
+
+/// A wallet's purchases from a pool, or `None` if it hasn't been created.
+pub async fn get_wallet_purchases_account(
+    client: &RpcClient,
+    pool: &Pubkey,
+    wallet: &Pubkey,
+) -> Result<Option<WalletPurchases>> {
+    let (address, _) = find_wallet_purchases_pda(pool, wallet);
+    match client.get_account_with_commitment(&address, client.commitment()).await?.value {
+        Some(account) => {
+            let purchases = WalletPurchases::unpack(&account.data)
+                .map_err(|e| anyhow!("Failed to unpack wallet purchases account: {}", e))
+                .copied()?;
+            Ok(Some(purchases))
+        }
+        None => Ok(None),
+    }
+}
+
+/// A beneficiary's vesting account for a pool, or `None` if it has none.
+pub async fn get_vesting_account(
+    client: &RpcClient,
+    pool: &Pubkey,
+    beneficiary: &Pubkey,
+) -> Result<Option<Vesting>> {
+    let (address, _) = find_vesting_pda(pool, beneficiary);
+    match client.get_account_with_commitment(&address, client.commitment()).await?.value {
+        Some(account) => {
+            let vesting = Vesting::unpack(&account.data)
+                .map_err(|e| anyhow!("Failed to unpack vesting account: {}", e))
+                .copied()?;
+            Ok(Some(vesting))
+        }
+        None => Ok(None),
+    }
+}
+
+/// The cash link for a one-time link key, or `None` once it has been claimed
+/// or reclaimed.
+pub async fn get_cash_link_account(
+    client: &RpcClient,
+    link_key: &Pubkey,
+) -> Result<Option<CashLink>> {
+    let (address, _) = find_cash_link_pda(link_key);
+    match client.get_account_with_commitment(&address, client.commitment()).await?.value {
+        Some(account) => {
+            let cash_link = CashLink::unpack(&account.data)
+                .map_err(|e| anyhow!("Failed to unpack cash link account: {}", e))
+                .copied()?;
+            Ok(Some(cash_link))
+        }
+        None => Ok(None),
+    }
+}
+
+pub async fn get_limit_order_account(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<LimitOrder>> {
+    match client.get_account_with_commitment(address, client.commitment()).await?.value {
+        Some(account) => {
+            let order = LimitOrder::unpack(&account.data)
+                .map_err(|e| anyhow!("Failed to unpack limit order account: {}", e))
+                .copied()?;
+            Ok(Some(order))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Lists the open limit orders against a pool.
+pub async fn get_limit_orders(
+    client: &RpcClient,
+    pool: &Pubkey,
+) -> Result<Vec<(LimitOrder, Pubkey)>> {
+    let discriminator_len = 8;
+    let pool_offset = discriminator_len + std::mem::size_of::<Pubkey>(); // After owner
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize((discriminator_len + std::mem::size_of::<LimitOrder>()) as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(pool_offset, pool.as_ref())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    get_program_account(client, config)
+        .await?
+        .into_iter()
+        .map(|(address, account)| {
+            let order = LimitOrder::unpack(&account.data)
+                .map_err(|e| anyhow!("Failed to unpack limit order account: {}", e))
+                .copied()?;
+            Ok((order, address))
+        })
+        .collect()
+}
+
+pub async fn get_dca_plan_account(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<DcaPlan>> {
+    match client.get_account_with_commitment(address, client.commitment()).await?.value {
+        Some(account) => {
+            let plan = DcaPlan::unpack(&account.data)
+                .map_err(|e| anyhow!("Failed to unpack DCA plan account: {}", e))
+                .copied()?;
+            Ok(Some(plan))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Lists the open DCA plans against a pool.
+pub async fn get_dca_plans(
+    client: &RpcClient,
+    pool: &Pubkey,
+) -> Result<Vec<(DcaPlan, Pubkey)>> {
+    let discriminator_len = 8;
+    let pool_offset = discriminator_len + std::mem::size_of::<Pubkey>(); // After owner
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize((discriminator_len + std::mem::size_of::<DcaPlan>()) as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(pool_offset, pool.as_ref())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    get_program_account(client, config)
+        .await?
+        .into_iter()
+        .map(|(address, account)| {
+            let plan = DcaPlan::unpack(&account.data)
+                .map_err(|e| anyhow!("Failed to unpack DCA plan account: {}", e))
+                .copied()?;
+            Ok((plan, address))
+        })
+        .collect()
+}
+
+/// A distributor and its claimed bitmap, or `None` once it has been clawed
+/// back.
+pub async fn get_distributor_account(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<(Distributor, Vec<u8>)>> {
+    match client.get_account_with_commitment(address, client.commitment()).await?.value {
+        Some(account) => {
+            let (distributor, claims) = Distributor::unpack_claims(&account.data)
+                .map_err(|e| anyhow!("Failed to unpack distributor account: {}", e))?;
+            Ok(Some((*distributor, claims.to_vec())))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Owner of a mint: SPL Token or Token-2022.
+pub async fn get_token_program(client: &RpcClient, mint: &Pubkey) -> Result<Pubkey> {
+    let account_bytes = get_account(client, mint).await?;
+    let account: Account = deserialize(&account_bytes)?;
+    Ok(account.owner)
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::consts::*;
+use crate::state::CurveTableEntry;
 
 #[repr(C)]
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
@@ -9,17 +10,39 @@ pub struct InitializeCurrencyArgs {
     pub seed: [u8; 32],
     pub bump: u8,
     pub mint_bump: u8,
-    _padding: [u8; 6],
+    pub decimals: u8,
+    pub symbol_bump: u8,
+    pub registry_bump: u8,
+    pub registry_page_bump: u8,
+    _padding: [u8; 2],
+    pub max_supply: u64,
 }
 
 #[repr(C)]
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
 pub struct InitializePoolArgs {
     pub sell_fee: u16,
+    pub burn_cooldown: i64,
+    pub curve_start_price: u64,
+    pub curve_end_price: u64,
+    pub curve_max_supply: u64,
+    pub curve_step_size: u64,
+    pub initial_buy_amount: u64,
+    pub vesting_cliff: i64,
+    pub vesting_duration: i64,
+    pub launch_at: i64,
+    pub presale_cap: u64,
+    pub presale_root: [u8; 32],
+    pub launch_window: u64,
+    pub launch_cap: u64,
+    pub curve_kind: u8,
+    pub burn_policy: u8,
+    pub allowed_extensions: u8,
     pub bump: u8,
     pub vault_a_bump: u8,
     pub vault_b_bump: u8,
-    _padding: [u8; 1],
+    pub vesting_bump: u8,
+    pub vesting_vault_bump: u8,
 }
 
 #[repr(C)]
@@ -51,3 +74,226 @@ pub struct SellAndDepositIntoVmArgs {
     pub min_amount_out: u64,
     pub vm_memory_index: u16,
 }
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct InitializeFeeScheduleArgs {
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct SetFeeExemptionArgs {
+    pub exempt: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct SetFeeTiersArgs {
+    pub min_volumes: [u64; MAX_FEE_TIERS],
+    pub sell_fees: [u16; MAX_FEE_TIERS],
+    pub num_tiers: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct InitializeTraderVolumeArgs {
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct SetBurnPolicyArgs {
+    pub burn_cooldown: i64,
+    pub burn_policy: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct InitializeCurveTableArgs {
+    pub seed: [u8; 32],
+    pub step_size: u64,
+    pub num_steps: u64,
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct WriteCurveTableArgs {
+    pub entries: [CurveTableEntry; CURVE_TABLE_CHUNK_LEN],
+    pub num_entries: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct MigrateCurrencyArgs {
+    pub symbol_bump: u8,
+    pub registry_bump: u8,
+    pub registry_page_bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct InitializeProtocolConfigArgs {
+    pub min_sell_fee: u16,
+    pub max_sell_fee: u16,
+    pub creation_policy: u8,
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct UpdateProtocolConfigArgs {
+    pub min_sell_fee: u16,
+    pub max_sell_fee: u16,
+    pub creation_policy: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct SetBaseMintAllowedArgs {
+    pub allowed: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct InitializeCurrencyProfileArgs {
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct SetProfileDescriptionArgs {
+    pub description: [u8; MAX_DESCRIPTION_LEN],
+    pub description_len: [u8; 2],
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct SetProfileLinkArgs {
+    pub key: [u8; MAX_LINK_KEY_LEN],
+    pub value: [u8; MAX_LINK_VALUE_LEN],
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct PauseArgs {
+    pub pause_sells: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct SetCircuitBreakerArgs {
+    pub window: u64,
+    pub band: u16,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct SetLaunchArgs {
+    pub launch_at: i64,
+    pub presale_cap: u64,
+    pub presale_root: [u8; 32],
+    pub launch_window: u64,
+    pub launch_cap: u64,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct InitializeWalletPurchasesArgs {
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct JoinPresaleArgs {
+    pub proof: [[u8; 32]; MAX_PRESALE_PROOF_LEN],
+    pub proof_len: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct BatchBuyArgs {
+    pub in_amount: u64,
+    pub min_amount_out: u64,
+    pub shares: [u32; MAX_BATCH_RECIPIENTS],
+    pub indexes: [u8; MAX_BATCH_RECIPIENTS],
+    pub num_recipients: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct CreateCashLinkArgs {
+    pub amount: u64,
+    pub in_amount: u64,
+    pub min_amount_out: u64,
+    pub expires_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct CreateLimitOrderArgs {
+    pub id: u64,
+    pub amount: u64,
+    pub min_amount_out: u64,
+    pub target_price: u64,
+    pub bounty: u64,
+    pub expires_at: i64,
+    pub side: u8,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct CreateDcaPlanArgs {
+    pub id: u64,
+    pub amount_per_fill: u64,
+    pub min_amount_out: u64,
+    pub interval: i64,
+    pub first_fill_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct DepositDcaArgs {
+    pub amount: u64,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct WithdrawDcaArgs {
+    pub amount: u64,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct CreateDistributorArgs {
+    pub id: u64,
+    pub root: [u8; 32],
+    pub total_amount: u64,
+    pub num_recipients: u64,
+    pub expires_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct ClaimDistributionArgs {
+    pub index: u64,
+    pub amount: u64,
+    pub proof: [[u8; 32]; MAX_DISTRIBUTOR_PROOF_LEN],
+    pub proof_len: u8,
+}
+
+#[repr(C)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct SetTradeHookArgs {
+    pub compute_units: u32,
+    pub required: u8,
+}
@@ -1,2 +1,13 @@
 pub const MAX_NAME_LEN: usize = 32;
 pub const MAX_SYMBOL_LEN: usize = 8;
+pub const MAX_FEE_EXEMPTIONS: usize = 16;
+pub const MAX_FEE_TIERS: usize = 4;
+pub const MAX_ALLOWED_BASE_MINTS: usize = 8;
+pub const CURVE_TABLE_CHUNK_LEN: usize = 24;
+pub const REGISTRY_PAGE_LEN: usize = 64;
+pub const MAX_DESCRIPTION_LEN: usize = 512;
+pub const MAX_LINK_KEY_LEN: usize = 16;
+pub const MAX_LINK_VALUE_LEN: usize = 128;
+pub const MAX_PRESALE_PROOF_LEN: usize = 16;
+pub const MAX_BATCH_RECIPIENTS: usize = 16;
+pub const MAX_DISTRIBUTOR_PROOF_LEN: usize = 16;
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
+use anchor_spl::token_interface::TokenInterface;
 
 use crate::state::*;
 
@@ -11,7 +12,13 @@ pub struct InitializeCurrency<'info> {
     pub mint: AccountInfo<'info>,
     #[account(mut)]
     pub currency: Account<'info, CurrencyConfig>,
-    pub token_program: Program<'info, Token>,
+    #[account(mut)]
+    pub symbol_reservation: AccountInfo<'info>,
+    #[account(mut)]
+    pub registry: AccountInfo<'info>,
+    #[account(mut)]
+    pub registry_page: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -20,32 +27,42 @@ pub struct InitializeCurrency<'info> {
 pub struct InitializePool<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
+    #[account(mut)]
     pub currency: Account<'info, CurrencyConfig>,
     #[account(mut)]
     pub currency_mint: AccountInfo<'info>,
     pub base_mint: AccountInfo<'info>,
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(mut)]
     pub pool: Account<'info, LiquidityPool>,
     #[account(mut)]
     pub currency_vault: AccountInfo<'info>,
     #[account(mut)]
     pub base_vault: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub base_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+    #[account(mut)]
+    pub authority_currency_token_account: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    pub authority_base_token_account: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    pub vesting: Option<AccountInfo<'info>>,
 }
 
 #[derive(Accounts)]
 pub struct InitializeMetadata<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
+    #[account(mut)]
     pub currency: Account<'info, CurrencyConfig>,
     #[account(mut)]
     pub mint: AccountInfo<'info>,
     #[account(mut)]
     pub metadata: AccountInfo<'info>,
     pub metadata_program: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -53,6 +70,7 @@ pub struct InitializeMetadata<'info> {
 #[derive(Accounts)]
 pub struct BuyTokens<'info> {
     pub buyer: Signer<'info>,
+    #[account(mut)]
     pub pool: Account<'info, LiquidityPool>,
     pub currency_mint: AccountInfo<'info>,
     pub base_mint: AccountInfo<'info>,
@@ -64,7 +82,10 @@ pub struct BuyTokens<'info> {
     pub buyer_currency_token_account: AccountInfo<'info>,
     #[account(mut)]
     pub buyer_base_token_account: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    #[account(mut)]
+    pub wallet_purchases: Option<Account<'info, WalletPurchases>>,
 }
 
 #[derive(Accounts)]
@@ -82,12 +103,17 @@ pub struct SellTokens<'info> {
     pub seller_currency_token_account: AccountInfo<'info>,
     #[account(mut)]
     pub seller_base_token_account: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub fee_schedule: Option<Account<'info, FeeSchedule>>,
+    #[account(mut)]
+    pub trader_volume: Option<Account<'info, TraderVolume>>,
 }
 
 #[derive(Accounts)]
 pub struct BuyAndDepositIntoVm<'info> {
     pub buyer: Signer<'info>,
+    #[account(mut)]
     pub pool: Account<'info, LiquidityPool>,
     pub currency_mint: AccountInfo<'info>,
     pub base_mint: AccountInfo<'info>,
@@ -107,7 +133,10 @@ pub struct BuyAndDepositIntoVm<'info> {
     pub vm_omnibus: AccountInfo<'info>,
     pub vta_owner: AccountInfo<'info>,
     pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
     pub vm_program: AccountInfo<'info>,
+    #[account(mut)]
+    pub wallet_purchases: Option<Account<'info, WalletPurchases>>,
 }
 
 #[derive(Accounts)]
@@ -132,8 +161,12 @@ pub struct SellAndDepositIntoVm<'info> {
     #[account(mut)]
     pub vm_omnibus: AccountInfo<'info>,
     pub vta_owner: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub base_token_program: Interface<'info, TokenInterface>,
     pub vm_program: AccountInfo<'info>,
+    pub fee_schedule: Option<Account<'info, FeeSchedule>>,
+    #[account(mut)]
+    pub trader_volume: Option<Account<'info, TraderVolume>>,
 }
 
 #[derive(Accounts)]
@@ -145,5 +178,496 @@ pub struct BurnFees<'info> {
     pub base_mint: AccountInfo<'info>,
     #[account(mut)]
     pub base_vault: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub pool: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCurrency<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub currency: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub pool: AccountInfo<'info>,
+    #[account(mut)]
+    pub symbol_reservation: AccountInfo<'info>,
+    #[account(mut)]
+    pub registry: AccountInfo<'info>,
+    #[account(mut)]
+    pub registry_page: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeSchedule<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub pool: Account<'info, LiquidityPool>,
+    #[account(mut)]
+    pub fee_schedule: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeExemption<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub pool: Account<'info, LiquidityPool>,
+    #[account(mut)]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+    pub wallet: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub pool: Account<'info, LiquidityPool>,
+    #[account(mut)]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTraderVolume<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub pool: Account<'info, LiquidityPool>,
+    pub trader: AccountInfo<'info>,
+    #[account(mut)]
+    pub trader_volume: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetBurnPolicy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidityPool>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCurveTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub curve_table: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WriteCurveTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub curve_table: Account<'info, CurveTable>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeCurveTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub curve_table: Account<'info, CurveTable>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub protocol_config: AccountInfo<'info>,
+    pub program_data: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub new_admin: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetBaseMintAllowed<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub base_mint: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCurrencyProfile<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub currency: Account<'info, CurrencyConfig>,
+    #[account(mut)]
+    pub profile: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetProfileDescription<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub currency: Account<'info, CurrencyConfig>,
+    #[account(mut)]
+    pub profile: Account<'info, CurrencyProfile>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetProfileLink<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub currency: Account<'info, CurrencyConfig>,
+    #[account(mut)]
+    pub profile: Account<'info, CurrencyProfile>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidityPool>,
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct Resume<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidityPool>,
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetCircuitBreaker<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidityPool>,
+}
+
+#[derive(Accounts)]
+pub struct SetLaunch<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidityPool>,
+    pub currency_mint: AccountInfo<'info>,
+    pub currency_vault: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeWalletPurchases<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub pool: Account<'info, LiquidityPool>,
+    pub wallet: AccountInfo<'info>,
+    #[account(mut)]
+    pub wallet_purchases: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinPresale<'info> {
+    pub wallet: Signer<'info>,
+    pub pool: Account<'info, LiquidityPool>,
+    #[account(mut)]
+    pub wallet_purchases: Account<'info, WalletPurchases>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    pub beneficiary: Signer<'info>,
+    #[account(mut)]
+    pub vesting: Account<'info, Vesting>,
+    #[account(mut)]
+    pub vesting_vault: AccountInfo<'info>,
+    pub currency_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub beneficiary_currency_token_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct BatchBuy<'info> {
+    pub buyer: Signer<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidityPool>,
+    pub currency_mint: AccountInfo<'info>,
+    pub base_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub currency_vault: AccountInfo<'info>,
+    #[account(mut)]
+    pub base_vault: AccountInfo<'info>,
+    #[account(mut)]
+    pub buyer_base_token_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    #[account(mut)]
+    pub wallet_purchases: Option<Account<'info, WalletPurchases>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateCashLink<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub link_key: AccountInfo<'info>,
+    #[account(mut)]
+    pub cash_link: AccountInfo<'info>,
+    #[account(mut)]
+    pub cash_link_vault: AccountInfo<'info>,
+    pub currency_mint: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(mut)]
+    pub creator_currency_token_account: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    pub pool: Option<Account<'info, LiquidityPool>>,
+    pub base_mint: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    pub currency_vault: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    pub base_vault: Option<AccountInfo<'info>>,
+    #[account(mut)]
+    pub creator_base_token_account: Option<AccountInfo<'info>>,
+    pub base_token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(mut)]
+    pub wallet_purchases: Option<Account<'info, WalletPurchases>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCashLink<'info> {
+    #[account(mut)]
+    pub cash_link: Account<'info, CashLink>,
+    #[account(mut)]
+    pub cash_link_vault: AccountInfo<'info>,
+    pub currency_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub destination_currency_token_account: AccountInfo<'info>,
+    #[account(mut)]
+    pub creator: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub instructions: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimCashLink<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(mut)]
+    pub cash_link: Account<'info, CashLink>,
+    #[account(mut)]
+    pub cash_link_vault: AccountInfo<'info>,
+    pub currency_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub creator_currency_token_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CreateLimitOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub pool: Account<'info, LiquidityPool>,
+    #[account(mut)]
+    pub limit_order: AccountInfo<'info>,
+    #[account(mut)]
+    pub limit_order_vault: AccountInfo<'info>,
+    pub escrow_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner_escrow_token_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteLimitOrder<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    #[account(mut)]
+    pub limit_order: Account<'info, LimitOrder>,
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+    #[account(mut)]
+    pub limit_order_vault: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner_escrow_token_account: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner_destination_token_account: AccountInfo<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidityPool>,
+    pub currency_mint: AccountInfo<'info>,
+    pub base_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub currency_vault: AccountInfo<'info>,
+    #[account(mut)]
+    pub base_vault: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelLimitOrder<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub limit_order: Account<'info, LimitOrder>,
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+    #[account(mut)]
+    pub limit_order_vault: AccountInfo<'info>,
+    pub escrow_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner_escrow_token_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CreateDcaPlan<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub pool: Account<'info, LiquidityPool>,
+    #[account(mut)]
+    pub dca_plan: AccountInfo<'info>,
+    #[account(mut)]
+    pub dca_plan_vault: AccountInfo<'info>,
+    pub base_mint: AccountInfo<'info>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct DepositDca<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub dca_plan: Account<'info, DcaPlan>,
+    #[account(mut)]
+    pub dca_plan_vault: AccountInfo<'info>,
+    pub base_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner_base_token_account: AccountInfo<'info>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawDca<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub dca_plan: Account<'info, DcaPlan>,
+    #[account(mut)]
+    pub dca_plan_vault: AccountInfo<'info>,
+    pub base_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner_base_token_account: AccountInfo<'info>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDcaPlan<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub dca_plan: Account<'info, DcaPlan>,
+    #[account(mut)]
+    pub dca_plan_vault: AccountInfo<'info>,
+    pub base_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner_base_token_account: AccountInfo<'info>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDca<'info> {
+    pub executor: Signer<'info>,
+    #[account(mut)]
+    pub dca_plan: Account<'info, DcaPlan>,
+    #[account(mut)]
+    pub dca_plan_vault: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner_currency_token_account: AccountInfo<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidityPool>,
+    pub currency_mint: AccountInfo<'info>,
+    pub base_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub currency_vault: AccountInfo<'info>,
+    #[account(mut)]
+    pub base_vault: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CreateDistributor<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub distributor: AccountInfo<'info>,
+    #[account(mut)]
+    pub distributor_vault: AccountInfo<'info>,
+    pub currency_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub authority_currency_token_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDistribution<'info> {
+    pub claimant: Signer<'info>,
+    #[account(mut)]
+    pub distributor: Account<'info, Distributor>,
+    #[account(mut)]
+    pub distributor_vault: AccountInfo<'info>,
+    pub currency_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub claimant_currency_token_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClawbackDistributor<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub distributor: Account<'info, Distributor>,
+    #[account(mut)]
+    pub distributor_vault: AccountInfo<'info>,
+    pub currency_mint: AccountInfo<'info>,
+    #[account(mut)]
+    pub authority_currency_token_account: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetTradeHook<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub pool: Account<'info, LiquidityPool>,
+    pub hook_program: AccountInfo<'info>,
 }
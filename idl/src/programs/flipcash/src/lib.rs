@@ -47,4 +47,160 @@ pub mod flipcash {
     pub fn burn_fees(_ctx: Context<BurnFees>) -> Result<()> {
         Ok(())
     }
+
+    pub fn migrate_pool(_ctx: Context<MigratePool>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn migrate_currency(_ctx: Context<MigrateCurrency>, _data: MigrateCurrencyArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn initialize_fee_schedule(_ctx: Context<InitializeFeeSchedule>, _data: InitializeFeeScheduleArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_fee_exemption(_ctx: Context<SetFeeExemption>, _data: SetFeeExemptionArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_fee_tiers(_ctx: Context<SetFeeTiers>, _data: SetFeeTiersArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn initialize_trader_volume(_ctx: Context<InitializeTraderVolume>, _data: InitializeTraderVolumeArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_burn_policy(_ctx: Context<SetBurnPolicy>, _data: SetBurnPolicyArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn initialize_curve_table(_ctx: Context<InitializeCurveTable>, _data: InitializeCurveTableArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn write_curve_table(_ctx: Context<WriteCurveTable>, _data: WriteCurveTableArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn finalize_curve_table(_ctx: Context<FinalizeCurveTable>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn initialize_protocol_config(_ctx: Context<InitializeProtocolConfig>, _data: InitializeProtocolConfigArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn update_protocol_config(_ctx: Context<UpdateProtocolConfig>, _data: UpdateProtocolConfigArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_base_mint_allowed(_ctx: Context<SetBaseMintAllowed>, _data: SetBaseMintAllowedArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn initialize_currency_profile(_ctx: Context<InitializeCurrencyProfile>, _data: InitializeCurrencyProfileArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_profile_description(_ctx: Context<SetProfileDescription>, _data: SetProfileDescriptionArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_profile_link(_ctx: Context<SetProfileLink>, _data: SetProfileLinkArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn pause(_ctx: Context<Pause>, _data: PauseArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn resume(_ctx: Context<Resume>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_circuit_breaker(_ctx: Context<SetCircuitBreaker>, _data: SetCircuitBreakerArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_launch(_ctx: Context<SetLaunch>, _data: SetLaunchArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn initialize_wallet_purchases(_ctx: Context<InitializeWalletPurchases>, _data: InitializeWalletPurchasesArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn join_presale(_ctx: Context<JoinPresale>, _data: JoinPresaleArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn claim_vested(_ctx: Context<ClaimVested>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn batch_buy(_ctx: Context<BatchBuy>, _data: BatchBuyArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn create_cash_link(_ctx: Context<CreateCashLink>, _data: CreateCashLinkArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn claim_cash_link(_ctx: Context<ClaimCashLink>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn reclaim_cash_link(_ctx: Context<ReclaimCashLink>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn create_limit_order(_ctx: Context<CreateLimitOrder>, _data: CreateLimitOrderArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn execute_limit_order(_ctx: Context<ExecuteLimitOrder>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn cancel_limit_order(_ctx: Context<CancelLimitOrder>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn create_dca_plan(_ctx: Context<CreateDcaPlan>, _data: CreateDcaPlanArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn deposit_dca(_ctx: Context<DepositDca>, _data: DepositDcaArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn withdraw_dca(_ctx: Context<WithdrawDca>, _data: WithdrawDcaArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn close_dca_plan(_ctx: Context<CloseDcaPlan>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn execute_dca(_ctx: Context<ExecuteDca>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn create_distributor(_ctx: Context<CreateDistributor>, _data: CreateDistributorArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn claim_distribution(_ctx: Context<ClaimDistribution>, _data: ClaimDistributionArgs) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn clawback_distributor(_ctx: Context<ClawbackDistributor>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_trade_hook(_ctx: Context<SetTradeHook>, _data: SetTradeHookArgs) -> Result<()> {
+        Ok(())
+    }
 }
@@ -12,7 +12,11 @@ pub struct CurrencyConfig {
     pub seed: [u8; 32],
     pub bump: u8,
     pub mint_bump: u8,
-    pub padding: [u8; 6],
+    pub decimals: u8,
+    pub has_metadata: u8,
+    pub padding: [u8; 4],
+    pub max_supply: u64,
+    pub supply_allocated: u64,
 }
 
 #[account]
@@ -30,5 +34,245 @@ pub struct LiquidityPool {
     pub bump: u8,
     pub vault_a_bump: u8,
     pub vault_b_bump: u8,
-    pub padding: [u8; 3],
+    pub burn_policy: u8,
+    pub curve_kind: u8,
+    pub allowed_extensions: u8,
+    pub burn_cooldown: i64,
+    pub last_burn_at: i64,
+    pub curve_start_price: u64,
+    pub curve_end_price: u64,
+    pub curve_max_supply: u64,
+    pub curve_step_size: u64,
+    pub curve_table: Pubkey,
+    pub paused: u8,
+    pub padding: [u8; 1],
+    pub breaker_band: u16,
+    pub breaker_padding: [u8; 4],
+    pub breaker_window: u64,
+    pub breaker_slot: u64,
+    pub breaker_price: [u8; 16],
+    pub launch_at: i64,
+    pub presale_cap: u64,
+    pub presale_root: [u8; 32],
+    pub launch_window: u64,
+    pub launch_cap: u64,
+    pub hook_program: Pubkey,
+    pub hook_compute_units: u32,
+    pub hook_required: u8,
+    pub hook_authority_bump: u8,
+    pub hook_padding: [u8; 2],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct FeeTier {
+    pub min_volume: u64,
+    pub sell_fee: u16,
+    pub padding: [u8; 6],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct FeeSchedule {
+    pub pool: Pubkey,
+    pub exemptions: [Pubkey; MAX_FEE_EXEMPTIONS],
+    pub tiers: [FeeTier; MAX_FEE_TIERS],
+    pub num_exemptions: u8,
+    pub num_tiers: u8,
+    pub bump: u8,
+    pub padding: [u8; 5],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct TraderVolume {
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    pub sell_volume: u64,
+    pub bump: u8,
+    pub padding: [u8; 7],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct CurveTableEntry {
+    pub price: u128,
+    pub cumulative_value: u128,
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct CurveTable {
+    pub authority: Pubkey,
+    pub seed: [u8; 32],
+    pub step_size: u64,
+    pub num_steps: u64,
+    pub num_entries: u64,
+    pub is_finalized: u8,
+    pub bump: u8,
+    pub padding: [u8; 6],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    pub base_mints: [Pubkey; MAX_ALLOWED_BASE_MINTS],
+    pub min_sell_fee: u16,
+    pub max_sell_fee: u16,
+    pub num_base_mints: u8,
+    pub creation_policy: u8,
+    pub bump: u8,
+    pub padding: [u8; 1],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct SymbolReservation {
+    pub symbol: [u8; MAX_SYMBOL_LEN],
+    pub mint: Pubkey,
+    pub bump: u8,
+    pub padding: [u8; 7],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct CurrencyRegistry {
+    pub num_currencies: u64,
+    pub bump: u8,
+    pub padding: [u8; 7],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct CurrencyRegistryPage {
+    pub page: u64,
+    pub num_mints: u64,
+    pub mints: [Pubkey; REGISTRY_PAGE_LEN],
+    pub bump: u8,
+    pub padding: [u8; 7],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct CurrencyProfile {
+    pub currency: Pubkey,
+    pub description_len: u16,
+    pub num_links: u8,
+    pub bump: u8,
+    pub padding: [u8; 4],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct WalletPurchases {
+    pub pool: Pubkey,
+    pub wallet: Pubkey,
+    pub presale_spent: u64,
+    pub launch_spent: u64,
+    pub presale_allowed: u8,
+    pub bump: u8,
+    pub padding: [u8; 6],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct Vesting {
+    pub pool: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_at: i64,
+    pub cliff_at: i64,
+    pub end_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub padding: [u8; 6],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct CashLink {
+    pub creator: Pubkey,
+    pub link_key: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub created_at: i64,
+    pub created_slot: u64,
+    pub expires_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub padding: [u8; 6],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct LimitOrder {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub vault: Pubkey,
+    pub id: u64,
+    pub amount: u64,
+    pub min_amount_out: u64,
+    pub target_price: u64,
+    pub bounty: u64,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub side: u8,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub padding: [u8; 5],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct DcaPlan {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub vault: Pubkey,
+    pub id: u64,
+    pub amount_per_fill: u64,
+    pub min_amount_out: u64,
+    pub interval: i64,
+    pub next_fill_at: i64,
+    pub fills: u64,
+    pub total_spent: u64,
+    pub total_bought: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub padding: [u8; 6],
+}
+
+#[account]
+#[repr(C, align(8))]
+#[derive(Copy, Debug, PartialEq)]
+pub struct Distributor {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub root: [u8; 32],
+    pub id: u64,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub num_recipients: u64,
+    pub num_claimed: u64,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub padding: [u8; 6],
 }
@@ -11,6 +11,45 @@ const instructionValues: Record<string, number[]> = {
     buy_and_deposit_into_vm: [6],
     sell_and_deposit_into_vm: [7],
     burn_fees: [8],
+    initialize_fee_schedule: [9],
+    set_fee_exemption: [10],
+    set_fee_tiers: [11],
+    initialize_trader_volume: [12],
+    set_burn_policy: [13],
+    migrate_pool: [14],
+    initialize_curve_table: [15],
+    write_curve_table: [16],
+    finalize_curve_table: [17],
+    migrate_currency: [18],
+    initialize_protocol_config: [19],
+    update_protocol_config: [20],
+    set_base_mint_allowed: [21],
+    initialize_currency_profile: [22],
+    set_profile_description: [23],
+    set_profile_link: [24],
+    pause: [25],
+    resume: [26],
+    set_circuit_breaker: [27],
+    set_launch: [28],
+    initialize_wallet_purchases: [29],
+    join_presale: [30],
+    claim_vested: [31],
+    batch_buy: [32],
+    create_cash_link: [33],
+    claim_cash_link: [34],
+    reclaim_cash_link: [35],
+    create_limit_order: [36],
+    execute_limit_order: [37],
+    cancel_limit_order: [38],
+    create_dca_plan: [39],
+    deposit_dca: [40],
+    withdraw_dca: [41],
+    close_dca_plan: [42],
+    execute_dca: [43],
+    create_distributor: [44],
+    claim_distribution: [45],
+    clawback_distributor: [46],
+    set_trade_hook: [47],
 };
 
 // Pulled from:
@@ -18,6 +57,20 @@ const instructionValues: Record<string, number[]> = {
 const accountValues: Record<string, number[]> = {
     CurrencyConfig: [1, 0, 0, 0, 0, 0, 0, 0],
     LiquidityPool: [2, 0, 0, 0, 0, 0, 0, 0],
+    FeeSchedule: [3, 0, 0, 0, 0, 0, 0, 0],
+    TraderVolume: [4, 0, 0, 0, 0, 0, 0, 0],
+    CurveTable: [5, 0, 0, 0, 0, 0, 0, 0],
+    ProtocolConfig: [6, 0, 0, 0, 0, 0, 0, 0],
+    SymbolReservation: [7, 0, 0, 0, 0, 0, 0, 0],
+    CurrencyRegistry: [8, 0, 0, 0, 0, 0, 0, 0],
+    CurrencyRegistryPage: [9, 0, 0, 0, 0, 0, 0, 0],
+    CurrencyProfile: [10, 0, 0, 0, 0, 0, 0, 0],
+    WalletPurchases: [11, 0, 0, 0, 0, 0, 0, 0],
+    Vesting: [12, 0, 0, 0, 0, 0, 0, 0],
+    CashLink: [13, 0, 0, 0, 0, 0, 0, 0],
+    LimitOrder: [14, 0, 0, 0, 0, 0, 0, 0],
+    DcaPlan: [15, 0, 0, 0, 0, 0, 0, 0],
+    Distributor: [16, 0, 0, 0, 0, 0, 0, 0],
 };
 
 function updateDiscriminators() {
@@ -0,0 +1,69 @@
+use brine_fp::UnsignedNumeric;
+use steel::*;
+use flipcash_api::prelude::*;
+use flipcash_api::event::CircuitBreakerEvent;
+
+pub fn process_set_circuit_breaker(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = SetCircuitBreakerIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        pool_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(pool_info)?;
+
+    let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        pool.authority.eq(authority_info.key),
+        "Pool authority does not match"
+    )?;
+
+    check_condition(
+        args.band == 0 || args.window > 0,
+        "Circuit breaker window must be at least one slot"
+    )?;
+
+    pool.breaker_band = args.band;
+    pool.breaker_window = args.window;
+
+    // The next trade opens a fresh window
+    pool.breaker_slot = 0;
+    pool.breaker_price = [0; 16];
+
+    Ok(())
+}
+
+/// Rejects a trade that would move the pool's spot price from `price_before`
+/// to `price_after` outside its circuit breaker band, logging why. Nothing
+/// about the trip is kept: failing the trade rolls back the event and any
+/// change to the pool, including a window the trade would have opened.
+pub fn check_circuit_breaker(
+    pool_info: &AccountInfo,
+    trader_info: &AccountInfo,
+    pool: &mut LiquidityPool,
+    price_before: &UnsignedNumeric,
+    price_after: &UnsignedNumeric,
+) -> ProgramResult {
+    let slot = Clock::get()?.slot;
+
+    if !pool.check_price_band(slot, price_before, price_after)? {
+        CircuitBreakerEvent::new(
+            *pool_info.key,
+            *trader_info.key,
+            u128::from_le_bytes(pool.breaker_price),
+            price_after.value.as_u128(),
+            pool.breaker_slot,
+            pool.breaker_band,
+        ).log();
+
+        return Err(FlipcashError::PriceBandExceeded.into());
+    }
+
+    Ok(())
+}
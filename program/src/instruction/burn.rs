@@ -17,7 +17,7 @@ pub fn process_burn_fees(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResul
     check_mut(pool_info)?;
     check_mut(base_mint_info)?;
     check_mut(base_vault_info)?;
-    check_program(token_program_info, &spl_token::id())?;
+    check_token_program(token_program_info, base_mint_info)?;
 
     let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
 
@@ -31,6 +31,13 @@ pub fn process_burn_fees(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResul
         "Invalid base vault"
     )?;
 
+    let now = Clock::get()?.unix_timestamp;
+
+    check_condition(
+        pool.can_burn_fees(payer_info.key, now)?,
+        "Fee burn not permitted by pool policy"
+    )?;
+
     let fees_to_burn = pool.fees_accumulated;
 
     // Only burn if there are fees to burn
@@ -56,6 +63,39 @@ pub fn process_burn_fees(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResul
 
     // Reset fees_accumulated to 0
     pool.fees_accumulated = 0;
+    pool.last_burn_at = now;
+
+    Ok(())
+}
+
+pub fn process_set_burn_policy(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = SetBurnPolicyIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        authority_info,
+        pool_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(pool_info)?;
+
+    let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        pool.authority.eq(authority_info.key),
+        "Pool authority does not match"
+    )?;
+
+    check_condition(
+        args.burn_cooldown >= 0,
+        "Burn cooldown cannot be negative"
+    )?;
+
+    pool.burn_policy = args.burn_policy.into();
+    pool.burn_cooldown = args.burn_cooldown;
 
     Ok(())
 }
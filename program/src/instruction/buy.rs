@@ -1,6 +1,8 @@
 use brine_fp::UnsignedNumeric;
 use steel::*;
 use flipcash_api::prelude::*;
+use flipcash_api::event::BatchBuyEvent;
+use super::{check_circuit_breaker, invoke_trade_hook, load_wallet_purchases, split_curve_table};
 
 pub fn process_buy_tokens(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let raw_args = BuyTokensIx::try_from_bytes(data)?;
@@ -16,20 +18,29 @@ pub fn process_buy_tokens(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         buyer_target_info,
         buyer_base_info,
         token_program_info,
+        base_token_program_info,
+        optional_accounts @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
     //solana_program::msg!("Args: {:?}", args);
 
-    let pool = pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+    check_mut(pool_info)?;
 
-    buyer_target_info.as_token_account()?
-        .assert(|t| t.owner().eq(buyer_info.key))?
-        .assert(|t| t.mint().eq(target_mint_info.key))?;
+    let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+    let buyer_target = unpack_token_account(buyer_target_info)?;
+    check_condition(
+        buyer_target.owner.eq(buyer_info.key) && buyer_target.mint.eq(target_mint_info.key),
+        "Invalid buyer target account"
+    )?;
+
+    let (curve_table_info, wallet_purchases_info, hook_accounts) = split_buy_accounts(pool_info, pool, buyer_info, optional_accounts)?;
 
-    let tokens_after_fee_raw= buy_common(
+    let (tokens_after_fee_raw, _) = buy_common(
         buyer_info,
+        pool_info,
         target_mint_info,
         base_mint_info,
         target_vault_info,
@@ -37,17 +48,26 @@ pub fn process_buy_tokens(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         buyer_target_info,
         buyer_base_info,
         token_program_info,
+        base_token_program_info,
         pool,
+        curve_table_info,
+        wallet_purchases_info,
+        false,
+        hook_accounts,
         args.in_amount,
         args.min_amount_out,
+        &[],
     )?;
 
-    transfer_signed_with_bump(
+    let target_mint = unpack_mint(target_mint_info)?;
+    transfer_checked_signed_with_bump(
         target_vault_info,
         target_vault_info,
+        target_mint_info,
         buyer_target_info,
         token_program_info,
         tokens_after_fee_raw,
+        target_mint.decimals,
         &[
             TREASURY,
             pool_info.key.as_ref(),
@@ -59,6 +79,118 @@ pub fn process_buy_tokens(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
     Ok(())
 }
 
+pub fn process_batch_buy(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = BatchBuyIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        buyer_info,
+        pool_info,
+        target_mint_info,
+        base_mint_info,
+        target_vault_info,
+        base_vault_info,
+        buyer_base_info,
+        token_program_info,
+        base_token_program_info,
+        remaining_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_mut(pool_info)?;
+
+    check_condition(
+        !args.recipients.is_empty(),
+        "Batch buy needs at least one recipient"
+    )?;
+
+    // Recipient token accounts come last, after the usual optional buy accounts
+    let num_accounts = args.recipients.iter()
+        .map(|recipient| recipient.index as usize + 1)
+        .max()
+        .unwrap_or_default();
+    let split_at = remaining_accounts.len()
+        .checked_sub(num_accounts)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let (optional_accounts, recipient_accounts) = remaining_accounts.split_at(split_at);
+
+    for recipient_info in recipient_accounts {
+        check_mut(recipient_info)?;
+        check_condition(
+            recipient_info.key.ne(target_vault_info.key),
+            "Invalid recipient account"
+        )?;
+        let recipient = unpack_token_account(recipient_info)?;
+        check_condition(
+            recipient.mint.eq(target_mint_info.key),
+            "Invalid recipient account"
+        )?;
+    }
+
+    let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+    let (curve_table_info, wallet_purchases_info, hook_accounts) = split_buy_accounts(pool_info, pool, buyer_info, optional_accounts)?;
+
+    let (tokens_after_fee_raw, _) = buy_common(
+        buyer_info,
+        pool_info,
+        target_mint_info,
+        base_mint_info,
+        target_vault_info,
+        base_vault_info,
+        &recipient_accounts[0],
+        buyer_base_info,
+        token_program_info,
+        base_token_program_info,
+        pool,
+        curve_table_info,
+        wallet_purchases_info,
+        false,
+        hook_accounts,
+        args.in_amount,
+        args.min_amount_out,
+        &[],
+    )?;
+
+    let shares: Vec<u32> = args.recipients.iter()
+        .map(|recipient| recipient.share)
+        .collect();
+    let amounts = split_by_shares(tokens_after_fee_raw, &shares)?;
+
+    let target_mint = unpack_mint(target_mint_info)?;
+    for (recipient, amount) in args.recipients.iter().zip(amounts) {
+        let recipient_info = &recipient_accounts[recipient.index as usize];
+        if amount > 0 {
+            transfer_checked_signed_with_bump(
+                target_vault_info,
+                target_vault_info,
+                target_mint_info,
+                recipient_info,
+                token_program_info,
+                amount,
+                target_mint.decimals,
+                &[
+                    TREASURY,
+                    pool_info.key.as_ref(),
+                    target_mint_info.key.as_ref()
+                ],
+                pool.vault_a_bump,
+            )?;
+        }
+
+        BatchBuyEvent::new(
+            *pool_info.key,
+            *buyer_info.key,
+            *recipient_info.key,
+            amount,
+            recipient.share,
+        ).log();
+    }
+
+    Ok(())
+}
+
 pub fn process_buy_and_deposit_into_vm(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let raw_args = BuyAndDepositIntoVmIx::try_from_bytes(data)?;
     let args = raw_args.to_struct();
@@ -77,7 +209,9 @@ pub fn process_buy_and_deposit_into_vm(accounts: &[AccountInfo], data: &[u8]) ->
         vm_omnibus_info,
         vta_owner_info,
         token_program_info,
+        base_token_program_info,
         vm_program_info,
+        optional_accounts @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -89,13 +223,23 @@ pub fn process_buy_and_deposit_into_vm(accounts: &[AccountInfo], data: &[u8]) ->
     check_mut(vm_memory_info)?;
     check_program(vm_program_info, &VM_PROGRAM_ID)?;
 
-    let pool = pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+    check_mut(pool_info)?;
+
+    let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        target_mint_info.owner.eq(&spl_token::ID),
+        "VM deposits require an SPL Token currency mint"
+    )?;
 
     vm_omnibus_info.as_token_account()?
         .assert(|t| t.mint().eq(target_mint_info.key))?;
 
-    let tokens_after_fee_raw= buy_common(
+    let (curve_table_info, wallet_purchases_info, hook_accounts) = split_buy_accounts(pool_info, pool, buyer_info, optional_accounts)?;
+
+    let (tokens_after_fee_raw, _) = buy_common(
         buyer_info,
+        pool_info,
         target_mint_info,
         base_mint_info,
         target_vault_info,
@@ -103,9 +247,15 @@ pub fn process_buy_and_deposit_into_vm(accounts: &[AccountInfo], data: &[u8]) ->
         vm_omnibus_info,
         buyer_base_info,
         token_program_info,
+        base_token_program_info,
         pool,
+        curve_table_info,
+        wallet_purchases_info,
+        false,
+        hook_accounts,
         args.in_amount,
         args.min_amount_out,
+        &[],
     )?;
 
     deposit_into_vm(
@@ -131,10 +281,35 @@ pub fn process_buy_and_deposit_into_vm(accounts: &[AccountInfo], data: &[u8]) ->
     return Ok(())
 }
 
+// Optional trailing accounts of a buy: the pool's curve table (table-backed
+// pools only), the buyer's purchases account, then the pool's trade hook
+// accounts and any accounts the base mint's transfer hook needs. Only
+// presale and launch window buys need the purchases account, so it's told
+// apart by its address and may be left out
+#[allow(clippy::type_complexity)]
+pub fn split_buy_accounts<'a, 'info>(
+    pool_info: &AccountInfo<'info>,
+    pool: &LiquidityPool,
+    buyer_info: &AccountInfo<'info>,
+    optional_accounts: &'a [AccountInfo<'info>],
+) -> Result<(Option<&'a AccountInfo<'info>>, Option<&'a AccountInfo<'info>>, &'a [AccountInfo<'info>]), ProgramError> {
+    let (curve_table_info, rest) = split_curve_table(pool, optional_accounts)?;
+    Ok(match rest.split_first() {
+        Some((wallet_purchases_info, hook_accounts))
+            if wallet_purchases_info.key.eq(&find_wallet_purchases_pda(pool_info.key, buyer_info.key).0) =>
+            (curve_table_info, Some(wallet_purchases_info), hook_accounts),
+        _ => (curve_table_info, None, rest),
+    })
+}
+
 // Buy ixn common utility that executes everything but transfering the bought
-// tokens to the intended destination.
-fn buy_common<'info>(
+// tokens to the intended destination. Returns the tokens bought and the base
+// quarks paid for them. A PDA buyer passes its seeds (bump last) to sign for
+// the base transfer; wallets pass none and must sign the transaction.
+#[allow(clippy::too_many_arguments)]
+pub fn buy_common<'info>(
     buyer_info: &AccountInfo<'info>,
+    pool_info: &AccountInfo<'info>,
     target_mint_info: &AccountInfo<'info>,
     base_mint_info: &AccountInfo<'info>,
     target_vault_info: &AccountInfo<'info>,
@@ -142,26 +317,41 @@ fn buy_common<'info>(
     buyer_target_info: &AccountInfo<'info>,
     buyer_base_info: &AccountInfo<'info>,
     token_program_info: &AccountInfo<'info>,
-    pool: &LiquidityPool,
+    base_token_program_info: &AccountInfo<'info>,
+    pool: &mut LiquidityPool,
+    curve_table_info: Option<&AccountInfo<'info>>,
+    wallet_purchases_info: Option<&AccountInfo<'info>>,
+    launch_exempt: bool, // Skips the presale and launch window caps
+    hook_accounts: &[AccountInfo<'info>],
     in_amount_arg: u64,
     min_amount_out_arg: u64,
-) -> Result<u64, ProgramError>{
+    buyer_seeds: &[&[u8]],
+) -> Result<(u64, u64), ProgramError>{
     // Basic checks
-    check_signer(buyer_info)?;
+    if buyer_seeds.is_empty() {
+        check_signer(buyer_info)?;
+    }
+    check_condition(
+        !pool.is_buy_paused(),
+        "Pool is paused"
+    )?;
     check_mut(target_vault_info)?;
     check_mut(base_vault_info)?;
     check_mut(buyer_target_info)?;
     check_mut(buyer_base_info)?;
-    check_program(token_program_info, &spl_token::id())?;
+    check_token_program(token_program_info, target_mint_info)?;
+    check_token_program(base_token_program_info, base_mint_info)?;
 
-    let base_mint = base_mint_info.as_mint()?;
-    let buyer_base = buyer_base_info.as_token_account()?;
-    let target_vault = target_vault_info.as_token_account()?;
-    let base_vault = base_vault_info.as_token_account()?;
+    let target_mint = unpack_mint(target_mint_info)?;
+    let base_mint = unpack_mint(base_mint_info)?;
+    let buyer_base = unpack_token_account(buyer_base_info)?;
+    let target_vault = unpack_token_account(target_vault_info)?;
+    let base_vault = unpack_token_account(base_vault_info)?;
 
-    buyer_base
-        .assert(|t| t.owner().eq(buyer_info.key))?
-        .assert(|t| t.mint().eq(base_mint_info.key))?;
+    check_condition(
+        buyer_base.owner.eq(buyer_info.key) && buyer_base.mint.eq(base_mint_info.key),
+        "Invalid buyer base account"
+    )?;
 
     check_condition(
         pool.mint_a == *target_mint_info.key && pool.mint_b == *base_mint_info.key,
@@ -172,34 +362,87 @@ fn buy_common<'info>(
         "Invalid vault accounts"
     )?;
 
-    let mint_a_decimals = TOKEN_DECIMALS;
-    let mint_b_decimals = base_mint.decimals();
+    let mint_a_decimals = target_mint.decimals;
+    let mint_b_decimals = base_mint.decimals;
 
-    let tokens_left_raw = target_vault.amount();
-    let supply_from_bonding = MAX_TOKEN_SUPPLY
-        .checked_mul(QUARKS_PER_TOKEN)
-        .ok_or(ProgramError::InvalidArgument)?
+    let tokens_left_raw = target_vault.amount;
+    let supply_from_bonding = to_quarks(pool.curve_max_supply, mint_a_decimals)?
         .checked_sub(tokens_left_raw)
         .ok_or(ProgramError::InvalidArgument)?;
 
-    let current_value_raw = base_vault.amount()
+    let current_value_raw = base_vault.amount
         .checked_sub(pool.fees_accumulated)
         .ok_or(ProgramError::InvalidArgument)?;
 
     let mut in_amount_raw = in_amount_arg;
     if in_amount_raw == 0 {
-        in_amount_raw = buyer_base.amount();
+        in_amount_raw = buyer_base.amount;
     }
 
+    let wallet_purchases = load_wallet_purchases(pool_info, buyer_info, wallet_purchases_info)?;
+
+    // Before launch only allowlisted wallets may buy, up to the presale cap.
+    // Right after launch every wallet is capped for the launch window.
+    let clock = Clock::get()?;
+    let launched = pool.is_launched(clock.unix_timestamp);
+    let (wallet_spent, wallet_cap) = if launch_exempt {
+        (None, 0)
+    } else if !launched {
+        let spent = wallet_purchases
+            .filter(|purchases| purchases.presale_allowed != 0)
+            .map(|purchases| &mut purchases.presale_spent);
+        check_condition(
+            spent.is_some(),
+            "Pool has not launched"
+        )?;
+        (spent, pool.presale_cap)
+    } else if pool.is_launch_window(clock.unix_timestamp) {
+        let spent = wallet_purchases.map(|purchases| &mut purchases.launch_spent);
+        check_condition(
+            spent.is_some(),
+            "Wallet purchases account required during the launch window"
+        )?;
+        (spent, pool.launch_cap)
+    } else {
+        (None, 0)
+    };
+    if let Some(spent) = &wallet_spent {
+        let wallet_left = wallet_cap.saturating_sub(**spent);
+        check_condition(
+            wallet_left > 0,
+            "Wallet buy cap reached"
+        )?;
+        in_amount_raw = in_amount_raw.min(wallet_left);
+    }
+
+    // Token-2022 transfer fees come out of what the vault receives
+    let epoch = clock.epoch;
+    let received_raw = in_amount_raw
+        .checked_sub(transfer_fee(base_mint_info, in_amount_raw, epoch)?)
+        .ok_or(ProgramError::InvalidArgument)?;
+
     let tokens_left = to_numeric(tokens_left_raw, mint_a_decimals)?;
     let supply = to_numeric(supply_from_bonding, mint_a_decimals)?;
     let current_value = to_numeric(current_value_raw, mint_b_decimals)?;
 
-    let in_amount = to_numeric(in_amount_raw, mint_b_decimals)?;
+    let in_amount = to_numeric(received_raw, mint_b_decimals)?;
     let uncapped_new_value = current_value
         .checked_add(&in_amount)
         .ok_or(ProgramError::InvalidArgument)?;
-    let max_cumulative_value = UnsignedNumeric::from_scaled_u128(SCALED_MAX_CUMULATIVE_VALUE);
+    let curve_table_data = curve_table_info
+        .map(|info| info.try_borrow_data())
+        .transpose()?;
+    let curve = match (curve_table_info, &curve_table_data) {
+        (Some(info), Some(data)) => pool.table_curve(info.key, data)?,
+        _ => pool.curve()?,
+    };
+    let zero = UnsignedNumeric::zero();
+    let curve_max_supply = to_numeric(
+        to_quarks(pool.curve_max_supply, mint_a_decimals)?,
+        mint_a_decimals,
+    )?;
+    let max_cumulative_value = curve.tokens_to_value(&zero, &curve_max_supply)
+        .ok_or(ProgramError::InvalidArgument)?;
     let capped_new_value = if uncapped_new_value.greater_than(&max_cumulative_value) {
         max_cumulative_value
     } else {
@@ -209,8 +452,6 @@ fn buy_common<'info>(
         .checked_sub(&current_value)
         .ok_or(ProgramError::InvalidArgument)?;
 
-    let curve = DiscreteExponentialCurve::default();
-    let zero = UnsignedNumeric::zero();
     let new_supply = curve.value_to_tokens(&zero, &capped_new_value)
         .ok_or(ProgramError::InvalidArgument)?;
     let mut tokens_bought = new_supply
@@ -220,10 +461,19 @@ fn buy_common<'info>(
         tokens_bought = tokens_left;
     }
 
+    let price_before = curve.spot_price_at_supply(&supply)
+        .ok_or(ProgramError::InvalidArgument)?;
+    let price_after = supply.checked_add(&tokens_bought)
+        .and_then(|supply| curve.spot_price_at_supply(&supply))
+        .ok_or(ProgramError::InvalidArgument)?;
+    check_circuit_breaker(pool_info, buyer_info, pool, &price_before, &price_after)?;
+
     //solana_program::msg!("paying: ${}", capped_in_amount.to_string());
     //solana_program::msg!("for: {}", tokens_bought.to_string());
 
-    let actual_in_amount_raw = from_numeric(capped_in_amount, mint_b_decimals)?;
+    let actual_received_raw = from_numeric(capped_in_amount, mint_b_decimals)?;
+    let actual_in_amount_raw = amount_before_transfer_fee(base_mint_info, actual_received_raw, epoch)?
+        .min(in_amount_raw);
     let tokens_bought_raw = from_numeric(tokens_bought, mint_a_decimals)?;
 
     check_condition(
@@ -235,13 +485,59 @@ fn buy_common<'info>(
         "Slippage exceeded"
     )?;
 
-    transfer(
-        buyer_info,
-        buyer_base_info,
-        base_vault_info,
-        token_program_info,
-        actual_in_amount_raw,
+    if buyer_seeds.is_empty() {
+        transfer_checked_with_extra_accounts(
+            buyer_info,
+            buyer_base_info,
+            base_mint_info,
+            base_vault_info,
+            base_token_program_info,
+            hook_accounts,
+            actual_in_amount_raw,
+            mint_b_decimals,
+        )?;
+    } else {
+        transfer_checked_signed_with_extra_accounts(
+            buyer_info,
+            buyer_base_info,
+            base_mint_info,
+            base_vault_info,
+            base_token_program_info,
+            hook_accounts,
+            actual_in_amount_raw,
+            mint_b_decimals,
+            buyer_seeds,
+        )?;
+    }
+
+    let vault_received_raw = unpack_token_account(base_vault_info)?.amount
+        .checked_sub(base_vault.amount)
+        .ok_or(ProgramError::InvalidArgument)?;
+    check_condition(
+        vault_received_raw >= actual_received_raw,
+        "Base transfer fee exceeded quote"
     )?;
 
-    Ok(tokens_bought_raw)
+    if let Some(spent) = wallet_spent {
+        *spent = spent
+            .checked_add(actual_in_amount_raw)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    let summary = TradeSummary::new(
+        *pool_info.key,
+        *buyer_info.key,
+        pool.mint_a,
+        pool.mint_b,
+        OrderSide::Buy,
+        tokens_bought_raw,
+        actual_in_amount_raw,
+        0,
+        price_after.value.as_u128(),
+        clock.slot,
+        clock.unix_timestamp,
+    );
+    invoke_trade_hook(pool_info, pool, hook_accounts, &summary)?;
+
+    Ok((tokens_bought_raw, actual_in_amount_raw))
 }
@@ -0,0 +1,322 @@
+use steel::*;
+use solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use flipcash_api::prelude::*;
+use super::{buy_common, split_buy_accounts};
+
+pub fn process_create_cash_link(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = CreateCashLinkIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        creator_info,
+        link_key_info,
+        cash_link_info,
+        cash_link_vault_info,
+        target_mint_info,
+        token_program_info,
+        system_program_info,
+        rent_sysvar_info,
+        source_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(creator_info)?;
+    check_mut(cash_link_info)?;
+    check_mut(cash_link_vault_info)?;
+    check_token_program(token_program_info, target_mint_info)?;
+    check_program(system_program_info, &system_program::id())?;
+    check_sysvar(rent_sysvar_info, &sysvar::rent::id())?;
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    check_condition(
+        args.expires_at > now,
+        "Cash link must expire in the future"
+    )?;
+    check_condition(
+        (args.amount > 0) != (args.in_amount > 0),
+        "Cash link needs either an amount to deposit or an amount to buy with"
+    )?;
+
+    let cash_link_seeds: &[&[u8]] = &[ CASH_LINK, link_key_info.key.as_ref() ];
+    check_uninitialized_pda(
+        cash_link_info,
+        cash_link_seeds,
+        &flipcash_api::id()
+    )?;
+
+    check_uninitialized_pda(
+        cash_link_vault_info,
+        &[ TREASURY, cash_link_info.key.as_ref(), target_mint_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    create_token_account(
+        target_mint_info,
+        cash_link_vault_info,
+        &[
+            TREASURY,
+            cash_link_info.key.as_ref(),
+            target_mint_info.key.as_ref(),
+            &[args.vault_bump]
+        ],
+        creator_info,
+        token_program_info,
+        system_program_info,
+        rent_sysvar_info,
+    )?;
+
+    let target_mint = unpack_mint(target_mint_info)?;
+
+    if args.in_amount > 0 {
+        // Buy straight from the pool into the link
+        let [
+            pool_info,
+            base_mint_info,
+            target_vault_info,
+            base_vault_info,
+            creator_base_info,
+            base_token_program_info,
+            optional_accounts @ ..
+        ] = source_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        check_mut(pool_info)?;
+
+        let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+        let (curve_table_info, wallet_purchases_info, hook_accounts) = split_buy_accounts(pool_info, pool, creator_info, optional_accounts)?;
+
+        let (tokens_after_fee_raw, _) = buy_common(
+            creator_info,
+            pool_info,
+            target_mint_info,
+            base_mint_info,
+            target_vault_info,
+            base_vault_info,
+            cash_link_vault_info,
+            creator_base_info,
+            token_program_info,
+            base_token_program_info,
+            pool,
+            curve_table_info,
+            wallet_purchases_info,
+            false,
+            hook_accounts,
+            args.in_amount,
+            args.min_amount_out,
+            &[],
+        )?;
+
+        transfer_checked_signed_with_bump(
+            target_vault_info,
+            target_vault_info,
+            target_mint_info,
+            cash_link_vault_info,
+            token_program_info,
+            tokens_after_fee_raw,
+            target_mint.decimals,
+            &[
+                TREASURY,
+                pool_info.key.as_ref(),
+                target_mint_info.key.as_ref()
+            ],
+            pool.vault_a_bump,
+        )?;
+    } else {
+        // Deposit currency the creator already holds
+        let [creator_target_info] = source_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        check_mut(creator_target_info)?;
+
+        transfer_checked(
+            creator_info,
+            creator_target_info,
+            target_mint_info,
+            cash_link_vault_info,
+            token_program_info,
+            args.amount,
+            target_mint.decimals,
+        )?;
+    }
+
+    create_program_account_with_bump::<CashLink>(
+        cash_link_info,
+        system_program_info,
+        creator_info,
+        &flipcash_api::ID,
+        cash_link_seeds,
+        args.bump,
+    )?;
+
+    let amount = unpack_token_account(cash_link_vault_info)?.amount;
+    let cash_link = cash_link_info.as_account_mut::<CashLink>(&flipcash_api::ID)?;
+
+    cash_link.creator = *creator_info.key;
+    cash_link.link_key = *link_key_info.key;
+    cash_link.mint = *target_mint_info.key;
+    cash_link.vault = *cash_link_vault_info.key;
+    cash_link.amount = amount;
+    cash_link.created_at = now;
+    cash_link.created_slot = clock.slot;
+    cash_link.expires_at = args.expires_at;
+    cash_link.bump = args.bump;
+    cash_link.vault_bump = args.vault_bump;
+
+    Ok(())
+}
+
+pub fn process_claim_cash_link(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = ClaimCashLinkIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        cash_link_info,
+        cash_link_vault_info,
+        target_mint_info,
+        destination_info,
+        creator_info,
+        token_program_info,
+        instructions_sysvar_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_sysvar(instructions_sysvar_info, &sysvar::instructions::id())?;
+
+    let cash_link = *cash_link_info.as_account::<CashLink>(&flipcash_api::ID)?;
+
+    check_condition(
+        !cash_link.is_expired(Clock::get()?.unix_timestamp),
+        "Cash link has expired"
+    )?;
+
+    // The instruction before this one must be the Ed25519 program checking
+    // the link key's signature over this cash link and destination
+    let current_index = load_current_index_checked(instructions_sysvar_info)?;
+    check_condition(
+        current_index > 0,
+        "Missing cash link signature"
+    )?;
+    let signature_ix = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar_info)?;
+    let message = cash_link_claim_message(cash_link_info.key, cash_link.created_slot, destination_info.key);
+    check_condition(
+        signature_ix.program_id.eq(&solana_program::ed25519_program::id()) &&
+        ed25519_signed_message(&signature_ix.data)
+            .is_some_and(|(key, signed)| key.eq(&cash_link.link_key) && signed.eq(&message)),
+        "Missing cash link signature"
+    )?;
+
+    release_cash_link(
+        &cash_link,
+        cash_link_info,
+        cash_link_vault_info,
+        target_mint_info,
+        destination_info,
+        creator_info,
+        token_program_info,
+    )
+}
+
+pub fn process_reclaim_cash_link(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = ReclaimCashLinkIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        creator_info,
+        cash_link_info,
+        cash_link_vault_info,
+        target_mint_info,
+        creator_target_info,
+        token_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(creator_info)?;
+
+    let cash_link = *cash_link_info.as_account::<CashLink>(&flipcash_api::ID)?;
+
+    check_condition(
+        cash_link.is_expired(Clock::get()?.unix_timestamp),
+        "Cash link has not expired"
+    )?;
+
+    release_cash_link(
+        &cash_link,
+        cash_link_info,
+        cash_link_vault_info,
+        target_mint_info,
+        creator_target_info,
+        creator_info,
+        token_program_info,
+    )
+}
+
+// Pays out everything in a cash link's vault and closes the link, returning
+// its rent to the creator
+fn release_cash_link<'info>(
+    cash_link: &CashLink,
+    cash_link_info: &AccountInfo<'info>,
+    cash_link_vault_info: &AccountInfo<'info>,
+    target_mint_info: &AccountInfo<'info>,
+    destination_info: &AccountInfo<'info>,
+    creator_info: &AccountInfo<'info>,
+    token_program_info: &AccountInfo<'info>,
+) -> ProgramResult {
+    check_mut(cash_link_info)?;
+    check_mut(cash_link_vault_info)?;
+    check_mut(destination_info)?;
+    check_mut(creator_info)?;
+    check_token_program(token_program_info, target_mint_info)?;
+
+    check_condition(
+        cash_link.creator.eq(creator_info.key),
+        "Cash link creator does not match"
+    )?;
+    check_condition(
+        cash_link.mint.eq(target_mint_info.key) && cash_link.vault.eq(cash_link_vault_info.key),
+        "Invalid cash link accounts"
+    )?;
+
+    let destination = unpack_token_account(destination_info)?;
+    check_condition(
+        destination.mint.eq(target_mint_info.key),
+        "Invalid destination account"
+    )?;
+
+    let vault_seeds: &[&[u8]] = &[
+        TREASURY,
+        cash_link_info.key.as_ref(),
+        target_mint_info.key.as_ref()
+    ];
+
+    let target_mint = unpack_mint(target_mint_info)?;
+    let amount = unpack_token_account(cash_link_vault_info)?.amount;
+    transfer_checked_signed_with_bump(
+        cash_link_vault_info,
+        cash_link_vault_info,
+        target_mint_info,
+        destination_info,
+        token_program_info,
+        amount,
+        target_mint.decimals,
+        vault_seeds,
+        cash_link.vault_bump,
+    )?;
+
+    close_token_account_signed_with_bump(
+        cash_link_vault_info,
+        creator_info,
+        cash_link_vault_info,
+        token_program_info,
+        vault_seeds,
+        cash_link.vault_bump,
+    )?;
+
+    close_account(cash_link_info, creator_info)
+}
@@ -9,6 +9,9 @@ pub fn process_initialize_currency(accounts: &[AccountInfo], data: &[u8]) -> Pro
         authority_info,
         mint_info,
         currency_info,
+        symbol_info,
+        registry_info,
+        registry_page_info,
 
         token_program_info,
         system_program_info,
@@ -22,11 +25,30 @@ pub fn process_initialize_currency(accounts: &[AccountInfo], data: &[u8]) -> Pro
     check_signer(authority_info)?;
     check_mut(mint_info)?;
     check_mut(currency_info)?;
+    check_mut(symbol_info)?;
+    check_mut(registry_info)?;
+    check_mut(registry_page_info)?;
 
-    check_program(token_program_info, &spl_token::id())?;
+    // SPL Token mints take Metaplex metadata, Token-2022 mints carry their own
+    check_condition(
+        is_token_program(token_program_info.key),
+        "Unsupported token program"
+    )?;
     check_program(system_program_info, &system_program::id())?;
     check_sysvar(rent_sysvar_info, &sysvar::rent::id())?;
 
+    check_condition(
+        args.decimals <= MAX_TOKEN_DECIMALS,
+        "Currency decimals out of bounds"
+    )?;
+
+    check_condition(
+        args.max_supply >= MIN_CURVE_SUPPLY &&
+        args.max_supply <= MAX_CURVE_SUPPLY &&
+        to_quarks(args.max_supply, args.decimals).is_ok(),
+        "Currency max supply out of bounds"
+    )?;
+
     check_uninitialized_pda(
         mint_info,
         &[ 
@@ -44,12 +66,23 @@ pub fn process_initialize_currency(accounts: &[AccountInfo], data: &[u8]) -> Pro
         &flipcash_api::id()
     )?;
 
+    check_condition(
+        symbol_info.data_is_empty(),
+        "Currency symbol is already taken"
+    )?;
+
+    check_uninitialized_pda(
+        symbol_info,
+        &[ SYMBOL, raw_args.symbol.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
     // Create the mint account.
     create_mint_account(
         mint_info,
         mint_info.key,  // mint_authority
         None,           // freeze_authority
-        TOKEN_DECIMALS,
+        args.decimals,
         &[
              MINT, 
              authority_info.key.as_ref(),
@@ -58,6 +91,7 @@ pub fn process_initialize_currency(accounts: &[AccountInfo], data: &[u8]) -> Pro
              &[args.mint_bump]
         ],
         authority_info,
+        token_program_info,
         system_program_info,
         rent_sysvar_info,
     )?;
@@ -82,9 +116,131 @@ pub fn process_initialize_currency(accounts: &[AccountInfo], data: &[u8]) -> Pro
     currency.name = raw_args.name;
     currency.symbol = raw_args.symbol;
     currency.seed = args.seed;
+    currency.decimals = args.decimals;
+    currency.max_supply = args.max_supply;
     currency.bump = args.bump;
     currency.mint_bump = args.mint_bump;
 
+    reserve_symbol(
+        mint_info,
+        symbol_info,
+        authority_info,
+        system_program_info,
+        &raw_args.symbol,
+        args.symbol_bump,
+    )?;
+
+    register_currency(
+        mint_info,
+        registry_info,
+        registry_page_info,
+        authority_info,
+        system_program_info,
+        args.registry_bump,
+        args.registry_page_bump,
+    )
+}
+
+/// Creates the symbol's reservation, held by the mint.
+pub fn reserve_symbol<'info>(
+    mint_info: &AccountInfo<'info>,
+    symbol_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    symbol: &[u8; MAX_SYMBOL_LEN],
+    symbol_bump: u8,
+) -> ProgramResult {
+    create_program_account_with_bump::<SymbolReservation>(
+        symbol_info,
+        system_program_info,
+        payer_info,
+        &flipcash_api::ID,
+        &[
+            SYMBOL,
+            symbol.as_ref()
+        ],
+        symbol_bump,
+    )?;
+
+    let reservation = symbol_info.as_account_mut::<SymbolReservation>(&flipcash_api::ID)?;
+
+    reservation.symbol = *symbol;
+    reservation.mint = *mint_info.key;
+    reservation.bump = symbol_bump;
+
+    Ok(())
+}
+
+/// Appends the mint to the currency registry, creating the registry with the
+/// first currency and each page as the previous one fills up.
+pub fn register_currency<'info>(
+    mint_info: &AccountInfo<'info>,
+    registry_info: &AccountInfo<'info>,
+    registry_page_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    registry_bump: u8,
+    registry_page_bump: u8,
+) -> ProgramResult {
+    if registry_info.data_is_empty() {
+        check_uninitialized_pda(
+            registry_info,
+            &[ REGISTRY ],
+            &flipcash_api::id()
+        )?;
+
+        create_program_account_with_bump::<CurrencyRegistry>(
+            registry_info,
+            system_program_info,
+            payer_info,
+            &flipcash_api::ID,
+            &[ REGISTRY ],
+            registry_bump,
+        )?;
+
+        let registry = registry_info.as_account_mut::<CurrencyRegistry>(&flipcash_api::ID)?;
+        registry.bump = registry_bump;
+    }
+
+    let registry = registry_info.as_account_mut::<CurrencyRegistry>(&flipcash_api::ID)?;
+    let page = registry.next_page();
+    let index = (registry.num_currencies % REGISTRY_PAGE_LEN as u64) as usize;
+
+    if index == 0 {
+        check_uninitialized_pda(
+            registry_page_info,
+            &[ REGISTRY, &page.to_le_bytes() ],
+            &flipcash_api::id()
+        )?;
+
+        create_program_account_with_bump::<CurrencyRegistryPage>(
+            registry_page_info,
+            system_program_info,
+            payer_info,
+            &flipcash_api::ID,
+            &[
+                REGISTRY,
+                &page.to_le_bytes()
+            ],
+            registry_page_bump,
+        )?;
+
+        let registry_page = registry_page_info.as_account_mut::<CurrencyRegistryPage>(&flipcash_api::ID)?;
+        registry_page.page = page;
+        registry_page.bump = registry_page_bump;
+    }
+
+    let registry_page = registry_page_info.as_account_mut::<CurrencyRegistryPage>(&flipcash_api::ID)?;
+
+    check_condition(
+        registry_page.page == page,
+        "Registry page does not match"
+    )?;
+
+    registry_page.mints[index] = *mint_info.key;
+    registry_page.num_mints += 1;
+    registry.num_currencies += 1;
+
     Ok(())
 }
 
@@ -0,0 +1,197 @@
+use solana_program::rent::Rent;
+use steel::*;
+use flipcash_api::prelude::*;
+
+pub fn process_initialize_curve_table(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = InitializeCurveTableIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        curve_table_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(curve_table_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    check_condition(
+        args.step_size > 0 && args.num_steps > 0,
+        "Curve table must have at least one step"
+    )?;
+
+    let max_supply = args.step_size
+        .checked_mul(args.num_steps)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    check_condition(
+        (MIN_CURVE_SUPPLY..=MAX_CURVE_SUPPLY).contains(&max_supply),
+        "Curve max supply out of bounds"
+    )?;
+
+    check_uninitialized_pda(
+        curve_table_info,
+        &[ CURVE_TABLE, authority_info.key.as_ref(), args.seed.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    create_program_account_with_bump::<CurveTable>(
+        curve_table_info,
+        system_program_info,
+        authority_info,
+        &flipcash_api::ID,
+        &[
+            CURVE_TABLE,
+            authority_info.key.as_ref(),
+            args.seed.as_ref()
+        ],
+        args.bump,
+    )?;
+
+    let mut data = curve_table_info.try_borrow_mut_data()?;
+    let curve_table = CurveTable::unpack_mut(&mut data)?;
+
+    curve_table.authority = *authority_info.key;
+    curve_table.seed = args.seed;
+    curve_table.step_size = args.step_size;
+    curve_table.num_steps = args.num_steps;
+    curve_table.num_entries = 0;
+    curve_table.is_finalized = 0;
+    curve_table.bump = args.bump;
+
+    Ok(())
+}
+
+pub fn process_write_curve_table(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = WriteCurveTableIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        authority_info,
+        curve_table_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(curve_table_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    check_condition(
+        !args.entries.is_empty(),
+        "No curve table entries to write"
+    )?;
+
+    curve_table_info.has_owner(&flipcash_api::ID)?;
+
+    let num_entries = {
+        let data = curve_table_info.try_borrow_data()?;
+        let (curve_table, entries) = CurveTable::unpack_entries(&data)?;
+
+        check_condition(
+            curve_table.authority.eq(authority_info.key),
+            "Curve table authority does not match"
+        )?;
+
+        check_condition(
+            curve_table.is_finalized == 0,
+            "Curve table is finalized"
+        )?;
+
+        check_condition(
+            curve_table.num_entries + (args.entries.len() as u64) <= curve_table.num_steps + 1,
+            "Curve table entries exceed its steps"
+        )?;
+
+        check_condition(
+            CurveTable::check_entries(curve_table.step_size, entries.last(), &args.entries),
+            "Curve table entries must be monotonic and consistent"
+        )?;
+
+        curve_table.num_entries as usize
+    };
+
+    // Grow the account to fit the new entries, topping up rent first
+    let old_len = curve_table_info.data_len();
+    let new_len = CurveTable::size_for(num_entries + args.entries.len())
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(curve_table_info.lamports());
+    if rent_due > 0 {
+        curve_table_info.collect(rent_due, authority_info)?;
+    }
+    curve_table_info.realloc(new_len, false)?;
+
+    let mut data = curve_table_info.try_borrow_mut_data()?;
+    data[old_len..].copy_from_slice(bytemuck::cast_slice(&args.entries));
+
+    let curve_table = CurveTable::unpack_mut(&mut data)?;
+    curve_table.num_entries += args.entries.len() as u64;
+
+    Ok(())
+}
+
+pub fn process_finalize_curve_table(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = FinalizeCurveTableIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        curve_table_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(curve_table_info)?;
+
+    curve_table_info.has_owner(&flipcash_api::ID)?;
+
+    let mut data = curve_table_info.try_borrow_mut_data()?;
+    let (curve_table, _) = CurveTable::unpack_entries_mut(&mut data)?;
+
+    check_condition(
+        curve_table.authority.eq(authority_info.key),
+        "Curve table authority does not match"
+    )?;
+
+    check_condition(
+        curve_table.is_finalized == 0,
+        "Curve table is finalized"
+    )?;
+
+    // Every entry was checked against its predecessor on write
+    check_condition(
+        curve_table.is_complete(),
+        "Curve table is missing entries"
+    )?;
+
+    curve_table.is_finalized = 1;
+
+    Ok(())
+}
+
+/// Takes the pool's CurveTable off the front of the optional trailing
+/// accounts when the pool is table-backed, returning it and the rest.
+pub fn split_curve_table<'a, 'info>(
+    pool: &LiquidityPool,
+    accounts: &'a [AccountInfo<'info>],
+) -> Result<(Option<&'a AccountInfo<'info>>, &'a [AccountInfo<'info>]), ProgramError> {
+    if !pool.is_table_backed() {
+        return Ok((None, accounts));
+    }
+
+    let [curve_table_info, rest @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    curve_table_info.has_owner(&flipcash_api::ID)?;
+
+    Ok((Some(curve_table_info), rest))
+}
@@ -0,0 +1,403 @@
+use steel::*;
+use flipcash_api::prelude::*;
+use flipcash_api::event::DcaFillEvent;
+use super::{buy_common, split_buy_accounts};
+
+pub fn process_create_dca_plan(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = CreateDcaPlanIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        owner_info,
+        pool_info,
+        plan_info,
+        plan_vault_info,
+        base_mint_info,
+        base_token_program_info,
+        system_program_info,
+        rent_sysvar_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(owner_info)?;
+    check_mut(owner_info)?;
+    check_mut(plan_info)?;
+    check_mut(plan_vault_info)?;
+    check_token_program(base_token_program_info, base_mint_info)?;
+    check_program(system_program_info, &system_program::id())?;
+    check_sysvar(rent_sysvar_info, &sysvar::rent::id())?;
+
+    let pool = pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        pool.mint_b.eq(base_mint_info.key),
+        "Invalid mint accounts"
+    )?;
+    check_condition(
+        args.amount_per_fill > 0 && args.interval > 0,
+        "DCA plan needs an amount per fill and an interval"
+    )?;
+    check_condition(
+        args.min_amount_out > 0,
+        "DCA plan needs a minimum amount out"
+    )?;
+
+    let id = args.id.to_le_bytes();
+    let plan_seeds: &[&[u8]] = &[ DCA, pool_info.key.as_ref(), owner_info.key.as_ref(), &id ];
+    check_uninitialized_pda(
+        plan_info,
+        plan_seeds,
+        &flipcash_api::id()
+    )?;
+
+    check_uninitialized_pda(
+        plan_vault_info,
+        &[ TREASURY, plan_info.key.as_ref(), base_mint_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    create_token_account(
+        base_mint_info,
+        plan_vault_info,
+        &[
+            TREASURY,
+            plan_info.key.as_ref(),
+            base_mint_info.key.as_ref(),
+            &[args.vault_bump]
+        ],
+        owner_info,
+        base_token_program_info,
+        system_program_info,
+        rent_sysvar_info,
+    )?;
+
+    create_program_account_with_bump::<DcaPlan>(
+        plan_info,
+        system_program_info,
+        owner_info,
+        &flipcash_api::ID,
+        plan_seeds,
+        args.bump,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let plan = plan_info.as_account_mut::<DcaPlan>(&flipcash_api::ID)?;
+
+    plan.owner = *owner_info.key;
+    plan.pool = *pool_info.key;
+    plan.vault = *plan_vault_info.key;
+    plan.id = args.id;
+    plan.amount_per_fill = args.amount_per_fill;
+    plan.min_amount_out = args.min_amount_out;
+    plan.interval = args.interval;
+    plan.next_fill_at = args.first_fill_at.max(now);
+    plan.bump = args.bump;
+    plan.vault_bump = args.vault_bump;
+
+    Ok(())
+}
+
+pub fn process_deposit_dca(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = DepositDcaIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        owner_info,
+        plan_info,
+        plan_vault_info,
+        base_mint_info,
+        owner_base_info,
+        base_token_program_info,
+        hook_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(owner_info)?;
+    check_mut(plan_vault_info)?;
+    check_mut(owner_base_info)?;
+    check_token_program(base_token_program_info, base_mint_info)?;
+
+    let plan = plan_info.as_account::<DcaPlan>(&flipcash_api::ID)?;
+
+    check_condition(
+        plan.owner.eq(owner_info.key),
+        "DCA plan owner does not match"
+    )?;
+    check_condition(
+        plan.vault.eq(plan_vault_info.key),
+        "Invalid DCA plan accounts"
+    )?;
+    check_condition(
+        args.amount > 0,
+        "Nothing to deposit"
+    )?;
+
+    let base_mint = unpack_mint(base_mint_info)?;
+    transfer_checked_with_extra_accounts(
+        owner_info,
+        owner_base_info,
+        base_mint_info,
+        plan_vault_info,
+        base_token_program_info,
+        hook_accounts,
+        args.amount,
+        base_mint.decimals,
+    )
+}
+
+pub fn process_withdraw_dca(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = WithdrawDcaIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        owner_info,
+        plan_info,
+        plan_vault_info,
+        base_mint_info,
+        owner_base_info,
+        base_token_program_info,
+        hook_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(owner_info)?;
+
+    let plan = *plan_info.as_account::<DcaPlan>(&flipcash_api::ID)?;
+
+    let balance = unpack_token_account(plan_vault_info)?.amount;
+    let amount = if args.amount == 0 { balance } else { args.amount };
+    check_condition(
+        amount > 0 && amount <= balance,
+        "Invalid withdrawal amount"
+    )?;
+
+    withdraw_from_plan(
+        &plan,
+        plan_info,
+        plan_vault_info,
+        base_mint_info,
+        owner_base_info,
+        owner_info,
+        base_token_program_info,
+        hook_accounts,
+        amount,
+    )
+}
+
+pub fn process_close_dca_plan(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = CloseDcaPlanIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        owner_info,
+        plan_info,
+        plan_vault_info,
+        base_mint_info,
+        owner_base_info,
+        base_token_program_info,
+        hook_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(owner_info)?;
+    check_mut(owner_info)?;
+    check_mut(plan_info)?;
+
+    let plan = *plan_info.as_account::<DcaPlan>(&flipcash_api::ID)?;
+
+    let balance = unpack_token_account(plan_vault_info)?.amount;
+    if balance > 0 {
+        withdraw_from_plan(
+            &plan,
+            plan_info,
+            plan_vault_info,
+            base_mint_info,
+            owner_base_info,
+            owner_info,
+            base_token_program_info,
+            hook_accounts,
+            balance,
+        )?;
+    }
+
+    close_token_account_signed_with_bump(
+        plan_vault_info,
+        owner_info,
+        plan_vault_info,
+        base_token_program_info,
+        &[
+            TREASURY,
+            plan_info.key.as_ref(),
+            base_mint_info.key.as_ref()
+        ],
+        plan.vault_bump,
+    )?;
+
+    close_account(plan_info, owner_info)
+}
+
+pub fn process_execute_dca(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = ExecuteDcaIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        executor_info,
+        plan_info,
+        plan_vault_info,
+        owner_target_info,
+        pool_info,
+        target_mint_info,
+        base_mint_info,
+        target_vault_info,
+        base_vault_info,
+        token_program_info,
+        base_token_program_info,
+        optional_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(executor_info)?;
+    check_mut(plan_info)?;
+    check_mut(pool_info)?;
+
+    let plan = plan_info.as_account_mut::<DcaPlan>(&flipcash_api::ID)?;
+
+    check_condition(
+        plan.pool.eq(pool_info.key) && plan.vault.eq(plan_vault_info.key),
+        "Invalid DCA plan accounts"
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    check_condition(
+        plan.is_due(now),
+        "DCA fill is not due yet"
+    )?;
+
+    let owner_target = unpack_token_account(owner_target_info)?;
+    check_condition(
+        owner_target.owner.eq(&plan.owner) && owner_target.mint.eq(target_mint_info.key),
+        "Invalid owner target account"
+    )?;
+
+    // The last fill may spend less than a full amount
+    let in_amount = plan.amount_per_fill.min(unpack_token_account(plan_vault_info)?.amount);
+    check_condition(
+        in_amount > 0,
+        "DCA plan has no funds left"
+    )?;
+
+    let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+    let (curve_table_info, wallet_purchases_info, hook_accounts) = split_buy_accounts(pool_info, pool, plan_vault_info, optional_accounts)?;
+
+    // The escrow vault owns itself, so it buys as its own PDA
+    let (tokens_bought_raw, paid_raw) = buy_common(
+        plan_vault_info,
+        pool_info,
+        target_mint_info,
+        base_mint_info,
+        target_vault_info,
+        base_vault_info,
+        owner_target_info,
+        plan_vault_info,
+        token_program_info,
+        base_token_program_info,
+        pool,
+        curve_table_info,
+        wallet_purchases_info,
+        false,
+        hook_accounts,
+        in_amount,
+        plan.min_fill_out(in_amount),
+        &[
+            TREASURY,
+            plan_info.key.as_ref(),
+            base_mint_info.key.as_ref(),
+            &[plan.vault_bump]
+        ],
+    )?;
+
+    let target_mint = unpack_mint(target_mint_info)?;
+    transfer_checked_signed_with_bump(
+        target_vault_info,
+        target_vault_info,
+        target_mint_info,
+        owner_target_info,
+        token_program_info,
+        tokens_bought_raw,
+        target_mint.decimals,
+        &[
+            TREASURY,
+            pool_info.key.as_ref(),
+            target_mint_info.key.as_ref()
+        ],
+        pool.vault_a_bump,
+    )?;
+
+    plan.fills += 1;
+    plan.total_spent = plan.total_spent.saturating_add(paid_raw);
+    plan.total_bought = plan.total_bought.saturating_add(tokens_bought_raw);
+    plan.advance(now);
+
+    DcaFillEvent::new(
+        plan.pool,
+        *plan_info.key,
+        plan.owner,
+        paid_raw,
+        tokens_bought_raw,
+        plan.fills,
+        plan.next_fill_at,
+    ).log();
+
+    Ok(())
+}
+
+// Pays base out of a plan's escrow to its owner.
+#[allow(clippy::too_many_arguments)]
+fn withdraw_from_plan<'info>(
+    plan: &DcaPlan,
+    plan_info: &AccountInfo<'info>,
+    plan_vault_info: &AccountInfo<'info>,
+    base_mint_info: &AccountInfo<'info>,
+    owner_base_info: &AccountInfo<'info>,
+    owner_info: &AccountInfo<'info>,
+    base_token_program_info: &AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> ProgramResult {
+    check_mut(plan_vault_info)?;
+    check_mut(owner_base_info)?;
+    check_token_program(base_token_program_info, base_mint_info)?;
+
+    check_condition(
+        plan.owner.eq(owner_info.key),
+        "DCA plan owner does not match"
+    )?;
+    check_condition(
+        plan.vault.eq(plan_vault_info.key),
+        "Invalid DCA plan accounts"
+    )?;
+
+    let base_mint = unpack_mint(base_mint_info)?;
+    transfer_checked_signed_with_extra_accounts(
+        plan_vault_info,
+        plan_vault_info,
+        base_mint_info,
+        owner_base_info,
+        base_token_program_info,
+        hook_accounts,
+        amount,
+        base_mint.decimals,
+        &[
+            TREASURY,
+            plan_info.key.as_ref(),
+            base_mint_info.key.as_ref(),
+            &[plan.vault_bump]
+        ],
+    )
+}
@@ -0,0 +1,294 @@
+use solana_program::rent::Rent;
+use steel::*;
+use flipcash_api::prelude::*;
+use flipcash_api::event::DistributorClaimEvent;
+
+pub fn process_create_distributor(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = CreateDistributorIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        distributor_info,
+        distributor_vault_info,
+        target_mint_info,
+        authority_source_info,
+        token_program_info,
+        system_program_info,
+        rent_sysvar_info,
+        hook_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(authority_info)?;
+    check_mut(distributor_info)?;
+    check_mut(distributor_vault_info)?;
+    check_mut(authority_source_info)?;
+    check_token_program(token_program_info, target_mint_info)?;
+    check_program(system_program_info, &system_program::id())?;
+    check_sysvar(rent_sysvar_info, &sysvar::rent::id())?;
+
+    let now = Clock::get()?.unix_timestamp;
+    check_condition(
+        args.expires_at > now,
+        "Distributor must expire in the future"
+    )?;
+    check_condition(
+        args.total_amount > 0,
+        "Distributor needs an amount to distribute"
+    )?;
+    check_condition(
+        args.num_recipients > 0 && args.num_recipients <= MAX_DISTRIBUTOR_RECIPIENTS,
+        "Invalid number of distributor recipients"
+    )?;
+
+    let id = args.id.to_le_bytes();
+    let distributor_seeds: &[&[u8]] = &[
+        DISTRIBUTOR,
+        target_mint_info.key.as_ref(),
+        authority_info.key.as_ref(),
+        &id
+    ];
+    check_uninitialized_pda(
+        distributor_info,
+        distributor_seeds,
+        &flipcash_api::id()
+    )?;
+
+    check_uninitialized_pda(
+        distributor_vault_info,
+        &[ TREASURY, distributor_info.key.as_ref(), target_mint_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    create_token_account(
+        target_mint_info,
+        distributor_vault_info,
+        &[
+            TREASURY,
+            distributor_info.key.as_ref(),
+            target_mint_info.key.as_ref(),
+            &[args.vault_bump]
+        ],
+        authority_info,
+        token_program_info,
+        system_program_info,
+        rent_sysvar_info,
+    )?;
+
+    create_program_account_with_bump::<Distributor>(
+        distributor_info,
+        system_program_info,
+        authority_info,
+        &flipcash_api::ID,
+        distributor_seeds,
+        args.bump,
+    )?;
+
+    let distributor = distributor_info.as_account_mut::<Distributor>(&flipcash_api::ID)?;
+
+    distributor.authority = *authority_info.key;
+    distributor.mint = *target_mint_info.key;
+    distributor.vault = *distributor_vault_info.key;
+    distributor.root = args.root;
+    distributor.id = args.id;
+    distributor.total_amount = args.total_amount;
+    distributor.num_recipients = args.num_recipients;
+    distributor.created_at = now;
+    distributor.expires_at = args.expires_at;
+    distributor.bump = args.bump;
+    distributor.vault_bump = args.vault_bump;
+
+    // Grow the account to fit the claimed bitmap, topping up rent first
+    let new_len = Distributor::size_for(args.num_recipients)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(distributor_info.lamports());
+    if rent_due > 0 {
+        distributor_info.collect(rent_due, authority_info)?;
+    }
+    distributor_info.realloc(new_len, true)?;
+
+    let target_mint = unpack_mint(target_mint_info)?;
+    transfer_checked_with_extra_accounts(
+        authority_info,
+        authority_source_info,
+        target_mint_info,
+        distributor_vault_info,
+        token_program_info,
+        hook_accounts,
+        args.total_amount,
+        target_mint.decimals,
+    )
+}
+
+pub fn process_claim_distribution(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = ClaimDistributionIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        claimant_info,
+        distributor_info,
+        distributor_vault_info,
+        target_mint_info,
+        claimant_destination_info,
+        token_program_info,
+        hook_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(claimant_info)?;
+    check_mut(distributor_info)?;
+    check_mut(distributor_vault_info)?;
+    check_mut(claimant_destination_info)?;
+    check_token_program(token_program_info, target_mint_info)?;
+
+    distributor_info.has_owner(&flipcash_api::ID)?;
+
+    let vault_bump = {
+        let mut data = distributor_info.try_borrow_mut_data()?;
+        let (distributor, claims) = Distributor::unpack_claims_mut(&mut data)?;
+
+        check_condition(
+            distributor.vault.eq(distributor_vault_info.key) &&
+            distributor.mint.eq(target_mint_info.key),
+            "Invalid distributor accounts"
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        check_condition(
+            !distributor.is_expired(now),
+            "Distributor has expired"
+        )?;
+
+        check_condition(
+            distributor.is_recipient(args.index, claimant_info.key, args.amount, &args.proof),
+            "Invalid distributor proof"
+        )?;
+        check_condition(
+            !Distributor::is_claimed(claims, args.index),
+            "Distribution already claimed"
+        )?;
+
+        Distributor::set_claimed(claims, args.index);
+        distributor.num_claimed += 1;
+        distributor.claimed_amount = distributor.claimed_amount
+            .checked_add(args.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        distributor.vault_bump
+    };
+
+    let target_mint = unpack_mint(target_mint_info)?;
+    transfer_checked_signed_with_extra_accounts(
+        distributor_vault_info,
+        distributor_vault_info,
+        target_mint_info,
+        claimant_destination_info,
+        token_program_info,
+        hook_accounts,
+        args.amount,
+        target_mint.decimals,
+        &[
+            TREASURY,
+            distributor_info.key.as_ref(),
+            target_mint_info.key.as_ref(),
+            &[vault_bump]
+        ],
+    )?;
+
+    DistributorClaimEvent::new(
+        *distributor_info.key,
+        *claimant_info.key,
+        args.index,
+        args.amount,
+    ).log();
+
+    Ok(())
+}
+
+pub fn process_clawback_distributor(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = ClawbackDistributorIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        distributor_info,
+        distributor_vault_info,
+        target_mint_info,
+        authority_destination_info,
+        token_program_info,
+        hook_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(authority_info)?;
+    check_mut(distributor_info)?;
+    check_mut(distributor_vault_info)?;
+    check_mut(authority_destination_info)?;
+    check_token_program(token_program_info, target_mint_info)?;
+
+    distributor_info.has_owner(&flipcash_api::ID)?;
+
+    let distributor = {
+        let data = distributor_info.try_borrow_data()?;
+        let (distributor, _) = Distributor::unpack_claims(&data)?;
+        *distributor
+    };
+
+    check_condition(
+        distributor.authority.eq(authority_info.key),
+        "Distributor authority does not match"
+    )?;
+    check_condition(
+        distributor.vault.eq(distributor_vault_info.key) &&
+        distributor.mint.eq(target_mint_info.key),
+        "Invalid distributor accounts"
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    check_condition(
+        distributor.is_expired(now),
+        "Distributor has not expired yet"
+    )?;
+
+    let vault_seeds: &[&[u8]] = &[
+        TREASURY,
+        distributor_info.key.as_ref(),
+        target_mint_info.key.as_ref(),
+    ];
+
+    let unclaimed = unpack_token_account(distributor_vault_info)?.amount;
+    if unclaimed > 0 {
+        let target_mint = unpack_mint(target_mint_info)?;
+        transfer_checked_signed_with_extra_accounts(
+            distributor_vault_info,
+            distributor_vault_info,
+            target_mint_info,
+            authority_destination_info,
+            token_program_info,
+            hook_accounts,
+            unclaimed,
+            target_mint.decimals,
+            &[vault_seeds, &[&[distributor.vault_bump]]].concat(),
+        )?;
+    }
+
+    close_token_account_signed_with_bump(
+        distributor_vault_info,
+        authority_info,
+        distributor_vault_info,
+        token_program_info,
+        vault_seeds,
+        distributor.vault_bump,
+    )?;
+
+    close_account(distributor_info, authority_info)
+}
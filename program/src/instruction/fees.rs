@@ -0,0 +1,219 @@
+use steel::*;
+use flipcash_api::prelude::*;
+
+pub fn process_initialize_fee_schedule(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = InitializeFeeScheduleIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        pool_info,
+        fee_schedule_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(fee_schedule_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    let pool = pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        pool.authority.eq(authority_info.key),
+        "Pool authority does not match"
+    )?;
+
+    check_uninitialized_pda(
+        fee_schedule_info,
+        &[ FEE_SCHEDULE, pool_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    create_program_account_with_bump::<FeeSchedule>(
+        fee_schedule_info,
+        system_program_info,
+        authority_info,
+        &flipcash_api::ID,
+        &[
+            FEE_SCHEDULE,
+            pool_info.key.as_ref()
+        ],
+        args.bump,
+    )?;
+
+    let fee_schedule = fee_schedule_info.as_account_mut::<FeeSchedule>(&flipcash_api::ID)?;
+
+    fee_schedule.pool = *pool_info.key;
+    fee_schedule.num_exemptions = 0;
+    fee_schedule.num_tiers = 0;
+    fee_schedule.bump = args.bump;
+
+    Ok(())
+}
+
+pub fn process_set_fee_exemption(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = SetFeeExemptionIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        pool_info,
+        fee_schedule_info,
+        wallet_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(fee_schedule_info)?;
+
+    let fee_schedule = load_fee_schedule_for_update(
+        authority_info,
+        pool_info,
+        fee_schedule_info,
+    )?;
+
+    let count = fee_schedule.num_exemptions as usize;
+    let position = fee_schedule.exemptions[..count]
+        .iter()
+        .position(|e| e.eq(wallet_info.key));
+
+    match (args.exempt, position) {
+        (true, None) => {
+            check_condition(
+                count < MAX_FEE_EXEMPTIONS,
+                "Fee exemption list is full"
+            )?;
+
+            fee_schedule.exemptions[count] = *wallet_info.key;
+            fee_schedule.num_exemptions += 1;
+        }
+        (false, Some(index)) => {
+            // Swap-remove to keep the used entries contiguous.
+            fee_schedule.exemptions[index] = fee_schedule.exemptions[count - 1];
+            fee_schedule.exemptions[count - 1] = Pubkey::default();
+            fee_schedule.num_exemptions -= 1;
+        }
+        // Already in the requested state.
+        _ => {}
+    }
+
+    Ok(())
+}
+
+pub fn process_set_fee_tiers(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = SetFeeTiersIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        authority_info,
+        pool_info,
+        fee_schedule_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(fee_schedule_info)?;
+
+    let pool = pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+
+    for (i, tier) in args.tiers.iter().enumerate() {
+        check_condition(
+            tier.sell_fee <= pool.sell_fee,
+            "Fee tier cannot exceed the pool sell fee"
+        )?;
+
+        if i > 0 {
+            check_condition(
+                tier.min_volume > args.tiers[i - 1].min_volume,
+                "Fee tiers must have strictly increasing volume thresholds"
+            )?;
+        }
+    }
+
+    let fee_schedule = load_fee_schedule_for_update(
+        authority_info,
+        pool_info,
+        fee_schedule_info,
+    )?;
+
+    fee_schedule.tiers = [FeeTier::default(); MAX_FEE_TIERS];
+    fee_schedule.tiers[..args.tiers.len()].copy_from_slice(&args.tiers);
+    fee_schedule.num_tiers = args.tiers.len() as u8;
+
+    Ok(())
+}
+
+pub fn process_initialize_trader_volume(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = InitializeTraderVolumeIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        payer_info,
+        pool_info,
+        trader_info,
+        trader_volume_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(payer_info)?;
+    check_mut(trader_volume_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_uninitialized_pda(
+        trader_volume_info,
+        &[ VOLUME, pool_info.key.as_ref(), trader_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    create_program_account_with_bump::<TraderVolume>(
+        trader_volume_info,
+        system_program_info,
+        payer_info,
+        &flipcash_api::ID,
+        &[
+            VOLUME,
+            pool_info.key.as_ref(),
+            trader_info.key.as_ref()
+        ],
+        args.bump,
+    )?;
+
+    let trader_volume = trader_volume_info.as_account_mut::<TraderVolume>(&flipcash_api::ID)?;
+
+    trader_volume.pool = *pool_info.key;
+    trader_volume.trader = *trader_info.key;
+    trader_volume.sell_volume = 0;
+    trader_volume.bump = args.bump;
+
+    Ok(())
+}
+
+fn load_fee_schedule_for_update<'a>(
+    authority_info: &AccountInfo,
+    pool_info: &AccountInfo,
+    fee_schedule_info: &'a AccountInfo,
+) -> Result<&'a mut FeeSchedule, ProgramError> {
+    let pool = pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        pool.authority.eq(authority_info.key),
+        "Pool authority does not match"
+    )?;
+
+    let fee_schedule = fee_schedule_info.as_account_mut::<FeeSchedule>(&flipcash_api::ID)?;
+
+    check_condition(
+        fee_schedule.pool.eq(pool_info.key),
+        "Fee schedule pool does not match"
+    )?;
+
+    Ok(fee_schedule)
+}
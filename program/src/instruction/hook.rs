@@ -0,0 +1,136 @@
+use solana_program::compute_units::sol_remaining_compute_units;
+use steel::*;
+use flipcash_api::prelude::*;
+
+pub fn process_set_trade_hook(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = SetTradeHookIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        pool_info,
+        hook_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(pool_info)?;
+
+    let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        pool.authority.eq(authority_info.key),
+        "Pool authority does not match"
+    )?;
+
+    // The system program stands in for "no hook"
+    if hook_program_info.key.eq(&system_program::ID) {
+        pool.hook_program = Pubkey::default();
+        pool.hook_compute_units = 0;
+        pool.hook_required = 0;
+        return Ok(());
+    }
+
+    check_condition(
+        hook_program_info.executable,
+        "Trade hook must be a program"
+    )?;
+    check_condition(
+        hook_program_info.key.ne(&flipcash_api::ID),
+        "Invalid trade hook program"
+    )?;
+    check_condition(
+        args.compute_units > 0 && args.compute_units <= MAX_TRADE_HOOK_COMPUTE_UNITS,
+        "Invalid trade hook compute budget"
+    )?;
+
+    let (_, hook_authority_bump) = find_trade_hook_authority_pda(pool_info.key);
+
+    pool.hook_program = *hook_program_info.key;
+    pool.hook_compute_units = args.compute_units;
+    pool.hook_required = args.required as u8;
+    pool.hook_authority_bump = hook_authority_bump;
+
+    Ok(())
+}
+
+/// CPIs into the pool's trade hook, if it has one. `hook_accounts` are the
+/// trailing accounts left after a trade's own optional accounts, laid out as:
+///
+/// 0. The hook program
+/// 1. The pool's trade hook authority PDA
+/// 2. Any accounts the hook needs, followed by the base mint's transfer hook
+///    accounts. All of them are passed on to the hook
+///
+/// A pool with a hook fails any trade that leaves these out. An optional hook
+/// is skipped when there isn't enough compute left to run it and finish the
+/// trade; a required one fails the trade instead. Either way a hook that
+/// errors or overruns its compute budget fails the trade.
+pub fn invoke_trade_hook<'info>(
+    pool_info: &AccountInfo<'info>,
+    pool: &LiquidityPool,
+    hook_accounts: &[AccountInfo<'info>],
+    summary: &TradeSummary,
+) -> ProgramResult {
+    if !pool.has_trade_hook() {
+        return Ok(());
+    }
+
+    let [hook_program_info, hook_authority_info, extra_accounts @ ..] = hook_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_condition(
+        hook_program_info.key.eq(&pool.hook_program),
+        "Invalid trade hook program"
+    )?;
+
+    let hook_authority_seeds: &[&[u8]] = &[
+        TRADE_HOOK,
+        pool_info.key.as_ref(),
+        &[pool.hook_authority_bump],
+    ];
+    let hook_authority = Pubkey::create_program_address(hook_authority_seeds, &flipcash_api::ID)?;
+    check_condition(
+        hook_authority_info.key.eq(&hook_authority),
+        "Invalid trade hook authority"
+    )?;
+
+    // Solana can't cap the compute of a CPI, so make sure the hook's whole
+    // budget fits before calling it, with enough left over to finish the trade
+    let budget = pool.hook_compute_units as u64;
+    let remaining = sol_remaining_compute_units();
+    if remaining < budget + TRADE_HOOK_COMPUTE_RESERVE {
+        check_condition(
+            !pool.is_trade_hook_required(),
+            "Not enough compute left for the trade hook"
+        )?;
+        return Ok(());
+    }
+
+    let hook_ix = trade_hook_instruction(
+        pool.hook_program,
+        hook_authority,
+        *pool_info.key,
+        summary,
+        extra_accounts,
+    );
+    let mut account_infos = vec![hook_authority_info.clone(), pool_info.clone()];
+    account_infos.extend_from_slice(extra_accounts);
+    account_infos.push(hook_program_info.clone());
+
+    solana_program::program::invoke_signed(
+        &hook_ix,
+        &account_infos,
+        &[hook_authority_seeds],
+    )?;
+
+    let used = remaining.saturating_sub(sol_remaining_compute_units());
+    check_condition(
+        used <= budget,
+        "Trade hook exceeded its compute budget"
+    )?;
+
+    Ok(())
+}
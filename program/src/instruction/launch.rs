@@ -0,0 +1,190 @@
+use steel::*;
+use flipcash_api::prelude::*;
+
+pub fn process_set_launch(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = SetLaunchIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        pool_info,
+        target_mint_info,
+        target_vault_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(pool_info)?;
+
+    let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        pool.authority.eq(authority_info.key),
+        "Pool authority does not match"
+    )?;
+    check_condition(
+        pool.mint_a.eq(target_mint_info.key) && pool.vault_a.eq(target_vault_info.key),
+        "Invalid target accounts"
+    )?;
+
+    // The launch can only move while it is still pending, or before anyone
+    // has bought, so it can't be used to lock holders out of a live market
+    let now = Clock::get()?.unix_timestamp;
+    let target_mint = unpack_mint(target_mint_info)?;
+    let target_vault = unpack_token_account(target_vault_info)?;
+    let unsold = target_vault.amount == to_quarks(pool.curve_max_supply, target_mint.decimals)?;
+    check_condition(
+        !pool.is_launched(now) || unsold,
+        "Pool has already launched"
+    )?;
+
+    set_launch(
+        pool,
+        args.launch_at,
+        args.presale_cap,
+        args.presale_root,
+        args.launch_window,
+        args.launch_cap,
+    )
+}
+
+/// Checks and applies a launch schedule. InitializePool applies one before
+/// the authority's initial buy, and SetLaunch while the launch is pending.
+/// A launch time already past is stored as now, so the launch window runs
+/// from when the schedule was set.
+pub fn set_launch(
+    pool: &mut LiquidityPool,
+    launch_at: i64,
+    presale_cap: u64,
+    presale_root: [u8; 32],
+    launch_window: u64,
+    launch_cap: u64,
+) -> ProgramResult {
+    check_condition(
+        launch_at >= 0,
+        "Invalid launch time"
+    )?;
+    check_condition(
+        launch_window == 0 || launch_cap > 0,
+        "Launch window cap must be positive"
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    pool.launch_at = launch_at.max(now);
+    pool.presale_cap = presale_cap;
+    pool.presale_root = presale_root;
+    pool.launch_window = launch_window;
+    pool.launch_cap = launch_cap;
+
+    Ok(())
+}
+
+pub fn process_initialize_wallet_purchases(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = InitializeWalletPurchasesIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        payer_info,
+        pool_info,
+        wallet_info,
+        wallet_purchases_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(payer_info)?;
+    check_mut(wallet_purchases_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_uninitialized_pda(
+        wallet_purchases_info,
+        &[ PURCHASES, pool_info.key.as_ref(), wallet_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    create_program_account_with_bump::<WalletPurchases>(
+        wallet_purchases_info,
+        system_program_info,
+        payer_info,
+        &flipcash_api::ID,
+        &[
+            PURCHASES,
+            pool_info.key.as_ref(),
+            wallet_info.key.as_ref()
+        ],
+        args.bump,
+    )?;
+
+    let wallet_purchases = wallet_purchases_info.as_account_mut::<WalletPurchases>(&flipcash_api::ID)?;
+
+    wallet_purchases.pool = *pool_info.key;
+    wallet_purchases.wallet = *wallet_info.key;
+    wallet_purchases.presale_spent = 0;
+    wallet_purchases.launch_spent = 0;
+    wallet_purchases.presale_allowed = 0;
+    wallet_purchases.bump = args.bump;
+
+    Ok(())
+}
+
+pub fn process_join_presale(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = JoinPresaleIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        wallet_info,
+        pool_info,
+        wallet_purchases_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(wallet_info)?;
+
+    let pool = pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        pool.is_presale_wallet(wallet_info.key, &args.proof),
+        "Wallet is not on the presale allowlist"
+    )?;
+
+    let wallet_purchases = load_wallet_purchases(pool_info, wallet_info, Some(wallet_purchases_info))?
+        .ok_or(ProgramError::UninitializedAccount)?;
+
+    wallet_purchases.presale_allowed = 1;
+
+    Ok(())
+}
+
+/// Loads a wallet's purchases account for a pool, or `None` if it hasn't been
+/// provided or created yet.
+pub fn load_wallet_purchases<'a, 'info>(
+    pool_info: &AccountInfo<'info>,
+    wallet_info: &AccountInfo<'info>,
+    wallet_purchases_info: Option<&'a AccountInfo<'info>>,
+) -> Result<Option<&'a mut WalletPurchases>, ProgramError> {
+    let Some(wallet_purchases_info) = wallet_purchases_info else {
+        return Ok(None);
+    };
+
+    check_seeds(
+        wallet_purchases_info,
+        &[ PURCHASES, pool_info.key.as_ref(), wallet_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    if wallet_purchases_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    check_mut(wallet_purchases_info)?;
+
+    let wallet_purchases = wallet_purchases_info.as_account_mut::<WalletPurchases>(&flipcash_api::ID)?;
+
+    Ok(Some(wallet_purchases))
+}
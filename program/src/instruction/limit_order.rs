@@ -0,0 +1,454 @@
+use brine_fp::UnsignedNumeric;
+use steel::*;
+use flipcash_api::prelude::*;
+use flipcash_api::event::LimitOrderEvent;
+use super::{buy_common, sell_common, split_buy_accounts, split_curve_table};
+
+pub fn process_create_limit_order(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = CreateLimitOrderIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        owner_info,
+        pool_info,
+        order_info,
+        order_vault_info,
+        escrow_mint_info,
+        owner_source_info,
+        token_program_info,
+        system_program_info,
+        rent_sysvar_info,
+        hook_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(owner_info)?;
+    check_mut(owner_info)?;
+    check_mut(order_info)?;
+    check_mut(order_vault_info)?;
+    check_mut(owner_source_info)?;
+    check_token_program(token_program_info, escrow_mint_info)?;
+    check_program(system_program_info, &system_program::id())?;
+    check_sysvar(rent_sysvar_info, &sysvar::rent::id())?;
+
+    let pool = pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+
+    // Buys escrow the base they spend, sells the currency
+    let escrow_mint = match args.side {
+        OrderSide::Buy => pool.mint_b,
+        OrderSide::Sell => pool.mint_a,
+    };
+    check_condition(
+        escrow_mint.eq(escrow_mint_info.key),
+        "Invalid escrow mint"
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    check_condition(
+        args.expires_at > now,
+        "Limit order must expire in the future"
+    )?;
+    check_condition(
+        args.amount > 0 && args.target_price > 0,
+        "Limit order needs an amount and a target price"
+    )?;
+
+    let id = args.id.to_le_bytes();
+    let order_seeds: &[&[u8]] = &[ LIMIT_ORDER, pool_info.key.as_ref(), owner_info.key.as_ref(), &id ];
+    check_uninitialized_pda(
+        order_info,
+        order_seeds,
+        &flipcash_api::id()
+    )?;
+
+    check_uninitialized_pda(
+        order_vault_info,
+        &[ TREASURY, order_info.key.as_ref(), escrow_mint_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    create_token_account(
+        escrow_mint_info,
+        order_vault_info,
+        &[
+            TREASURY,
+            order_info.key.as_ref(),
+            escrow_mint_info.key.as_ref(),
+            &[args.vault_bump]
+        ],
+        owner_info,
+        token_program_info,
+        system_program_info,
+        rent_sysvar_info,
+    )?;
+
+    let escrow_mint = unpack_mint(escrow_mint_info)?;
+    transfer_checked_with_extra_accounts(
+        owner_info,
+        owner_source_info,
+        escrow_mint_info,
+        order_vault_info,
+        token_program_info,
+        hook_accounts,
+        args.amount,
+        escrow_mint.decimals,
+    )?;
+
+    create_program_account_with_bump::<LimitOrder>(
+        order_info,
+        system_program_info,
+        owner_info,
+        &flipcash_api::ID,
+        order_seeds,
+        args.bump,
+    )?;
+
+    // The bounty rides along in the order account until it's executed
+    if args.bounty > 0 {
+        order_info.collect(args.bounty, owner_info)?;
+    }
+
+    let amount = unpack_token_account(order_vault_info)?.amount;
+    let order = order_info.as_account_mut::<LimitOrder>(&flipcash_api::ID)?;
+
+    order.owner = *owner_info.key;
+    order.pool = *pool_info.key;
+    order.vault = *order_vault_info.key;
+    order.id = args.id;
+    order.amount = amount;
+    order.min_amount_out = args.min_amount_out;
+    order.target_price = args.target_price;
+    order.bounty = args.bounty;
+    order.created_at = now;
+    order.expires_at = args.expires_at;
+    order.side = args.side.into();
+    order.bump = args.bump;
+    order.vault_bump = args.vault_bump;
+
+    Ok(())
+}
+
+pub fn process_execute_limit_order(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = ExecuteLimitOrderIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        executor_info,
+        order_info,
+        owner_info,
+        order_vault_info,
+        owner_source_info,
+        owner_destination_info,
+        pool_info,
+        target_mint_info,
+        base_mint_info,
+        target_vault_info,
+        base_vault_info,
+        token_program_info,
+        base_token_program_info,
+        optional_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(executor_info)?;
+    check_mut(executor_info)?;
+    check_mut(order_info)?;
+    check_mut(pool_info)?;
+
+    let order = *order_info.as_account::<LimitOrder>(&flipcash_api::ID)?;
+
+    check_condition(
+        order.pool.eq(pool_info.key),
+        "Invalid limit order accounts"
+    )?;
+    check_condition(
+        !order.is_expired(Clock::get()?.unix_timestamp),
+        "Limit order has expired"
+    )?;
+
+    let side = order.side()?;
+    let (escrow_mint_info, escrow_token_program_info, output_mint_info) = match side {
+        OrderSide::Buy => (base_mint_info, base_token_program_info, target_mint_info),
+        OrderSide::Sell => (target_mint_info, token_program_info, base_mint_info),
+    };
+
+    check_mut(owner_destination_info)?;
+    let owner_destination = unpack_token_account(owner_destination_info)?;
+    check_condition(
+        owner_destination.owner.eq(&order.owner) && owner_destination.mint.eq(output_mint_info.key),
+        "Invalid owner destination account"
+    )?;
+
+    let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        pool.vault_a.eq(target_vault_info.key),
+        "Invalid vault accounts"
+    )?;
+
+    let (curve_table_info, _) = split_curve_table(pool, optional_accounts)?;
+    let spot_price = spot_price(pool, target_mint_info, target_vault_info, curve_table_info)?;
+    check_condition(
+        order.is_crossed(&spot_price)?,
+        "Limit order price not reached"
+    )?;
+
+    // The escrow vault owns itself, so it trades as its own PDA
+    let vault_seeds: &[&[u8]] = &[
+        TREASURY,
+        order_info.key.as_ref(),
+        escrow_mint_info.key.as_ref(),
+        &[order.vault_bump]
+    ];
+
+    // A crossed spot price only lets the order start filling. The fill as a
+    // whole must still average no worse than the target price
+    let target_mint = unpack_mint(target_mint_info)?;
+    let base_mint = unpack_mint(base_mint_info)?;
+    let min_amount_out = order.min_fill_amount(target_mint.decimals, base_mint.decimals)?;
+
+    let (in_amount_raw, out_amount_raw, refund_hook_accounts) = match side {
+        OrderSide::Buy => {
+            let (curve_table_info, wallet_purchases_info, hook_accounts) = split_buy_accounts(pool_info, pool, order_vault_info, optional_accounts)?;
+
+            let (tokens_bought_raw, paid_raw) = buy_common(
+                order_vault_info,
+                pool_info,
+                target_mint_info,
+                base_mint_info,
+                target_vault_info,
+                base_vault_info,
+                owner_destination_info,
+                order_vault_info,
+                token_program_info,
+                base_token_program_info,
+                pool,
+                curve_table_info,
+                wallet_purchases_info,
+                false,
+                hook_accounts,
+                order.amount,
+                min_amount_out,
+                vault_seeds,
+            )?;
+
+            transfer_checked_signed_with_bump(
+                target_vault_info,
+                target_vault_info,
+                target_mint_info,
+                owner_destination_info,
+                token_program_info,
+                tokens_bought_raw,
+                target_mint.decimals,
+                &[
+                    TREASURY,
+                    pool_info.key.as_ref(),
+                    target_mint_info.key.as_ref()
+                ],
+                pool.vault_a_bump,
+            )?;
+
+            (paid_raw, tokens_bought_raw, hook_accounts)
+        }
+        OrderSide::Sell => {
+            let value_after_fee_raw = sell_common(
+                order_vault_info,
+                pool_info,
+                target_mint_info,
+                base_mint_info,
+                target_vault_info,
+                base_vault_info,
+                order_vault_info,
+                owner_destination_info,
+                token_program_info,
+                base_token_program_info,
+                pool,
+                optional_accounts,
+                order.amount,
+                min_amount_out,
+                vault_seeds,
+            )?;
+
+            // Transfer hook accounts are found by key among the optional accounts
+            transfer_checked_signed_with_extra_accounts(
+                base_vault_info,
+                base_vault_info,
+                base_mint_info,
+                owner_destination_info,
+                base_token_program_info,
+                optional_accounts,
+                value_after_fee_raw,
+                base_mint.decimals,
+                &[
+                    TREASURY,
+                    pool_info.key.as_ref(),
+                    base_mint_info.key.as_ref(),
+                    &[pool.vault_b_bump]
+                ],
+            )?;
+
+            (order.amount, value_after_fee_raw, &[][..])
+        }
+    };
+
+    close_limit_order(
+        &order,
+        order_info,
+        order_vault_info,
+        escrow_mint_info,
+        owner_source_info,
+        owner_info,
+        escrow_token_program_info,
+        refund_hook_accounts,
+    )?;
+
+    LimitOrderEvent::new(
+        order.pool,
+        *order_info.key,
+        order.owner,
+        *executor_info.key,
+        in_amount_raw,
+        out_amount_raw,
+        order.bounty,
+        order.side,
+    ).log();
+
+    // Pay the bounty before the rest of the order's lamports go to the owner
+    order_info.send(order.bounty, executor_info);
+    close_account(order_info, owner_info)
+}
+
+pub fn process_cancel_limit_order(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = CancelLimitOrderIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        order_info,
+        owner_info,
+        order_vault_info,
+        escrow_mint_info,
+        owner_source_info,
+        token_program_info,
+        hook_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(order_info)?;
+
+    let order = *order_info.as_account::<LimitOrder>(&flipcash_api::ID)?;
+
+    // Owners can cancel anytime. Anyone can clean up an expired order, which
+    // still pays everything back to the owner.
+    check_condition(
+        order.owner.eq(authority_info.key) || order.is_expired(Clock::get()?.unix_timestamp),
+        "Only the owner can cancel a limit order before it expires"
+    )?;
+
+    close_limit_order(
+        &order,
+        order_info,
+        order_vault_info,
+        escrow_mint_info,
+        owner_source_info,
+        owner_info,
+        token_program_info,
+        hook_accounts,
+    )?;
+
+    close_account(order_info, owner_info)
+}
+
+// Refunds whatever is left in an order's escrow to the owner and closes the
+// escrow vault. The order account itself is closed by the caller.
+#[allow(clippy::too_many_arguments)]
+fn close_limit_order<'info>(
+    order: &LimitOrder,
+    order_info: &AccountInfo<'info>,
+    order_vault_info: &AccountInfo<'info>,
+    escrow_mint_info: &AccountInfo<'info>,
+    owner_source_info: &AccountInfo<'info>,
+    owner_info: &AccountInfo<'info>,
+    token_program_info: &AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+) -> ProgramResult {
+    check_mut(order_vault_info)?;
+    check_mut(owner_source_info)?;
+    check_mut(owner_info)?;
+    check_token_program(token_program_info, escrow_mint_info)?;
+
+    check_condition(
+        order.owner.eq(owner_info.key),
+        "Limit order owner does not match"
+    )?;
+    check_condition(
+        order.vault.eq(order_vault_info.key),
+        "Invalid limit order accounts"
+    )?;
+
+    let owner_source = unpack_token_account(owner_source_info)?;
+    check_condition(
+        owner_source.owner.eq(&order.owner) && owner_source.mint.eq(escrow_mint_info.key),
+        "Invalid owner source account"
+    )?;
+
+    let vault_seeds: &[&[u8]] = &[
+        TREASURY,
+        order_info.key.as_ref(),
+        escrow_mint_info.key.as_ref()
+    ];
+
+    let amount = unpack_token_account(order_vault_info)?.amount;
+    if amount > 0 {
+        let escrow_mint = unpack_mint(escrow_mint_info)?;
+        transfer_checked_signed_with_extra_accounts(
+            order_vault_info,
+            order_vault_info,
+            escrow_mint_info,
+            owner_source_info,
+            token_program_info,
+            hook_accounts,
+            amount,
+            escrow_mint.decimals,
+            &[vault_seeds, &[&[order.vault_bump]]].concat(),
+        )?;
+    }
+
+    close_token_account_signed_with_bump(
+        order_vault_info,
+        owner_info,
+        order_vault_info,
+        token_program_info,
+        vault_seeds,
+        order.vault_bump,
+    )
+}
+
+// Spot price of the pool's curve at its current supply.
+fn spot_price(
+    pool: &LiquidityPool,
+    target_mint_info: &AccountInfo,
+    target_vault_info: &AccountInfo,
+    curve_table_info: Option<&AccountInfo>,
+) -> Result<UnsignedNumeric, ProgramError> {
+    let decimals = unpack_mint(target_mint_info)?.decimals;
+    let supply_raw = to_quarks(pool.curve_max_supply, decimals)?
+        .checked_sub(unpack_token_account(target_vault_info)?.amount)
+        .ok_or(ProgramError::InvalidArgument)?;
+    let supply = to_numeric(supply_raw, decimals)?;
+
+    let curve_table_data = curve_table_info
+        .map(|info| info.try_borrow_data())
+        .transpose()?;
+    let curve = match (curve_table_info, &curve_table_data) {
+        (Some(info), Some(data)) => pool.table_curve(info.key, data)?,
+        _ => pool.curve()?,
+    };
+
+    curve.spot_price_at_supply(&supply)
+        .ok_or(ProgramError::InvalidArgument)
+}
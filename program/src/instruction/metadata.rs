@@ -22,15 +22,15 @@ pub fn process_initialize_metadata(accounts: &[AccountInfo], _data: &[u8]) -> Pr
     //solana_program::msg!("Args: {:?}", args);
 
     check_signer(authority_info)?;
+    check_mut(currency_info)?;
     check_mut(mint_info)?;
     check_mut(metadata_info)?;
 
-    check_program(metadata_program_info, &mpl_token_metadata::ID)?;
-    check_program(token_program_info, &spl_token::id())?;
+    check_token_program(token_program_info, mint_info)?;
     check_program(system_program_info, &system_program::id())?;
     check_sysvar(rent_sysvar_info, &sysvar::rent::id())?;
 
-    let currency = currency_info.as_account::<CurrencyConfig>(&flipcash_api::ID)?;
+    let currency = currency_info.as_account_mut::<CurrencyConfig>(&flipcash_api::ID)?;
 
     check_condition(
         currency.authority.eq(authority_info.key),
@@ -42,6 +42,85 @@ pub fn process_initialize_metadata(accounts: &[AccountInfo], _data: &[u8]) -> Pr
         "Currency mint does not match"
     )?;
 
+    let uri = METADATA_URI.replace("{}", &mint_info.key.to_string());
+
+    if mint_info.owner.eq(&spl_token_2022::ID) {
+        // Token-2022 currencies keep their metadata on the mint itself
+        check_program(metadata_program_info, &spl_token_2022::ID)?;
+        check_condition(
+            metadata_info.key.eq(mint_info.key),
+            "Token-2022 metadata must be the mint"
+        )?;
+
+        initialize_token_metadata_signed_with_bump(
+            mint_info,
+            authority_info, // update_authority
+            authority_info, // payer
+            token_program_info,
+            system_program_info,
+            CurrencyName::from_bytes(&currency.name)?.to_string(),
+            CurrencySymbol::from_legacy_bytes(&currency.symbol)?.to_string(),
+            uri,
+            &[
+                 MINT,
+                 authority_info.key.as_ref(),
+                 currency.name.as_ref(),
+                 currency.seed.as_ref(),
+                 &[currency.mint_bump]
+            ],
+        )?;
+    } else {
+        check_program(metadata_program_info, &mpl_token_metadata::ID)?;
+        initialize_metaplex_metadata(
+            authority_info,
+            mint_info,
+            metadata_info,
+            metadata_program_info,
+            system_program_info,
+            rent_sysvar_info,
+            currency,
+            uri,
+        )?;
+    }
+
+    currency.has_metadata = 1;
+
+    // Now that metadata has been uploaded, we can remove mint authority. If
+    // supply is left for additional pools, the last of them removes it.
+    if currency.supply_unallocated() > 0 {
+        return Ok(());
+    }
+
+    set_authority_signed_with_bump(
+        mint_info,
+        mint_info,
+        Option::None,
+        spl_token_2022::instruction::AuthorityType::MintTokens,
+        token_program_info,
+        &[
+             MINT,
+             authority_info.key.as_ref(),
+             currency.name.as_ref(),
+             currency.seed.as_ref(),
+        ],
+        currency.mint_bump,
+    )?;
+
+    Ok(())
+}
+
+// Metaplex metadata for currencies minted under the SPL Token program.
+#[allow(clippy::too_many_arguments)]
+fn initialize_metaplex_metadata<'info>(
+    authority_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    metadata_info: &AccountInfo<'info>,
+    metadata_program_info: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    rent_sysvar_info: &AccountInfo<'info>,
+    currency: &CurrencyConfig,
+    uri: String,
+) -> ProgramResult {
     let (metadata_address, _metadata_bump) = metadata_pda(mint_info.key);
 
     metadata_info
@@ -49,8 +128,6 @@ pub fn process_initialize_metadata(accounts: &[AccountInfo], _data: &[u8]) -> Pr
         .is_writable()?
         .has_address(&metadata_address)?;
 
-    let uri = METADATA_URI.replace("{}", &mint_info.key.to_string());
-
     // Initialize mint metadata.
     mpl_token_metadata::instructions::CreateMetadataAccountV3Cpi {
         __program: metadata_program_info,
@@ -63,9 +140,9 @@ pub fn process_initialize_metadata(accounts: &[AccountInfo], _data: &[u8]) -> Pr
         rent: Some(rent_sysvar_info),
         __args: mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs {
             data: mpl_token_metadata::types::DataV2 {
-                name: from_name(currency.name.as_ref()),
-                symbol: from_symbol(currency.symbol.as_ref()),
-                uri: uri.to_string(),
+                name: CurrencyName::from_bytes(&currency.name)?.to_string(),
+                symbol: CurrencySymbol::from_legacy_bytes(&currency.symbol)?.to_string(),
+                uri,
                 seller_fee_basis_points: 0,
                 creators: None,
                 collection: None,
@@ -85,21 +162,5 @@ pub fn process_initialize_metadata(accounts: &[AccountInfo], _data: &[u8]) -> Pr
         ]],
     )?;
 
-    // Now that metaplex metadata been uploaded, we can remove mint authority.
-    set_authority_signed_with_bump(
-        mint_info,
-        mint_info,
-        Option::None,
-        spl_token_2022::instruction::AuthorityType::MintTokens,
-        token_program_info,
-        &[
-             MINT,
-             authority_info.key.as_ref(),
-             currency.name.as_ref(),
-             currency.seed.as_ref(),
-        ],
-        currency.mint_bump,
-    )?;
-
     Ok(())
-}
\ No newline at end of file
+}
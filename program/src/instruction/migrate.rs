@@ -0,0 +1,146 @@
+use solana_program::rent::Rent;
+use steel::*;
+use flipcash_api::prelude::*;
+use super::{register_currency, reserve_symbol};
+
+pub fn process_migrate_pool(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = MigratePoolIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        payer_info,
+        pool_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(payer_info)?;
+    check_mut(pool_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    pool_info.has_owner(&flipcash_api::ID)?;
+
+    check_condition(
+        pool_info.data_len() == LIQUIDITY_POOL_V0_LEN,
+        "Pool does not need migrating"
+    )?;
+
+    let pool = LiquidityPool::unpack_v0(&pool_info.try_borrow_data()?)?;
+
+    grow_account::<LiquidityPool>(pool_info, payer_info)?;
+
+    let mut data = pool_info.try_borrow_mut_data()?;
+    data[8..].copy_from_slice(bytemuck::bytes_of(&pool));
+
+    Ok(())
+}
+
+pub fn process_migrate_currency(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = MigrateCurrencyIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        payer_info,
+        currency_info,
+        mint_info,
+        pool_info,
+        symbol_info,
+        registry_info,
+        registry_page_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(payer_info)?;
+    check_mut(currency_info)?;
+    check_mut(symbol_info)?;
+    check_mut(registry_info)?;
+    check_mut(registry_page_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    currency_info.has_owner(&flipcash_api::ID)?;
+
+    check_condition(
+        currency_info.data_len() == CURRENCY_CONFIG_V0_LEN,
+        "Currency does not need migrating"
+    )?;
+
+    check_seeds(
+        pool_info,
+        &[ POOL, currency_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    // v0 currencies minted their whole supply to their pool, and dropped the
+    // mint authority once their metadata was written
+    let has_pool = !pool_info.data_is_empty();
+    let has_metadata = unpack_mint(mint_info)?.mint_authority.is_none();
+
+    let mut currency = CurrencyConfig::unpack_v0(
+        &currency_info.try_borrow_data()?,
+        has_pool,
+        has_metadata,
+    )?;
+
+    check_condition(
+        currency.mint.eq(mint_info.key),
+        "Currency mint does not match"
+    )?;
+
+    // v0 symbols weren't normalized, so lowercase ones are uppercased
+    let symbol = *CurrencySymbol::from_legacy_bytes(&currency.symbol)?.as_bytes();
+    currency.symbol = symbol;
+
+    check_seeds(
+        symbol_info,
+        &[ SYMBOL, symbol.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    grow_account::<CurrencyConfig>(currency_info, payer_info)?;
+
+    currency_info.try_borrow_mut_data()?[8..]
+        .copy_from_slice(bytemuck::bytes_of(&currency));
+
+    // v0 currencies predate symbol reservations and the registry. Symbols
+    // used to differ by case alone, so the first of a clashing pair to be
+    // migrated keeps the symbol and the other is left unreserved.
+    if symbol_info.data_is_empty() {
+        reserve_symbol(
+            mint_info,
+            symbol_info,
+            payer_info,
+            system_program_info,
+            &symbol,
+            args.symbol_bump,
+        )?;
+    }
+
+    register_currency(
+        mint_info,
+        registry_info,
+        registry_page_info,
+        payer_info,
+        system_program_info,
+        args.registry_bump,
+        args.registry_page_bump,
+    )
+}
+
+/// Grows an account to the current size of `T`, zero-filled, with the payer
+/// topping up its rent.
+fn grow_account<'info, T: Pod>(
+    account_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+) -> ProgramResult {
+    let new_len = 8 + std::mem::size_of::<T>();
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account_info.lamports());
+    if rent_due > 0 {
+        account_info.collect(rent_due, payer_info)?;
+    }
+    account_info.realloc(new_len, true)
+}
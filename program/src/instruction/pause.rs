@@ -0,0 +1,90 @@
+use steel::*;
+use flipcash_api::prelude::*;
+use flipcash_api::event::{PauseEvent, ResumeEvent};
+
+pub fn process_pause(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = PauseIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        pool_info,
+        protocol_config_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(pool_info)?;
+
+    let pool = load_pool_for_pause(authority_info, pool_info, protocol_config_info)?;
+
+    let paused = if args.pause_sells {
+        PAUSE_BUYS | PAUSE_SELLS
+    } else {
+        PAUSE_BUYS
+    };
+
+    // Already in the requested state.
+    if pool.paused == paused {
+        return Ok(());
+    }
+
+    pool.paused = paused;
+
+    PauseEvent::new(
+        *pool_info.key,
+        *authority_info.key,
+        paused,
+    ).log();
+
+    Ok(())
+}
+
+pub fn process_resume(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = ResumeIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        pool_info,
+        protocol_config_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(pool_info)?;
+
+    let pool = load_pool_for_pause(authority_info, pool_info, protocol_config_info)?;
+
+    // Already trading.
+    if pool.paused == 0 {
+        return Ok(());
+    }
+
+    pool.paused = 0;
+
+    ResumeEvent::new(
+        *pool_info.key,
+        *authority_info.key,
+    ).log();
+
+    Ok(())
+}
+
+fn load_pool_for_pause<'a>(
+    authority_info: &AccountInfo,
+    pool_info: &'a AccountInfo,
+    protocol_config_info: &AccountInfo,
+) -> Result<&'a mut LiquidityPool, ProgramError> {
+    let protocol_config = protocol_config_info.as_account::<ProtocolConfig>(&flipcash_api::ID)?;
+    let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
+
+    check_condition(
+        pool.can_pause(authority_info.key, protocol_config),
+        "Only the pool authority or protocol admin can pause"
+    )?;
+
+    Ok(pool)
+}
@@ -1,5 +1,7 @@
 use steel::*;
 use flipcash_api::prelude::*;
+use flipcash_api::event::PoolCreatedEvent;
+use super::{buy_common, initialize_vesting, set_launch};
 
 pub fn process_initialize_pool(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let raw_args = InitializePoolIx::try_from_bytes(data)?;
@@ -10,12 +12,15 @@ pub fn process_initialize_pool(accounts: &[AccountInfo], data: &[u8]) -> Program
         currency_info,
         target_mint_info,
         base_mint_info,
+        protocol_config_info,
         pool_info,
         target_vault_info,
         base_vault_info,
         token_program_info,
+        base_token_program_info,
         system_program_info,
         rent_sysvar_info,
+        optional_accounts @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -23,22 +28,26 @@ pub fn process_initialize_pool(accounts: &[AccountInfo], data: &[u8]) -> Program
     //solana_program::msg!("Args: {:?}", args);
 
     check_signer(authority_info)?;
+    check_mut(currency_info)?;
     check_mut(target_mint_info)?;
     check_mut(pool_info)?;
     check_mut(target_vault_info)?;
     check_mut(base_vault_info)?;
 
-    check_program(token_program_info, &spl_token::id())?;
+    check_token_program(token_program_info, target_mint_info)?;
+    check_token_program(base_token_program_info, base_mint_info)?;
     check_program(system_program_info, &system_program::id())?;
     check_sysvar(rent_sysvar_info, &sysvar::rent::id())?;
 
     // Check mint and token accounts
 
-    let base_mint = base_mint_info.as_mint()?;
-    target_mint_info.as_mint()?;
+    let base_mint = unpack_mint(base_mint_info)?;
+    unpack_mint(target_mint_info)?;
+
+    check_base_mint_extensions(base_mint_info, args.allowed_extensions)?;
 
     check_condition(
-        base_mint.decimals() <= 18,
+        base_mint.decimals <= 18,
         "Base mint decimals cannot exceed 18"
     )?;
 
@@ -47,9 +56,47 @@ pub fn process_initialize_pool(accounts: &[AccountInfo], data: &[u8]) -> Program
         "Target and base mints must be different"
     )?;
 
+    let protocol_config = protocol_config_info.as_account::<ProtocolConfig>(&flipcash_api::ID)?;
+
+    check_condition(
+        protocol_config.can_create_pool(authority_info.key)?,
+        "Pool creation is restricted to the protocol admin"
+    )?;
+
+    check_condition(
+        protocol_config.is_base_mint_allowed(base_mint_info.key),
+        "Base mint is not allowed"
+    )?;
+
+    let currency = currency_info.as_account_mut::<CurrencyConfig>(&flipcash_api::ID)?;
+
+    // A currency's first pool keeps the original seeds; later pools are keyed
+    // by base mint so each base can back at most one of them
+    let primary_pool_seeds: &[&[u8]] = &[ POOL, currency_info.key.as_ref() ];
+    let additional_pool_seeds: &[&[u8]] = &[ POOL, currency_info.key.as_ref(), base_mint_info.key.as_ref() ];
+    let (pool_seeds, optional_accounts) = if currency.supply_allocated == 0 {
+        (primary_pool_seeds, optional_accounts)
+    } else {
+        // The primary pool isn't keyed by its base mint, so it's checked here
+        let [primary_pool_info, rest @ ..] = optional_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        check_seeds(
+            primary_pool_info,
+            primary_pool_seeds,
+            &flipcash_api::id()
+        )?;
+        let primary_pool = primary_pool_info.as_account::<LiquidityPool>(&flipcash_api::ID)?;
+        check_condition(
+            primary_pool.mint_b.ne(base_mint_info.key),
+            "Base mint already backs the currency's primary pool"
+        )?;
+        (additional_pool_seeds, rest)
+    };
+
     check_uninitialized_pda(
         pool_info,
-        &[ POOL, currency_info.key.as_ref() ],
+        pool_seeds,
         &flipcash_api::id()
     )?;
 
@@ -65,8 +112,6 @@ pub fn process_initialize_pool(accounts: &[AccountInfo], data: &[u8]) -> Program
         &flipcash_api::id()
     )?;
 
-    let currency = currency_info.as_account::<CurrencyConfig>(&flipcash_api::ID)?;
-
     check_condition(
         currency.authority.eq(authority_info.key),
         "Currency authority does not match"
@@ -82,6 +127,99 @@ pub fn process_initialize_pool(accounts: &[AccountInfo], data: &[u8]) -> Program
         "Sell fee must be less than 10,000 bps"
     )?;
 
+    check_condition(
+        args.sell_fee >= protocol_config.min_sell_fee
+            && args.sell_fee <= protocol_config.max_sell_fee,
+        "Sell fee is outside the protocol bounds"
+    )?;
+
+    check_condition(
+        args.burn_cooldown >= 0,
+        "Burn cooldown cannot be negative"
+    )?;
+
+    check_condition(
+        args.vesting_duration == 0 || args.initial_buy_amount > 0,
+        "Vesting requires an initial buy"
+    )?;
+
+    if args.vesting_duration < 0 ||
+        args.vesting_duration > MAX_VESTING_DURATION ||
+        args.vesting_cliff < 0 ||
+        args.vesting_cliff > args.vesting_duration {
+        return Err(FlipcashError::InvalidVestingSchedule.into());
+    }
+
+    // Check curve parameters. Table-backed curves take theirs from the table.
+
+    let (curve_table_info, initial_buy_accounts) = match args.curve.kind {
+        CurveKind::Table => {
+            let [curve_table_info, rest @ ..] = optional_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            curve_table_info.has_owner(&flipcash_api::ID)?;
+            (Some(curve_table_info), rest)
+        }
+        _ => (None, optional_accounts),
+    };
+
+    let curve_params = match curve_table_info {
+        Some(curve_table_info) => table_curve_params(curve_table_info)?,
+        None => args.curve,
+    };
+
+    check_condition(
+        curve_params.max_supply >= MIN_CURVE_SUPPLY &&
+        curve_params.max_supply <= MAX_CURVE_SUPPLY,
+        "Curve max supply out of bounds"
+    )?;
+
+    check_condition(
+        curve_params.max_supply <= currency.supply_unallocated(),
+        "Curve max supply exceeds unallocated currency supply"
+    )?;
+
+    check_condition(
+        curve_params.kind != CurveKind::DiscreteExponential ||
+        curve_params.num_steps().is_some(),
+        "Curve step size must evenly divide max supply"
+    )?;
+
+    check_condition(
+        curve_params.start_price > 0,
+        "Curve start price must be positive"
+    )?;
+
+    check_condition(
+        curve_params.end_price >= curve_params.start_price &&
+        curve_params.end_price / curve_params.start_price <= MAX_CURVE_PRICE_RATIO,
+        "Curve end price out of bounds"
+    )?;
+
+    check_condition(
+        curve_params.kind != CurveKind::Exponential ||
+        curve_params.end_price > curve_params.start_price,
+        "Exponential curve end price must exceed start price"
+    )?;
+
+    let curve_max_supply = UnsignedNumeric::new(curve_params.max_supply.into())
+        .ok_or(ProgramError::InvalidArgument)?;
+    let max_cumulative_value = match curve_table_info {
+        Some(curve_table_info) => {
+            let data = curve_table_info.try_borrow_data()?;
+            let (curve_table, entries) = CurveTable::unpack_entries(&data)?;
+            PoolCurve::from_table(curve_table.step_size, entries)
+                .and_then(|curve| curve.tokens_to_value(&UnsignedNumeric::zero(), &curve_max_supply))
+        }
+        None => PoolCurve::from_params(&curve_params)
+            .and_then(|curve| curve.tokens_to_value(&UnsignedNumeric::zero(), &curve_max_supply)),
+    }.ok_or(ProgramError::InvalidArgument)?;
+
+    check_condition(
+        from_numeric(max_cumulative_value, base_mint.decimals).is_ok(),
+        "Curve value exceeds base mint capacity"
+    )?;
+
     create_token_account(
         target_mint_info,
         target_vault_info,
@@ -92,6 +230,7 @@ pub fn process_initialize_pool(accounts: &[AccountInfo], data: &[u8]) -> Program
             &[args.vault_a_bump]
         ],
         authority_info,
+        token_program_info,
         system_program_info,
         rent_sysvar_info,
     )?;
@@ -106,13 +245,12 @@ pub fn process_initialize_pool(accounts: &[AccountInfo], data: &[u8]) -> Program
             &[args.vault_b_bump]
         ],
         authority_info,
+        base_token_program_info,
         system_program_info,
         rent_sysvar_info,
     )?;
 
-    let max_supply = MAX_TOKEN_SUPPLY
-        .checked_mul(QUARKS_PER_TOKEN)
-        .ok_or(ProgramError::InvalidArgument)?;
+    let max_supply = to_quarks(curve_params.max_supply, currency.decimals)?;
     mint_to_signed_with_bump(
         target_mint_info, 
         target_vault_info, 
@@ -128,16 +266,34 @@ pub fn process_initialize_pool(accounts: &[AccountInfo], data: &[u8]) -> Program
         currency.mint_bump
     )?;
 
+    currency.supply_allocated += curve_params.max_supply;
+
+    // Metadata is written with the mint authority, so it is only dropped once
+    // both the metadata and the full supply are in place
+    if currency.supply_unallocated() == 0 && currency.has_metadata != 0 {
+        set_authority_signed_with_bump(
+            target_mint_info,
+            target_mint_info,
+            Option::None,
+            spl_token_2022::instruction::AuthorityType::MintTokens,
+            token_program_info,
+            &[
+                 MINT,
+                 authority_info.key.as_ref(),
+                 currency.name.as_ref(),
+                 currency.seed.as_ref(),
+            ],
+            currency.mint_bump,
+        )?;
+    }
+
     // Create the liquidity pool account.
     create_program_account_with_bump::<LiquidityPool>(
         pool_info,
         system_program_info,
         authority_info,
         &flipcash_api::ID,
-        &[
-            POOL, 
-            currency_info.key.as_ref()
-        ],
+        pool_seeds,
         args.bump,
     )?;
 
@@ -151,10 +307,159 @@ pub fn process_initialize_pool(accounts: &[AccountInfo], data: &[u8]) -> Program
     pool.vault_b = *base_vault_info.key;
     pool.fees_accumulated = 0;
     pool.sell_fee = args.sell_fee;
+    pool.burn_policy = args.burn_policy.into();
+    pool.burn_cooldown = args.burn_cooldown;
+    pool.last_burn_at = Clock::get()?.unix_timestamp;
+    pool.curve_kind = curve_params.kind.into();
+    pool.allowed_extensions = args.allowed_extensions;
+    pool.curve_start_price = curve_params.start_price;
+    pool.curve_end_price = curve_params.end_price;
+    pool.curve_max_supply = curve_params.max_supply;
+    pool.curve_step_size = curve_params.step_size;
+    pool.curve_table = curve_table_info
+        .map(|info| *info.key)
+        .unwrap_or_default();
     pool.bump = args.bump;
     pool.vault_a_bump = args.vault_a_bump;
     pool.vault_b_bump = args.vault_b_bump;
 
+    set_launch(
+        pool,
+        args.launch_at,
+        args.presale_cap,
+        args.presale_root,
+        args.launch_window,
+        args.launch_cap,
+    )?;
+
+    // The authority's initial buy goes through before the pool is visible to
+    // anyone else, and isn't held to the launch schedule
+    let (initial_buy_in, initial_buy_out) = if args.initial_buy_amount > 0 {
+        let [
+            authority_target_info,
+            authority_base_info,
+            remaining_accounts @ ..
+        ] = initial_buy_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // A vesting buy lands in the vesting vault instead of with the
+        // authority, and the vesting account follows the base account
+        let (vesting_info, hook_accounts) = if args.vesting_duration > 0 {
+            let [vesting_info, hook_accounts @ ..] = remaining_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            (Some(vesting_info), hook_accounts)
+        } else {
+            (None, remaining_accounts)
+        };
+
+        match vesting_info {
+            Some(vesting_info) => initialize_vesting(
+                authority_info,
+                pool_info,
+                target_mint_info,
+                vesting_info,
+                authority_target_info,
+                token_program_info,
+                system_program_info,
+                rent_sysvar_info,
+                args.vesting_cliff,
+                args.vesting_duration,
+                args.vesting_bump,
+                args.vesting_vault_bump,
+            )?,
+            None => {
+                let authority_target = unpack_token_account(authority_target_info)?;
+                check_condition(
+                    authority_target.owner.eq(authority_info.key) && authority_target.mint.eq(target_mint_info.key),
+                    "Invalid authority target account"
+                )?;
+            }
+        }
+
+        let (tokens_bought_raw, base_paid_raw) = buy_common(
+            authority_info,
+            pool_info,
+            target_mint_info,
+            base_mint_info,
+            target_vault_info,
+            base_vault_info,
+            authority_target_info,
+            authority_base_info,
+            token_program_info,
+            base_token_program_info,
+            pool,
+            curve_table_info,
+            None,
+            true,
+            hook_accounts,
+            args.initial_buy_amount,
+            0,
+            &[],
+        )?;
+
+        transfer_checked_signed_with_bump(
+            target_vault_info,
+            target_vault_info,
+            target_mint_info,
+            authority_target_info,
+            token_program_info,
+            tokens_bought_raw,
+            currency.decimals,
+            &[
+                TREASURY,
+                pool_info.key.as_ref(),
+                target_mint_info.key.as_ref()
+            ],
+            pool.vault_a_bump,
+        )?;
+
+        if let Some(vesting_info) = vesting_info {
+            let vesting = vesting_info.as_account_mut::<Vesting>(&flipcash_api::ID)?;
+            vesting.total_amount = tokens_bought_raw;
+        }
+
+        (base_paid_raw, tokens_bought_raw)
+    } else {
+        (0, 0)
+    };
+
+    PoolCreatedEvent::new(
+        *pool_info.key,
+        *authority_info.key,
+        *currency_info.key,
+        *base_mint_info.key,
+        initial_buy_in,
+        initial_buy_out,
+    ).log();
+
     Ok(())
 }
 
+
+/// Curve parameters of a finalized CurveTable. Prices are the first and last
+/// step prices, rounded down to CURVE_PRICE_DECIMALS.
+fn table_curve_params(curve_table_info: &AccountInfo) -> Result<CurveParams, ProgramError> {
+    let data = curve_table_info.try_borrow_data()?;
+    let (curve_table, entries) = CurveTable::unpack_entries(&data)?;
+
+    check_condition(
+        curve_table.is_finalized != 0,
+        "Curve table is not finalized"
+    )?;
+
+    let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    Ok(CurveParams {
+        kind: CurveKind::Table,
+        start_price: from_numeric(UnsignedNumeric::from_scaled_u128(first.price()), CURVE_PRICE_DECIMALS)?,
+        end_price: from_numeric(UnsignedNumeric::from_scaled_u128(last.price()), CURVE_PRICE_DECIMALS)?,
+        max_supply: curve_table.step_size
+            .checked_mul(curve_table.num_steps)
+            .ok_or(ProgramError::InvalidArgument)?,
+        step_size: curve_table.step_size,
+    })
+}
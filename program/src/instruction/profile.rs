@@ -0,0 +1,181 @@
+use solana_program::rent::Rent;
+use steel::*;
+use flipcash_api::prelude::*;
+
+pub fn process_initialize_currency_profile(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = InitializeCurrencyProfileIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        authority_info,
+        currency_info,
+        profile_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(profile_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    let currency = currency_info.as_account::<CurrencyConfig>(&flipcash_api::ID)?;
+
+    check_condition(
+        currency.authority.eq(authority_info.key),
+        "Currency authority does not match"
+    )?;
+
+    check_uninitialized_pda(
+        profile_info,
+        &[ PROFILE, currency_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    create_program_account_with_bump::<CurrencyProfile>(
+        profile_info,
+        system_program_info,
+        authority_info,
+        &flipcash_api::ID,
+        &[
+            PROFILE,
+            currency_info.key.as_ref()
+        ],
+        args.bump,
+    )?;
+
+    let profile = profile_info.as_account_mut::<CurrencyProfile>(&flipcash_api::ID)?;
+
+    profile.currency = *currency_info.key;
+    profile.description_len = 0;
+    profile.num_links = 0;
+    profile.bump = args.bump;
+
+    Ok(())
+}
+
+pub fn process_set_profile_description(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = SetProfileDescriptionIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        authority_info,
+        currency_info,
+        profile_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(profile_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    check_condition(
+        CurrencyProfile::is_valid_description(args.description.as_bytes()),
+        "Invalid profile description"
+    )?;
+
+    let (_, links) = load_profile(authority_info, currency_info, profile_info)?;
+
+    write_profile(authority_info, profile_info, args.description.as_bytes(), &links)
+}
+
+pub fn process_set_profile_link(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = SetProfileLinkIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        authority_info,
+        currency_info,
+        profile_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(authority_info)?;
+    check_mut(profile_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    let (description, mut links) = load_profile(authority_info, currency_info, profile_info)?;
+    let index = links.iter().position(|link| link.key() == args.key);
+
+    if let Some(value) = args.value {
+        let link = ProfileLink::new(&args.key, &value);
+
+        check_condition(
+            link.is_some(),
+            "Invalid profile link"
+        )?;
+
+        if let Some(index) = index {
+            links[index] = link.unwrap();
+        } else {
+            check_condition(
+                links.len() < MAX_PROFILE_LINKS,
+                "Too many profile links"
+            )?;
+
+            links.push(link.unwrap());
+        }
+    } else {
+        check_condition(
+            index.is_some(),
+            "Profile link not found"
+        )?;
+
+        links.remove(index.unwrap());
+    }
+
+    write_profile(authority_info, profile_info, &description, &links)
+}
+
+/// Checks the currency authority and returns the profile's current contents.
+fn load_profile(
+    authority_info: &AccountInfo,
+    currency_info: &AccountInfo,
+    profile_info: &AccountInfo,
+) -> Result<(Vec<u8>, Vec<ProfileLink>), ProgramError> {
+    let currency = currency_info.as_account::<CurrencyConfig>(&flipcash_api::ID)?;
+
+    check_condition(
+        currency.authority.eq(authority_info.key),
+        "Currency authority does not match"
+    )?;
+
+    profile_info.has_owner(&flipcash_api::ID)?;
+
+    let data = profile_info.try_borrow_data()?;
+    let (profile, description, links) = CurrencyProfile::unpack_profile(&data)?;
+
+    check_condition(
+        profile.currency.eq(currency_info.key),
+        "Profile currency does not match"
+    )?;
+
+    Ok((description.as_bytes().to_vec(), links.to_vec()))
+}
+
+/// Resizes the profile to fit its new contents, with the authority covering
+/// any extra rent and getting back whatever a smaller profile frees up.
+fn write_profile<'info>(
+    authority_info: &AccountInfo<'info>,
+    profile_info: &AccountInfo<'info>,
+    description: &[u8],
+    links: &[ProfileLink],
+) -> ProgramResult {
+    let new_len = CurrencyProfile::size_for(description.len(), links.len());
+    let rent = Rent::get()?.minimum_balance(new_len);
+    let lamports = profile_info.lamports();
+
+    if rent > lamports {
+        profile_info.collect(rent - lamports, authority_info)?;
+    } else if lamports > rent {
+        profile_info.send(lamports - rent, authority_info);
+    }
+    profile_info.realloc(new_len, false)?;
+
+    let mut data = profile_info.try_borrow_mut_data()?;
+    CurrencyProfile::pack_body(&mut data, description, links)
+}
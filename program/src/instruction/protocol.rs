@@ -0,0 +1,192 @@
+use solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use steel::*;
+use flipcash_api::prelude::*;
+
+pub fn process_initialize_protocol_config(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = InitializeProtocolConfigIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        admin_info,
+        protocol_config_info,
+        program_data_info,
+        system_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(admin_info)?;
+    check_mut(protocol_config_info)?;
+    check_program(system_program_info, &system_program::id())?;
+
+    check_sell_fee_bounds(args.min_sell_fee, args.max_sell_fee)?;
+
+    // Only whoever deployed the program may claim the singleton
+    check_seeds(
+        program_data_info,
+        &[ flipcash_api::ID.as_ref() ],
+        &bpf_loader_upgradeable::id()
+    )?;
+    program_data_info.has_owner(&bpf_loader_upgradeable::id())?;
+
+    check_condition(
+        upgrade_authority(program_data_info)? == Some(*admin_info.key),
+        "Protocol config must be initialized by the upgrade authority"
+    )?;
+
+    check_uninitialized_pda(
+        protocol_config_info,
+        &[ PROTOCOL_CONFIG ],
+        &flipcash_api::id()
+    )?;
+
+    create_program_account_with_bump::<ProtocolConfig>(
+        protocol_config_info,
+        system_program_info,
+        admin_info,
+        &flipcash_api::ID,
+        &[ PROTOCOL_CONFIG ],
+        args.bump,
+    )?;
+
+    let protocol_config = protocol_config_info.as_account_mut::<ProtocolConfig>(&flipcash_api::ID)?;
+
+    protocol_config.admin = *admin_info.key;
+    protocol_config.num_base_mints = 0;
+    protocol_config.min_sell_fee = args.min_sell_fee;
+    protocol_config.max_sell_fee = args.max_sell_fee;
+    protocol_config.creation_policy = args.creation_policy.into();
+    protocol_config.bump = args.bump;
+
+    Ok(())
+}
+
+pub fn process_update_protocol_config(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = UpdateProtocolConfigIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct()?;
+
+    let [
+        admin_info,
+        protocol_config_info,
+        new_admin_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(admin_info)?;
+    check_mut(protocol_config_info)?;
+
+    check_sell_fee_bounds(args.min_sell_fee, args.max_sell_fee)?;
+
+    let protocol_config = load_protocol_config_for_update(
+        admin_info,
+        protocol_config_info,
+    )?;
+
+    protocol_config.admin = *new_admin_info.key;
+    protocol_config.min_sell_fee = args.min_sell_fee;
+    protocol_config.max_sell_fee = args.max_sell_fee;
+    protocol_config.creation_policy = args.creation_policy.into();
+
+    Ok(())
+}
+
+pub fn process_set_base_mint_allowed(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = SetBaseMintAllowedIx::try_from_bytes(data)?;
+    let args = raw_args.to_struct();
+
+    let [
+        admin_info,
+        protocol_config_info,
+        base_mint_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(admin_info)?;
+    check_mut(protocol_config_info)?;
+
+    let protocol_config = load_protocol_config_for_update(
+        admin_info,
+        protocol_config_info,
+    )?;
+
+    let count = protocol_config.num_base_mints as usize;
+    let position = protocol_config.base_mints[..count]
+        .iter()
+        .position(|m| m.eq(base_mint_info.key));
+
+    match (args.allowed, position) {
+        (true, None) => {
+            check_condition(
+                count < MAX_ALLOWED_BASE_MINTS,
+                "Base mint allowlist is full"
+            )?;
+
+            // Only real mints, so a typo can't open the allowlist to nothing
+            unpack_mint(base_mint_info)?;
+
+            protocol_config.base_mints[count] = *base_mint_info.key;
+            protocol_config.num_base_mints += 1;
+        }
+        (false, Some(index)) => {
+            // Swap-remove to keep the used entries contiguous. Existing pools
+            // against the mint keep trading.
+            protocol_config.base_mints[index] = protocol_config.base_mints[count - 1];
+            protocol_config.base_mints[count - 1] = Pubkey::default();
+            protocol_config.num_base_mints -= 1;
+        }
+        // Already in the requested state.
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn check_sell_fee_bounds(min_sell_fee: u16, max_sell_fee: u16) -> ProgramResult {
+    check_condition(
+        max_sell_fee < 10000,
+        "Sell fee must be less than 10,000 bps"
+    )?;
+
+    check_condition(
+        min_sell_fee <= max_sell_fee,
+        "Min sell fee cannot exceed max sell fee"
+    )?;
+
+    Ok(())
+}
+
+fn load_protocol_config_for_update<'a>(
+    admin_info: &AccountInfo,
+    protocol_config_info: &'a AccountInfo,
+) -> Result<&'a mut ProtocolConfig, ProgramError> {
+    let protocol_config = protocol_config_info.as_account_mut::<ProtocolConfig>(&flipcash_api::ID)?;
+
+    check_condition(
+        protocol_config.admin.eq(admin_info.key),
+        "Protocol admin does not match"
+    )?;
+
+    Ok(protocol_config)
+}
+
+/// Upgrade authority recorded in the program's ProgramData account, None if
+/// the program is immutable. Read by hand rather than with bincode:
+/// a u32 variant tag, the deployment slot, then an optional pubkey.
+fn upgrade_authority(program_data_info: &AccountInfo) -> Result<Option<Pubkey>, ProgramError> {
+    let data = program_data_info.try_borrow_data()?;
+    let Some(metadata) = data.get(..UpgradeableLoaderState::size_of_programdata_metadata()) else {
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    const PROGRAM_DATA_TAG: [u8; 4] = [3, 0, 0, 0];
+    if metadata[..4] != PROGRAM_DATA_TAG {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(match metadata[12] {
+        0 => None,
+        _ => Some(Pubkey::try_from(&metadata[13..45]).map_err(|_| ProgramError::InvalidAccountData)?),
+    })
+}
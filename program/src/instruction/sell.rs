@@ -1,6 +1,8 @@
 use brine_fp::UnsignedNumeric;
 use steel::*;
 use flipcash_api::prelude::*;
+use super::{check_circuit_breaker, invoke_trade_hook, split_curve_table};
+use flipcash_api::event::SellEvent;
 
 pub fn process_sell_tokens(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let raw_args = SellTokensIx::try_from_bytes(data)?;
@@ -16,6 +18,8 @@ pub fn process_sell_tokens(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
         seller_target_info,
         seller_base_info,
         token_program_info,
+        base_token_program_info,
+        optional_accounts @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -26,12 +30,15 @@ pub fn process_sell_tokens(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
 
     let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
 
-    seller_base_info.as_token_account()?
-        .assert(|t| t.owner().eq(seller_info.key))?
-        .assert(|t| t.mint().eq(base_mint_info.key))?;
+    let seller_base = unpack_token_account(seller_base_info)?;
+    check_condition(
+        seller_base.owner.eq(seller_info.key) && seller_base.mint.eq(base_mint_info.key),
+        "Invalid seller base account"
+    )?;
 
     let value_after_fee_raw= sell_common(
         seller_info,
+        pool_info,
         target_mint_info,
         base_mint_info,
         target_vault_info,
@@ -39,23 +46,31 @@ pub fn process_sell_tokens(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
         seller_target_info,
         seller_base_info,
         token_program_info,
+        base_token_program_info,
         pool,
+        optional_accounts,
         args.in_amount,
         args.min_amount_out,
+        &[],
     )?;
 
-    transfer_signed_with_bump(
+    // Transfer hook accounts are found by key among the optional accounts
+    let base_mint = unpack_mint(base_mint_info)?;
+    transfer_checked_signed_with_extra_accounts(
         base_vault_info,
         base_vault_info,
+        base_mint_info,
         seller_base_info,
-        token_program_info,
+        base_token_program_info,
+        optional_accounts,
         value_after_fee_raw,
+        base_mint.decimals,
         &[
             TREASURY,
             pool_info.key.as_ref(),
-            base_mint_info.key.as_ref()
+            base_mint_info.key.as_ref(),
+            &[pool.vault_b_bump]
         ],
-        pool.vault_b_bump,
     )?;
 
     Ok(())
@@ -79,7 +94,9 @@ pub fn process_sell_and_deposit_into_vm(accounts: &[AccountInfo], data: &[u8]) -
         vm_omnibus_info,
         vta_owner_info,
         token_program_info,
+        base_token_program_info,
         vm_program_info,
+        optional_accounts @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -94,11 +111,17 @@ pub fn process_sell_and_deposit_into_vm(accounts: &[AccountInfo], data: &[u8]) -
 
     let pool = pool_info.as_account_mut::<LiquidityPool>(&flipcash_api::ID)?;
 
+    check_condition(
+        base_mint_info.owner.eq(&spl_token::ID),
+        "VM deposits require an SPL Token base mint"
+    )?;
+
     vm_omnibus_info.as_token_account()?
         .assert(|t| t.mint().eq(base_mint_info.key))?;
 
     let value_after_fee_raw= sell_common(
         seller_info,
+        pool_info,
         target_mint_info,
         base_mint_info,
         target_vault_info,
@@ -106,9 +129,12 @@ pub fn process_sell_and_deposit_into_vm(accounts: &[AccountInfo], data: &[u8]) -
         seller_target_info,
         vm_omnibus_info,
         token_program_info,
+        base_token_program_info,
         pool,
+        optional_accounts,
         args.in_amount,
         args.min_amount_out,
+        &[],
     )?;
 
     deposit_into_vm(
@@ -127,7 +153,7 @@ pub fn process_sell_and_deposit_into_vm(accounts: &[AccountInfo], data: &[u8]) -
             base_mint_info.key.as_ref(),
             &[pool.vault_b_bump]
         ],
-        token_program_info,
+        base_token_program_info,
         vm_program_info,
     )?;
 
@@ -135,9 +161,13 @@ pub fn process_sell_and_deposit_into_vm(accounts: &[AccountInfo], data: &[u8]) -
 }
 
 // Sell ixn common utility that executes everything but transfering the value
-// received for selling tokens to the intended destination.
-fn sell_common<'info>(
+// received for selling tokens to the intended destination. A PDA seller passes
+// its seeds (bump last) to sign for the currency transfer; wallets pass none
+// and must sign the transaction.
+#[allow(clippy::too_many_arguments)]
+pub fn sell_common<'info>(
     seller_info: &AccountInfo<'info>,
+    pool_info: &AccountInfo<'info>,
     target_mint_info: &AccountInfo<'info>,
     base_mint_info: &AccountInfo<'info>,
     target_vault_info: &AccountInfo<'info>,
@@ -145,26 +175,38 @@ fn sell_common<'info>(
     seller_target_info: &AccountInfo<'info>,
     seller_base_info: &AccountInfo<'info>,
     token_program_info: &AccountInfo<'info>,
+    base_token_program_info: &AccountInfo<'info>,
     pool: &mut LiquidityPool,
+    optional_accounts: &[AccountInfo<'info>],
     in_amount_arg: u64,
     min_amount_out_arg: u64,
+    seller_seeds: &[&[u8]],
 ) -> Result<u64, ProgramError>{
     // Basic checks
-    check_signer(seller_info)?;
+    if seller_seeds.is_empty() {
+        check_signer(seller_info)?;
+    }
+    check_condition(
+        !pool.is_sell_paused(),
+        "Pool is paused"
+    )?;
     check_mut(target_vault_info)?;
     check_mut(base_vault_info)?;
     check_mut(seller_target_info)?;
     check_mut(seller_base_info)?;
-    check_program(token_program_info, &spl_token::id())?;
+    check_token_program(token_program_info, target_mint_info)?;
+    check_token_program(base_token_program_info, base_mint_info)?;
 
-    let base_mint = base_mint_info.as_mint()?;
-    let seller_target = seller_target_info.as_token_account()?;
-    let target_vault = target_vault_info.as_token_account()?;
-    let base_vault = base_vault_info.as_token_account()?;
+    let target_mint = unpack_mint(target_mint_info)?;
+    let base_mint = unpack_mint(base_mint_info)?;
+    let seller_target = unpack_token_account(seller_target_info)?;
+    let target_vault = unpack_token_account(target_vault_info)?;
+    let base_vault = unpack_token_account(base_vault_info)?;
 
-    seller_target
-        .assert(|t| t.owner().eq(seller_info.key))?
-        .assert(|t| t.mint().eq(target_mint_info.key))?;
+    check_condition(
+        seller_target.owner.eq(seller_info.key) && seller_target.mint.eq(target_mint_info.key),
+        "Invalid seller target account"
+    )?;
 
     check_condition(
         pool.mint_a == *target_mint_info.key && pool.mint_b == *base_mint_info.key,
@@ -175,37 +217,70 @@ fn sell_common<'info>(
         "Invalid vault accounts"
     )?;
 
-    let mint_a_decimals = TOKEN_DECIMALS;
-    let mint_b_decimals = base_mint.decimals();
+    let mint_a_decimals = target_mint.decimals;
+    let mint_b_decimals = base_mint.decimals;
 
-    let tokens_left_raw = target_vault.amount();
-    let supply_from_bonding = MAX_TOKEN_SUPPLY
-        .checked_mul(QUARKS_PER_TOKEN)
-        .ok_or(ProgramError::InvalidArgument)?
+    let tokens_left_raw = target_vault.amount;
+    let supply_from_bonding = to_quarks(pool.curve_max_supply, mint_a_decimals)?
         .checked_sub(tokens_left_raw)
         .ok_or(ProgramError::InvalidArgument)?;
 
-    let value_left_raw = base_vault.amount()
+    let value_left_raw = base_vault.amount
         .checked_sub(pool.fees_accumulated)
         .ok_or(ProgramError::InvalidArgument)?;
 
     let mut in_amount_raw = in_amount_arg;
     if in_amount_raw == 0 {
-        in_amount_raw = seller_target.amount();
+        in_amount_raw = seller_target.amount;
     }
 
+    // Tokens are fungible across a currency's pools, but each pool only
+    // buys back what it has sold
+    check_condition(
+        in_amount_raw <= supply_from_bonding,
+        "Cannot sell more than the pool has sold"
+    )?;
+
     let in_amount = to_numeric(in_amount_raw, mint_a_decimals)?;
-    let new_supply = to_numeric(supply_from_bonding, mint_a_decimals)?
+    let supply = to_numeric(supply_from_bonding, mint_a_decimals)?;
+    let new_supply = supply
         .checked_sub(&in_amount)
-        .unwrap();
+        .ok_or(ProgramError::InvalidArgument)?;
     let value_left = to_numeric(value_left_raw, mint_b_decimals)?;
-    let fee_rate = from_basis_points(pool.sell_fee)?;
 
-    let curve = DiscreteExponentialCurve::default();
+    // Optional trailing accounts: the pool's curve table (table-backed pools
+    // only), then the fee schedule and trader volume, then the pool's trade
+    // hook accounts
+    let (curve_table_info, rest) = split_curve_table(pool, optional_accounts)?;
+    let (fee_accounts, hook_accounts) = rest.split_at(rest.len().min(2));
+
+    let fee_schedule = load_fee_schedule(pool_info, fee_accounts)?;
+    let trader_volume = load_trader_volume(pool_info, seller_info, fee_accounts)?;
+    let applied_fee = select_sell_fee(
+        pool,
+        fee_schedule,
+        trader_volume.as_deref(),
+        seller_info.key,
+    );
+    let fee_rate = from_basis_points(applied_fee.sell_fee)?;
+
+    let curve_table_data = curve_table_info
+        .map(|info| info.try_borrow_data())
+        .transpose()?;
+    let curve = match (curve_table_info, &curve_table_data) {
+        (Some(info), Some(data)) => pool.table_curve(info.key, data)?,
+        _ => pool.curve()?,
+    };
     let zero = UnsignedNumeric::zero();
     let new_value = curve.tokens_to_value(&zero, &new_supply)
         .ok_or(ProgramError::InvalidArgument)?;
 
+    let price_before = curve.spot_price_at_supply(&supply)
+        .ok_or(ProgramError::InvalidArgument)?;
+    let price_after = curve.spot_price_at_supply(&new_supply)
+        .ok_or(ProgramError::InvalidArgument)?;
+    check_circuit_breaker(pool_info, seller_info, pool, &price_before, &price_after)?;
+
     let mut total_sell_value = value_left
         .checked_sub(&new_value)
         .ok_or(ProgramError::InvalidArgument)?;
@@ -228,30 +303,186 @@ fn sell_common<'info>(
     let fee_amount_raw = from_numeric(fee_amount, mint_b_decimals)?;
     let sell_value_after_fee_raw = from_numeric(sell_value_after_fee, mint_b_decimals)?;
 
+    // Slippage applies to what the seller receives after any Token-2022
+    // transfer fee on the base mint
+    let clock = Clock::get()?;
+    let epoch = clock.epoch;
+    let seller_received_raw = sell_value_after_fee_raw
+        .checked_sub(transfer_fee(base_mint_info, sell_value_after_fee_raw, epoch)?)
+        .ok_or(ProgramError::InvalidArgument)?;
+
     check_condition(
-        sell_value_after_fee_raw > 0,
+        seller_received_raw > 0,
         "No value received"
     )?;
-    if pool.sell_fee > 0 {
+    if applied_fee.sell_fee > 0 {
         check_condition(
             fee_amount_raw > 0,
             "No fees generated"
         )?;
     }
     check_condition(
-        sell_value_after_fee_raw >= min_amount_out_arg,
+        seller_received_raw >= min_amount_out_arg,
         "Slippage exceeded"
     )?;
 
-    transfer(
-        seller_info,
-        seller_target_info,
-        target_vault_info,
-        token_program_info,
-        in_amount_raw,
-    )?;
+    if seller_seeds.is_empty() {
+        transfer_checked(
+            seller_info,
+            seller_target_info,
+            target_mint_info,
+            target_vault_info,
+            token_program_info,
+            in_amount_raw,
+            mint_a_decimals,
+        )?;
+    } else {
+        transfer_checked_signed_with_extra_accounts(
+            seller_info,
+            seller_target_info,
+            target_mint_info,
+            target_vault_info,
+            token_program_info,
+            &[],
+            in_amount_raw,
+            mint_a_decimals,
+            seller_seeds,
+        )?;
+    }
 
     pool.fees_accumulated = pool.fees_accumulated + fee_amount_raw;
 
+    if let Some(trader_volume) = trader_volume {
+        let total_sell_value_raw = sell_value_after_fee_raw
+            .checked_add(fee_amount_raw)
+            .ok_or(ProgramError::InvalidArgument)?;
+        trader_volume.sell_volume = trader_volume.sell_volume.saturating_add(total_sell_value_raw);
+    }
+
+    SellEvent::new(
+        *pool_info.key,
+        *seller_info.key,
+        in_amount_raw,
+        sell_value_after_fee_raw,
+        fee_amount_raw,
+        applied_fee.sell_fee,
+        applied_fee.tier,
+        applied_fee.exempt,
+    ).log();
+
+    let summary = TradeSummary::new(
+        *pool_info.key,
+        *seller_info.key,
+        pool.mint_a,
+        pool.mint_b,
+        OrderSide::Sell,
+        in_amount_raw,
+        sell_value_after_fee_raw,
+        fee_amount_raw,
+        price_after.value.as_u128(),
+        clock.slot,
+        clock.unix_timestamp,
+    );
+    invoke_trade_hook(pool_info, pool, hook_accounts, &summary)?;
+
     Ok(sell_value_after_fee_raw)
+}
+
+// The sell fee that ends up being charged, along with how it was chosen.
+struct AppliedFee {
+    sell_fee: u16,
+    tier: u8,
+    exempt: bool,
+}
+
+// Exemptions take priority over volume tiers, which take priority over the
+// pool's default sell fee.
+fn select_sell_fee(
+    pool: &LiquidityPool,
+    fee_schedule: Option<&FeeSchedule>,
+    trader_volume: Option<&TraderVolume>,
+    seller: &Pubkey,
+) -> AppliedFee {
+    let default_fee = AppliedFee {
+        sell_fee: pool.sell_fee,
+        tier: NO_FEE_TIER,
+        exempt: false,
+    };
+
+    let Some(fee_schedule) = fee_schedule else {
+        return default_fee;
+    };
+
+    if fee_schedule.is_exempt(seller) {
+        return AppliedFee {
+            sell_fee: 0,
+            tier: NO_FEE_TIER,
+            exempt: true,
+        };
+    }
+
+    let tier = trader_volume
+        .and_then(|v| fee_schedule.tier_for_volume(v.sell_volume));
+
+    match tier {
+        Some((index, tier)) => AppliedFee {
+            sell_fee: tier.sell_fee,
+            tier: index,
+            exempt: false,
+        },
+        None => default_fee,
+    }
+}
+
+// The fee schedule is the first optional trailing account. It's skipped when
+// not provided or not yet created for the pool.
+fn load_fee_schedule<'a, 'info>(
+    pool_info: &AccountInfo<'info>,
+    fee_accounts: &'a [AccountInfo<'info>],
+) -> Result<Option<&'a FeeSchedule>, ProgramError> {
+    let Some(fee_schedule_info) = fee_accounts.first() else {
+        return Ok(None);
+    };
+
+    check_seeds(
+        fee_schedule_info,
+        &[ FEE_SCHEDULE, pool_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    if fee_schedule_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let fee_schedule = fee_schedule_info.as_account::<FeeSchedule>(&flipcash_api::ID)?;
+
+    Ok(Some(fee_schedule))
+}
+
+// The seller's volume account is the second optional trailing account. It's
+// skipped when not provided or not yet created for the seller.
+fn load_trader_volume<'a, 'info>(
+    pool_info: &AccountInfo<'info>,
+    seller_info: &AccountInfo<'info>,
+    fee_accounts: &'a [AccountInfo<'info>],
+) -> Result<Option<&'a mut TraderVolume>, ProgramError> {
+    let Some(trader_volume_info) = fee_accounts.get(1) else {
+        return Ok(None);
+    };
+
+    check_seeds(
+        trader_volume_info,
+        &[ VOLUME, pool_info.key.as_ref(), seller_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    if trader_volume_info.data_is_empty() {
+        return Ok(None);
+    }
+
+    check_mut(trader_volume_info)?;
+
+    let trader_volume = trader_volume_info.as_account_mut::<TraderVolume>(&flipcash_api::ID)?;
+
+    Ok(Some(trader_volume))
 }
\ No newline at end of file
@@ -0,0 +1,146 @@
+use steel::*;
+use flipcash_api::prelude::*;
+
+pub fn process_claim_vested(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let raw_args = ClaimVestedIx::try_from_bytes(data)?;
+    let _args = raw_args.to_struct();
+
+    let [
+        beneficiary_info,
+        vesting_info,
+        vesting_vault_info,
+        target_mint_info,
+        beneficiary_target_info,
+        token_program_info,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_signer(beneficiary_info)?;
+    check_mut(vesting_info)?;
+    check_mut(vesting_vault_info)?;
+    check_mut(beneficiary_target_info)?;
+    check_token_program(token_program_info, target_mint_info)?;
+
+    let vesting = vesting_info.as_account_mut::<Vesting>(&flipcash_api::ID)?;
+
+    check_condition(
+        vesting.beneficiary.eq(beneficiary_info.key),
+        "Vesting beneficiary does not match"
+    )?;
+    check_condition(
+        vesting.mint.eq(target_mint_info.key) && vesting.vault.eq(vesting_vault_info.key),
+        "Invalid vesting accounts"
+    )?;
+
+    let beneficiary_target = unpack_token_account(beneficiary_target_info)?;
+    check_condition(
+        beneficiary_target.mint.eq(target_mint_info.key),
+        "Invalid beneficiary target account"
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let amount = vesting.claimable_amount(now);
+    check_condition(
+        amount > 0,
+        "Nothing to claim"
+    )?;
+
+    let target_mint = unpack_mint(target_mint_info)?;
+    transfer_checked_signed_with_bump(
+        vesting_vault_info,
+        vesting_vault_info,
+        target_mint_info,
+        beneficiary_target_info,
+        token_program_info,
+        amount,
+        target_mint.decimals,
+        &[
+            TREASURY,
+            vesting_info.key.as_ref(),
+            target_mint_info.key.as_ref()
+        ],
+        vesting.vault_bump,
+    )?;
+
+    vesting.claimed_amount += amount;
+
+    Ok(())
+}
+
+/// Creates the vesting account and its vault for a pool authority's initial
+/// buy. The caller fills in `total_amount` once the buy has gone through.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_vesting<'info>(
+    authority_info: &AccountInfo<'info>,
+    pool_info: &AccountInfo<'info>,
+    target_mint_info: &AccountInfo<'info>,
+    vesting_info: &AccountInfo<'info>,
+    vesting_vault_info: &AccountInfo<'info>,
+    token_program_info: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    rent_sysvar_info: &AccountInfo<'info>,
+    cliff: i64,
+    duration: i64,
+    bump: u8,
+    vault_bump: u8,
+) -> ProgramResult {
+    check_mut(vesting_info)?;
+    check_mut(vesting_vault_info)?;
+
+    let vesting_seeds: &[&[u8]] = &[ VESTING, pool_info.key.as_ref(), authority_info.key.as_ref() ];
+    check_uninitialized_pda(
+        vesting_info,
+        vesting_seeds,
+        &flipcash_api::id()
+    )?;
+
+    check_uninitialized_pda(
+        vesting_vault_info,
+        &[ TREASURY, vesting_info.key.as_ref(), target_mint_info.key.as_ref() ],
+        &flipcash_api::id()
+    )?;
+
+    create_token_account(
+        target_mint_info,
+        vesting_vault_info,
+        &[
+            TREASURY,
+            vesting_info.key.as_ref(),
+            target_mint_info.key.as_ref(),
+            &[vault_bump]
+        ],
+        authority_info,
+        token_program_info,
+        system_program_info,
+        rent_sysvar_info,
+    )?;
+
+    create_program_account_with_bump::<Vesting>(
+        vesting_info,
+        system_program_info,
+        authority_info,
+        &flipcash_api::ID,
+        vesting_seeds,
+        bump,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let (cliff_at, end_at) = now.checked_add(cliff)
+        .zip(now.checked_add(duration))
+        .ok_or(FlipcashError::InvalidVestingSchedule)?;
+
+    let vesting = vesting_info.as_account_mut::<Vesting>(&flipcash_api::ID)?;
+
+    vesting.pool = *pool_info.key;
+    vesting.beneficiary = *authority_info.key;
+    vesting.mint = *target_mint_info.key;
+    vesting.vault = *vesting_vault_info.key;
+    vesting.start_at = now;
+    vesting.cliff_at = cliff_at;
+    vesting.end_at = end_at;
+    vesting.bump = bump;
+    vesting.vault_bump = vault_bump;
+
+    Ok(())
+}
@@ -25,6 +25,45 @@ pub fn process_instruction(
         InstructionType::BuyAndDepositIntoVmIx => process_buy_and_deposit_into_vm(accounts, data)?,
         InstructionType::SellAndDepositIntoVmIx => process_sell_and_deposit_into_vm(accounts, data)?,
         InstructionType::BurnFeesIx => process_burn_fees(accounts, data)?,
+        InstructionType::InitializeFeeScheduleIx => process_initialize_fee_schedule(accounts, data)?,
+        InstructionType::SetFeeExemptionIx => process_set_fee_exemption(accounts, data)?,
+        InstructionType::SetFeeTiersIx => process_set_fee_tiers(accounts, data)?,
+        InstructionType::InitializeTraderVolumeIx => process_initialize_trader_volume(accounts, data)?,
+        InstructionType::SetBurnPolicyIx => process_set_burn_policy(accounts, data)?,
+        InstructionType::MigratePoolIx => process_migrate_pool(accounts, data)?,
+        InstructionType::InitializeCurveTableIx => process_initialize_curve_table(accounts, data)?,
+        InstructionType::WriteCurveTableIx => process_write_curve_table(accounts, data)?,
+        InstructionType::FinalizeCurveTableIx => process_finalize_curve_table(accounts, data)?,
+        InstructionType::MigrateCurrencyIx => process_migrate_currency(accounts, data)?,
+        InstructionType::InitializeProtocolConfigIx => process_initialize_protocol_config(accounts, data)?,
+        InstructionType::UpdateProtocolConfigIx => process_update_protocol_config(accounts, data)?,
+        InstructionType::SetBaseMintAllowedIx => process_set_base_mint_allowed(accounts, data)?,
+        InstructionType::InitializeCurrencyProfileIx => process_initialize_currency_profile(accounts, data)?,
+        InstructionType::SetProfileDescriptionIx => process_set_profile_description(accounts, data)?,
+        InstructionType::SetProfileLinkIx => process_set_profile_link(accounts, data)?,
+        InstructionType::PauseIx => process_pause(accounts, data)?,
+        InstructionType::ResumeIx => process_resume(accounts, data)?,
+        InstructionType::SetCircuitBreakerIx => process_set_circuit_breaker(accounts, data)?,
+        InstructionType::SetLaunchIx => process_set_launch(accounts, data)?,
+        InstructionType::InitializeWalletPurchasesIx => process_initialize_wallet_purchases(accounts, data)?,
+        InstructionType::JoinPresaleIx => process_join_presale(accounts, data)?,
+        InstructionType::ClaimVestedIx => process_claim_vested(accounts, data)?,
+        InstructionType::BatchBuyIx => process_batch_buy(accounts, data)?,
+        InstructionType::CreateCashLinkIx => process_create_cash_link(accounts, data)?,
+        InstructionType::ClaimCashLinkIx => process_claim_cash_link(accounts, data)?,
+        InstructionType::ReclaimCashLinkIx => process_reclaim_cash_link(accounts, data)?,
+        InstructionType::CreateLimitOrderIx => process_create_limit_order(accounts, data)?,
+        InstructionType::ExecuteLimitOrderIx => process_execute_limit_order(accounts, data)?,
+        InstructionType::CancelLimitOrderIx => process_cancel_limit_order(accounts, data)?,
+        InstructionType::CreateDcaPlanIx => process_create_dca_plan(accounts, data)?,
+        InstructionType::DepositDcaIx => process_deposit_dca(accounts, data)?,
+        InstructionType::WithdrawDcaIx => process_withdraw_dca(accounts, data)?,
+        InstructionType::CloseDcaPlanIx => process_close_dca_plan(accounts, data)?,
+        InstructionType::ExecuteDcaIx => process_execute_dca(accounts, data)?,
+        InstructionType::CreateDistributorIx => process_create_distributor(accounts, data)?,
+        InstructionType::ClaimDistributionIx => process_claim_distribution(accounts, data)?,
+        InstructionType::ClawbackDistributorIx => process_clawback_distributor(accounts, data)?,
+        InstructionType::SetTradeHookIx => process_set_trade_hook(accounts, data)?,
     }
 
     Ok(())
@@ -5,7 +5,24 @@ use rand::{Rng, RngCore};
 use utils::*;
 
 use flipcash_api::prelude::*;
-use solana_sdk::{signer::Signer, transaction::Transaction};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use spl_token_2022::extension::{
+    transfer_fee::instruction::initialize_transfer_fee_config,
+    BaseStateWithExtensions,
+    ExtensionType,
+    StateWithExtensions,
+};
+use spl_token_metadata_interface::state::TokenMetadata;
 
 fn as_token(val: u64, decimals: u8) -> u64 {
     val.checked_mul(10u64.pow(decimals as u32))
@@ -13,15 +30,11 @@ fn as_token(val: u64, decimals: u8) -> u64 {
 }
 
 struct TestCurrency {
-    name: String,
-    symbol: String,
+    name: CurrencyName,
+    symbol: CurrencySymbol,
     seed: [u8; 32],
 }
 
-struct TestPool {
-    sell_fee: u16,
-}
-
 #[test]
 fn run_integration() {
     let mut svm = setup_svm();
@@ -30,15 +43,16 @@ fn run_integration() {
     let payer_pk = payer.pubkey();
 
     let usdc_decimals = 6;
-    let darksky_decimals = TOKEN_DECIMALS;
+    let darksky_decimals = DEFAULT_TOKEN_DECIMALS;
 
     let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
 
     let sell_fee = to_basis_points(&to_numeric(1, 2).unwrap()).unwrap();
 
     let currency = TestCurrency {
-        name: "dark-sky".to_string(),
-        symbol: "DSKY".to_string(),
+        name: CurrencyName::new("dark-sky").unwrap(),
+        symbol: CurrencySymbol::new("DSKY").unwrap(),
         seed: [0u8; 32],
     };
 
@@ -48,9 +62,13 @@ fn run_integration() {
     let blockhash = svm.latest_blockhash();
     let ix = build_initialize_currency_ix(
         payer_pk,
-        currency.name.clone(),
-        currency.symbol.clone(),
+        currency.name,
+        currency.symbol,
         currency.seed,
+        DEFAULT_TOKEN_DECIMALS,
+        DEFAULT_TOKEN_SUPPLY,
+        spl_token::id(),
+        0,
     );
     let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
     let res = send_tx(&mut svm, tx);
@@ -61,13 +79,16 @@ fn run_integration() {
 
     assert_eq!(account.authority, payer_pk);
     assert_eq!(account.mint, mint_pda);
-    assert_eq!(account.name, to_name(&currency.name));
+    assert_eq!(&account.name, currency.name.as_bytes());
     assert_eq!(account.seed, currency.seed);
     assert_eq!(account.bump, currency_bump);
     assert_eq!(account.mint_bump, mint_bump);
 
-    let pool = TestPool {
+    let pool = PoolOptions {
         sell_fee,
+        burn_policy: FeeBurnPolicy::PermissionlessAfterCooldown,
+        burn_cooldown: 3_600,
+        ..Default::default()
     };
 
     let (pool_pda, pool_bump) = find_pool_pda(&currency_pda);
@@ -79,8 +100,10 @@ fn run_integration() {
         payer_pk,
         currency_pda,
         mint_pda,
+        spl_token::id(),
         usdc,
-        pool.sell_fee,
+        spl_token::id(),
+        pool,
     );
     let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
     let res = send_tx(&mut svm, tx);
@@ -100,8 +123,10 @@ fn run_integration() {
     assert_eq!(account.bump, pool_bump);
     assert_eq!(account.vault_a_bump, vault_a_bump);
     assert_eq!(account.vault_b_bump, vault_b_bump);
+    assert_eq!(account.burn_policy().unwrap(), pool.burn_policy);
+    assert_eq!(account.burn_cooldown, pool.burn_cooldown);
 
-    let darksky_total_supply = as_token(MAX_TOKEN_SUPPLY, TOKEN_DECIMALS);
+    let darksky_total_supply = as_token(DEFAULT_TOKEN_SUPPLY, DEFAULT_TOKEN_DECIMALS);
 
     assert_eq!(get_ata_balance(&svm, &vault_a_pda), darksky_total_supply);
     assert_eq!(get_ata_balance(&svm, &vault_b_pda), 0);
@@ -111,6 +136,7 @@ fn run_integration() {
         payer_pk,
         currency_pda,
         mint_pda,
+        spl_token::id(),
     );
     let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
     let res = send_tx(&mut svm, tx);
@@ -135,11 +161,14 @@ fn run_integration() {
         user_pk,
         pool_pda,
         mint_pda,
+        spl_token::id(),
         usdc,
+        spl_token::id(),
         buy_amount,
         0,
         user_mint_ata,
         user_usdc_ata,
+        TradeOptions::default(),
     );
     let blockhash = svm.latest_blockhash();
     let tx = Transaction::new_signed_with_payer(&[buy_ix], Some(&user_pk), &[&user], blockhash);
@@ -166,11 +195,14 @@ fn run_integration() {
         user_pk,
         pool_pda,
         mint_pda,
+        spl_token::id(),
         usdc,
+        spl_token::id(),
         sell_amount,
         0,
         user_mint_ata,
         user_usdc_ata,
+        TradeOptions::default(),
     );
     let blockhash = svm.latest_blockhash();
     let tx = Transaction::new_signed_with_payer(&[sell_ix], Some(&user_pk), &[&user], blockhash);
@@ -200,7 +232,18 @@ fn run_integration() {
         random_payer_pk,
         pool_pda,
         usdc,
+        spl_token::id(),
     );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(std::slice::from_ref(&burn_ix), Some(&random_payer_pk), &[&random_payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err(), "Burn fees should fail before the cooldown has elapsed");
+
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp += pool.burn_cooldown;
+    svm.set_sysvar(&clock);
+    svm.expire_blockhash();
+
     let blockhash = svm.latest_blockhash();
     let tx = Transaction::new_signed_with_payer(&[burn_ix], Some(&random_payer_pk), &[&random_payer], blockhash);
     let res = send_tx(&mut svm, tx);
@@ -212,6 +255,7 @@ fn run_integration() {
     let account = svm.get_account(&pool_pda).unwrap();
     let account = LiquidityPool::unpack(&account.data).unwrap();
     assert_eq!(account.fees_accumulated, 0, "Fees should be reset to 0 after burn");
+    assert_eq!(account.last_burn_at, clock.unix_timestamp);
 }
 
 #[test]
@@ -225,49 +269,16 @@ fn run_buy_and_sell_simulation_up_and_down_curve() {
     let usdc_decimals = 6;
 
     let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
 
-    let sell_fee = to_basis_points(&to_numeric(0, 2).unwrap()).unwrap();
-
-    let currency = TestCurrency {
-        name: "dark-sky".to_string(),
-        symbol: "DSKY".to_string(),
-        seed: [0u8; 32],
-    };
-
-    let (mint_pda, _) = find_mint_pda(&payer_pk, &currency.name, &currency.seed);
-    let (currency_pda, _) = find_currency_pda(&mint_pda);
-
-    let blockhash = svm.latest_blockhash();
-    let ix = build_initialize_currency_ix(
-        payer_pk,
-        currency.name.clone(),
-        currency.symbol.clone(),
-        currency.seed,
-    );
-    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
-    let res = send_tx(&mut svm, tx);
-    assert!(res.is_ok());
-
-    let pool = TestPool {
-        sell_fee,
-    };
-
-    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "dark-sky", "DSKY", usdc, PoolOptions {
+            burn_policy: FeeBurnPolicy::Permissionless,
+            ..Default::default()
+        });
     let (vault_a_pda, _) = find_vault_pda(&pool_pda, &mint_pda);
     let (vault_b_pda, _) = find_vault_pda(&pool_pda, &usdc);
 
-    let blockhash = svm.latest_blockhash();
-    let ix = build_initialize_pool_ix(
-        payer_pk,
-        currency_pda,
-        mint_pda,
-        usdc,
-        pool.sell_fee,
-    );
-    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
-    let res = send_tx(&mut svm, tx);
-    assert!(res.is_ok());
-
     let user = create_payer(&mut svm);
     let user_pk = user.pubkey();
 
@@ -289,11 +300,14 @@ fn run_buy_and_sell_simulation_up_and_down_curve() {
             user_pk,
             pool_pda,
             mint_pda,
+            spl_token::id(),
             usdc,
+            spl_token::id(),
             buy_amount,
             0,
             user_mint_ata,
             user_usdc_ata,
+            TradeOptions::default(),
         );
         let blockhash = svm.latest_blockhash();
         let tx = Transaction::new_signed_with_payer(&[buy_ix], Some(&user_pk), &[&user], blockhash);
@@ -309,7 +323,7 @@ fn run_buy_and_sell_simulation_up_and_down_curve() {
         println!("User USDC balance: {:?}", user_usdc_balance);
         println!("Vault USDC balance: {:?}", vault_usdc_balance);
 
-        if user_mint_balance == as_token(MAX_TOKEN_SUPPLY, TOKEN_DECIMALS) {
+        if user_mint_balance == as_token(DEFAULT_TOKEN_SUPPLY, DEFAULT_TOKEN_DECIMALS) {
             break;
         }
     }
@@ -318,7 +332,7 @@ fn run_buy_and_sell_simulation_up_and_down_curve() {
     let user_usdc_balance = get_ata_balance(&svm, &user_usdc_ata);
     let vault_a_balance = get_ata_balance(&svm, &vault_a_pda);
     let vault_b_balance = get_ata_balance(&svm, &vault_b_pda);
-    assert!(user_mint_balance == as_token(MAX_TOKEN_SUPPLY, TOKEN_DECIMALS), "User should have all DSKY");
+    assert!(user_mint_balance == as_token(DEFAULT_TOKEN_SUPPLY, DEFAULT_TOKEN_DECIMALS), "User should have all DSKY");
     assert!(user_usdc_balance > 0, "User should have some USDC left");
     assert!(vault_a_balance == 0, "Vault A should have no DSKY");
     assert!(vault_b_balance == 1_139_973_004_315_032_343, "Vault B should have the cumulative USDC to buy all tokens");
@@ -334,11 +348,14 @@ fn run_buy_and_sell_simulation_up_and_down_curve() {
             user_pk,
             pool_pda,
             mint_pda,
+            spl_token::id(),
             usdc,
+            spl_token::id(),
             sell_amount,
             0,
             user_mint_ata,
             user_usdc_ata,
+            TradeOptions::default(),
         );
         let blockhash = svm.latest_blockhash();
         let tx = Transaction::new_signed_with_payer(&[sell_ix], Some(&user_pk), &[&user], blockhash);
@@ -361,7 +378,7 @@ fn run_buy_and_sell_simulation_up_and_down_curve() {
     let vault_b_balance = get_ata_balance(&svm, &vault_b_pda);
     assert!(user_mint_balance == 0, "User should have no DSKY");
     assert!(user_usdc_balance == mint_amt, "User should have all USDC");
-    assert!(vault_a_balance == as_token(MAX_TOKEN_SUPPLY, TOKEN_DECIMALS), "Vault A should have all DSKY");
+    assert!(vault_a_balance == as_token(DEFAULT_TOKEN_SUPPLY, DEFAULT_TOKEN_DECIMALS), "Vault A should have all DSKY");
     assert!(vault_b_balance == 0, "Vault B should have no USDC");
 }
 
@@ -376,49 +393,16 @@ fn run_buy_and_sell_simulation_random() {
     let usdc_decimals = 6;
 
     let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
 
-    let sell_fee = to_basis_points(&to_numeric(0, 2).unwrap()).unwrap();
-
-    let currency = TestCurrency {
-        name: "dark-sky".to_string(),
-        symbol: "DSKY".to_string(),
-        seed: [0u8; 32],
-    };
-
-    let (mint_pda, _) = find_mint_pda(&payer_pk, &currency.name, &currency.seed);
-    let (currency_pda, _) = find_currency_pda(&mint_pda);
-
-    let blockhash = svm.latest_blockhash();
-    let ix = build_initialize_currency_ix(
-        payer_pk,
-        currency.name.clone(),
-        currency.symbol.clone(),
-        currency.seed,
-    );
-    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
-    let res = send_tx(&mut svm, tx);
-    assert!(res.is_ok());
-
-    let pool = TestPool {
-        sell_fee,
-    };
-
-    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "dark-sky", "DSKY", usdc, PoolOptions {
+            burn_policy: FeeBurnPolicy::Permissionless,
+            ..Default::default()
+        });
     let (vault_a_pda, _) = find_vault_pda(&pool_pda, &mint_pda);
     let (vault_b_pda, _) = find_vault_pda(&pool_pda, &usdc);
 
-    let blockhash = svm.latest_blockhash();
-    let ix = build_initialize_pool_ix(
-        payer_pk,
-        currency_pda,
-        mint_pda,
-        usdc,
-        pool.sell_fee,
-    );
-    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
-    let res = send_tx(&mut svm, tx);
-    assert!(res.is_ok());
-
     let user = create_payer(&mut svm);
     let user_pk = user.pubkey();
 
@@ -438,11 +422,14 @@ fn run_buy_and_sell_simulation_random() {
                 user_pk,
                 pool_pda,
                 mint_pda,
+                spl_token::id(),
                 usdc,
+                spl_token::id(),
                 buy_amount,
                 0,
                 user_mint_ata,
                 user_usdc_ata,
+                TradeOptions::default(),
             );
             let blockhash = svm.latest_blockhash();
             let tx = Transaction::new_signed_with_payer(&[buy_ix], Some(&user_pk), &[&user], blockhash);
@@ -454,11 +441,14 @@ fn run_buy_and_sell_simulation_random() {
                 user_pk,
                 pool_pda,
                 mint_pda,
+                spl_token::id(),
                 usdc,
+                spl_token::id(),
                 sell_amount,
                 0,
                 user_mint_ata,
                 user_usdc_ata,
+                TradeOptions::default(),
             );
             let blockhash = svm.latest_blockhash();
             let tx = Transaction::new_signed_with_payer(&[sell_ix], Some(&user_pk), &[&user], blockhash);
@@ -477,10 +467,10 @@ fn run_buy_and_sell_simulation_random() {
 
         let mut difference;
         let curve = DiscreteExponentialCurve::default();
-        let zero_supply = to_numeric(0, TOKEN_DECIMALS).unwrap();
+        let zero_supply = to_numeric(0, DEFAULT_TOKEN_DECIMALS).unwrap();
         let usdc_buy_amount = to_numeric(vault_usdc_balance, usdc_decimals).unwrap();
         let expected_token_supply = curve.value_to_tokens(&zero_supply, &usdc_buy_amount).unwrap();
-        let expected_quark_supply = from_numeric(expected_token_supply, TOKEN_DECIMALS).unwrap();
+        let expected_quark_supply = from_numeric(expected_token_supply, DEFAULT_TOKEN_DECIMALS).unwrap();
         if expected_quark_supply > user_mint_balance {
             difference = expected_quark_supply - user_mint_balance;
         } else {
@@ -492,7 +482,7 @@ fn run_buy_and_sell_simulation_random() {
         println!("DSKY supply difference from expectation: {:?}", difference);
         println!("Max DSKY supply difference from expectation so far: {:?}", max_supply_difference);
 
-        let current_supply = to_numeric(user_mint_balance, TOKEN_DECIMALS).unwrap();
+        let current_supply = to_numeric(user_mint_balance, DEFAULT_TOKEN_DECIMALS).unwrap();
         let expected_locked_usdc = curve.tokens_to_value(&zero_supply, &current_supply).unwrap();
         let expected_locked_usdc_quarks = from_numeric(expected_locked_usdc, usdc_decimals).unwrap();
         if expected_locked_usdc_quarks > vault_usdc_balance {
@@ -513,3 +503,3226 @@ fn run_buy_and_sell_simulation_random() {
     assert!(max_supply_difference < 500_000, "Significant imprecision detected");
     assert!(max_usdc_locked_difference < 10, "Significant imprecision detected");
 }
+
+#[test]
+fn run_migrate_pool() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "migrated", "MIGR", usdc, PoolOptions {
+            sell_fee: 100,
+            burn_policy: FeeBurnPolicy::Permissionless,
+            ..Default::default()
+        });
+
+    // Cut the pool back to the v0 layout, as if it was created before pools
+    // kept their own settings
+    let mut account = svm.get_account(&pool_pda).unwrap();
+    let current = *LiquidityPool::unpack(&account.data).unwrap();
+    account.data.truncate(LIQUIDITY_POOL_V0_LEN);
+    account.data[LIQUIDITY_POOL_V0_LEN - 3..].fill(0);
+    account.lamports = svm.minimum_balance_for_rent_exemption(LIQUIDITY_POOL_V0_LEN);
+    svm.set_account(pool_pda, account).unwrap();
+
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+
+    let res = mint_to(&mut svm, &user, &usdc, &payer, &user_usdc_ata, as_token(100, usdc_decimals));
+    assert!(res.is_ok());
+
+    let buy = |svm: &mut LiteSVM| {
+        let ix = build_buy_tokens_ix(
+            user_pk,
+            pool_pda,
+            mint_pda,
+            spl_token::id(),
+            usdc,
+            spl_token::id(),
+            as_token(1, usdc_decimals),
+            0,
+            user_mint_ata,
+            user_usdc_ata,
+            TradeOptions::default(),
+        );
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&user_pk), &[&user], blockhash);
+        send_tx(svm, tx)
+    };
+    let migrate = |svm: &mut LiteSVM| {
+        let blockhash = svm.latest_blockhash();
+        let ix = build_migrate_pool_ix(user_pk, pool_pda);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&user_pk), &[&user], blockhash);
+        send_tx(svm, tx)
+    };
+
+    // A v0 pool can't be loaded until it's migrated
+    assert!(buy(&mut svm).is_err());
+
+    assert!(migrate(&mut svm).is_ok());
+
+    let account = svm.get_account(&pool_pda).unwrap();
+    assert_eq!(account.data.len(), 8 + std::mem::size_of::<LiquidityPool>());
+    assert_eq!(account.lamports, svm.minimum_balance_for_rent_exemption(account.data.len()));
+
+    let pool = LiquidityPool::unpack(&account.data).unwrap();
+    assert_eq!(pool.authority, current.authority);
+    assert_eq!(pool.vault_b, current.vault_b);
+    assert_eq!(pool.sell_fee, 100);
+    assert_eq!(pool.vault_b_bump, current.vault_b_bump);
+    assert_eq!(pool.curve_params().unwrap(), CurveParams::default());
+    assert_eq!(pool.burn_policy().unwrap(), FeeBurnPolicy::Permissionless);
+
+    assert!(buy(&mut svm).is_ok());
+
+    // Migrating twice does nothing
+    assert!(migrate(&mut svm).is_err());
+}
+
+#[test]
+fn run_migrate_currency() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, 6);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let TestMarket { mint: mint_pda, currency: currency_pda, .. } =
+        create_market(&mut svm, &payer, "migrated", "MIGR", usdc, PoolOptions::default());
+
+    let other_name = CurrencyName::new("migrated-2").unwrap();
+    let (other_mint_pda, _) = find_mint_pda(&payer_pk, &other_name, &[0u8; 32]);
+    let (other_currency_pda, _) = find_currency_pda(&other_mint_pda);
+
+    let blockhash = svm.latest_blockhash();
+    let ixs = [
+        build_initialize_metadata_ix(
+            payer_pk,
+            currency_pda,
+            mint_pda,
+            spl_token::id(),
+        ),
+        build_initialize_currency_ix(
+            payer_pk,
+            other_name,
+            CurrencySymbol::new("MIGR2").unwrap(),
+            [0u8; 32],
+            DEFAULT_TOKEN_DECIMALS,
+            DEFAULT_TOKEN_SUPPLY,
+            spl_token::id(),
+            0,
+        ),
+    ];
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let current = *CurrencyConfig::unpack(&svm.get_account(&currency_pda).unwrap().data).unwrap();
+
+    // Cut both currencies back to the v0 layout, as if they were created
+    // before currencies recorded their decimals and supply. Symbols weren't
+    // uppercased back then, so two could differ by case alone.
+    let symbol_at = 8 + std::mem::offset_of!(CurrencyConfig, symbol);
+    for (currency_pda, legacy_symbol) in [(currency_pda, b"migr\0\0\0\0"), (other_currency_pda, b"Migr\0\0\0\0")] {
+        let mut account = svm.get_account(&currency_pda).unwrap();
+        account.data[symbol_at..symbol_at + MAX_SYMBOL_LEN].copy_from_slice(legacy_symbol);
+        account.data.truncate(CURRENCY_CONFIG_V0_LEN);
+        account.data[CURRENCY_CONFIG_V0_LEN - 6..].fill(0);
+        account.lamports = svm.minimum_balance_for_rent_exemption(CURRENCY_CONFIG_V0_LEN);
+        svm.set_account(currency_pda, account).unwrap();
+    }
+
+    // Nor were there symbol reservations or a registry
+    let (symbol_pda, _) = find_symbol_pda(CurrencySymbol::new("MIGR").unwrap().as_bytes());
+    let (other_symbol_pda, _) = find_symbol_pda(CurrencySymbol::new("MIGR2").unwrap().as_bytes());
+    let (registry_pda, _) = find_registry_pda();
+    let (registry_page_pda, _) = find_registry_page_pda(0);
+    for address in [symbol_pda, other_symbol_pda, registry_pda, registry_page_pda] {
+        svm.set_account(address, Account::default()).unwrap();
+    }
+
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let migrate = |svm: &mut LiteSVM, mint: Pubkey| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let ix = build_migrate_currency_ix(user_pk, mint, CurrencySymbol::new("MIGR").unwrap(), 0);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&user_pk), &[&user], blockhash);
+        send_tx(svm, tx)
+    };
+
+    assert!(migrate(&mut svm, mint_pda).is_ok());
+
+    let account = svm.get_account(&currency_pda).unwrap();
+    assert_eq!(account.data.len(), 8 + std::mem::size_of::<CurrencyConfig>());
+    assert_eq!(account.lamports, svm.minimum_balance_for_rent_exemption(account.data.len()));
+
+    // Everything the current program recorded is recovered, with the symbol
+    // uppercased
+    let currency = CurrencyConfig::unpack(&account.data).unwrap();
+    assert_eq!(*currency, current);
+
+    // The symbol is reserved for the currency, and the currency registered
+    let reservation = *SymbolReservation::unpack(&svm.get_account(&symbol_pda).unwrap().data).unwrap();
+    assert_eq!(reservation.mint, mint_pda);
+    let registry_page = *CurrencyRegistryPage::unpack(&svm.get_account(&registry_page_pda).unwrap().data).unwrap();
+    assert_eq!(registry_page.mints(), [mint_pda]);
+
+    // Migrating twice does nothing
+    assert!(migrate(&mut svm, mint_pda).is_err());
+
+    // The clashing currency migrates and is registered, but the symbol stays
+    // with the first one
+    assert!(migrate(&mut svm, other_mint_pda).is_ok());
+    let other_currency = *CurrencyConfig::unpack(&svm.get_account(&other_currency_pda).unwrap().data).unwrap();
+    assert_eq!(CurrencySymbol::from_bytes(&other_currency.symbol).unwrap().as_str(), "MIGR");
+    let reservation = *SymbolReservation::unpack(&svm.get_account(&symbol_pda).unwrap().data).unwrap();
+    assert_eq!(reservation.mint, mint_pda);
+    let registry = *CurrencyRegistry::unpack(&svm.get_account(&registry_pda).unwrap().data).unwrap();
+    assert_eq!(registry.num_currencies, 2);
+    let registry_page = *CurrencyRegistryPage::unpack(&svm.get_account(&registry_page_pda).unwrap().data).unwrap();
+    assert_eq!(registry_page.mints(), [mint_pda, other_mint_pda]);
+}
+
+#[test]
+fn run_fee_schedule() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let sell_fee = to_basis_points(&to_numeric(1, 2).unwrap()).unwrap();
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "dark-sky", "DSKY", usdc, PoolOptions {
+            sell_fee,
+            burn_policy: FeeBurnPolicy::Permissionless,
+            ..Default::default()
+        });
+    let (fee_schedule_pda, fee_schedule_bump) = find_fee_schedule_pda(&pool_pda);
+
+    // FEE SCHEDULE
+    let merchant = create_payer(&mut svm);
+    let merchant_pk = merchant.pubkey();
+    let trader = create_payer(&mut svm);
+    let trader_pk = trader.pubkey();
+
+    let tier_volume = as_token(10, usdc_decimals);
+    let tier_fee = sell_fee / 2;
+
+    let blockhash = svm.latest_blockhash();
+    let ixs = [
+        build_initialize_fee_schedule_ix(payer_pk, pool_pda),
+        build_set_fee_exemption_ix(payer_pk, pool_pda, merchant_pk, true),
+        build_set_fee_tiers_ix(payer_pk, pool_pda, vec![FeeTier::new(tier_volume, tier_fee)]).unwrap(),
+        build_initialize_trader_volume_ix(payer_pk, pool_pda, trader_pk),
+    ];
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&fee_schedule_pda).unwrap();
+    let account = FeeSchedule::unpack(&account.data).unwrap();
+    assert_eq!(account.pool, pool_pda);
+    assert_eq!(account.bump, fee_schedule_bump);
+    assert!(account.is_exempt(&merchant_pk));
+    assert!(!account.is_exempt(&trader_pk));
+    assert_eq!(account.num_tiers, 1);
+    assert_eq!(account.tiers[0], FeeTier::new(tier_volume, tier_fee));
+
+    // Only the pool authority can manage the schedule
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_fee_exemption_ix(trader_pk, pool_pda, trader_pk, true);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&trader_pk), &[&trader], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err(), "Non-authority should not be able to grant exemptions");
+
+    // There's room for at most MAX_FEE_TIERS tiers
+    assert!(build_set_fee_tiers_ix(payer_pk, pool_pda, vec![FeeTier::new(tier_volume, tier_fee); MAX_FEE_TIERS + 1]).is_err());
+
+    // Tiers can only discount the pool's sell fee
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_fee_tiers_ix(payer_pk, pool_pda, vec![FeeTier::new(tier_volume, sell_fee + 1)]).unwrap();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err(), "Tier fee above the pool sell fee should be rejected");
+
+    let mut balances = vec![];
+    for user in [&merchant, &trader] {
+        let user_pk = user.pubkey();
+        let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+        let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+
+        let mint_amt = as_token(1000, usdc_decimals);
+        let res = mint_to(&mut svm, &payer, &usdc, &payer, &user_usdc_ata, mint_amt);
+        assert!(res.is_ok());
+
+        let buy_ix = build_buy_tokens_ix(
+            user_pk,
+            pool_pda,
+            mint_pda,
+            spl_token::id(),
+            usdc,
+            spl_token::id(),
+            mint_amt,
+            0,
+            user_mint_ata,
+            user_usdc_ata,
+            TradeOptions::default(),
+        );
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[buy_ix], Some(&user_pk), &[user], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert!(res.is_ok());
+
+        balances.push((user_mint_ata, user_usdc_ata));
+    }
+
+    // SELL (exempt)
+    let (merchant_mint_ata, merchant_usdc_ata) = balances[0];
+    let sell_ix = build_sell_tokens_ix(
+        merchant_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        get_ata_balance(&svm, &merchant_mint_ata) / 2,
+        0,
+        merchant_mint_ata,
+        merchant_usdc_ata,
+        TradeOptions::default(),
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[sell_ix], Some(&merchant_pk), &[&merchant], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&pool_pda).unwrap();
+    let account = LiquidityPool::unpack(&account.data).unwrap();
+    assert_eq!(account.fees_accumulated, 0, "Exempt sellers should not pay fees");
+
+    // SELL (below tier, then within tier)
+    let (trader_mint_ata, trader_usdc_ata) = balances[1];
+    let (trader_volume_pda, _) = find_trader_volume_pda(&pool_pda, &trader_pk);
+    let sell_amount = get_ata_balance(&svm, &trader_mint_ata) / 4;
+
+    let mut fees_per_sell = vec![];
+    let mut usdc_per_sell = vec![];
+    for _ in 0..2 {
+        let fees_before = LiquidityPool::unpack(&svm.get_account(&pool_pda).unwrap().data).unwrap().fees_accumulated;
+        let usdc_before = get_ata_balance(&svm, &trader_usdc_ata);
+
+        let sell_ix = build_sell_tokens_ix(
+            trader_pk,
+            pool_pda,
+            mint_pda,
+            spl_token::id(),
+            usdc,
+            spl_token::id(),
+            sell_amount,
+            0,
+            trader_mint_ata,
+            trader_usdc_ata,
+            TradeOptions::default(),
+        );
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[sell_ix], Some(&trader_pk), &[&trader], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert!(res.is_ok());
+
+        let fees_after = LiquidityPool::unpack(&svm.get_account(&pool_pda).unwrap().data).unwrap().fees_accumulated;
+        fees_per_sell.push(fees_after - fees_before);
+        usdc_per_sell.push(get_ata_balance(&svm, &trader_usdc_ata) - usdc_before);
+    }
+
+    let account = svm.get_account(&trader_volume_pda).unwrap();
+    let account = TraderVolume::unpack(&account.data).unwrap();
+    let total_sold = usdc_per_sell.iter().sum::<u64>() + fees_per_sell.iter().sum::<u64>();
+    assert_eq!(account.sell_volume, total_sold, "Volume should track gross sell value");
+    assert!(usdc_per_sell[0] + fees_per_sell[0] >= tier_volume, "First sell should unlock the tier");
+
+    // Buying back doesn't add to the trader's sell volume
+    let buy_ix = build_buy_tokens_ix(
+        trader_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        usdc_per_sell[0],
+        0,
+        trader_mint_ata,
+        trader_usdc_ata,
+        TradeOptions::default(),
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[buy_ix], Some(&trader_pk), &[&trader], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&trader_volume_pda).unwrap();
+    let account = TraderVolume::unpack(&account.data).unwrap();
+    assert_eq!(account.sell_volume, total_sold, "Buys should not count as sell volume");
+
+    // First sell pays the pool fee (1%), the second the discounted tier (0.5%)
+    assert!(fees_per_sell[0] > 0 && fees_per_sell[1] > 0);
+    assert!(fees_per_sell[1] * 10_000 / (usdc_per_sell[1] + fees_per_sell[1]) <= tier_fee as u64);
+    assert!(fees_per_sell[0] * 10_000 / (usdc_per_sell[0] + fees_per_sell[0]) > tier_fee as u64);
+}
+
+#[test]
+fn run_custom_curve() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    // A smaller currency than the default, with fewer decimals
+    let currency_decimals = 6;
+    let currency_supply = 1_000_000;
+
+    let currency = TestCurrency {
+        name: CurrencyName::new("stable-ish").unwrap(),
+        symbol: CurrencySymbol::new("STBL").unwrap(),
+        seed: [0u8; 32],
+    };
+
+    let (mint_pda, _) = find_mint_pda(&payer_pk, &currency.name, &currency.seed);
+    let (currency_pda, _) = find_currency_pda(&mint_pda);
+
+    let init_currency_ix = |decimals, max_supply| build_initialize_currency_ix(
+        payer_pk,
+        currency.name,
+        currency.symbol,
+        currency.seed,
+        decimals,
+        max_supply,
+        spl_token::id(),
+        0,
+    );
+
+    // Out of bounds currency parameters are rejected
+    for (decimals, max_supply) in [
+        (MAX_TOKEN_DECIMALS + 1, currency_supply),
+        (currency_decimals, MIN_CURVE_SUPPLY - 1),
+        (currency_decimals, MAX_CURVE_SUPPLY + 1),
+        (MAX_TOKEN_DECIMALS, MAX_CURVE_SUPPLY), // Overflows u64 in quarks
+    ] {
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[init_currency_ix(decimals, max_supply)], Some(&payer_pk), &[&payer], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert!(res.is_err(), "Invalid currency ({}, {}) should be rejected", decimals, max_supply);
+    }
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_currency_ix(currency_decimals, currency_supply)], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&currency_pda).unwrap();
+    let account = CurrencyConfig::unpack(&account.data).unwrap();
+    assert_eq!(account.decimals, currency_decimals);
+    assert_eq!(account.max_supply, currency_supply);
+
+    let pool = PoolOptions {
+        burn_policy: FeeBurnPolicy::Permissionless,
+        ..Default::default()
+    };
+
+    // $1.00 to $1.10 over 1_000_000 tokens
+    let curve_params = CurveParams {
+        kind: CurveKind::DiscreteExponential,
+        start_price: 1_000_000_000,
+        end_price: 1_100_000_000,
+        max_supply: 1_000_000,
+        step_size: 1_000,
+    };
+
+    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (vault_a_pda, _) = find_vault_pda(&pool_pda, &mint_pda);
+    let (vault_b_pda, _) = find_vault_pda(&pool_pda, &usdc);
+
+    // Out of bounds parameters are rejected
+    for invalid in [
+        CurveParams { step_size: 3_000, ..curve_params },
+        CurveParams { end_price: curve_params.start_price - 1, ..curve_params },
+        CurveParams { max_supply: MAX_CURVE_SUPPLY + 1_000, ..curve_params },
+        CurveParams { max_supply: currency_supply + 1_000, ..curve_params },
+    ] {
+        let blockhash = svm.latest_blockhash();
+        let ix = build_initialize_pool_ix(
+            payer_pk,
+            currency_pda,
+            mint_pda,
+            spl_token::id(),
+            usdc,
+            spl_token::id(),
+            PoolOptions { curve: invalid, ..pool },
+        );
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert!(res.is_err(), "Invalid curve {:?} should be rejected", invalid);
+    }
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_pool_ix(
+        payer_pk,
+        currency_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        PoolOptions { curve: curve_params, ..pool },
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&pool_pda).unwrap();
+    let account = LiquidityPool::unpack(&account.data).unwrap();
+    assert_eq!(account.curve_params().unwrap(), curve_params);
+
+    let total_supply = as_token(curve_params.max_supply, currency_decimals);
+    assert_eq!(get_ata_balance(&svm, &vault_a_pda), total_supply);
+
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+
+    let mint_amt = as_token(100, usdc_decimals);
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &user_usdc_ata, mint_amt);
+    assert!(res.is_ok());
+
+    let buy_ix = build_buy_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        mint_amt,
+        0,
+        user_mint_ata,
+        user_usdc_ata,
+        TradeOptions::default(),
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[buy_ix], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // $100 at ~$1.00 buys ~100 tokens, not the ~10_000 the default curve would give
+    let tokens_bought = get_ata_balance(&svm, &user_mint_ata);
+    let curve = PoolCurve::from_params(&curve_params).unwrap();
+    let expected = curve.value_to_tokens(
+        &UnsignedNumeric::zero(),
+        &to_numeric(mint_amt, usdc_decimals).unwrap(),
+    ).unwrap();
+    assert_eq!(tokens_bought, from_numeric(expected, currency_decimals).unwrap());
+    assert_eq!(get_ata_balance(&svm, &vault_b_pda), mint_amt);
+}
+
+#[test]
+fn run_multiple_pools() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdf_decimals = 6;
+
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    let usdf = create_mint(&mut svm, &payer, &payer_pk, usdf_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc, usdf]);
+
+    // Room for two pools of 1_000_000 tokens each
+    let currency_decimals = 6;
+    let currency_supply = 2_000_000;
+
+    let currency = TestCurrency {
+        name: CurrencyName::new("two-reserves").unwrap(),
+        symbol: CurrencySymbol::new("TWO").unwrap(),
+        seed: [0u8; 32],
+    };
+
+    let (mint_pda, _) = find_mint_pda(&payer_pk, &currency.name, &currency.seed);
+    let (currency_pda, _) = find_currency_pda(&mint_pda);
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_currency_ix(
+        payer_pk,
+        currency.name,
+        currency.symbol,
+        currency.seed,
+        currency_decimals,
+        currency_supply,
+        spl_token::id(),
+        0,
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let pool = PoolOptions {
+        burn_policy: FeeBurnPolicy::Permissionless,
+        ..Default::default()
+    };
+
+    let curve_params = CurveParams {
+        kind: CurveKind::DiscreteExponential,
+        start_price: 1_000_000_000,
+        end_price: 1_100_000_000,
+        max_supply: 1_000_000,
+        step_size: 1_000,
+    };
+
+    let init_pool_ix = |additional: bool, base_mint, curve| {
+        let build = if additional { build_initialize_additional_pool_ix } else { build_initialize_pool_ix };
+        build(
+            payer_pk,
+            currency_pda,
+            mint_pda,
+            spl_token::id(),
+            base_mint,
+            spl_token::id(),
+            PoolOptions { curve, ..pool },
+        )
+    };
+
+    // The first pool keeps the original seeds
+    let (usdc_pool_pda, _) = find_pool_pda(&currency_pda);
+    let blockhash = svm.latest_blockhash();
+    let ix = init_pool_ix(false, usdc, curve_params);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&currency_pda).unwrap();
+    let account = CurrencyConfig::unpack(&account.data).unwrap();
+    assert_eq!(account.supply_allocated, curve_params.max_supply);
+    assert_eq!(account.supply_unallocated(), currency_supply - curve_params.max_supply);
+
+    // Metadata no longer revokes the mint authority while supply is unallocated
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_metadata_ix(
+        payer_pk,
+        currency_pda,
+        mint_pda,
+        spl_token::id(),
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&mint_pda).unwrap();
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data).unwrap();
+    assert!(mint.base.mint_authority.is_some());
+
+    // A second pool can't take more than what is left
+    let blockhash = svm.latest_blockhash();
+    let ix = init_pool_ix(
+        true,
+        usdf,
+        CurveParams { max_supply: 1_001_000, ..curve_params },
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    // The first pool's seeds are taken
+    let blockhash = svm.latest_blockhash();
+    let ix = init_pool_ix(false, usdf, curve_params);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    // Nor can a second pool share the first pool's base mint
+    let blockhash = svm.latest_blockhash();
+    let ix = init_pool_ix(true, usdc, curve_params);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let (usdf_pool_pda, _) = find_additional_pool_pda(&currency_pda, &usdf);
+    let blockhash = svm.latest_blockhash();
+    let ix = init_pool_ix(true, usdf, curve_params);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&usdf_pool_pda).unwrap();
+    let account = LiquidityPool::unpack(&account.data).unwrap();
+    assert_eq!(account.currency, currency_pda);
+    assert_eq!(account.mint_b, usdf);
+
+    let (usdf_vault_a_pda, _) = find_vault_pda(&usdf_pool_pda, &mint_pda);
+    assert_eq!(get_ata_balance(&svm, &usdf_vault_a_pda), as_token(curve_params.max_supply, currency_decimals));
+
+    // Fully allocated with metadata set, so the last pool revoked the authority
+    let account = svm.get_account(&currency_pda).unwrap();
+    let account = CurrencyConfig::unpack(&account.data).unwrap();
+    assert_eq!(account.supply_unallocated(), 0);
+
+    let account = svm.get_account(&mint_pda).unwrap();
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data).unwrap();
+    assert!(mint.base.mint_authority.is_none());
+    assert_eq!(mint.base.supply, as_token(currency_supply, currency_decimals));
+
+    // No room for a third pool
+    let usdt = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[build_set_base_mint_allowed_ix(payer_pk, usdt, true)], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+    let blockhash = svm.latest_blockhash();
+    let ix = init_pool_ix(true, usdt, curve_params);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    // Buy from the USDC pool
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+    let user_usdf_ata = create_ata(&mut svm, &payer, &usdf, &user_pk);
+
+    let mint_amt = as_token(100, usdc_decimals);
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &user_usdc_ata, mint_amt);
+    assert!(res.is_ok());
+
+    let buy_ix = build_buy_tokens_ix(
+        user_pk,
+        usdc_pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        mint_amt,
+        0,
+        user_mint_ata,
+        user_usdc_ata,
+        TradeOptions::default(),
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[buy_ix], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let tokens_bought = get_ata_balance(&svm, &user_mint_ata);
+    assert!(tokens_bought > 0);
+
+    // The USDF pool hasn't sold anything, so it can't pay out for USDC-bought tokens
+    let sell_ix = |pool_pda, base_mint, seller_base| build_sell_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        base_mint,
+        spl_token::id(),
+        tokens_bought,
+        0,
+        user_mint_ata,
+        seller_base,
+        TradeOptions::default(),
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[sell_ix(usdf_pool_pda, usdf, user_usdf_ata)], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[sell_ix(usdc_pool_pda, usdc, user_usdc_ata)], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    assert_eq!(get_ata_balance(&svm, &user_mint_ata), 0);
+    assert_eq!(get_ata_balance(&svm, &user_usdf_ata), 0);
+}
+
+#[test]
+fn run_protocol_config() {
+    let mut svm = setup_svm();
+
+    let admin = create_payer(&mut svm);
+    let admin_pk = admin.pubkey();
+
+    let creator = create_payer(&mut svm);
+    let creator_pk = creator.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &admin, &admin_pk, usdc_decimals);
+    let junk = create_mint(&mut svm, &admin, &admin_pk, usdc_decimals);
+
+    let (protocol_config_pda, protocol_config_bump) = find_protocol_config_pda();
+
+    set_upgrade_authority(&mut svm, &admin_pk);
+
+    // Only the upgrade authority can claim the config
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_protocol_config_ix(creator_pk, 50, 200, CreationPolicy::Permissionless);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&creator_pk), &[&creator], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+    assert!(svm.get_account(&protocol_config_pda).is_none());
+
+    // Fee bounds must be ordered
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_protocol_config_ix(admin_pk, 200, 100, CreationPolicy::Permissionless);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin_pk), &[&admin], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_protocol_config_ix(admin_pk, 50, 200, CreationPolicy::Permissionless);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin_pk), &[&admin], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&protocol_config_pda).unwrap();
+    let account = ProtocolConfig::unpack(&account.data).unwrap();
+    assert_eq!(account.admin, admin_pk);
+    assert_eq!(account.min_sell_fee, 50);
+    assert_eq!(account.max_sell_fee, 200);
+    assert_eq!(account.creation_policy().unwrap(), CreationPolicy::Permissionless);
+    assert_eq!(account.num_base_mints, 0);
+    assert_eq!(account.bump, protocol_config_bump);
+
+    // It's a singleton
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_protocol_config_ix(admin_pk, 0, 9_999, CreationPolicy::Permissionless);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin_pk), &[&admin], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    // Only the admin manages the allowlist
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_base_mint_allowed_ix(creator_pk, junk, true);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&creator_pk), &[&creator], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_base_mint_allowed_ix(admin_pk, usdc, true);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin_pk), &[&admin], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&protocol_config_pda).unwrap();
+    let account = ProtocolConfig::unpack(&account.data).unwrap();
+    assert!(account.is_base_mint_allowed(&usdc));
+    assert!(!account.is_base_mint_allowed(&junk));
+
+    let currencies: Vec<_> = [("junk-backed", "JUNK"), ("fee-too-low", "LOW"), ("admin-only", "ADMN"), ("allowed", "OK")]
+        .iter()
+        .map(|(name, symbol)| {
+            let currency = TestCurrency {
+                name: CurrencyName::new(name).unwrap(),
+                symbol: CurrencySymbol::new(symbol).unwrap(),
+                seed: [0u8; 32],
+            };
+
+            let blockhash = svm.latest_blockhash();
+            let ix = build_initialize_currency_ix(
+                creator_pk,
+                currency.name,
+                currency.symbol,
+                currency.seed,
+                DEFAULT_TOKEN_DECIMALS,
+                DEFAULT_TOKEN_SUPPLY,
+                spl_token::id(),
+                0,
+            );
+            let tx = Transaction::new_signed_with_payer(&[ix], Some(&creator_pk), &[&creator], blockhash);
+            let res = send_tx(&mut svm, tx);
+            assert!(res.is_ok());
+
+            let (mint_pda, _) = find_mint_pda(&creator_pk, &currency.name, &currency.seed);
+            let (currency_pda, _) = find_currency_pda(&mint_pda);
+            (currency_pda, mint_pda)
+        })
+        .collect();
+
+    let init_pool_ix = |(currency_pda, mint_pda): (Pubkey, Pubkey), base_mint, sell_fee| build_initialize_pool_ix(
+        creator_pk,
+        currency_pda,
+        mint_pda,
+        spl_token::id(),
+        base_mint,
+        spl_token::id(),
+        PoolOptions {
+            sell_fee,
+            burn_policy: FeeBurnPolicy::Permissionless,
+            ..Default::default()
+        },
+    );
+
+    // A base mint off the allowlist is rejected
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix(currencies[0], junk, 100)], Some(&creator_pk), &[&creator], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    // So is a sell fee outside the bounds
+    for sell_fee in [49, 201] {
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[init_pool_ix(currencies[1], usdc, sell_fee)], Some(&creator_pk), &[&creator], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert!(res.is_err());
+    }
+
+    // Restrict creation to the admin
+    let blockhash = svm.latest_blockhash();
+    let ix = build_update_protocol_config_ix(admin_pk, admin_pk, 50, 200, CreationPolicy::AdminOnly);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin_pk), &[&admin], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix(currencies[2], usdc, 100)], Some(&creator_pk), &[&creator], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    // Hand the protocol over; the old admin loses control
+    let new_admin = create_payer(&mut svm);
+    let new_admin_pk = new_admin.pubkey();
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_update_protocol_config_ix(admin_pk, new_admin_pk, 50, 200, CreationPolicy::Permissionless);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin_pk), &[&admin], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_update_protocol_config_ix(admin_pk, admin_pk, 0, 9_999, CreationPolicy::Permissionless);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&admin_pk), &[&admin], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix(currencies[3], usdc, 100)], Some(&creator_pk), &[&creator], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // Removing a mint only stops new pools
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_base_mint_allowed_ix(new_admin_pk, usdc, false);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&new_admin_pk), &[&new_admin], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&protocol_config_pda).unwrap();
+    let account = ProtocolConfig::unpack(&account.data).unwrap();
+    assert_eq!(account.admin, new_admin_pk);
+    assert_eq!(account.num_base_mints, 0);
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix(currencies[2], usdc, 100)], Some(&creator_pk), &[&creator], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+}
+
+#[test]
+fn run_currency_registry() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+    svm.airdrop(&payer_pk, 10_000_000_000).unwrap();
+
+    let init_currency_ix = |name: &str, symbol: &str, registry_page| build_initialize_currency_ix(
+        payer_pk,
+        CurrencyName::new(name).unwrap(),
+        CurrencySymbol::new(symbol).unwrap(),
+        [0u8; 32],
+        DEFAULT_TOKEN_DECIMALS,
+        DEFAULT_TOKEN_SUPPLY,
+        spl_token::id(),
+        registry_page,
+    );
+
+    // Fill the first page and spill into the second
+    let mut mints = vec![];
+    for i in 0..=REGISTRY_PAGE_LEN {
+        let name = format!("currency-{}", i);
+        let symbol = format!("C{}", i);
+
+        let blockhash = svm.latest_blockhash();
+        let ix = init_currency_ix(&name, &symbol, (i / REGISTRY_PAGE_LEN) as u64);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert!(res.is_ok());
+
+        let (mint_pda, _) = find_mint_pda(&payer_pk, &CurrencyName::new(&name).unwrap(), &[0u8; 32]);
+        mints.push(mint_pda);
+    }
+
+    let (registry_pda, _) = find_registry_pda();
+    let account = svm.get_account(&registry_pda).unwrap();
+    let registry = CurrencyRegistry::unpack(&account.data).unwrap();
+    assert_eq!(registry.num_currencies, mints.len() as u64);
+    assert_eq!(registry.num_pages(), 2);
+
+    let registered: Vec<_> = (0..registry.num_pages())
+        .flat_map(|page| {
+            let (registry_page_pda, _) = find_registry_page_pda(page);
+            let account = svm.get_account(&registry_page_pda).unwrap();
+            let registry_page = CurrencyRegistryPage::unpack(&account.data).unwrap();
+            assert_eq!(registry_page.page, page);
+            registry_page.mints().to_vec()
+        })
+        .collect();
+    assert_eq!(registered, mints);
+
+    // Appending to a page other than the current one is rejected
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_currency_ix("stale-page", "STALE", 0)], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    // Symbols are unique
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_currency_ix("copycat", "C0", 1)], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let (symbol_pda, _) = find_symbol_pda(CurrencySymbol::new("C0").unwrap().as_bytes());
+    let account = svm.get_account(&symbol_pda).unwrap();
+    let reservation = SymbolReservation::unpack(&account.data).unwrap();
+    assert_eq!(reservation.mint, mints[0]);
+    assert_eq!(CurrencySymbol::from_bytes(&reservation.symbol).unwrap().as_str(), "C0");
+}
+
+#[test]
+fn run_currency_charset() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    // Bypass the SDK constructors to send raw bytes to the program
+    let init_currency_ix = |name: &[u8], symbol: &[u8]| {
+        let mut ix = build_initialize_currency_ix(
+            payer_pk,
+            CurrencyName::new("placeholder").unwrap(),
+            CurrencySymbol::new("PH").unwrap(),
+            [0u8; 32],
+            DEFAULT_TOKEN_DECIMALS,
+            DEFAULT_TOKEN_SUPPLY,
+            spl_token::id(),
+            0,
+        );
+        let mut name_bytes = [0u8; MAX_NAME_LEN];
+        name_bytes[..name.len()].copy_from_slice(name);
+        let mut symbol_bytes = [0u8; MAX_SYMBOL_LEN];
+        symbol_bytes[..symbol.len()].copy_from_slice(symbol);
+
+        let mut args = *InitializeCurrencyIx::try_from_bytes(&ix.data[1..]).unwrap();
+        args.name = name_bytes;
+        args.symbol = symbol_bytes;
+        ix.data = args.to_bytes();
+        ix
+    };
+
+    let cases: [(&[u8], &[u8], FlipcashError); 6] = [
+        (b"", b"PH", FlipcashError::NameEmpty),
+        (b"dark\x07sky", b"PH", FlipcashError::NameInvalidCharacter),
+        (b"dark-sky\xff", b"PH", FlipcashError::NameInvalidCharacter),
+        (b" dark-sky", b"PH", FlipcashError::NameNotTrimmed),
+        (b"dark-sky", b"D SK", FlipcashError::SymbolInvalidCharacter),
+        (b"dark-sky", b"dsky", FlipcashError::SymbolNotUppercase),
+    ];
+
+    for (name, symbol, error) in cases {
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[init_currency_ix(name, symbol)], Some(&payer_pk), &[&payer], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert_eq!(
+            res.unwrap_err().err,
+            TransactionError::InstructionError(0, InstructionError::Custom(error.into()))
+        );
+    }
+}
+
+#[test]
+fn run_curve_table() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    // $1.00 rising by $0.10 every 100 tokens, over 1_000 tokens
+    let step_size = 100;
+    let num_steps = 10;
+    let mut entries = Vec::new();
+    let mut cumulative_value = 0u128;
+    for i in 0..=num_steps as u128 {
+        let price = 1_000_000_000_000_000_000 + i * 100_000_000_000_000_000;
+        entries.push(CurveTableEntry::new(price, cumulative_value));
+        cumulative_value += price * step_size as u128;
+    }
+
+    let seed = [7u8; 32];
+    let (curve_table_pda, _) = find_curve_table_pda(&payer_pk, &seed);
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_curve_table_ix(payer_pk, seed, step_size, num_steps);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // Falling prices are rejected
+    let mut falling = entries[..3].to_vec();
+    falling[2] = CurveTableEntry::new(entries[1].price() - 1, entries[2].cumulative_value());
+    let blockhash = svm.latest_blockhash();
+    let ix = build_write_curve_table_ix(payer_pk, curve_table_pda, falling);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    for chunk in entries.chunks(6) {
+        let blockhash = svm.latest_blockhash();
+        let ix = build_write_curve_table_ix(payer_pk, curve_table_pda, chunk.to_vec());
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert!(res.is_ok());
+    }
+
+    // No room past the last step
+    let blockhash = svm.latest_blockhash();
+    let ix = build_write_curve_table_ix(payer_pk, curve_table_pda, vec![*entries.last().unwrap()]);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let account = svm.get_account(&curve_table_pda).unwrap();
+    let (table, uploaded) = CurveTable::unpack_entries(&account.data).unwrap();
+    assert!(table.is_complete());
+    assert_eq!(uploaded, entries.as_slice());
+
+    let currency = TestCurrency {
+        name: CurrencyName::new("hand-tuned").unwrap(),
+        symbol: CurrencySymbol::new("TUNE").unwrap(),
+        seed: [0u8; 32],
+    };
+
+    let (mint_pda, _) = find_mint_pda(&payer_pk, &currency.name, &currency.seed);
+    let (currency_pda, _) = find_currency_pda(&mint_pda);
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_currency_ix(
+        payer_pk,
+        currency.name,
+        currency.symbol,
+        currency.seed,
+        DEFAULT_TOKEN_DECIMALS,
+        DEFAULT_TOKEN_SUPPLY,
+        spl_token::id(),
+        0,
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let pool = PoolOptions {
+        burn_policy: FeeBurnPolicy::Permissionless,
+        ..Default::default()
+    };
+
+    // Everything but the kind comes from the table
+    let curve_params = CurveParams {
+        kind: CurveKind::Table,
+        start_price: 0,
+        end_price: 0,
+        max_supply: 0,
+        step_size: 0,
+    };
+
+    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (vault_b_pda, _) = find_vault_pda(&pool_pda, &usdc);
+
+    let init_pool_ix = || build_initialize_pool_ix(
+        payer_pk,
+        currency_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        PoolOptions {
+            curve: curve_params,
+            curve_table: Some(curve_table_pda),
+            ..pool
+        },
+    );
+
+    // Tables can't back a pool until finalized
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix()], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_finalize_curve_table_ix(payer_pk, curve_table_pda);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix()], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&pool_pda).unwrap();
+    let account = LiquidityPool::unpack(&account.data).unwrap();
+    assert!(account.is_table_backed());
+    assert_eq!(account.curve_table, curve_table_pda);
+    assert_eq!(account.curve_params().unwrap(), CurveParams {
+        kind: CurveKind::Table,
+        start_price: 1_000_000_000,
+        end_price: 2_000_000_000,
+        max_supply: 1_000,
+        step_size: 100,
+    });
+
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+
+    let mint_amt = as_token(150, usdc_decimals);
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &user_usdc_ata, mint_amt);
+    assert!(res.is_ok());
+
+    let buy_ix = |curve_table| build_buy_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        mint_amt,
+        0,
+        user_mint_ata,
+        user_usdc_ata,
+        TradeOptions { curve_table, ..Default::default() },
+    );
+
+    // Table-backed pools need their table to price trades
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[buy_ix(None)], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[buy_ix(Some(curve_table_pda))], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // $100 buys the first step at $1.00, the last $50 goes at $1.10
+    let tokens_bought = get_ata_balance(&svm, &user_mint_ata);
+    let curve = PoolCurve::from_table(step_size, &entries).unwrap();
+    let expected = curve.value_to_tokens(
+        &UnsignedNumeric::zero(),
+        &to_numeric(mint_amt, usdc_decimals).unwrap(),
+    ).unwrap();
+    assert_eq!(tokens_bought, from_numeric(expected, DEFAULT_TOKEN_DECIMALS).unwrap());
+    assert!(tokens_bought > as_token(145, DEFAULT_TOKEN_DECIMALS));
+    assert!(tokens_bought < as_token(146, DEFAULT_TOKEN_DECIMALS));
+    assert_eq!(get_ata_balance(&svm, &vault_b_pda), mint_amt);
+
+    // Selling back down the same steps
+    let sell_ix = build_sell_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        tokens_bought,
+        0,
+        user_mint_ata,
+        user_usdc_ata,
+        TradeOptions { curve_table: Some(curve_table_pda), ..Default::default() },
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[sell_ix], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    assert_eq!(get_ata_balance(&svm, &user_mint_ata), 0);
+    assert!(get_ata_balance(&svm, &user_usdc_ata) <= mint_amt);
+    assert!(get_ata_balance(&svm, &user_usdc_ata) >= mint_amt - 1);
+}
+
+#[test]
+fn run_token_2022_base_mint() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usd_decimals = 6;
+
+    // 1% transfer fee on every base transfer
+    let transfer_fee_bps = 100;
+    let usd = create_mint_2022(
+        &mut svm,
+        &payer,
+        &payer_pk,
+        usd_decimals,
+        &[ExtensionType::TransferFeeConfig],
+        |mint| vec![initialize_transfer_fee_config(
+            &spl_token_2022::ID,
+            mint,
+            Some(&payer_pk),
+            Some(&payer_pk),
+            transfer_fee_bps,
+            as_token(1_000, usd_decimals),
+        ).unwrap()],
+    );
+
+    // The delegate could move funds out of any vault
+    let delegated_usd = create_mint_2022(
+        &mut svm,
+        &payer,
+        &payer_pk,
+        usd_decimals,
+        &[ExtensionType::PermanentDelegate],
+        |mint| vec![spl_token_2022::instruction::initialize_permanent_delegate(
+            &spl_token_2022::ID,
+            mint,
+            &payer_pk,
+        ).unwrap()],
+    );
+
+    setup_protocol(&mut svm, &payer, &[usd, delegated_usd]);
+
+    let currencies = [
+        TestCurrency {
+            name: CurrencyName::new("fee-backed").unwrap(),
+            symbol: CurrencySymbol::new("FEE").unwrap(),
+            seed: [0u8; 32],
+        },
+        TestCurrency {
+            name: CurrencyName::new("delegate-backed").unwrap(),
+            symbol: CurrencySymbol::new("DLGT").unwrap(),
+            seed: [0u8; 32],
+        },
+    ];
+
+    let mut currency_pdas = Vec::new();
+    for currency in &currencies {
+        let (mint_pda, _) = find_mint_pda(&payer_pk, &currency.name, &currency.seed);
+        let (currency_pda, _) = find_currency_pda(&mint_pda);
+
+        let blockhash = svm.latest_blockhash();
+        let ix = build_initialize_currency_ix(
+            payer_pk,
+            currency.name,
+            currency.symbol,
+            currency.seed,
+            DEFAULT_TOKEN_DECIMALS,
+            DEFAULT_TOKEN_SUPPLY,
+            spl_token::id(),
+            0,
+        );
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert!(res.is_ok());
+
+        currency_pdas.push((mint_pda, currency_pda));
+    }
+
+    let pool = PoolOptions {
+        burn_policy: FeeBurnPolicy::Permissionless,
+        ..Default::default()
+    };
+
+    let init_pool_ix = |(mint_pda, currency_pda): (Pubkey, Pubkey), base_mint, base_token_program, allowed_extensions| {
+        build_initialize_pool_ix(
+            payer_pk,
+            currency_pda,
+            mint_pda,
+            spl_token::id(),
+            base_mint,
+            base_token_program,
+            PoolOptions { allowed_extensions, ..pool },
+        )
+    };
+
+    // Permanent delegates need an explicit opt in
+    let blockhash = svm.latest_blockhash();
+    let ix = init_pool_ix(currency_pdas[1], delegated_usd, spl_token_2022::ID, 0);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let blockhash = svm.latest_blockhash();
+    let ix = init_pool_ix(currency_pdas[1], delegated_usd, spl_token_2022::ID, ALLOW_PERMANENT_DELEGATE);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let (delegated_pool_pda, _) = find_pool_pda(&currency_pdas[1].1);
+    let account = svm.get_account(&delegated_pool_pda).unwrap();
+    let account = LiquidityPool::unpack(&account.data).unwrap();
+    assert_eq!(account.allowed_extensions, ALLOW_PERMANENT_DELEGATE);
+
+    // The base token program must own the base mint
+    let blockhash = svm.latest_blockhash();
+    let ix = init_pool_ix(currency_pdas[0], usd, spl_token::id(), 0);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let blockhash = svm.latest_blockhash();
+    let ix = init_pool_ix(currency_pdas[0], usd, spl_token_2022::ID, 0);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let (mint_pda, currency_pda) = currency_pdas[0];
+    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (vault_b_pda, _) = find_vault_pda(&pool_pda, &usd);
+
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usd_ata = create_ata_2022(&mut svm, &payer, &usd, &user_pk);
+
+    let mint_amt = as_token(100, usd_decimals);
+    let res = mint_to_2022(&mut svm, &payer, &usd, &payer, &user_usd_ata, mint_amt);
+    assert!(res.is_ok());
+
+    let buy_ix = build_buy_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usd,
+        spl_token_2022::ID,
+        mint_amt,
+        0,
+        user_mint_ata,
+        user_usd_ata,
+        TradeOptions::default(),
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[buy_ix], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // Tokens are priced off what the vault received, net of the transfer fee
+    let received = as_token(99, usd_decimals);
+    assert_eq!(get_ata_balance(&svm, &user_usd_ata), 0);
+    assert_eq!(get_ata_balance(&svm, &vault_b_pda), received);
+
+    let tokens_bought = get_ata_balance(&svm, &user_mint_ata);
+    let curve = PoolCurve::from_params(&CurveParams::default()).unwrap();
+    let expected = curve.value_to_tokens(
+        &UnsignedNumeric::zero(),
+        &to_numeric(received, usd_decimals).unwrap(),
+    ).unwrap();
+    assert_eq!(tokens_bought, from_numeric(expected, DEFAULT_TOKEN_DECIMALS).unwrap());
+
+    let sell_ix = |min_amount_out| build_sell_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usd,
+        spl_token_2022::ID,
+        tokens_bought,
+        min_amount_out,
+        user_mint_ata,
+        user_usd_ata,
+        TradeOptions::default(),
+    );
+
+    // Slippage is checked against what the seller receives after the fee
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[sell_ix(as_token(98, usd_decimals) + 500_000)], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[sell_ix(0)], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let paid = received - get_ata_balance(&svm, &vault_b_pda);
+    let transfer_fee = (paid * transfer_fee_bps as u64).div_ceil(10_000);
+    assert_eq!(get_ata_balance(&svm, &user_mint_ata), 0);
+    assert_eq!(get_ata_balance(&svm, &user_usd_ata), paid - transfer_fee);
+}
+
+#[test]
+fn run_token_2022_currency() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let currency = TestCurrency {
+        name: CurrencyName::new("native-metadata").unwrap(),
+        symbol: CurrencySymbol::new("NTV").unwrap(),
+        seed: [0u8; 32],
+    };
+
+    let (mint_pda, _) = find_mint_pda(&payer_pk, &currency.name, &currency.seed);
+    let (currency_pda, _) = find_currency_pda(&mint_pda);
+    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (vault_a_pda, _) = find_vault_pda(&pool_pda, &mint_pda);
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_currency_ix(
+        payer_pk,
+        currency.name,
+        currency.symbol,
+        currency.seed,
+        DEFAULT_TOKEN_DECIMALS,
+        DEFAULT_TOKEN_SUPPLY,
+        spl_token_2022::ID,
+        0,
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&mint_pda).unwrap();
+    assert_eq!(account.owner, spl_token_2022::ID);
+
+    // The target token program must own the currency mint
+    let init_pool_ix = |target_token_program| build_initialize_pool_ix(
+        payer_pk,
+        currency_pda,
+        mint_pda,
+        target_token_program,
+        usdc,
+        spl_token::id(),
+        PoolOptions {
+            burn_policy: FeeBurnPolicy::Permissionless,
+            ..Default::default()
+        },
+    );
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix(spl_token::id())], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix(spl_token_2022::ID)], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let max_supply = as_token(DEFAULT_TOKEN_SUPPLY, DEFAULT_TOKEN_DECIMALS);
+    assert_eq!(get_ata_balance(&svm, &vault_a_pda), max_supply);
+
+    // Metadata is written to the mint itself, no Metaplex account involved
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_metadata_ix(
+        payer_pk,
+        currency_pda,
+        mint_pda,
+        spl_token_2022::ID,
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&mint_pda).unwrap();
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data).unwrap();
+    assert!(mint.base.mint_authority.is_none());
+    assert_eq!(mint.base.supply, max_supply);
+
+    let metadata = mint.get_variable_len_extension::<TokenMetadata>().unwrap();
+    assert_eq!(metadata.name, currency.name.as_str());
+    assert_eq!(metadata.symbol, currency.symbol.as_str());
+    assert_eq!(metadata.uri, METADATA_URI.replace("{}", &mint_pda.to_string()));
+    assert_eq!(Option::<Pubkey>::from(metadata.update_authority), Some(payer_pk));
+
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let user_mint_ata = create_ata_2022(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+
+    let mint_amt = as_token(100, usdc_decimals);
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &user_usdc_ata, mint_amt);
+    assert!(res.is_ok());
+
+    let blockhash = svm.latest_blockhash();
+    let buy_ix = build_buy_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token_2022::ID,
+        usdc,
+        spl_token::id(),
+        mint_amt,
+        0,
+        user_mint_ata,
+        user_usdc_ata,
+        TradeOptions::default(),
+    );
+    let tx = Transaction::new_signed_with_payer(&[buy_ix], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let tokens_bought = get_ata_balance(&svm, &user_mint_ata);
+    assert!(tokens_bought > 0);
+    assert_eq!(get_ata_balance(&svm, &vault_a_pda), max_supply - tokens_bought);
+
+    let blockhash = svm.latest_blockhash();
+    let sell_ix = build_sell_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token_2022::ID,
+        usdc,
+        spl_token::id(),
+        tokens_bought,
+        0,
+        user_mint_ata,
+        user_usdc_ata,
+        TradeOptions::default(),
+    );
+    let tx = Transaction::new_signed_with_payer(&[sell_ix], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    assert_eq!(get_ata_balance(&svm, &user_mint_ata), 0);
+    assert_eq!(get_ata_balance(&svm, &vault_a_pda), max_supply);
+}
+
+#[test]
+fn run_currency_profile() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let stranger = create_payer(&mut svm);
+    let stranger_pk = stranger.pubkey();
+
+    let name = CurrencyName::new("profiled").unwrap();
+    let (mint_pda, _) = find_mint_pda(&payer_pk, &name, &[0u8; 32]);
+    let (currency_pda, _) = find_currency_pda(&mint_pda);
+    let (profile_pda, _) = find_profile_pda(&currency_pda);
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_currency_ix(
+        payer_pk,
+        name,
+        CurrencySymbol::new("PRF").unwrap(),
+        [0u8; 32],
+        DEFAULT_TOKEN_DECIMALS,
+        DEFAULT_TOKEN_SUPPLY,
+        spl_token::id(),
+        0,
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // Only the currency authority can create the profile
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_currency_profile_ix(stranger_pk, currency_pda);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&stranger_pk), &[&stranger], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    let description = "Dark skies ahead.\nBuy early.";
+    let blockhash = svm.latest_blockhash();
+    let ixs = [
+        build_initialize_currency_profile_ix(payer_pk, currency_pda),
+        build_set_profile_description_ix(payer_pk, currency_pda, description.to_string()),
+        build_set_profile_link_ix(payer_pk, currency_pda, "website".to_string(), Some("https://flipcash.com".to_string())),
+        build_set_profile_link_ix(payer_pk, currency_pda, "x".to_string(), Some("@flipcash".to_string())),
+    ];
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&profile_pda).unwrap();
+    assert_eq!(account.data.len(), CurrencyProfile::size_for(description.len(), 2));
+    let (profile, unpacked_description, links) = CurrencyProfile::unpack_profile(&account.data).unwrap();
+    assert_eq!(profile.currency, currency_pda);
+    assert_eq!(unpacked_description, description);
+    assert_eq!(links, &[
+        ProfileLink::new("website", "https://flipcash.com").unwrap(),
+        ProfileLink::new("x", "@flipcash").unwrap(),
+    ]);
+    let rent_before = account.lamports;
+
+    // Updating a link replaces it in place, removing one shrinks the account
+    // and refunds the freed rent
+    let blockhash = svm.latest_blockhash();
+    let ixs = [
+        build_set_profile_link_ix(payer_pk, currency_pda, "x".to_string(), Some("@flipcash_app".to_string())),
+        build_set_profile_link_ix(payer_pk, currency_pda, "website".to_string(), None),
+        build_set_profile_description_ix(payer_pk, currency_pda, String::new()),
+    ];
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let account = svm.get_account(&profile_pda).unwrap();
+    assert_eq!(account.data.len(), CurrencyProfile::size_for(0, 1));
+    assert!(account.lamports < rent_before);
+    let (_, unpacked_description, links) = CurrencyProfile::unpack_profile(&account.data).unwrap();
+    assert_eq!(unpacked_description, "");
+    assert_eq!(links, &[ProfileLink::new("x", "@flipcash_app").unwrap()]);
+
+    // Strangers can't edit it, invalid links are rejected and removing a
+    // missing link fails
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_profile_description_ix(stranger_pk, currency_pda, "rugged".to_string());
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&stranger_pk), &[&stranger], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    for (key, value) in [("Website", Some("https://flipcash.com")), ("website", Some(" padded")), ("website", None)] {
+        let blockhash = svm.latest_blockhash();
+        let ix = build_set_profile_link_ix(payer_pk, currency_pda, key.to_string(), value.map(str::to_string));
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert!(res.is_err());
+    }
+
+    // At most MAX_PROFILE_LINKS links
+    for i in 1..MAX_PROFILE_LINKS {
+        let blockhash = svm.latest_blockhash();
+        let ix = build_set_profile_link_ix(payer_pk, currency_pda, format!("link-{}", i), Some("value".to_string()));
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert!(res.is_ok());
+    }
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_profile_link_ix(payer_pk, currency_pda, "one-too-many".to_string(), Some("value".to_string()));
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+}
+
+#[test]
+fn run_pause() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let admin = create_payer(&mut svm);
+    let admin_pk = admin.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &admin, &[usdc]);
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "pausable", "PAUSE", usdc, PoolOptions::default());
+
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+
+    let res = mint_to(&mut svm, &user, &usdc, &payer, &user_usdc_ata, as_token(100, usdc_decimals));
+    assert!(res.is_ok());
+
+    let buy = |svm: &mut LiteSVM| {
+        let ix = build_buy_tokens_ix(
+            user_pk,
+            pool_pda,
+            mint_pda,
+            spl_token::id(),
+            usdc,
+            spl_token::id(),
+            as_token(1, usdc_decimals),
+            0,
+            user_mint_ata,
+            user_usdc_ata,
+            TradeOptions::default(),
+        );
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&user_pk), &[&user], blockhash);
+        send_tx(svm, tx)
+    };
+    let sell = |svm: &mut LiteSVM| {
+        let ix = build_sell_tokens_ix(
+            user_pk,
+            pool_pda,
+            mint_pda,
+            spl_token::id(),
+            usdc,
+            spl_token::id(),
+            as_token(1, DEFAULT_TOKEN_DECIMALS),
+            0,
+            user_mint_ata,
+            user_usdc_ata,
+            TradeOptions::default(),
+        );
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&user_pk), &[&user], blockhash);
+        send_tx(svm, tx)
+    };
+    let set_paused = |svm: &mut LiteSVM, signer: &Keypair, ix: Instruction| {
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+        send_tx(svm, tx)
+    };
+    let paused = |svm: &LiteSVM| {
+        let account = svm.get_account(&pool_pda).unwrap();
+        LiquidityPool::unpack(&account.data).unwrap().paused
+    };
+
+    assert!(buy(&mut svm).is_ok());
+    assert!(buy(&mut svm).is_ok());
+
+    // Only the pool authority or protocol admin can pause
+    assert!(set_paused(&mut svm, &user, build_pause_ix(user_pk, pool_pda, true)).is_err());
+
+    // Pausing blocks buys, but holders can still sell
+    assert!(set_paused(&mut svm, &payer, build_pause_ix(payer_pk, pool_pda, false)).is_ok());
+    assert_eq!(paused(&svm), PAUSE_BUYS);
+    assert!(buy(&mut svm).is_err());
+    assert!(sell(&mut svm).is_ok());
+
+    // The protocol admin can halt sells too
+    assert!(set_paused(&mut svm, &admin, build_pause_ix(admin_pk, pool_pda, true)).is_ok());
+    assert_eq!(paused(&svm), PAUSE_BUYS | PAUSE_SELLS);
+    assert!(buy(&mut svm).is_err());
+    assert!(sell(&mut svm).is_err());
+
+    assert!(set_paused(&mut svm, &user, build_resume_ix(user_pk, pool_pda)).is_err());
+    assert!(set_paused(&mut svm, &payer, build_resume_ix(payer_pk, pool_pda)).is_ok());
+    assert_eq!(paused(&svm), 0);
+    assert!(buy(&mut svm).is_ok());
+    assert!(sell(&mut svm).is_ok());
+}
+
+#[test]
+fn run_circuit_breaker() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "guarded", "GRD", usdc, PoolOptions::default());
+
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+
+    let res = mint_to(&mut svm, &user, &usdc, &payer, &user_usdc_ata, as_token(10_000, usdc_decimals));
+    assert!(res.is_ok());
+
+    let buy = |svm: &mut LiteSVM, amount: u64| {
+        let ix = build_buy_tokens_ix(
+            user_pk,
+            pool_pda,
+            mint_pda,
+            spl_token::id(),
+            usdc,
+            spl_token::id(),
+            as_token(amount, usdc_decimals),
+            0,
+            user_mint_ata,
+            user_usdc_ata,
+            TradeOptions::default(),
+        );
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&user_pk), &[&user], blockhash);
+        send_tx(svm, tx)
+    };
+    let band_exceeded = TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(FlipcashError::PriceBandExceeded.into()),
+    );
+
+    // Only the pool authority can configure the breaker
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_circuit_breaker_ix(user_pk, pool_pda, 1_000, 100);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    // 10% per 100 slots
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_circuit_breaker_ix(payer_pk, pool_pda, 1_000, 100);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // Small buys stay within the band, a large one would push past it
+    assert!(buy(&mut svm, 100).is_ok());
+    assert!(buy(&mut svm, 200).is_ok());
+
+    // A trip fails the whole trade, so the pool is left exactly as it was
+    // and the event is only in the failed transaction's logs
+    let pool_before = svm.get_account(&pool_pda).unwrap();
+    let trip = buy(&mut svm, 2_000).unwrap_err();
+    assert_eq!(trip.err, band_exceeded);
+    assert!(trip.meta.logs.iter().any(|log| log.starts_with("Program data: ")));
+    assert_eq!(svm.get_account(&pool_pda).unwrap(), pool_before);
+
+    let pool = LiquidityPool::unpack(&pool_before.data).unwrap();
+    let window_slot = pool.breaker_slot;
+    assert_eq!(pool.paused, 0);
+
+    // Buys that add up past the band within the window are rejected too
+    assert!(buy(&mut svm, 400).is_ok());
+    assert!(buy(&mut svm, 400).is_ok());
+    assert_eq!(buy(&mut svm, 400).unwrap_err().err, band_exceeded);
+
+    // Once the window rolls over, the reference price moves up
+    svm.warp_to_slot(window_slot + 100);
+    assert!(buy(&mut svm, 400).is_ok());
+
+    let account = svm.get_account(&pool_pda).unwrap();
+    let pool = LiquidityPool::unpack(&account.data).unwrap();
+    assert_eq!(pool.breaker_slot, window_slot + 100);
+
+    // Disabling the breaker lets large trades through
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_circuit_breaker_ix(payer_pk, pool_pda, 0, 0);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    assert!(buy(&mut svm, 2_000).is_ok());
+}
+
+#[test]
+fn run_launch() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "launchpad", "LNCH", usdc, PoolOptions::default());
+    let (vault_a_pda, _) = find_vault_pda(&pool_pda, &mint_pda);
+
+    let alice = create_payer(&mut svm);
+    let alice_pk = alice.pubkey();
+    let bob = create_payer(&mut svm);
+    let bob_pk = bob.pubkey();
+
+    let mut atas = vec![];
+    for user in [&alice, &bob] {
+        let mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user.pubkey());
+        let usdc_ata = create_ata(&mut svm, &payer, &usdc, &user.pubkey());
+        let res = mint_to(&mut svm, user, &usdc, &payer, &usdc_ata, as_token(10_000, usdc_decimals));
+        assert!(res.is_ok());
+        atas.push((mint_ata, usdc_ata));
+    }
+
+    let buy = |svm: &mut LiteSVM, user: &Keypair, (mint_ata, usdc_ata): (Pubkey, Pubkey), amount: u64| {
+        let ix = build_buy_tokens_ix(
+            user.pubkey(),
+            pool_pda,
+            mint_pda,
+            spl_token::id(),
+            usdc,
+            spl_token::id(),
+            as_token(amount, usdc_decimals),
+            0,
+            mint_ata,
+            usdc_ata,
+            TradeOptions::default(),
+        );
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&user.pubkey()), &[user], blockhash);
+        send_tx(svm, tx)
+    };
+    let join_presale = |svm: &mut LiteSVM, user: &Keypair, proof: Vec<[u8; 32]>| {
+        let ixs = [
+            build_initialize_wallet_purchases_ix(user.pubkey(), pool_pda, user.pubkey()),
+            build_join_presale_ix(user.pubkey(), pool_pda, proof),
+        ];
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&ixs, Some(&user.pubkey()), &[user], blockhash);
+        send_tx(svm, tx)
+    };
+
+    // Only alice is on the allowlist, with a 100 USDC presale cap
+    let tree = MerkleTree::new(vec![presale_leaf(&Pubkey::new_unique()), presale_leaf(&alice_pk)]);
+    let alice_proof = tree.proof(1).unwrap();
+    let launch_at = svm.get_sysvar::<Clock>().unix_timestamp + 3_600;
+    let presale_cap = as_token(100, usdc_decimals);
+    let presale = LaunchSchedule {
+        launch_at,
+        presale_cap,
+        presale_root: tree.root(),
+        ..Default::default()
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_launch_ix(bob_pk, pool_pda, mint_pda, presale);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&bob_pk), &[&bob], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err(), "Only the pool authority can set the launch");
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_launch_ix(payer_pk, pool_pda, mint_pda, presale);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // Nobody can buy before launch without joining the presale
+    assert!(buy(&mut svm, &alice, atas[0], 50).is_err());
+    assert!(buy(&mut svm, &bob, atas[1], 50).is_err());
+
+    // Bob can't join with someone else's proof
+    assert!(join_presale(&mut svm, &bob, alice_proof.clone()).is_err());
+    assert!(join_presale(&mut svm, &alice, alice_proof).is_ok());
+
+    // Alice's buy is clamped to the presale cap, after which she's done
+    assert!(buy(&mut svm, &alice, atas[0], 300).is_ok());
+    assert_eq!(get_ata_balance(&svm, &atas[0].1), as_token(9_900, usdc_decimals));
+    assert!(buy(&mut svm, &alice, atas[0], 1).is_err());
+
+    let (purchases_pda, _) = find_wallet_purchases_pda(&pool_pda, &alice_pk);
+    let account = svm.get_account(&purchases_pda).unwrap();
+    let purchases = WalletPurchases::unpack(&account.data).unwrap();
+    assert_eq!(purchases.presale_spent, presale_cap);
+
+    // Once launched, anyone can buy without a purchases account
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = launch_at;
+    svm.set_sysvar(&clock);
+    svm.expire_blockhash();
+
+    assert!(buy(&mut svm, &bob, atas[1], 50).is_ok());
+    assert!(buy(&mut svm, &alice, atas[0], 50).is_ok());
+
+    // The launch can't be moved once the pool is live and has sold tokens
+    assert!(get_ata_balance(&svm, &vault_a_pda) < as_token(DEFAULT_TOKEN_SUPPLY, DEFAULT_TOKEN_DECIMALS));
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_launch_ix(payer_pk, pool_pda, mint_pda, LaunchSchedule { launch_at: launch_at + 3_600, ..Default::default() });
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+}
+
+#[test]
+fn run_launch_window() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "unsnipeable", "SNIP", usdc, PoolOptions::default());
+
+    // Open in an hour, with buys capped at 50 USDC per wallet for the first
+    // 100 seconds
+    let launch_at = svm.get_sysvar::<Clock>().unix_timestamp + 3_600;
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_launch_ix(payer_pk, pool_pda, mint_pda, LaunchSchedule {
+        launch_at,
+        launch_window: 100,
+        launch_cap: as_token(50, usdc_decimals),
+        ..Default::default()
+    });
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+
+    let res = mint_to(&mut svm, &user, &usdc, &payer, &user_usdc_ata, as_token(10_000, usdc_decimals));
+    assert!(res.is_ok());
+
+    let buy_ix = |amount: u64| build_buy_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        as_token(amount, usdc_decimals),
+        0,
+        user_mint_ata,
+        user_usdc_ata,
+        TradeOptions::default(),
+    );
+    let send = |svm: &mut LiteSVM, ix: Instruction| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&user_pk), &[&user], blockhash);
+        send_tx(svm, tx)
+    };
+    let warp_to = |svm: &mut LiteSVM, unix_timestamp: i64| {
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        svm.set_sysvar(&clock);
+    };
+
+    // Buys in the window need the wallet's purchases account
+    warp_to(&mut svm, launch_at);
+    assert!(send(&mut svm, buy_ix(20)).is_err());
+
+    let ix = build_initialize_wallet_purchases_ix(user_pk, pool_pda, user_pk);
+    assert!(send(&mut svm, ix).is_ok());
+
+    // The window runs from launch, not from the first buy, so a late first
+    // buy is still capped, and a buy over the cap is clamped
+    warp_to(&mut svm, launch_at + 50);
+    assert!(send(&mut svm, buy_ix(20)).is_ok());
+    assert!(send(&mut svm, buy_ix(100)).is_ok());
+    assert_eq!(get_ata_balance(&svm, &user_usdc_ata), as_token(9_950, usdc_decimals));
+    assert!(send(&mut svm, buy_ix(10)).is_err());
+
+    // After the window, buys are no longer capped, and can leave out the
+    // purchases account
+    warp_to(&mut svm, launch_at + 100);
+    assert!(send(&mut svm, buy_ix(100)).is_ok());
+    assert_eq!(get_ata_balance(&svm, &user_usdc_ata), as_token(9_850, usdc_decimals));
+
+    let (wallet_purchases_pda, _) = find_wallet_purchases_pda(&pool_pda, &user_pk);
+    let mut ix = buy_ix(100);
+    ix.accounts.retain(|meta| meta.pubkey != wallet_purchases_pda);
+    assert!(send(&mut svm, ix).is_ok());
+    assert_eq!(get_ata_balance(&svm, &user_usdc_ata), as_token(9_750, usdc_decimals));
+}
+
+#[test]
+fn run_initial_buy() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let name = CurrencyName::new("first dibs").unwrap();
+    let (mint_pda, _) = find_mint_pda(&payer_pk, &name, &[0u8; 32]);
+    let (currency_pda, _) = find_currency_pda(&mint_pda);
+    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (vault_a_pda, _) = find_vault_pda(&pool_pda, &mint_pda);
+    let (vault_b_pda, _) = find_vault_pda(&pool_pda, &usdc);
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_currency_ix(
+        payer_pk,
+        name,
+        CurrencySymbol::new("DIBS").unwrap(),
+        [0u8; 32],
+        DEFAULT_TOKEN_DECIMALS,
+        DEFAULT_TOKEN_SUPPLY,
+        spl_token::id(),
+        0,
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let payer_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &payer_pk);
+    let payer_usdc_ata = create_ata(&mut svm, &payer, &usdc, &payer_pk);
+
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &payer_usdc_ata, as_token(1_000, usdc_decimals));
+    assert!(res.is_ok());
+
+    let init_pool_ix = |amount: u64| build_initialize_pool_ix(
+        payer_pk,
+        currency_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        PoolOptions {
+            initial_buy: Some(InitialBuy {
+                amount: as_token(amount, usdc_decimals),
+                authority_target: payer_mint_ata,
+                authority_base: payer_usdc_ata,
+                vesting: None,
+            }),
+            ..Default::default()
+        },
+    );
+
+    // The buy can't spend more than the authority holds, which undoes the pool too
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix(2_000)], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+    assert!(svm.get_account(&pool_pda).is_none_or(|account| account.data.is_empty()));
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix(100)], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // The authority bought at the bottom of the curve
+    assert_eq!(get_ata_balance(&svm, &payer_usdc_ata), as_token(900, usdc_decimals));
+    assert_eq!(get_ata_balance(&svm, &vault_b_pda), as_token(100, usdc_decimals));
+
+    let bought = get_ata_balance(&svm, &payer_mint_ata);
+    assert!(bought > 0);
+    assert_eq!(
+        get_ata_balance(&svm, &vault_a_pda),
+        as_token(DEFAULT_TOKEN_SUPPLY, DEFAULT_TOKEN_DECIMALS) - bought,
+    );
+}
+
+#[test]
+fn run_initial_buy_with_launch() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let name = CurrencyName::new("early dibs").unwrap();
+    let (mint_pda, _) = find_mint_pda(&payer_pk, &name, &[0u8; 32]);
+    let (currency_pda, _) = find_currency_pda(&mint_pda);
+    let (pool_pda, _) = find_pool_pda(&currency_pda);
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_currency_ix(
+        payer_pk,
+        name,
+        CurrencySymbol::new("EDIB").unwrap(),
+        [0u8; 32],
+        DEFAULT_TOKEN_DECIMALS,
+        DEFAULT_TOKEN_SUPPLY,
+        spl_token::id(),
+        0,
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let payer_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &payer_pk);
+    let payer_usdc_ata = create_ata(&mut svm, &payer, &usdc, &payer_pk);
+
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &payer_usdc_ata, as_token(1_000, usdc_decimals));
+    assert!(res.is_ok());
+
+    // Opens in an hour, with buys capped at 50 USDC per wallet right after
+    let launch_at = svm.get_sysvar::<Clock>().unix_timestamp + 3_600;
+    let launch = LaunchSchedule {
+        launch_at,
+        launch_window: 100,
+        launch_cap: as_token(50, usdc_decimals),
+        ..Default::default()
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_pool_ix(
+        payer_pk,
+        currency_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        PoolOptions {
+            initial_buy: Some(InitialBuy {
+                amount: as_token(100, usdc_decimals),
+                authority_target: payer_mint_ata,
+                authority_base: payer_usdc_ata,
+                vesting: None,
+            }),
+            launch,
+            ..Default::default()
+        },
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // The schedule is in place from creation, but the authority's own buy
+    // isn't held to it
+    let account = svm.get_account(&pool_pda).unwrap();
+    let pool = LiquidityPool::unpack(&account.data).unwrap();
+    assert_eq!(pool.launch_at, launch_at);
+    assert_eq!(pool.launch_window, 100);
+    assert_eq!(get_ata_balance(&svm, &payer_usdc_ata), as_token(900, usdc_decimals));
+    assert!(get_ata_balance(&svm, &payer_mint_ata) > 0);
+
+    // Nobody else can buy before launch
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+    let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+    let res = mint_to(&mut svm, &user, &usdc, &payer, &user_usdc_ata, as_token(100, usdc_decimals));
+    assert!(res.is_ok());
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_buy_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        as_token(10, usdc_decimals),
+        0,
+        user_mint_ata,
+        user_usdc_ata,
+        TradeOptions::default(),
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&user_pk), &[&user], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    // The initial buy doesn't count as a launch, so the schedule can still move
+    let blockhash = svm.latest_blockhash();
+    let ix = build_set_launch_ix(payer_pk, pool_pda, mint_pda, LaunchSchedule { launch_at: launch_at + 3_600, ..launch });
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+}
+
+#[test]
+fn run_vesting() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let name = CurrencyName::new("slow release").unwrap();
+    let (mint_pda, _) = find_mint_pda(&payer_pk, &name, &[0u8; 32]);
+    let (currency_pda, _) = find_currency_pda(&mint_pda);
+    let (pool_pda, _) = find_pool_pda(&currency_pda);
+    let (vesting_pda, _) = find_vesting_pda(&pool_pda, &payer_pk);
+    let (vesting_vault_pda, _) = find_vault_pda(&vesting_pda, &mint_pda);
+
+    let blockhash = svm.latest_blockhash();
+    let ix = build_initialize_currency_ix(
+        payer_pk,
+        name,
+        CurrencySymbol::new("SLOW").unwrap(),
+        [0u8; 32],
+        DEFAULT_TOKEN_DECIMALS,
+        DEFAULT_TOKEN_SUPPLY,
+        spl_token::id(),
+        0,
+    );
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    let payer_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &payer_pk);
+    let payer_usdc_ata = create_ata(&mut svm, &payer, &usdc, &payer_pk);
+
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &payer_usdc_ata, as_token(1_000, usdc_decimals));
+    assert!(res.is_ok());
+
+    let init_pool_ix = |cliff: i64, duration: i64| build_initialize_pool_ix(
+        payer_pk,
+        currency_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        PoolOptions {
+            initial_buy: Some(InitialBuy {
+                amount: as_token(100, usdc_decimals),
+                authority_target: payer_mint_ata,
+                authority_base: payer_usdc_ata,
+                vesting: Some(VestingSchedule { cliff, duration }),
+            }),
+            ..Default::default()
+        },
+    );
+
+    // The cliff can't come after the end of the schedule, and the schedule
+    // can't run past the longest duration
+    let invalid_schedule = TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(FlipcashError::InvalidVestingSchedule.into()),
+    );
+    for (cliff, duration) in [(2_000, 1_000), (0, MAX_VESTING_DURATION + 1), (0, i64::MAX)] {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[init_pool_ix(cliff, duration)], Some(&payer_pk), &[&payer], blockhash);
+        let res = send_tx(&mut svm, tx);
+        assert_eq!(res.unwrap_err().err, invalid_schedule);
+    }
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[init_pool_ix(100, 1_000)], Some(&payer_pk), &[&payer], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_ok());
+
+    // The buy is held by the vesting account, not paid out
+    let bought = get_ata_balance(&svm, &vesting_vault_pda);
+    assert!(bought > 0);
+    assert_eq!(get_ata_balance(&svm, &payer_mint_ata), 0);
+
+    let account = svm.get_account(&vesting_pda).unwrap();
+    let vesting = *Vesting::unpack(&account.data).unwrap();
+    assert_eq!(vesting.total_amount, bought);
+    assert_eq!(vesting.beneficiary, payer_pk);
+    assert_eq!(vesting.cliff_at, vesting.start_at + 100);
+    assert_eq!(vesting.end_at, vesting.start_at + 1_000);
+
+    let claim = |svm: &mut LiteSVM| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let ix = build_claim_vested_ix(payer_pk, pool_pda, mint_pda, spl_token::id(), payer_mint_ata);
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+        send_tx(svm, tx)
+    };
+    let set_time = |svm: &mut LiteSVM, unix_timestamp: i64| {
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        svm.set_sysvar(&clock);
+    };
+
+    // Nothing is claimable before the cliff
+    assert!(claim(&mut svm).is_err());
+
+    // Halfway through, half has vested
+    set_time(&mut svm, vesting.start_at + 500);
+    assert!(claim(&mut svm).is_ok());
+    assert_eq!(get_ata_balance(&svm, &payer_mint_ata), bought / 2);
+
+    // Claiming again releases nothing new
+    assert!(claim(&mut svm).is_err());
+
+    // Only the beneficiary can claim
+    let other = create_payer(&mut svm);
+    let other_mint_ata = create_ata(&mut svm, &other, &mint_pda, &other.pubkey());
+    let blockhash = svm.latest_blockhash();
+    let mut ix = build_claim_vested_ix(other.pubkey(), pool_pda, mint_pda, spl_token::id(), other_mint_ata);
+    ix.accounts[1].pubkey = vesting_pda;
+    ix.accounts[2].pubkey = vesting_vault_pda;
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&other.pubkey()), &[&other], blockhash);
+    let res = send_tx(&mut svm, tx);
+    assert!(res.is_err());
+
+    // Everything is claimable at the end
+    set_time(&mut svm, vesting.end_at);
+    assert!(claim(&mut svm).is_ok());
+    assert_eq!(get_ata_balance(&svm, &payer_mint_ata), bought);
+    assert_eq!(get_ata_balance(&svm, &vesting_vault_pda), 0);
+}
+
+#[test]
+fn run_batch_buy() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "payroll", "PAY", usdc, PoolOptions::default());
+    let (vault_a_pda, _) = find_vault_pda(&pool_pda, &mint_pda);
+
+    let payer_usdc_ata = create_ata(&mut svm, &payer, &usdc, &payer_pk);
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &payer_usdc_ata, as_token(1_000, usdc_decimals));
+    assert!(res.is_ok());
+
+    let recipient_atas: Vec<Pubkey> = (0..3)
+        .map(|_| create_ata(&mut svm, &payer, &mint_pda, &create_keypair().pubkey()))
+        .collect();
+
+    let batch_buy = |svm: &mut LiteSVM, recipients: &[(Pubkey, u32)]| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let ix = build_batch_buy_ix(
+            payer_pk,
+            pool_pda,
+            mint_pda,
+            spl_token::id(),
+            usdc,
+            spl_token::id(),
+            as_token(100, usdc_decimals),
+            0,
+            payer_usdc_ata,
+            recipients,
+            None,
+        );
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+        send_tx(svm, tx)
+    };
+
+    // Shares must add up to something
+    assert!(batch_buy(&mut svm, &[(recipient_atas[0], 0)]).is_err());
+
+    // Recipients must hold the currency
+    assert!(batch_buy(&mut svm, &[(payer_usdc_ata, 1)]).is_err());
+
+    // A recipient listed twice is paid both of its shares
+    let recipients = [
+        (recipient_atas[0], 1),
+        (recipient_atas[1], 2),
+        (recipient_atas[2], 1),
+        (recipient_atas[0], 1),
+    ];
+    let res = batch_buy(&mut svm, &recipients);
+    assert!(res.is_ok());
+
+    assert_eq!(get_ata_balance(&svm, &payer_usdc_ata), as_token(900, usdc_decimals));
+
+    let bought = as_token(DEFAULT_TOKEN_SUPPLY, DEFAULT_TOKEN_DECIMALS) - get_ata_balance(&svm, &vault_a_pda);
+    let balances: Vec<u64> = recipient_atas.iter()
+        .map(|ata| get_ata_balance(&svm, ata))
+        .collect();
+    assert_eq!(balances.iter().sum::<u64>(), bought);
+    assert!(balances[0].abs_diff(balances[1]) <= 1);
+    assert!(balances[1].abs_diff(2 * balances[2]) <= 1);
+}
+
+#[test]
+fn run_cash_link() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "gift card", "GIFT", usdc, PoolOptions::default());
+
+    let payer_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &payer_pk);
+    let payer_usdc_ata = create_ata(&mut svm, &payer, &usdc, &payer_pk);
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &payer_usdc_ata, as_token(1_000, usdc_decimals));
+    assert!(res.is_ok());
+
+    let now = svm.get_sysvar::<Clock>().unix_timestamp;
+    let expires_at = now + 3_600;
+
+    let send = |svm: &mut LiteSVM, ixs: &[Instruction], signers: &[&Keypair]| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&signers[0].pubkey()), signers, blockhash);
+        send_tx(svm, tx)
+    };
+
+    // Buy currency straight into a link
+    let bought_link = create_keypair();
+    let ix = build_buy_cash_link_ix(
+        payer_pk,
+        bought_link.pubkey(),
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        as_token(100, usdc_decimals),
+        0,
+        expires_at,
+        payer_usdc_ata,
+        None,
+    );
+    assert!(send(&mut svm, &[ix], &[&payer]).is_ok());
+
+    let (bought_link_pda, _) = find_cash_link_pda(&bought_link.pubkey());
+    let account = svm.get_account(&bought_link_pda).unwrap();
+    let cash_link = *CashLink::unpack(&account.data).unwrap();
+    assert!(cash_link.amount > 0);
+    assert_eq!(get_ata_balance(&svm, &cash_link.vault), cash_link.amount);
+    assert_eq!(get_ata_balance(&svm, &payer_usdc_ata), as_token(900, usdc_decimals));
+
+    let claimer = create_payer(&mut svm);
+    let claimer_mint_ata = create_ata(&mut svm, &claimer, &mint_pda, &claimer.pubkey());
+    let claim_ixs = |svm: &LiteSVM, link: &Keypair, signed_destination: Pubkey, destination: Pubkey| {
+        let (cash_link_pda, _) = find_cash_link_pda(&link.pubkey());
+        let created_slot = svm.get_account(&cash_link_pda)
+            .and_then(|account| CashLink::unpack(&account.data).ok().map(|cash_link| cash_link.created_slot))
+            .unwrap_or_default();
+        let message = cash_link_claim_message(&cash_link_pda, created_slot, &signed_destination);
+        [
+            build_cash_link_signature_ix(link.pubkey(), created_slot, link.sign_message(&message).into(), signed_destination),
+            build_claim_cash_link_ix(link.pubkey(), payer_pk, mint_pda, spl_token::id(), destination),
+        ]
+    };
+
+    // A signature from any other key is rejected
+    let mut ixs = claim_ixs(&svm, &create_keypair(), claimer_mint_ata, claimer_mint_ata);
+    ixs[1] = claim_ixs(&svm, &bought_link, claimer_mint_ata, claimer_mint_ata)[1].clone();
+    assert!(send(&mut svm, &ixs, &[&claimer]).is_err());
+
+    // So is a signature meant for another destination
+    let ixs = claim_ixs(&svm, &bought_link, payer_mint_ata, claimer_mint_ata);
+    assert!(send(&mut svm, &ixs, &[&claimer]).is_err());
+
+    // And a claim without any signature
+    let ixs = claim_ixs(&svm, &bought_link, claimer_mint_ata, claimer_mint_ata);
+    assert!(send(&mut svm, &ixs[1..], &[&claimer]).is_err());
+
+    // The creator can't take it back before it expires
+    let reclaim_ix = |link: &Keypair| build_reclaim_cash_link_ix(payer_pk, link.pubkey(), mint_pda, spl_token::id(), payer_mint_ata);
+    assert!(send(&mut svm, &[reclaim_ix(&bought_link)], &[&payer]).is_err());
+
+    let claimed_ixs = claim_ixs(&svm, &bought_link, claimer_mint_ata, claimer_mint_ata);
+    let res = send(&mut svm, &claimed_ixs, &[&claimer]);
+    assert!(res.is_ok());
+    assert_eq!(get_ata_balance(&svm, &claimer_mint_ata), cash_link.amount);
+    assert!(svm.get_account(&bought_link_pda).is_none_or(|account| account.lamports == 0));
+
+    // A link can only be claimed once
+    assert!(send(&mut svm, &claimed_ixs, &[&claimer]).is_err());
+
+    // Nor can its claim be replayed on a link recreated with the same key
+    svm.warp_to_slot(svm.get_sysvar::<Clock>().slot + 1);
+    let ix = build_buy_cash_link_ix(
+        payer_pk,
+        bought_link.pubkey(),
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        as_token(100, usdc_decimals),
+        0,
+        expires_at,
+        payer_usdc_ata,
+        None,
+    );
+    assert!(send(&mut svm, &[ix], &[&payer]).is_ok());
+    assert!(send(&mut svm, &claimed_ixs, &[&claimer]).is_err());
+    let ixs = claim_ixs(&svm, &bought_link, claimer_mint_ata, claimer_mint_ata);
+    assert!(send(&mut svm, &ixs, &[&claimer]).is_ok());
+
+    // Deposit currency the creator holds into a second link
+    let buy_ix = build_buy_tokens_ix(
+        payer_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        as_token(100, usdc_decimals),
+        0,
+        payer_mint_ata,
+        payer_usdc_ata,
+        TradeOptions::default(),
+    );
+    assert!(send(&mut svm, &[buy_ix], &[&payer]).is_ok());
+    let deposit = get_ata_balance(&svm, &payer_mint_ata) / 2;
+
+    let deposit_link = create_keypair();
+    let create_ix = |expires_at: i64| build_create_cash_link_ix(
+        payer_pk,
+        deposit_link.pubkey(),
+        mint_pda,
+        spl_token::id(),
+        deposit,
+        expires_at,
+        payer_mint_ata,
+    );
+
+    // Links must expire in the future
+    assert!(send(&mut svm, &[create_ix(now)], &[&payer]).is_err());
+    assert!(send(&mut svm, &[create_ix(expires_at)], &[&payer]).is_ok());
+
+    let balance = get_ata_balance(&svm, &payer_mint_ata);
+
+    // Expired links can't be claimed, only reclaimed by their creator
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = expires_at;
+    svm.set_sysvar(&clock);
+
+    let ixs = claim_ixs(&svm, &deposit_link, claimer_mint_ata, claimer_mint_ata);
+    assert!(send(&mut svm, &ixs, &[&claimer]).is_err());
+
+    let mut ix = reclaim_ix(&deposit_link);
+    ix.accounts[0].pubkey = claimer.pubkey();
+    assert!(send(&mut svm, &[ix], &[&claimer]).is_err());
+
+    assert!(send(&mut svm, &[reclaim_ix(&deposit_link)], &[&payer]).is_ok());
+    assert_eq!(get_ata_balance(&svm, &payer_mint_ata), balance + deposit);
+}
+
+#[test]
+fn run_limit_order() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "limit test", "LMT", usdc, PoolOptions::default());
+
+    let owner = create_payer(&mut svm);
+    let owner_pk = owner.pubkey();
+    let owner_mint_ata = create_ata(&mut svm, &owner, &mint_pda, &owner_pk);
+    let owner_usdc_ata = create_ata(&mut svm, &owner, &usdc, &owner_pk);
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &owner_usdc_ata, as_token(1_000, usdc_decimals));
+    assert!(res.is_ok());
+
+    let executor = create_payer(&mut svm);
+    let executor_pk = executor.pubkey();
+
+    let now = svm.get_sysvar::<Clock>().unix_timestamp;
+    let expires_at = now + 3_600;
+    let bounty = 50_000;
+    let price = |dollars: f64| (dollars * 10f64.powi(CURVE_PRICE_DECIMALS as i32)) as u64;
+
+    let send = |svm: &mut LiteSVM, ix: Instruction, signer: &Keypair| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+        send_tx(svm, tx)
+    };
+    let create_ix = |id: u64, side: OrderSide, amount: u64, target_price: u64| {
+        let (escrow_mint, owner_source) = match side {
+            OrderSide::Buy => (usdc, owner_usdc_ata),
+            OrderSide::Sell => (mint_pda, owner_mint_ata),
+        };
+        build_create_limit_order_ix(
+            owner_pk,
+            pool_pda,
+            id,
+            side,
+            escrow_mint,
+            spl_token::id(),
+            owner_source,
+            amount,
+            0,
+            target_price,
+            bounty,
+            expires_at,
+        )
+    };
+    let execute_ix = |id: u64, side: OrderSide| {
+        let (owner_source, owner_destination) = match side {
+            OrderSide::Buy => (owner_usdc_ata, owner_mint_ata),
+            OrderSide::Sell => (owner_mint_ata, owner_usdc_ata),
+        };
+        build_execute_limit_order_ix(
+            executor_pk,
+            owner_pk,
+            pool_pda,
+            id,
+            side,
+            mint_pda,
+            spl_token::id(),
+            usdc,
+            spl_token::id(),
+            owner_source,
+            owner_destination,
+            None,
+        )
+    };
+    let cancel_ix = |authority: Pubkey, id: u64, side: OrderSide| {
+        let (escrow_mint, owner_source) = match side {
+            OrderSide::Buy => (usdc, owner_usdc_ata),
+            OrderSide::Sell => (mint_pda, owner_mint_ata),
+        };
+        build_cancel_limit_order_ix(authority, owner_pk, pool_pda, id, escrow_mint, spl_token::id(), owner_source)
+    };
+
+    // Orders need a price to trade at
+    assert!(send(&mut svm, create_ix(0, OrderSide::Buy, as_token(100, usdc_decimals), 0), &owner).is_err());
+
+    // A buy below the current price waits
+    assert!(send(&mut svm, create_ix(1, OrderSide::Buy, as_token(100, usdc_decimals), price(0.001)), &owner).is_ok());
+    assert_eq!(get_ata_balance(&svm, &owner_usdc_ata), as_token(900, usdc_decimals));
+    assert!(send(&mut svm, execute_ix(1, OrderSide::Buy), &executor).is_err());
+
+    // A buy at or above the current price fills, and the executor earns the bounty
+    assert!(send(&mut svm, create_ix(2, OrderSide::Buy, as_token(100, usdc_decimals), price(1.0)), &owner).is_ok());
+    let executor_lamports = svm.get_balance(&executor_pk).unwrap();
+    assert!(send(&mut svm, execute_ix(2, OrderSide::Buy), &executor).is_ok());
+    assert!(svm.get_balance(&executor_pk).unwrap() > executor_lamports);
+
+    let bought = get_ata_balance(&svm, &owner_mint_ata);
+    assert!(bought > 0);
+    let (order_pda, _) = find_limit_order_pda(&pool_pda, &owner_pk, 2);
+    assert!(svm.get_account(&order_pda).is_none_or(|account| account.lamports == 0));
+
+    // Only the owner can cancel an open order, getting its escrow back
+    assert!(send(&mut svm, cancel_ix(executor_pk, 1, OrderSide::Buy), &executor).is_err());
+    assert!(send(&mut svm, cancel_ix(owner_pk, 1, OrderSide::Buy), &owner).is_ok());
+    assert_eq!(get_ata_balance(&svm, &owner_usdc_ata), as_token(900, usdc_decimals));
+
+    // A buy crossed right at the current price still can't fill, since
+    // walking up the curve would average more than its target
+    let account = svm.get_account(&pool_pda).unwrap();
+    let pool = LiquidityPool::unpack(&account.data).unwrap();
+    let (vault_a_pda, _) = find_vault_pda(&pool_pda, &mint_pda);
+    let supply = as_token(pool.curve_max_supply, DEFAULT_TOKEN_DECIMALS) - get_ata_balance(&svm, &vault_a_pda);
+    let spot_price = pool.curve().unwrap()
+        .spot_price_at_supply(&to_numeric(supply, DEFAULT_TOKEN_DECIMALS).unwrap())
+        .unwrap();
+    let spot_price = from_numeric(spot_price, CURVE_PRICE_DECIMALS).unwrap() + 1;
+    assert!(send(&mut svm, create_ix(5, OrderSide::Buy, as_token(100, usdc_decimals), spot_price), &owner).is_ok());
+    assert!(send(&mut svm, execute_ix(5, OrderSide::Buy), &executor).is_err());
+    assert!(send(&mut svm, cancel_ix(owner_pk, 5, OrderSide::Buy), &owner).is_ok());
+    assert_eq!(get_ata_balance(&svm, &owner_usdc_ata), as_token(900, usdc_decimals));
+
+    // A sell above the current price waits
+    assert!(send(&mut svm, create_ix(3, OrderSide::Sell, bought / 2, price(100.0)), &owner).is_ok());
+    assert!(send(&mut svm, execute_ix(3, OrderSide::Sell), &executor).is_err());
+
+    // A sell at or below the current price fills
+    assert!(send(&mut svm, create_ix(4, OrderSide::Sell, bought / 2, price(0.001)), &owner).is_ok());
+    assert!(send(&mut svm, execute_ix(4, OrderSide::Sell), &executor).is_ok());
+    assert!(get_ata_balance(&svm, &owner_usdc_ata) > as_token(900, usdc_decimals));
+
+    // Once expired an order can't fill, and anyone can return it to the owner
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = expires_at;
+    svm.set_sysvar(&clock);
+
+    assert!(send(&mut svm, execute_ix(3, OrderSide::Sell), &executor).is_err());
+    assert!(send(&mut svm, cancel_ix(executor_pk, 3, OrderSide::Sell), &executor).is_ok());
+    assert_eq!(get_ata_balance(&svm, &owner_mint_ata), bought - bought / 2);
+}
+
+#[test]
+fn run_dca() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "dca test", "DCA", usdc, PoolOptions::default());
+
+    let owner = create_payer(&mut svm);
+    let owner_pk = owner.pubkey();
+    let owner_mint_ata = create_ata(&mut svm, &owner, &mint_pda, &owner_pk);
+    let owner_usdc_ata = create_ata(&mut svm, &owner, &usdc, &owner_pk);
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &owner_usdc_ata, as_token(1_000, usdc_decimals));
+    assert!(res.is_ok());
+
+    let executor = create_payer(&mut svm);
+    let executor_pk = executor.pubkey();
+
+    let id = 7;
+    let interval = 3_600;
+    let amount_per_fill = as_token(100, usdc_decimals);
+    let min_amount_out = as_token(9_000, DEFAULT_TOKEN_DECIMALS); // At most ~$0.011 a token
+    let (plan_pda, _) = find_dca_plan_pda(&pool_pda, &owner_pk, id);
+    let (plan_vault_pda, _) = find_vault_pda(&plan_pda, &usdc);
+
+    let send = |svm: &mut LiteSVM, ix: Instruction, signer: &Keypair| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+        send_tx(svm, tx)
+    };
+    let execute_ix = || build_execute_dca_ix(
+        executor_pk,
+        owner_pk,
+        pool_pda,
+        id,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        owner_mint_ata,
+        None,
+    );
+    let warp = |svm: &mut LiteSVM, unix_timestamp: i64| {
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        svm.set_sysvar(&clock);
+    };
+
+    // Plans need an interval and a minimum amount out
+    let bad_ix = build_create_dca_plan_ix(owner_pk, pool_pda, id, usdc, spl_token::id(), amount_per_fill, min_amount_out, 0, 0);
+    assert!(send(&mut svm, bad_ix, &owner).is_err());
+    let bad_ix = build_create_dca_plan_ix(owner_pk, pool_pda, id, usdc, spl_token::id(), amount_per_fill, 0, interval, 0);
+    assert!(send(&mut svm, bad_ix, &owner).is_err());
+
+    let create_ix = build_create_dca_plan_ix(owner_pk, pool_pda, id, usdc, spl_token::id(), amount_per_fill, min_amount_out, interval, 0);
+    assert!(send(&mut svm, create_ix, &owner).is_ok());
+
+    // An unfunded plan has nothing to buy with
+    assert!(send(&mut svm, execute_ix(), &executor).is_err());
+
+    let deposit_ix = build_deposit_dca_ix(owner_pk, pool_pda, id, usdc, spl_token::id(), owner_usdc_ata, as_token(250, usdc_decimals));
+    assert!(send(&mut svm, deposit_ix, &owner).is_ok());
+    assert_eq!(get_ata_balance(&svm, &owner_usdc_ata), as_token(750, usdc_decimals));
+
+    // The first fill is due right away, and anyone can run it
+    let start = svm.get_sysvar::<Clock>().unix_timestamp;
+    assert!(send(&mut svm, execute_ix(), &executor).is_ok());
+    let bought = get_ata_balance(&svm, &owner_mint_ata);
+    assert!(bought > 0);
+    assert_eq!(get_ata_balance(&svm, &plan_vault_pda), as_token(150, usdc_decimals));
+
+    let plan = svm.get_account(&plan_pda).unwrap();
+    let plan = DcaPlan::unpack(&plan.data).unwrap();
+    assert_eq!(plan.fills, 1);
+    assert_eq!(plan.total_spent, amount_per_fill);
+    assert_eq!(plan.total_bought, bought);
+    assert_eq!(plan.next_fill_at, start + interval);
+
+    // The next fill waits for the interval
+    assert!(send(&mut svm, execute_ix(), &executor).is_err());
+    warp(&mut svm, start + interval);
+
+    // A fill run into a pumped price buys too little and fails
+    let whale = create_payer(&mut svm);
+    let whale_pk = whale.pubkey();
+    let whale_mint_ata = create_ata(&mut svm, &whale, &mint_pda, &whale_pk);
+    let whale_usdc_ata = create_ata(&mut svm, &whale, &usdc, &whale_pk);
+    let res = mint_to(&mut svm, &payer, &usdc, &payer, &whale_usdc_ata, as_token(20_000, usdc_decimals));
+    assert!(res.is_ok());
+    let pump_ix = build_buy_tokens_ix(
+        whale_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        as_token(20_000, usdc_decimals),
+        0,
+        whale_mint_ata,
+        whale_usdc_ata,
+        TradeOptions::default(),
+    );
+    assert!(send(&mut svm, pump_ix, &whale).is_ok());
+    assert!(send(&mut svm, execute_ix(), &executor).is_err());
+    assert_eq!(get_ata_balance(&svm, &plan_vault_pda), as_token(150, usdc_decimals));
+
+    // Once the price comes back down, the fill goes through
+    let dump_ix = build_sell_tokens_ix(
+        whale_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        get_ata_balance(&svm, &whale_mint_ata),
+        0,
+        whale_mint_ata,
+        whale_usdc_ata,
+        TradeOptions::default(),
+    );
+    assert!(send(&mut svm, dump_ix, &whale).is_ok());
+    assert!(send(&mut svm, execute_ix(), &executor).is_ok());
+    assert!(get_ata_balance(&svm, &owner_mint_ata) > bought);
+    assert_eq!(get_ata_balance(&svm, &plan_vault_pda), as_token(50, usdc_decimals));
+
+    // Only the owner can withdraw
+    let withdraw_ix = |authority: Pubkey| build_withdraw_dca_ix(authority, pool_pda, id, usdc, spl_token::id(), owner_usdc_ata, as_token(20, usdc_decimals));
+    assert!(send(&mut svm, withdraw_ix(executor_pk), &executor).is_err());
+    assert!(send(&mut svm, withdraw_ix(owner_pk), &owner).is_ok());
+    assert_eq!(get_ata_balance(&svm, &owner_usdc_ata), as_token(770, usdc_decimals));
+
+    // The last fill spends whatever is left
+    warp(&mut svm, start + 2 * interval);
+    assert!(send(&mut svm, execute_ix(), &executor).is_ok());
+    assert_eq!(get_ata_balance(&svm, &plan_vault_pda), 0);
+
+    // Closing refunds the plan's remaining escrow
+    let deposit_ix = build_deposit_dca_ix(owner_pk, pool_pda, id, usdc, spl_token::id(), owner_usdc_ata, as_token(70, usdc_decimals));
+    assert!(send(&mut svm, deposit_ix, &owner).is_ok());
+    let close_ix = build_close_dca_plan_ix(owner_pk, pool_pda, id, usdc, spl_token::id(), owner_usdc_ata);
+    assert!(send(&mut svm, close_ix, &owner).is_ok());
+    assert_eq!(get_ata_balance(&svm, &owner_usdc_ata), as_token(770, usdc_decimals));
+    assert!(svm.get_account(&plan_pda).is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn run_distributor() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let decimals = 6;
+    let mint = create_mint(&mut svm, &payer, &payer_pk, decimals);
+    let payer_ata = create_ata(&mut svm, &payer, &mint, &payer_pk);
+    let res = mint_to(&mut svm, &payer, &mint, &payer, &payer_ata, as_token(1_000, decimals));
+    assert!(res.is_ok());
+
+    let alice = create_payer(&mut svm);
+    let bob = create_payer(&mut svm);
+    let alice_ata = create_ata(&mut svm, &alice, &mint, &alice.pubkey());
+    let bob_ata = create_ata(&mut svm, &bob, &mint, &bob.pubkey());
+
+    let recipients = [
+        (alice.pubkey(), as_token(100, decimals)),
+        (bob.pubkey(), as_token(200, decimals)),
+        (alice.pubkey(), as_token(50, decimals)),
+    ];
+    let leaves = recipients.iter()
+        .enumerate()
+        .map(|(i, (wallet, amount))| distributor_leaf(i as u64, wallet, *amount))
+        .collect();
+    let tree = MerkleTree::new(leaves);
+    let total = as_token(350, decimals);
+
+    let id = 1;
+    let now = svm.get_sysvar::<Clock>().unix_timestamp;
+    let expires_at = now + 3_600;
+    let (distributor_pda, _) = find_distributor_pda(&mint, &payer_pk, id);
+
+    let send = |svm: &mut LiteSVM, ix: Instruction, signer: &Keypair| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+        send_tx(svm, tx)
+    };
+    let claim_ix = |claimant: &Keypair, destination: Pubkey, index: usize| {
+        let (_, amount) = recipients[index];
+        build_claim_distribution_ix(
+            claimant.pubkey(),
+            distributor_pda,
+            mint,
+            spl_token::id(),
+            destination,
+            index as u64,
+            amount,
+            tree.proof(index).unwrap(),
+        )
+    };
+
+    let create_ix = build_create_distributor_ix(
+        payer_pk,
+        id,
+        mint,
+        spl_token::id(),
+        payer_ata,
+        tree.root(),
+        total,
+        recipients.len() as u64,
+        expires_at,
+    );
+    assert!(send(&mut svm, create_ix, &payer).is_ok());
+    assert_eq!(get_ata_balance(&svm, &payer_ata), as_token(650, decimals));
+
+    // Each entry pays out once, only to its own wallet
+    assert!(send(&mut svm, claim_ix(&bob, bob_ata, 0), &bob).is_err());
+    assert!(send(&mut svm, claim_ix(&alice, alice_ata, 0), &alice).is_ok());
+    assert_eq!(get_ata_balance(&svm, &alice_ata), as_token(100, decimals));
+    assert!(send(&mut svm, claim_ix(&alice, alice_ata, 0), &alice).is_err());
+
+    // A wallet listed twice claims both of its entries
+    assert!(send(&mut svm, claim_ix(&alice, alice_ata, 2), &alice).is_ok());
+    assert_eq!(get_ata_balance(&svm, &alice_ata), as_token(150, decimals));
+
+    let account = svm.get_account(&distributor_pda).unwrap();
+    let (distributor, claims) = Distributor::unpack_claims(&account.data).unwrap();
+    assert_eq!(distributor.num_claimed, 2);
+    assert_eq!(distributor.claimed_amount, as_token(150, decimals));
+    assert!(Distributor::is_claimed(claims, 0));
+    assert!(!Distributor::is_claimed(claims, 1));
+
+    // Nothing can be clawed back until expiry
+    let clawback_ix = |authority: Pubkey| build_clawback_distributor_ix(authority, distributor_pda, mint, spl_token::id(), payer_ata);
+    assert!(send(&mut svm, clawback_ix(payer_pk), &payer).is_err());
+
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = expires_at;
+    svm.set_sysvar(&clock);
+
+    // After expiry claims stop and the authority takes back the rest
+    assert!(send(&mut svm, claim_ix(&bob, bob_ata, 1), &bob).is_err());
+    assert!(send(&mut svm, clawback_ix(bob.pubkey()), &bob).is_err());
+    assert!(send(&mut svm, clawback_ix(payer_pk), &payer).is_ok());
+    assert_eq!(get_ata_balance(&svm, &payer_ata), as_token(850, decimals));
+    assert!(svm.get_account(&distributor_pda).is_none_or(|account| account.lamports == 0));
+}
+
+#[test]
+fn run_trade_hook() {
+    let mut svm = setup_svm();
+
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+
+    let usdc_decimals = 6;
+    let usdc = create_mint(&mut svm, &payer, &payer_pk, usdc_decimals);
+    setup_protocol(&mut svm, &payer, &[usdc]);
+
+    let TestMarket { mint: mint_pda, pool: pool_pda, .. } =
+        create_market(&mut svm, &payer, "hooked", "HOOK", usdc, PoolOptions::default());
+
+    let user = create_payer(&mut svm);
+    let user_pk = user.pubkey();
+
+    let user_mint_ata = create_ata(&mut svm, &payer, &mint_pda, &user_pk);
+    let user_usdc_ata = create_ata(&mut svm, &payer, &usdc, &user_pk);
+
+    let res = mint_to(&mut svm, &user, &usdc, &payer, &user_usdc_ata, as_token(10_000, usdc_decimals));
+    assert!(res.is_ok());
+
+    let send = |svm: &mut LiteSVM, ix: Instruction, signer: &Keypair| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+        send_tx(svm, tx)
+    };
+
+    // The memo program stands in for a hook that always fails, since a
+    // trade summary isn't valid UTF-8
+    let hook_program = Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+    let with_hook = |mut ix: Instruction| {
+        ix.accounts.extend(trade_hook_metas(&pool_pda, hook_program, vec![]));
+        ix
+    };
+    let buy_ix = || build_buy_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        as_token(10, usdc_decimals),
+        0,
+        user_mint_ata,
+        user_usdc_ata,
+        TradeOptions::default(),
+    );
+    let sell_ix = || build_sell_tokens_ix(
+        user_pk,
+        pool_pda,
+        mint_pda,
+        spl_token::id(),
+        usdc,
+        spl_token::id(),
+        as_token(1, DEFAULT_TOKEN_DECIMALS),
+        0,
+        user_mint_ata,
+        user_usdc_ata,
+        TradeOptions::default(),
+    );
+
+    // Only the pool authority can set a hook, and only to a program with a
+    // bounded compute budget
+    let set_ix = |authority: Pubkey, hook: Option<Pubkey>, units: u32, required: bool|
+        build_set_trade_hook_ix(authority, pool_pda, hook, units, required);
+    assert!(send(&mut svm, set_ix(user_pk, Some(hook_program), 10_000, false), &user).is_err());
+    assert!(send(&mut svm, set_ix(payer_pk, Some(user_pk), 10_000, false), &payer).is_err());
+    assert!(send(&mut svm, set_ix(payer_pk, Some(hook_program), 0, false), &payer).is_err());
+    assert!(send(&mut svm, set_ix(payer_pk, Some(hook_program), MAX_TRADE_HOOK_COMPUTE_UNITS + 1, false), &payer).is_err());
+    assert!(send(&mut svm, set_ix(payer_pk, Some(hook_program), 10_000, false), &payer).is_ok());
+
+    let account = svm.get_account(&pool_pda).unwrap();
+    let pool = LiquidityPool::unpack(&account.data).unwrap();
+    assert_eq!(pool.hook_program, hook_program);
+    assert_eq!(pool.hook_compute_units, 10_000);
+    assert!(!pool.is_trade_hook_required());
+    assert_eq!(pool.hook_authority_bump, find_trade_hook_authority_pda(&pool_pda).1);
+
+    // A hook that fails fails the trade, and leaving it out doesn't get
+    // around it, even when it's optional
+    assert!(send(&mut svm, with_hook(buy_ix()), &user).is_err());
+    assert!(send(&mut svm, buy_ix(), &user).is_err());
+    assert!(send(&mut svm, with_hook(sell_ix()), &user).is_err());
+    assert!(send(&mut svm, sell_ix(), &user).is_err());
+
+    // The hook accounts go right after the fee accounts, not anywhere later
+    let mut late_hook_ix = sell_ix();
+    late_hook_ix.accounts.push(AccountMeta::new_readonly(user_pk, false));
+    assert!(send(&mut svm, with_hook(late_hook_ix), &user).is_err());
+
+    // An optional hook is skipped when there isn't enough compute to run it,
+    // and a required one fails the trade instead
+    let send_with_compute = |svm: &mut LiteSVM, ix: Instruction, units: u32| {
+        svm.expire_blockhash();
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            &[ComputeBudgetInstruction::set_compute_unit_limit(units), ix],
+            Some(&user_pk),
+            &[&user],
+            blockhash,
+        );
+        send_tx(svm, tx)
+    };
+    let max_units = MAX_TRADE_HOOK_COMPUTE_UNITS;
+    let low_compute = 100_000;
+    assert!(send(&mut svm, set_ix(payer_pk, Some(hook_program), max_units, false), &payer).is_ok());
+    assert!(send_with_compute(&mut svm, with_hook(buy_ix()), low_compute).is_ok());
+    assert!(send_with_compute(&mut svm, with_hook(sell_ix()), low_compute).is_ok());
+
+    assert!(send(&mut svm, set_ix(payer_pk, Some(hook_program), max_units, true), &payer).is_ok());
+    assert!(send_with_compute(&mut svm, with_hook(buy_ix()), low_compute).is_err());
+    assert!(send(&mut svm, buy_ix(), &user).is_err());
+
+    // Clearing the hook opens trading back up
+    assert!(send(&mut svm, set_ix(payer_pk, None, 0, false), &payer).is_ok());
+    assert!(send(&mut svm, buy_ix(), &user).is_ok());
+    assert!(send(&mut svm, with_hook(sell_ix()), &user).is_ok());
+
+    let account = svm.get_account(&pool_pda).unwrap();
+    let pool = LiquidityPool::unpack(&account.data).unwrap();
+    assert!(!pool.has_trade_hook());
+}
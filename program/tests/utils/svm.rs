@@ -1,7 +1,8 @@
 #![cfg(test)]
 use std::path::PathBuf;
-use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use solana_sdk::{account::Account, bpf_loader_upgradeable, pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction};
 use litesvm::{types::TransactionResult, LiteSVM};
+use flipcash_api::prelude::*;
 use super::print_tx;
 
 pub fn program_bytes() -> Vec<u8> {
@@ -52,3 +53,102 @@ pub fn create_payer(svm: &mut LiteSVM) -> Keypair {
 pub fn create_keypair() -> Keypair {
     Keypair::new()
 }
+
+/// LiteSVM deploys programs without a ProgramData account, so one is written
+/// by hand to give the program an upgrade authority.
+pub fn set_upgrade_authority(svm: &mut LiteSVM, authority: &Pubkey) {
+    let (program_data_pda, _) = find_program_data_pda();
+
+    let mut data = vec![3, 0, 0, 0];             // ProgramData
+    data.extend_from_slice(&0u64.to_le_bytes()); // Slot
+    data.push(1);                                // Some
+    data.extend_from_slice(authority.as_ref());
+
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: bpf_loader_upgradeable::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(program_data_pda, account).unwrap();
+}
+
+/// Creates a permissive protocol config administered by `admin`, with
+/// `base_mints` on the allowlist.
+pub fn setup_protocol(svm: &mut LiteSVM, admin: &Keypair, base_mints: &[Pubkey]) {
+    let admin_pk = admin.pubkey();
+    set_upgrade_authority(svm, &admin_pk);
+
+    let ixs: Vec<_> = std::iter::once(build_initialize_protocol_config_ix(admin_pk, 0, 9_999, CreationPolicy::Permissionless))
+        .chain(base_mints.iter().map(|mint| build_set_base_mint_allowed_ix(admin_pk, *mint, true)))
+        .collect();
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&admin_pk), &[admin], blockhash);
+    let res = send_tx(svm, tx);
+    assert!(res.is_ok());
+}
+
+/// A currency and its first pool.
+pub struct TestMarket {
+    pub mint: Pubkey,
+    pub currency: Pubkey,
+    pub pool: Pubkey,
+}
+
+/// Creates an SPL Token currency with the default decimals and supply, and
+/// its first pool against `base_mint`, both owned by `authority`.
+pub fn create_market(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    name: &str,
+    symbol: &str,
+    base_mint: Pubkey,
+    options: PoolOptions,
+) -> TestMarket {
+    let authority_pk = authority.pubkey();
+    let name = CurrencyName::new(name).unwrap();
+    let seed = [0u8; 32];
+
+    let (mint_pda, _) = find_mint_pda(&authority_pk, &name, &seed);
+    let (currency_pda, _) = find_currency_pda(&mint_pda);
+    let (pool_pda, _) = find_pool_pda(&currency_pda);
+
+    let (registry_pda, _) = find_registry_pda();
+    let registry_page = svm.get_account(&registry_pda)
+        .map_or(0, |account| CurrencyRegistry::unpack(&account.data).unwrap().next_page());
+
+    let ixs = [
+        build_initialize_currency_ix(
+            authority_pk,
+            name,
+            CurrencySymbol::new(symbol).unwrap(),
+            seed,
+            DEFAULT_TOKEN_DECIMALS,
+            DEFAULT_TOKEN_SUPPLY,
+            spl_token::id(),
+            registry_page,
+        ),
+        build_initialize_pool_ix(
+            authority_pk,
+            currency_pda,
+            mint_pda,
+            spl_token::id(),
+            base_mint,
+            spl_token::id(),
+            options,
+        ),
+    ];
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&authority_pk), &[authority], blockhash);
+    let res = send_tx(svm, tx);
+    assert!(res.is_ok());
+
+    TestMarket {
+        mint: mint_pda,
+        currency: currency_pda,
+        pool: pool_pda,
+    }
+}
@@ -1,7 +1,11 @@
 #![cfg(test)]
-use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction, transaction::Transaction};
 use litesvm::{types::FailedTransactionMetadata, LiteSVM};
-use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token::state::Account, get_spl_account};
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo};
+use spl_token_2022::{
+    extension::{ExtensionType, StateWithExtensions},
+    state::{Account, Mint},
+};
 
 pub fn create_mint(svm: &mut LiteSVM, payer_kp: &Keypair, owner_pk: &Pubkey, decimals: u8) -> Pubkey {
     CreateMint::new(svm, payer_kp)
@@ -11,6 +15,44 @@ pub fn create_mint(svm: &mut LiteSVM, payer_kp: &Keypair, owner_pk: &Pubkey, dec
         .unwrap()
 }
 
+/// Creates a Token-2022 mint with `extensions`, initialized by the
+/// instructions `init_extensions` returns for the new mint's address.
+pub fn create_mint_2022(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    owner_pk: &Pubkey,
+    decimals: u8,
+    extensions: &[ExtensionType],
+    init_extensions: impl FnOnce(&Pubkey) -> Vec<Instruction>,
+) -> Pubkey {
+    let mint_kp = Keypair::new();
+    let mint_pk = mint_kp.pubkey();
+    let payer_pk = payer_kp.pubkey();
+    let space = ExtensionType::try_calculate_account_len::<Mint>(extensions).unwrap();
+
+    let mut ixs = vec![system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(space),
+        space as u64,
+        &spl_token_2022::ID,
+    )];
+    ixs.extend(init_extensions(&mint_pk));
+    ixs.push(spl_token_2022::instruction::initialize_mint2(
+        &spl_token_2022::ID,
+        &mint_pk,
+        owner_pk,
+        None,
+        decimals,
+    ).unwrap());
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&payer_pk), &[payer_kp, &mint_kp], blockhash);
+    svm.send_transaction(tx).unwrap();
+
+    mint_pk
+}
+
 pub fn create_ata(svm: &mut LiteSVM, payer_kp: &Keypair, mint_pk: &Pubkey, owner_pk: &Pubkey) -> Pubkey {
     CreateAssociatedTokenAccount::new(svm, payer_kp, mint_pk)
         .owner(owner_pk)
@@ -18,9 +60,18 @@ pub fn create_ata(svm: &mut LiteSVM, payer_kp: &Keypair, mint_pk: &Pubkey, owner
         .unwrap()
 }
 
+pub fn create_ata_2022(svm: &mut LiteSVM, payer_kp: &Keypair, mint_pk: &Pubkey, owner_pk: &Pubkey) -> Pubkey {
+    CreateAssociatedTokenAccount::new(svm, payer_kp, mint_pk)
+        .owner(owner_pk)
+        .token_program_id(&spl_token_2022::ID)
+        .send()
+        .unwrap()
+}
+
+/// Balance of a token account from either token program.
 pub fn get_ata_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
-    let info:Account = get_spl_account(svm, &ata).unwrap();
-    info.amount
+    let account = svm.get_account(ata).unwrap();
+    StateWithExtensions::<Account>::unpack(&account.data).unwrap().base.amount
 }
 
 pub fn mint_to(svm: &mut LiteSVM,
@@ -34,3 +85,16 @@ pub fn mint_to(svm: &mut LiteSVM,
         .owner(mint_owner)
         .send()
 }
+
+pub fn mint_to_2022(svm: &mut LiteSVM,
+        payer: &Keypair,
+        mint: &Pubkey,
+        mint_owner: &Keypair,
+        destination: &Pubkey,
+        amount: u64,
+) -> Result<(), FailedTransactionMetadata> {
+    MintTo::new(svm, payer, mint, destination, amount)
+        .owner(mint_owner)
+        .token_program_id(&spl_token_2022::ID)
+        .send()
+}